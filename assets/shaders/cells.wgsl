@@ -0,0 +1,147 @@
+// Instanced cell sphere shader
+
+struct Camera {
+    view_proj: mat4x4<f32>,
+    camera_position: vec4<f32>,
+    light_direction: vec4<f32>,
+    // x: decode authored sRGB colors to linear, y: encode output to sRGB ourselves
+    color_options: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct InstanceInput {
+    @location(1) center: vec3<f32>,
+    @location(2) radius: f32,
+    @location(3) color: vec4<f32>,
+    @location(4) emissive: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) world_position: vec3<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) emissive: f32,
+};
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    return select(pow((c + vec3<f32>(0.055)) / 1.055, vec3<f32>(2.4)), c / 12.92, c <= vec3<f32>(0.04045));
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    return select(1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - vec3<f32>(0.055), c * 12.92, c <= vec3<f32>(0.0031308));
+}
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    // Mesh is a unit sphere, so the vertex position doubles as the normal
+    let world_position = instance.center + vertex.position * instance.radius;
+
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(world_position, 1.0);
+    out.normal = vertex.position;
+    out.world_position = world_position;
+    out.color = instance.color;
+    out.emissive = instance.emissive;
+    return out;
+}
+
+/// Lighting shared by the mesh and impostor paths
+fn shade(normal: vec3<f32>, world_position: vec3<f32>, color: vec4<f32>, emissive: f32) -> vec4<f32> {
+    let to_light = normalize(-camera.light_direction.xyz);
+    let to_camera = normalize(camera.camera_position.xyz - world_position);
+
+    let diffuse = max(dot(normal, to_light), 0.0);
+    let rim = pow(1.0 - max(dot(normal, to_camera), 0.0), 3.0);
+
+    // Legacy path: authored colors lit as-is, no emissive
+    if camera.color_options.x < 0.5 {
+        let rgb = color.rgb * (0.25 + 0.75 * diffuse) + vec3<f32>(rim * 0.15);
+        return vec4<f32>(rgb, color.a);
+    }
+
+    // Light in linear space; the sRGB surface encodes on write
+    let base = srgb_to_linear(color.rgb);
+    var rgb = base * (0.25 + 0.75 * diffuse) + vec3<f32>(rim * 0.15) + base * emissive;
+    if camera.color_options.y > 0.5 {
+        rgb = linear_to_srgb(clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0)));
+    }
+    return vec4<f32>(rgb, color.a);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return shade(normalize(in.normal), in.world_position, in.color, in.emissive);
+}
+
+// Impostors: a camera-facing quad per cell, shaded and depth-corrected as a sphere
+
+// Quad half-size relative to the radius, so the silhouette still fits under perspective
+const IMPOSTOR_SCALE: f32 = 1.25;
+
+struct ImpostorOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Position on the quad in units of the radius
+    @location(0) corner: vec2<f32>,
+    @location(1) center: vec3<f32>,
+    @location(2) radius: f32,
+    @location(3) color: vec4<f32>,
+    @location(4) emissive: f32,
+    @location(5) right: vec3<f32>,
+    @location(6) up: vec3<f32>,
+};
+
+struct ImpostorFragment {
+    @location(0) color: vec4<f32>,
+    @builtin(frag_depth) depth: f32,
+};
+
+@vertex
+fn vs_impostor(@builtin(vertex_index) vertex_index: u32, instance: InstanceInput) -> ImpostorOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, -1.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, 1.0), vec2<f32>(-1.0, 1.0),
+    );
+    let corner = corners[vertex_index] * IMPOSTOR_SCALE;
+
+    let forward = normalize(instance.center - camera.camera_position.xyz);
+    let up_hint = select(vec3<f32>(0.0, 1.0, 0.0), vec3<f32>(1.0, 0.0, 0.0), abs(forward.y) > 0.99);
+    let right = normalize(cross(forward, up_hint));
+    let up = cross(right, forward);
+    let world_position = instance.center + (right * corner.x + up * corner.y) * instance.radius;
+
+    var out: ImpostorOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(world_position, 1.0);
+    out.corner = corner;
+    out.center = instance.center;
+    out.radius = instance.radius;
+    out.color = instance.color;
+    out.emissive = instance.emissive;
+    out.right = right;
+    out.up = up;
+    return out;
+}
+
+@fragment
+fn fs_impostor(in: ImpostorOutput) -> ImpostorFragment {
+    let r2 = dot(in.corner, in.corner);
+    if r2 > 1.0 {
+        discard;
+    }
+    // Point on the camera-facing hemisphere under this pixel
+    let forward = cross(in.up, in.right);
+    let normal = normalize(in.right * in.corner.x + in.up * in.corner.y - forward * sqrt(1.0 - r2));
+    let world_position = in.center + normal * in.radius;
+    let clip = camera.view_proj * vec4<f32>(world_position, 1.0);
+
+    var out: ImpostorFragment;
+    out.color = shade(normal, world_position, in.color, in.emissive);
+    out.depth = clip.z / clip.w;
+    return out;
+}
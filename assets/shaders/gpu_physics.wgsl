@@ -0,0 +1,147 @@
+// GPU physics substep: adhesion springs, then collisions, boundary and integration per cell.
+// Mirrors CpuSimulation::compute_forces and CpuSimulation::integrate.
+
+struct Params {
+    cell_count: u32,
+    adhesion_count: u32,
+    // 0: explicit Euler, 1: semi-implicit Euler, 2: velocity Verlet
+    integrator: u32,
+    _padding: u32,
+    dt: f32,
+    // Velocity multiplier per substep, exp(-velocity_damping * dt)
+    damping: f32,
+    collision_stiffness: f32,
+    world_radius: f32,
+};
+
+struct Motion {
+    position: vec4<f32>,
+    velocity: vec4<f32>,
+    acceleration: vec4<f32>,
+};
+
+struct CellStatic {
+    radius: f32,
+    inverse_mass: f32,
+    // Terminal cells stay in place
+    anchored: u32,
+    _padding: u32,
+};
+
+struct Adhesion {
+    // Cell indices, not ids
+    cell_a: u32,
+    cell_b: u32,
+    can_break: u32,
+    _padding: u32,
+    rest_length: f32,
+    stiffness: f32,
+    damping: f32,
+    break_force: f32,
+};
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> cells: array<CellStatic>;
+@group(0) @binding(2) var<storage, read> adhesions: array<Adhesion>;
+// cell_count + 1 offsets into the entries that follow them; an entry is adhesion_index * 2 + (1 if the cell is cell_b)
+@group(0) @binding(3) var<storage, read> cell_adhesions: array<u32>;
+@group(0) @binding(4) var<storage, read> motion_in: array<Motion>;
+@group(0) @binding(5) var<storage, read_write> motion_out: array<Motion>;
+// xyz: spring force on cell_a, w: 1 while the adhesion holds, 0 once it has broken
+@group(0) @binding(6) var<storage, read_write> adhesion_forces: array<vec4<f32>>;
+
+@compute @workgroup_size(64)
+fn adhesion_springs(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.adhesion_count || adhesion_forces[i].w == 0.0) {
+        return;
+    }
+    let adhesion = adhesions[i];
+    let a = motion_in[adhesion.cell_a];
+    let b = motion_in[adhesion.cell_b];
+
+    let delta = b.position.xyz - a.position.xyz;
+    let distance = length(delta);
+    var direction = vec3<f32>(0.0, 1.0, 0.0);
+    if (distance > 1e-6) {
+        direction = delta / distance;
+    }
+    let closing_speed = dot(b.velocity.xyz - a.velocity.xyz, direction);
+    let magnitude = adhesion.stiffness * (distance - adhesion.rest_length) + adhesion.damping * closing_speed;
+
+    if (adhesion.can_break != 0u && abs(magnitude) > adhesion.break_force) {
+        adhesion_forces[i] = vec4<f32>(0.0);
+        return;
+    }
+    adhesion_forces[i] = vec4<f32>(direction * magnitude, 1.0);
+}
+
+@compute @workgroup_size(64)
+fn integrate_cells(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.cell_count) {
+        return;
+    }
+    var state = motion_in[i];
+    let cell = cells[i];
+    let position = state.position.xyz;
+    var force = vec3<f32>(0.0);
+
+    // Every pair is tested; coincident cells push apart along y, the lower index downwards
+    for (var j = 0u; j < params.cell_count; j++) {
+        if (j == i) {
+            continue;
+        }
+        let delta = motion_in[j].position.xyz - position;
+        let distance = length(delta);
+        let overlap = cell.radius + cells[j].radius - distance;
+        if (overlap > 0.0) {
+            var normal = vec3<f32>(0.0, select(-1.0, 1.0, i < j), 0.0);
+            if (distance > 1e-6) {
+                normal = delta / distance;
+            }
+            force -= normal * overlap * params.collision_stiffness;
+        }
+    }
+
+    // Keep cells inside the world sphere
+    let penetration = length(position) + cell.radius - params.world_radius;
+    if (penetration > 0.0 && length(position) > 0.0) {
+        force -= normalize(position) * penetration * params.collision_stiffness;
+    }
+
+    let first = cell_adhesions[i];
+    let last = cell_adhesions[i + 1u];
+    for (var k = first; k < last; k++) {
+        let entry = cell_adhesions[params.cell_count + 1u + k];
+        let spring = adhesion_forces[entry >> 1u].xyz;
+        force += select(spring, -spring, (entry & 1u) == 1u);
+    }
+
+    if (cell.anchored != 0u) {
+        state.velocity = vec4<f32>(0.0);
+        state.acceleration = vec4<f32>(0.0);
+        motion_out[i] = state;
+        return;
+    }
+
+    let dt = params.dt;
+    let acceleration = force * cell.inverse_mass;
+    var velocity = state.velocity.xyz;
+    var next_position = position;
+    switch params.integrator {
+        case 0u: {
+            next_position += velocity * dt;
+            velocity = (velocity + acceleration * dt) * params.damping;
+        }
+        case 1u: {
+            velocity = (velocity + acceleration * dt) * params.damping;
+            next_position += velocity * dt;
+        }
+        default: {
+            velocity = (velocity + 0.5 * (state.acceleration.xyz + acceleration) * dt) * params.damping;
+            next_position += velocity * dt + 0.5 * acceleration * dt * dt;
+        }
+    }
+    motion_out[i] = Motion(vec4<f32>(next_position, 1.0), vec4<f32>(velocity, 0.0), vec4<f32>(acceleration, 0.0));
+}
@@ -11,4 +11,30 @@ fn main() {
             let _ = res.compile();
         }
     }
+    
+    emit_git_hash();
+}
+
+/// Expose the short commit hash as BIOSPHERES_GIT_HASH for the About dialog.
+/// Falls back to "unknown" when building outside a git checkout or without git installed.
+fn emit_git_hash() {
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|text| text.trim().to_string())
+    };
+    
+    let hash = git(&["rev-parse", "--short", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=BIOSPHERES_GIT_HASH={}", hash);
+    
+    // Rebuild when HEAD moves, either to another branch or to a new commit on this one
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    if let Some(branch) = git(&["symbolic-ref", "-q", "HEAD"]) {
+        println!("cargo:rerun-if-changed=.git/{}", branch);
+    }
 }
@@ -0,0 +1,49 @@
+// Build information, data locations and crash reporting
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Crate version from Cargo.toml
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Short commit hash the binary was built from, or "unknown"
+pub const GIT_HASH: &str = env!("BIOSPHERES_GIT_HASH");
+
+/// Directory crash reports are written to, next to the settings and autosaves
+pub const CRASH_LOG_DIR: &str = "crash_logs";
+
+/// Directory settings, scenes, autosaves and crash logs are written to.
+/// All of them use paths relative to the working directory.
+pub fn data_dir() -> PathBuf {
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+}
+
+/// Write a crash report (panic message, location and backtrace) to the crash log directory
+/// before the default hook prints the panic, so users can attach it to bug reports.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        let thread = std::thread::current();
+        let report = format!(
+            "BioSpheres {} ({})\nThread '{}' {}\n\nBacktrace:\n{}\n",
+            VERSION,
+            GIT_HASH,
+            thread.name().unwrap_or("<unnamed>"),
+            info,
+            backtrace
+        );
+        match write_crash_log(Path::new(CRASH_LOG_DIR), &report) {
+            Ok(path) => eprintln!("Crash log written to {}", path.display()),
+            Err(e) => eprintln!("Failed to write crash log: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+fn write_crash_log(dir: &Path, report: &str) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let path = dir.join(format!("crash_{}.log", secs));
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
@@ -1 +1,37 @@
 // Cell adhesion system
+use super::types::{AdhesionConnection, Cell};
+use std::collections::HashMap;
+
+/// Group cells into connected components of the adhesion graph.
+/// Returns, for each cell in order, the smallest cell id in its component, so the label of a
+/// group stays the same while it grows and only changes when the group splits or merges.
+pub fn adhesion_components(cells: &[Cell], adhesions: &[AdhesionConnection]) -> Vec<u32> {
+    let index_of: HashMap<u32, usize> = cells.iter().enumerate().map(|(i, c)| (c.id, i)).collect();
+    let mut parent: Vec<usize> = (0..cells.len()).collect();
+
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            // Path halving keeps the trees shallow without recursion
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for adhesion in adhesions {
+        let (Some(&a), Some(&b)) = (index_of.get(&adhesion.cell_a), index_of.get(&adhesion.cell_b)) else {
+            continue;
+        };
+        let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+        if root_a != root_b {
+            // Keep the cell with the smaller id as the root so it names the component
+            if cells[root_a].id < cells[root_b].id {
+                parent[root_b] = root_a;
+            } else {
+                parent[root_a] = root_b;
+            }
+        }
+    }
+
+    (0..cells.len()).map(|i| cells[find(&mut parent, i)].id).collect()
+}
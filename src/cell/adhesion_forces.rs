@@ -1 +1,48 @@
 // Adhesion force calculations
+
+/// Fraction of the theoretical stability limit treated as safe.
+/// Leaves headroom for cells with several connections pulling at once.
+pub const STABILITY_SAFETY_FACTOR: f32 = 0.5;
+
+/// Largest timestep for which an explicitly integrated damped spring between two masses stays stable.
+/// Uses the reduced mass of the pair and the damped critical timestep `2/w * (sqrt(1 + z^2) - z)`.
+pub fn critical_timestep(stiffness: f32, damping: f32, mass_a: f32, mass_b: f32) -> f32 {
+    let reduced_mass = (mass_a * mass_b) / (mass_a + mass_b).max(f32::EPSILON);
+    if stiffness <= 0.0 || reduced_mass <= 0.0 {
+        return f32::INFINITY;
+    }
+    let omega = (stiffness / reduced_mass).sqrt();
+    let zeta = damping / (2.0 * (stiffness * reduced_mass).sqrt());
+    2.0 / omega * ((1.0 + zeta * zeta).sqrt() - zeta)
+}
+
+/// Whether a spring is comfortably stable at the given timestep (within the safety factor)
+pub fn is_spring_stable(stiffness: f32, damping: f32, mass_a: f32, mass_b: f32, dt: f32) -> bool {
+    dt <= critical_timestep(stiffness, damping, mass_a, mass_b) * STABILITY_SAFETY_FACTOR
+}
+
+/// Largest stiffness that is still stable at the given timestep and damping (within the safety factor)
+pub fn max_stable_stiffness(damping: f32, mass_a: f32, mass_b: f32, dt: f32) -> f32 {
+    // Critical timestep falls monotonically with stiffness, so bisect
+    let mut low = 0.0_f32;
+    let mut high = 1.0e6_f32;
+    if is_spring_stable(high, damping, mass_a, mass_b, dt) {
+        return high;
+    }
+    for _ in 0..48 {
+        let mid = 0.5 * (low + high);
+        if is_spring_stable(mid, damping, mass_a, mass_b, dt) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// Largest damping that is stable at the given timestep regardless of stiffness (within the safety factor)
+pub fn max_stable_damping(mass_a: f32, mass_b: f32, dt: f32) -> f32 {
+    // As stiffness goes to zero the damped critical timestep approaches 2 * reduced_mass / damping
+    let reduced_mass = (mass_a * mass_b) / (mass_a + mass_b).max(f32::EPSILON);
+    2.0 * reduced_mass * STABILITY_SAFETY_FACTOR / dt.max(f32::EPSILON)
+}
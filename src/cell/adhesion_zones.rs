@@ -1 +1,55 @@
 // Adhesion zone definitions
+use glam::Vec3;
+
+/// Half-width of the equatorial band around the split plane, in degrees.
+/// Bonds within this band of the plane are kept by both children.
+pub const EQUATORIAL_HALF_WIDTH_DEG: f32 = 2.0;
+
+/// Zone a parent adhesion falls into relative to the split plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdhesionZone {
+    /// Bond points away from the split direction; inherited by Child B
+    ZoneA,
+    /// Bond points along the split direction; inherited by Child A
+    ZoneB,
+    /// Bond lies in the equatorial band of the split plane; inherited by both children
+    ZoneC,
+}
+
+impl AdhesionZone {
+    /// Short label for UI display
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdhesionZone::ZoneA => "Zone A",
+            AdhesionZone::ZoneB => "Zone B",
+            AdhesionZone::ZoneC => "Zone C",
+        }
+    }
+
+    /// Whether Child A (placed along the split direction) inherits bonds in this zone
+    pub fn inherited_by_child_a(&self) -> bool {
+        matches!(self, AdhesionZone::ZoneB | AdhesionZone::ZoneC)
+    }
+
+    /// Whether Child B (placed against the split direction) inherits bonds in this zone
+    pub fn inherited_by_child_b(&self) -> bool {
+        matches!(self, AdhesionZone::ZoneA | AdhesionZone::ZoneC)
+    }
+}
+
+/// Classify a bond direction (parent to partner, in the parent's frame) against the split direction
+pub fn classify_bond_direction(bond_direction: Vec3, split_direction: Vec3) -> AdhesionZone {
+    let dot = bond_direction
+        .normalize_or_zero()
+        .dot(split_direction.normalize_or_zero())
+        .clamp(-1.0, 1.0);
+    let angle_deg = dot.acos().to_degrees();
+
+    if (angle_deg - 90.0).abs() <= EQUATORIAL_HALF_WIDTH_DEG {
+        AdhesionZone::ZoneC
+    } else if dot > 0.0 {
+        AdhesionZone::ZoneB
+    } else {
+        AdhesionZone::ZoneA
+    }
+}
@@ -1 +1,35 @@
 // Cell division logic
+use glam::{EulerRot, Quat, Vec3};
+
+/// Split direction in the parent's local frame from the genome's pitch/yaw angles (degrees).
+/// Zero pitch and yaw splits along +Z; Child A is placed along this direction, Child B opposite.
+pub fn split_direction(pitch_deg: f32, yaw_deg: f32) -> Vec3 {
+    Quat::from_euler(EulerRot::YXZ, yaw_deg.to_radians(), pitch_deg.to_radians(), 0.0) * Vec3::Z
+}
+
+/// Hash a seed and stream index into a uniform value in [0, 1).
+/// Stateless so the result only depends on the dividing cell, not on step order.
+pub(crate) fn hash_unit(seed: u32, stream: u32) -> f32 {
+    let mut x = seed.wrapping_mul(0x9E37_79B9) ^ stream.wrapping_mul(0x85EB_CA6B);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846C_A68B);
+    x ^= x >> 16;
+    (x >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// Perturb a unit split direction uniformly within a cone of `jitter_deg` half-angle.
+/// The same seed always gives the same direction; zero jitter returns `direction` unchanged.
+pub fn jitter_split_direction(direction: Vec3, jitter_deg: f32, seed: u32) -> Vec3 {
+    if jitter_deg <= 0.0 {
+        return direction;
+    }
+    let max_angle = jitter_deg.min(180.0).to_radians();
+    // Uniform over the spherical cap: cos(theta) is uniform in [cos(max), 1]
+    let cos_theta = 1.0 - hash_unit(seed, 0) * (1.0 - max_angle.cos());
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let phi = hash_unit(seed, 1) * std::f32::consts::TAU;
+    let (u, v) = direction.any_orthonormal_pair();
+    (direction * cos_theta + (u * phi.cos() + v * phi.sin()) * sin_theta).normalize()
+}
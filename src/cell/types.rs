@@ -1 +1,103 @@
 // Cell type definitions
+use glam::{Quat, Vec3};
+
+/// Cell type of a mode, matching `ModeSettings::cell_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellType {
+    /// Basic cell that only grows and divides
+    Test,
+    /// Swims forward along its heading, spending mass to do so
+    Flagellocyte,
+    /// Gains extra mass from absorbed light
+    Photocyte,
+    /// Digests the mass of other cells within reach
+    Phagocyte,
+}
+
+impl CellType {
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => CellType::Flagellocyte,
+            2 => CellType::Photocyte,
+            3 => CellType::Phagocyte,
+            _ => CellType::Test,
+        }
+    }
+
+    /// Value stored in `ModeSettings::cell_type`
+    pub fn index(&self) -> i32 {
+        match self {
+            CellType::Test => 0,
+            CellType::Flagellocyte => 1,
+            CellType::Photocyte => 2,
+            CellType::Phagocyte => 3,
+        }
+    }
+
+    pub fn all() -> &'static [CellType] {
+        &[CellType::Test, CellType::Flagellocyte, CellType::Photocyte, CellType::Phagocyte]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CellType::Test => "Test",
+            CellType::Flagellocyte => "Flagellocyte",
+            CellType::Photocyte => "Photocyte",
+            CellType::Phagocyte => "Phagocyte",
+        }
+    }
+}
+
+/// A single simulated cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    /// Unique id, never reused within a simulation
+    pub id: u32,
+    /// Index into the genome's modes
+    pub mode_index: usize,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    /// Acceleration from the previous step, used by the Verlet integrator
+    pub acceleration: Vec3,
+    pub rotation: Quat,
+    pub mass: f32,
+    /// Seconds since this cell was created
+    pub age: f32,
+    /// Number of times this cell's lineage has split in the current mode
+    pub split_count: i32,
+    /// Genome revision this cell follows (see `CpuSimulation::apply_genome`)
+    pub genome_generation: u32,
+}
+
+impl Cell {
+    pub fn new(id: u32, mode_index: usize, position: Vec3, mass: f32) -> Self {
+        Self {
+            id,
+            mode_index,
+            position,
+            velocity: Vec3::ZERO,
+            acceleration: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            mass,
+            age: 0.0,
+            split_count: 0,
+            genome_generation: 0,
+        }
+    }
+
+    /// Radius derived from mass, treating cells as unit-density spheres
+    pub fn radius(&self) -> f32 {
+        self.mass.max(0.0).cbrt()
+    }
+}
+
+/// Spring connection between two cells, referenced by cell id
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdhesionConnection {
+    pub cell_a: u32,
+    pub cell_b: u32,
+    /// Mode whose adhesion settings govern this connection
+    pub mode_index: usize,
+    /// Genome revision `mode_index` refers to
+    pub genome_generation: u32,
+}
@@ -0,0 +1,221 @@
+use super::{GenomeData, ModeSettings};
+use crate::cell::types::CellType;
+
+/// Split intervals above this are shown as "Never" in the editor
+pub const NEVER_SPLIT_INTERVAL: f32 = 59.0;
+
+/// Reason a mode can never divide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivisionBlocker {
+    Terminal,
+    ZeroMaxSplits,
+    NeverSplitInterval,
+}
+
+impl DivisionBlocker {
+    pub fn description(&self) -> &'static str {
+        match self {
+            DivisionBlocker::Terminal => "the mode is terminal",
+            DivisionBlocker::ZeroMaxSplits => "max splits is 0",
+            DivisionBlocker::NeverSplitInterval => "the split interval is set to Never",
+        }
+    }
+}
+
+/// Why cells in `mode` will never divide, if they can't
+pub fn division_blocker(mode: &ModeSettings) -> Option<DivisionBlocker> {
+    if mode.terminal {
+        Some(DivisionBlocker::Terminal)
+    } else if mode.max_splits == 0 {
+        Some(DivisionBlocker::ZeroMaxSplits)
+    } else if mode.split_interval > NEVER_SPLIT_INTERVAL {
+        Some(DivisionBlocker::NeverSplitInterval)
+    } else {
+        None
+    }
+}
+
+/// Why a mode that is allowed to divide never meets its split condition
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitDeadlock {
+    /// Min connections is not below max connections, so no connection count qualifies
+    ConnectionRange { min: i32, max: i32 },
+    /// Every parent producing this mode bonds the children, and that bond already fills max connections
+    SiblingBondFillsMax { max: i32 },
+    /// Cells never gain mass, so they never grow to split mass
+    NoNutrientGain { split_mass: f32 },
+}
+
+impl SplitDeadlock {
+    pub fn description(&self) -> String {
+        match self {
+            SplitDeadlock::ConnectionRange { min, max } => {
+                format!("min connections ({}) is not below max connections ({})", min, max)
+            }
+            SplitDeadlock::SiblingBondFillsMax { max } => format!(
+                "every cell is born bonded to its sibling, which already uses up max connections ({})",
+                max
+            ),
+            SplitDeadlock::NoNutrientGain { split_mass } => {
+                format!("nutrient gain 0 and split requires mass {:.2}", split_mass)
+            }
+        }
+    }
+}
+
+/// Every reason cells in mode `index` can never reach their split condition, for modes that
+/// are otherwise allowed to divide. Modes stopped on purpose (terminal, Never) have none.
+pub fn split_deadlocks(genome: &GenomeData, index: usize) -> Vec<SplitDeadlock> {
+    let mut deadlocks = Vec::new();
+    let Some(mode) = genome.modes.get(index) else {
+        return deadlocks;
+    };
+    if division_blocker(mode).is_some() {
+        return deadlocks;
+    }
+    if mode.min_adhesions >= mode.max_adhesions {
+        deadlocks.push(SplitDeadlock::ConnectionRange { min: mode.min_adhesions, max: mode.max_adhesions });
+    }
+
+    // Children of a bonding parent start with one connection: the bond to their sibling
+    let mut producers = genome
+        .modes
+        .iter()
+        .filter(|parent| division_blocker(parent).is_none())
+        .filter(|parent| [&parent.child_a, &parent.child_b].iter().any(|child| child.mode_number == index as i32))
+        .peekable();
+    let has_producers = producers.peek().is_some();
+    if has_producers && producers.all(|parent| parent.parent_make_adhesion) && mode.max_adhesions == 1 {
+        deadlocks.push(SplitDeadlock::SiblingBondFillsMax { max: mode.max_adhesions });
+    }
+
+    let digests = CellType::from_index(mode.cell_type) == CellType::Phagocyte && mode.digestion_rate > 0.0;
+    if mode.passive_gain_rate() <= 0.0 && !digests {
+        deadlocks.push(SplitDeadlock::NoNutrientGain { split_mass: mode.split_mass });
+    }
+    deadlocks
+}
+
+/// Why a colony seeded from the genome's initial mode can never grow, if it can't
+pub fn initial_mode_growth_blocker(genome: &GenomeData) -> Option<DivisionBlocker> {
+    let initial = genome.modes.get(genome.initial_mode.max(0) as usize)?;
+    division_blocker(initial)
+}
+
+/// Modes of the genome that lie on a division cycle through two or more modes
+/// (A → B → A), grouped by strongly connected component of the child links.
+/// Entry `i` is the cycle group of mode `i`, or `None` when the mode is on no such cycle.
+/// A child pointing back at its own mode is the normal "keep splitting" case and is not a cycle here.
+pub fn division_cycle_groups(genome: &GenomeData) -> Vec<Option<usize>> {
+    let count = genome.modes.len();
+    let children = |mode: usize| {
+        let mode = &genome.modes[mode];
+        [mode.child_a.mode_number, mode.child_b.mode_number]
+            .into_iter()
+            .filter(move |&child| child >= 0 && (child as usize) < count)
+            .map(|child| child as usize)
+    };
+
+    // Iterative Tarjan's algorithm, so long chains of modes can't overflow the stack
+    let mut index = vec![usize::MAX; count];
+    let mut low_link = vec![0; count];
+    let mut on_stack = vec![false; count];
+    let mut stack = Vec::new();
+    let mut component = vec![usize::MAX; count];
+    let mut component_sizes = Vec::new();
+    let mut next_index = 0;
+
+    for root in 0..count {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        // (mode, position in its child list)
+        let mut call_stack = vec![(root, 0)];
+        while let Some(frame) = call_stack.last_mut() {
+            let (mode, next_child) = (frame.0, frame.1);
+            if next_child == 0 {
+                index[mode] = next_index;
+                low_link[mode] = next_index;
+                next_index += 1;
+                stack.push(mode);
+                on_stack[mode] = true;
+            }
+            if let Some(child) = children(mode).nth(next_child) {
+                frame.1 += 1;
+                if index[child] == usize::MAX {
+                    call_stack.push((child, 0));
+                } else if on_stack[child] {
+                    low_link[mode] = low_link[mode].min(index[child]);
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                low_link[parent] = low_link[parent].min(low_link[mode]);
+            }
+            if low_link[mode] == index[mode] {
+                let id = component_sizes.len();
+                let mut size = 0;
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component[member] = id;
+                    size += 1;
+                    if member == mode {
+                        break;
+                    }
+                }
+                component_sizes.push(size);
+            }
+        }
+    }
+
+    component
+        .into_iter()
+        .map(|id| (component_sizes[id] > 1).then_some(id))
+        .collect()
+}
+
+/// Whether the child link `parent → child` is part of a division cycle through two or more modes
+pub fn is_division_cycle_link(groups: &[Option<usize>], parent: usize, child: usize) -> bool {
+    parent != child && groups.get(parent).copied().flatten().is_some_and(|group| groups.get(child) == Some(&Some(group)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genome_with_children(children: &[(i32, i32)]) -> GenomeData {
+        let modes = children
+            .iter()
+            .enumerate()
+            .map(|(index, &(a, b))| {
+                let mut mode = ModeSettings::new_self_splitting(index as i32, format!("Mode {}", index));
+                mode.child_a.mode_number = a;
+                mode.child_b.mode_number = b;
+                mode
+            })
+            .collect();
+        GenomeData { name: "Test".to_string(), initial_mode: 0, modes }
+    }
+
+    #[test]
+    fn test_self_reference_is_not_a_cycle() {
+        let genome = genome_with_children(&[(0, 1), (1, 1)]);
+        assert_eq!(division_cycle_groups(&genome), vec![None, None]);
+    }
+
+    #[test]
+    fn test_two_mode_cycle_is_detected() {
+        // 0 → 1 → 2 → 1, with 2 also splitting into itself
+        let genome = genome_with_children(&[(0, 1), (2, 1), (1, 2)]);
+        let groups = division_cycle_groups(&genome);
+
+        assert_eq!(groups[0], None);
+        assert!(groups[1].is_some() && groups[1] == groups[2]);
+        assert!(is_division_cycle_link(&groups, 1, 2));
+        assert!(is_division_cycle_link(&groups, 2, 1));
+        assert!(!is_division_cycle_link(&groups, 1, 1));
+        assert!(!is_division_cycle_link(&groups, 0, 1));
+    }
+}
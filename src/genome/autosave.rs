@@ -0,0 +1,121 @@
+use super::{CurrentGenome, GenomeData, GenomeNodeGraph};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Directory the rotating autosave files are written to
+pub const AUTOSAVE_DIR: &str = "autosave";
+
+/// Number of autosave files kept; the oldest is overwritten first
+pub const AUTOSAVE_ROTATION: usize = 3;
+
+/// Default time between autosaves
+pub const DEFAULT_AUTOSAVE_INTERVAL_SECS: f32 = 120.0;
+
+fn slot_path(slot: usize) -> PathBuf {
+    Path::new(AUTOSAVE_DIR).join(format!("genome.{}.autosave.biogenome", slot))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Most recently written autosave slot, if any
+fn newest_slot() -> Option<(usize, SystemTime)> {
+    (0..AUTOSAVE_ROTATION)
+        .filter_map(|slot| modified_time(&slot_path(slot)).map(|time| (slot, time)))
+        .max_by_key(|(_, time)| *time)
+}
+
+/// Periodically writes the working genome to a small rotation of autosave files
+pub struct GenomeAutoSaver {
+    elapsed: f32,
+    /// Genome as of the last autosave; a save only happens when the genome differs (dirty)
+    last_saved: GenomeData,
+    next_slot: usize,
+    /// Autosave found at startup that is newer than the last manual save
+    pending_restore: Option<PathBuf>,
+}
+
+impl GenomeAutoSaver {
+    /// Look for an existing autosave and decide whether to offer restoring it
+    pub fn new(current_genome: &CurrentGenome) -> Self {
+        let newest = newest_slot();
+        let next_slot = newest.map(|(slot, _)| (slot + 1) % AUTOSAVE_ROTATION).unwrap_or(0);
+
+        // Without a manual save on record, any autosave is worth offering
+        let manual_save_time = current_genome.file_path.as_deref().and_then(modified_time);
+        let pending_restore = newest
+            .filter(|(_, time)| manual_save_time.is_none_or(|manual| *time > manual))
+            .map(|(slot, _)| slot_path(slot))
+            .filter(|path| {
+                GenomeData::load_from_file(path).is_ok_and(|genome| genome != current_genome.genome)
+            });
+
+        Self {
+            elapsed: 0.0,
+            last_saved: current_genome.genome.clone(),
+            next_slot,
+            pending_restore,
+        }
+    }
+
+    /// Autosave waiting for the user to restore or dismiss it
+    pub fn pending_restore(&self) -> Option<&Path> {
+        self.pending_restore.as_deref()
+    }
+
+    /// Advance the timer and write the genome if the interval passed and it changed
+    pub fn update(&mut self, delta_time: f32, interval_secs: f32, current_genome: &CurrentGenome) {
+        // Don't rotate over the autosave the user hasn't decided about yet
+        if self.pending_restore.is_some() {
+            return;
+        }
+
+        self.elapsed += delta_time;
+        if self.elapsed < interval_secs.max(1.0) {
+            return;
+        }
+        self.elapsed = 0.0;
+
+        if current_genome.genome != self.last_saved {
+            self.save(&current_genome.genome);
+        }
+    }
+
+    fn save(&mut self, genome: &GenomeData) {
+        let path = slot_path(self.next_slot);
+        let result = std::fs::create_dir_all(AUTOSAVE_DIR)
+            .map_err(|source| super::io::GenomeError::io(Path::new(AUTOSAVE_DIR), source))
+            .and_then(|_| genome.save_to_file(&path));
+        match result {
+            Ok(()) => {
+                println!("Autosaved genome to {}", path.display());
+                self.last_saved = genome.clone();
+                self.next_slot = (self.next_slot + 1) % AUTOSAVE_ROTATION;
+            }
+            Err(e) => eprintln!("Failed to autosave genome to {}: {}", path.display(), e),
+        }
+    }
+
+    /// Replace the current genome with the pending autosave
+    pub fn restore(&mut self, current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph) {
+        let Some(path) = self.pending_restore.take() else {
+            return;
+        };
+        match GenomeData::load_from_file(&path) {
+            Ok(genome) => {
+                println!("Restored genome from {}", path.display());
+                current_genome.load_genome(genome);
+                current_genome.select_single_mode(0);
+                self.last_saved = current_genome.genome.clone();
+                node_graph.mark_for_rebuild();
+            }
+            Err(e) => eprintln!("Failed to restore autosave {}: {}", path.display(), e),
+        }
+    }
+
+    /// Keep the current genome and resume autosaving
+    pub fn dismiss_restore(&mut self) {
+        self.pending_restore = None;
+    }
+}
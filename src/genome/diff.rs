@@ -0,0 +1,347 @@
+use super::{GenomeData, ModeSettings};
+use serde_json::Value;
+
+/// Numbers closer than this are treated as unchanged, so a save/load round trip shows no diff
+const NUMBER_EPSILON: f64 = 1e-6;
+
+/// Mode settings that hold a mode index, compared by which mode they point at
+const MODE_REFERENCE_FIELDS: [&str; 4] = [
+    "child_a.mode_number",
+    "child_b.mode_number",
+    "mode_a_after_splits",
+    "mode_b_after_splits",
+];
+
+/// How a mode differs between the two genomes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeStatus {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+impl ModeStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModeStatus::Added => "Added",
+            ModeStatus::Removed => "Removed",
+            ModeStatus::Changed => "Changed",
+            ModeStatus::Unchanged => "Unchanged",
+        }
+    }
+
+    /// Green for added, red for removed, yellow for changed
+    pub fn color(&self) -> [f32; 4] {
+        match self {
+            ModeStatus::Added => [0.4, 0.9, 0.4, 1.0],
+            ModeStatus::Removed => [1.0, 0.4, 0.4, 1.0],
+            ModeStatus::Changed => [1.0, 0.85, 0.3, 1.0],
+            ModeStatus::Unchanged => [0.6, 0.6, 0.6, 1.0],
+        }
+    }
+}
+
+/// A setting whose value differs, shown as text
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// Dotted setting path, e.g. `adhesion_settings.linear_spring_stiffness`
+    pub path: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// One mode of either genome and how it changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeDiff {
+    pub status: ModeStatus,
+    pub old_index: Option<usize>,
+    pub new_index: Option<usize>,
+    /// Name in the new genome, or the old one for removed modes
+    pub name: String,
+    /// Previous name when a mode matched by position was renamed
+    pub renamed_from: Option<String>,
+    pub fields: Vec<FieldChange>,
+}
+
+impl ModeDiff {
+    /// Whether the mode sits at a different position in the new genome
+    pub fn moved(&self) -> bool {
+        matches!((self.old_index, self.new_index), (Some(old), Some(new)) if old != new)
+    }
+}
+
+/// Everything that differs between two genomes, from `diff`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GenomeDiff {
+    /// Genome-wide settings: name and initial mode
+    pub fields: Vec<FieldChange>,
+    /// Modes in new-genome order, then removed modes in old order
+    pub modes: Vec<ModeDiff>,
+}
+
+impl GenomeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.modes.iter().all(|mode| mode.status == ModeStatus::Unchanged && !mode.moved())
+    }
+
+    pub fn count(&self, status: ModeStatus) -> usize {
+        self.modes.iter().filter(|mode| mode.status == status).count()
+    }
+}
+
+/// Compare two genomes. Modes are matched by name first, then by position among the
+/// unmatched ones (reported as renames), so reordering never shows up as remove + add.
+/// Mode references are compared by the mode they point at rather than by index.
+pub fn diff(old: &GenomeData, new: &GenomeData) -> GenomeDiff {
+    let old_to_new = match_modes(old, new);
+    let mut new_to_old = vec![None; new.modes.len()];
+    for (old_index, new_index) in old_to_new.iter().enumerate() {
+        if let Some(new_index) = new_index {
+            new_to_old[*new_index] = Some(old_index);
+        }
+    }
+
+    let mut fields = Vec::new();
+    if old.name != new.name {
+        fields.push(FieldChange { path: "name".to_string(), old: old.name.clone(), new: new.name.clone() });
+    }
+    let initial_old = mode_reference_target(old.initial_mode as i64, &old_to_new);
+    if initial_old != Some(new.initial_mode as i64) {
+        fields.push(FieldChange {
+            path: "initial_mode".to_string(),
+            old: mode_reference_label(old, old.initial_mode as i64),
+            new: mode_reference_label(new, new.initial_mode as i64),
+        });
+    }
+
+    let mut modes = Vec::new();
+    for (new_index, new_mode) in new.modes.iter().enumerate() {
+        let diff = match new_to_old[new_index] {
+            Some(old_index) => {
+                let old_mode = &old.modes[old_index];
+                let fields = mode_field_changes(old, old_mode, new, new_mode, &old_to_new);
+                let renamed_from = (old_mode.name != new_mode.name).then(|| old_mode.name.clone());
+                let status = if fields.is_empty() && renamed_from.is_none() {
+                    ModeStatus::Unchanged
+                } else {
+                    ModeStatus::Changed
+                };
+                ModeDiff { status, old_index: Some(old_index), new_index: Some(new_index), name: new_mode.name.clone(), renamed_from, fields }
+            }
+            None => ModeDiff {
+                status: ModeStatus::Added,
+                old_index: None,
+                new_index: Some(new_index),
+                name: new_mode.name.clone(),
+                renamed_from: None,
+                fields: Vec::new(),
+            },
+        };
+        modes.push(diff);
+    }
+    for (old_index, old_mode) in old.modes.iter().enumerate() {
+        if old_to_new[old_index].is_none() {
+            modes.push(ModeDiff {
+                status: ModeStatus::Removed,
+                old_index: Some(old_index),
+                new_index: None,
+                name: old_mode.name.clone(),
+                renamed_from: None,
+                fields: Vec::new(),
+            });
+        }
+    }
+
+    GenomeDiff { fields, modes }
+}
+
+/// For each old mode, the new mode it corresponds to
+fn match_modes(old: &GenomeData, new: &GenomeData) -> Vec<Option<usize>> {
+    let mut old_to_new = vec![None; old.modes.len()];
+    let mut new_matched = vec![false; new.modes.len()];
+
+    for (new_index, new_mode) in new.modes.iter().enumerate() {
+        let same_name = old
+            .modes
+            .iter()
+            .enumerate()
+            .position(|(old_index, old_mode)| old_to_new[old_index].is_none() && old_mode.name == new_mode.name);
+        if let Some(old_index) = same_name {
+            old_to_new[old_index] = Some(new_index);
+            new_matched[new_index] = true;
+        }
+    }
+
+    for (new_index, matched) in new_matched.iter().enumerate() {
+        if !matched && new_index < old_to_new.len() && old_to_new[new_index].is_none() {
+            old_to_new[new_index] = Some(new_index);
+        }
+    }
+    old_to_new
+}
+
+/// Where an old mode reference points in the new genome; negative references (none) stay as they are
+fn mode_reference_target(reference: i64, old_to_new: &[Option<usize>]) -> Option<i64> {
+    if reference < 0 {
+        return Some(reference);
+    }
+    old_to_new.get(reference as usize).copied().flatten().map(|index| index as i64)
+}
+
+fn mode_reference_label(genome: &GenomeData, reference: i64) -> String {
+    if reference < 0 {
+        return "none".to_string();
+    }
+    match genome.modes.get(reference as usize) {
+        Some(mode) => format!("[{}] {}", reference, mode.name),
+        None => format!("[{}] (missing)", reference),
+    }
+}
+
+/// Changed settings of a matched mode, excluding its name
+fn mode_field_changes(
+    old: &GenomeData,
+    old_mode: &ModeSettings,
+    new: &GenomeData,
+    new_mode: &ModeSettings,
+    old_to_new: &[Option<usize>],
+) -> Vec<FieldChange> {
+    let mut old_fields = Vec::new();
+    let mut new_fields = Vec::new();
+    flatten("", &serde_json::to_value(old_mode).unwrap_or(Value::Null), &mut old_fields);
+    flatten("", &serde_json::to_value(new_mode).unwrap_or(Value::Null), &mut new_fields);
+
+    let mut changes = Vec::new();
+    for (path, new_value) in &new_fields {
+        if path == "name" {
+            continue;
+        }
+        let old_value = old_fields.iter().find(|(old_path, _)| old_path == path).map(|(_, value)| value);
+        if MODE_REFERENCE_FIELDS.contains(&path.as_str()) {
+            let old_reference = old_value.and_then(Value::as_i64).unwrap_or(-1);
+            let new_reference = new_value.as_i64().unwrap_or(-1);
+            if mode_reference_target(old_reference, old_to_new) != Some(new_reference) {
+                changes.push(FieldChange {
+                    path: path.clone(),
+                    old: mode_reference_label(old, old_reference),
+                    new: mode_reference_label(new, new_reference),
+                });
+            }
+            continue;
+        }
+        let unchanged = old_value.is_some_and(|old_value| values_equal(old_value, new_value));
+        if !unchanged {
+            changes.push(FieldChange {
+                path: path.clone(),
+                old: old_value.map(format_value).unwrap_or_else(|| "-".to_string()),
+                new: format_value(new_value),
+            });
+        }
+    }
+    changes
+}
+
+/// Collect leaf settings as (dotted path, value). Arrays such as colors and quaternions are leaves.
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&path, child, out);
+            }
+        }
+        _ => out.push((prefix.to_string(), value.clone())),
+    }
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => match (a.as_f64(), b.as_f64()) {
+            (Some(a), Some(b)) => (a - b).abs() <= NUMBER_EPSILON,
+            _ => a == b,
+        },
+        (Value::Array(a), Value::Array(b)) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| values_equal(a, b)),
+        _ => a == b,
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Number(number) => match number.as_f64() {
+            Some(float) if number.is_f64() => format!("{:.3}", float).trim_end_matches('0').trim_end_matches('.').to_string(),
+            _ => number.to_string(),
+        },
+        Value::Array(items) => format!("({})", items.iter().map(format_value).collect::<Vec<_>>().join(", ")),
+        Value::String(text) => text.clone(),
+        Value::Null => "none".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genome(names: &[&str]) -> GenomeData {
+        GenomeData {
+            name: "Test".to_string(),
+            initial_mode: 0,
+            modes: names
+                .iter()
+                .enumerate()
+                .map(|(index, name)| ModeSettings::new_self_splitting(index as i32, name.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_identical_genomes_have_no_diff() {
+        let genome = genome(&["A", "B"]);
+        assert!(diff(&genome, &genome.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_reordered_modes_are_moves_not_remove_and_add() {
+        let old = genome(&["A", "B", "C"]);
+        let mut new = old.clone();
+        new.modes.swap(0, 2);
+        // Keep every reference pointing at the same modes after the swap
+        for mode in &mut new.modes {
+            for child in [&mut mode.child_a, &mut mode.child_b] {
+                child.mode_number = match child.mode_number {
+                    0 => 2,
+                    2 => 0,
+                    other => other,
+                };
+            }
+        }
+        new.initial_mode = 2;
+
+        let result = diff(&old, &new);
+        assert_eq!(result.count(ModeStatus::Added), 0);
+        assert_eq!(result.count(ModeStatus::Removed), 0);
+        assert_eq!(result.count(ModeStatus::Changed), 0);
+        assert!(result.fields.is_empty());
+        assert!(result.modes.iter().any(ModeDiff::moved));
+    }
+
+    #[test]
+    fn test_added_removed_renamed_and_changed_modes() {
+        let old = genome(&["A", "B", "C"]);
+        let mut new = genome(&["A", "Renamed", "D", "E"]);
+        new.modes[0].split_mass += 1.0;
+
+        let result = diff(&old, &new);
+        let a = &result.modes[0];
+        assert_eq!(a.status, ModeStatus::Changed);
+        assert_eq!(a.fields.len(), 1);
+        assert_eq!(a.fields[0].path, "split_mass");
+
+        let renamed = &result.modes[1];
+        assert_eq!(renamed.renamed_from.as_deref(), Some("B"));
+        assert_eq!(result.modes[2].renamed_from.as_deref(), Some("C"));
+        assert_eq!(result.count(ModeStatus::Added), 1);
+        assert_eq!(result.count(ModeStatus::Removed), 0);
+    }
+}
@@ -0,0 +1,128 @@
+use super::{CurrentGenome, GenomeData, GenomeNodeGraph};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver};
+
+/// Genome file operation running on a worker thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOperation {
+    Save,
+    Load,
+    /// Load, then keep watching the file for changes
+    LoadAndWatch,
+}
+
+impl FileOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FileOperation::Save => "Saving",
+            FileOperation::Load | FileOperation::LoadAndWatch => "Loading",
+        }
+    }
+}
+
+/// What the worker sends back when it is done
+enum FileResult {
+    Saved(PathBuf),
+    Loaded(PathBuf, GenomeData),
+    Failed(PathBuf, String),
+}
+
+/// Saves and loads genomes off the render thread so slow disks never stall a frame.
+/// Only one operation runs at a time.
+#[derive(Default)]
+pub struct GenomeFileIo {
+    pending: Option<(FileOperation, Receiver<FileResult>)>,
+    /// Result of the last finished operation (message, is_error)
+    status: Option<(String, bool)>,
+}
+
+impl GenomeFileIo {
+    /// The operation currently in flight, if any
+    pub fn pending(&self) -> Option<FileOperation> {
+        self.pending.as_ref().map(|(operation, _)| *operation)
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Whether a load is in flight; the genome must not be edited until it lands
+    pub fn is_loading(&self) -> bool {
+        matches!(self.pending(), Some(FileOperation::Load | FileOperation::LoadAndWatch))
+    }
+
+    /// Message from the last finished operation and whether it failed
+    pub fn status(&self) -> Option<(&str, bool)> {
+        self.status.as_ref().map(|(message, is_error)| (message.as_str(), *is_error))
+    }
+
+    /// Write a snapshot of the genome to `path` in the background. Ignored while busy.
+    pub fn save(&mut self, genome: GenomeData, path: PathBuf) {
+        if self.is_busy() {
+            return;
+        }
+        let (sender, receiver) = channel();
+        std::thread::spawn(move || {
+            let result = match genome.save_to_file(&path) {
+                Ok(()) => FileResult::Saved(path),
+                Err(e) => FileResult::Failed(path, e.to_string()),
+            };
+            let _ = sender.send(result);
+        });
+        self.pending = Some((FileOperation::Save, receiver));
+    }
+
+    /// Read and parse the genome at `path` in the background. Ignored while busy.
+    pub fn load(&mut self, path: PathBuf, watch: bool) {
+        if self.is_busy() {
+            return;
+        }
+        let (sender, receiver) = channel();
+        std::thread::spawn(move || {
+            let result = match GenomeData::load_from_file(&path) {
+                Ok(genome) => FileResult::Loaded(path, genome),
+                Err(e) => FileResult::Failed(path, e.to_string()),
+            };
+            let _ = sender.send(result);
+        });
+        let operation = if watch { FileOperation::LoadAndWatch } else { FileOperation::Load };
+        self.pending = Some((operation, receiver));
+    }
+
+    /// Apply the result of a finished operation. Call once per frame.
+    pub fn poll(&mut self, current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph) {
+        let Some((operation, receiver)) = self.pending.as_ref() else {
+            return;
+        };
+        let operation = *operation;
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                FileResult::Failed(PathBuf::new(), "file worker stopped unexpectedly".to_string())
+            }
+        };
+        self.pending = None;
+
+        self.status = Some(match result {
+            FileResult::Saved(path) => {
+                println!("Saved genome to {}", path.display());
+                current_genome.file_path = Some(path.clone());
+                (format!("Saved {}", path.display()), false)
+            }
+            FileResult::Loaded(path, genome) => {
+                println!("Loaded genome from {}", path.display());
+                current_genome.load_genome(genome);
+                current_genome.select_single_mode(0);
+                current_genome.file_path = Some(path.clone());
+                current_genome.watch_file = operation == FileOperation::LoadAndWatch;
+                node_graph.mark_for_rebuild();
+                (format!("Loaded {}", path.display()), false)
+            }
+            FileResult::Failed(path, error) => {
+                eprintln!("Genome {} failed for {}: {}", operation.name().to_lowercase(), path.display(), error);
+                (format!("{} failed: {}", operation.name(), error), true)
+            }
+        });
+    }
+}
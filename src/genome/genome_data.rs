@@ -1,3 +1,4 @@
+use crate::cell::types::CellType;
 use serde::{Deserialize, Serialize};
 
 /// Represents a 3D vector for colors and positions
@@ -33,6 +34,9 @@ pub struct AdhesionSettings {
     pub can_break: bool,
     pub break_force: f32,
     pub rest_length: f32,
+    /// Rest at the sum of the connected cells' current radii instead of `rest_length`
+    #[serde(default)]
+    pub auto_rest_length: bool,
     pub linear_spring_stiffness: f32,
     pub linear_spring_damping: f32,
     pub orientation_spring_stiffness: f32,
@@ -49,6 +53,7 @@ impl Default for AdhesionSettings {
             can_break: false,
             break_force: 10.0,
             rest_length: 2.0,
+            auto_rest_length: false,
             linear_spring_stiffness: 50.0,
             linear_spring_damping: 2.0,
             orientation_spring_stiffness: 10.0,
@@ -100,6 +105,12 @@ pub struct ModeSettings {
     pub max_splits: i32,
     pub mode_a_after_splits: i32,
     pub mode_b_after_splits: i32,
+    /// Cells in this mode never split or move; useful for structural cells
+    #[serde(default)]
+    pub terminal: bool,
+    /// Terminal cells also stop gaining and spending nutrients
+    #[serde(default)]
+    pub terminal_stops_nutrients: bool,
     
     // Growth settings
     pub nutrient_gain_rate: f32,
@@ -110,9 +121,25 @@ pub struct ModeSettings {
     // Flagellocyte settings
     pub swim_force: f32,
     
+    // Photocyte settings
+    /// Mass gained per second from light, on top of the nutrient gain rate
+    #[serde(default = "default_light_absorption_rate")]
+    pub light_absorption_rate: f32,
+    
+    // Phagocyte settings
+    /// Gap beyond touching at which other cells can be digested
+    #[serde(default = "default_engulf_radius")]
+    pub engulf_radius: f32,
+    /// Mass drained per second from each cell within reach
+    #[serde(default = "default_digestion_rate")]
+    pub digestion_rate: f32,
+    
     // Split direction
     pub parent_split_direction: Vec3,
     pub enable_parent_angle_snapping: bool,
+    /// Half-angle in degrees of the cone the split direction is randomly perturbed within
+    #[serde(default)]
+    pub split_angle_jitter: f32,
     
     // Adhesion settings
     pub max_adhesions: i32,
@@ -125,7 +152,34 @@ pub struct ModeSettings {
     pub child_b: ChildSettings,
 }
 
+fn default_light_absorption_rate() -> f32 {
+    0.2
+}
+
+fn default_engulf_radius() -> f32 {
+    0.3
+}
+
+fn default_digestion_rate() -> f32 {
+    0.4
+}
+
 impl ModeSettings {
+    /// Mass gained per second without eating other cells: nutrients, plus light for photocytes
+    pub fn passive_gain_rate(&self) -> f32 {
+        self.passive_gain_rate_at(1.0)
+    }
+
+    /// Passive gain rate in a medium whose nutrient concentration is `concentration` times the
+    /// ambient level; light absorption does not depend on it
+    pub fn passive_gain_rate_at(&self, concentration: f32) -> f32 {
+        let nutrients = self.nutrient_gain_rate * concentration;
+        match CellType::from_index(self.cell_type) {
+            CellType::Photocyte => nutrients + self.light_absorption_rate,
+            _ => nutrients,
+        }
+    }
+
     pub fn new_self_splitting(mode_number: i32, name: String) -> Self {
         Self {
             name: name.clone(),
@@ -143,6 +197,8 @@ impl ModeSettings {
             max_splits: -1,
             mode_a_after_splits: -1,
             mode_b_after_splits: -1,
+            terminal: false,
+            terminal_stops_nutrients: false,
             
             nutrient_gain_rate: 0.1,
             max_cell_size: 1.0,
@@ -151,8 +207,14 @@ impl ModeSettings {
             
             swim_force: 0.5,
             
+            light_absorption_rate: default_light_absorption_rate(),
+            
+            engulf_radius: default_engulf_radius(),
+            digestion_rate: default_digestion_rate(),
+            
             parent_split_direction: Vec3::new(0.0, 0.0, 0.0),
             enable_parent_angle_snapping: false,
+            split_angle_jitter: 0.0,
             
             max_adhesions: 10,
             min_adhesions: 0,
@@ -190,16 +252,64 @@ impl Default for GenomeData {
 }
 
 impl GenomeData {
-    pub fn save_to_file(&self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
-        let json = serde_json::to_string_pretty(self)?;
-        std::fs::write(path, json)?;
-        Ok(())
+    pub fn save_to_file(&self, path: &std::path::Path) -> Result<(), super::io::GenomeError> {
+        super::io::save_genome(self, path)
     }
     
-    pub fn load_from_file(path: &std::path::Path) -> Result<Self, Box<dyn std::error::Error>> {
-        let json = std::fs::read_to_string(path)?;
-        let genome = serde_json::from_str(&json)?;
-        Ok(genome)
+    pub fn load_from_file(path: &std::path::Path) -> Result<Self, super::io::GenomeError> {
+        super::io::load_genome(path)
+    }
+
+    /// Fix structural problems that would leave the genome unusable, returning a
+    /// description of each repair made
+    pub fn repair(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.modes.is_empty() {
+            self.modes.push(ModeSettings::new_self_splitting(0, "Mode 0".to_string()));
+            warnings.push("Genome had no modes; added a default self-splitting mode".to_string());
+        }
+
+        let last = self.modes.len() as i32 - 1;
+        if !(0..=last).contains(&self.initial_mode) {
+            let clamped = self.initial_mode.clamp(0, last);
+            warnings.push(format!("Initial mode {} does not exist; using mode {}", self.initial_mode, clamped));
+            self.initial_mode = clamped;
+        }
+
+        for (index, mode) in self.modes.iter_mut().enumerate() {
+            for (label, child) in [("A", &mut mode.child_a), ("B", &mut mode.child_b)] {
+                if !(0..=last).contains(&child.mode_number) {
+                    let clamped = child.mode_number.clamp(0, last);
+                    warnings.push(format!(
+                        "Mode {} child {} points to missing mode {}; using mode {}",
+                        index, label, child.mode_number, clamped
+                    ));
+                    child.mode_number = clamped;
+                }
+            }
+        }
+        warnings
+    }
+}
+
+/// Which modes the editor's mode list shows; only hides entries, never changes indices
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModeListFilter {
+    /// Case-insensitive substring of the mode name; empty matches every mode
+    pub text: String,
+    /// `ModeSettings::cell_type` to show, or every type
+    pub cell_type: Option<i32>,
+}
+
+impl ModeListFilter {
+    pub fn is_active(&self) -> bool {
+        !self.text.trim().is_empty() || self.cell_type.is_some()
+    }
+
+    pub fn matches(&self, mode: &ModeSettings) -> bool {
+        let text = self.text.trim();
+        let name_matches = text.is_empty() || mode.name.to_lowercase().contains(&text.to_lowercase());
+        name_matches && self.cell_type.is_none_or(|cell_type| mode.cell_type == cell_type)
     }
 }
 
@@ -209,6 +319,65 @@ pub struct CurrentGenome {
     pub selected_mode_index: i32,
     pub show_mode_glow: bool,
     pub show_genome_graph: bool,
+    /// File the genome was loaded from, if any
+    pub file_path: Option<std::path::PathBuf>,
+    /// Reload the genome automatically when its file changes on disk
+    pub watch_file: bool,
+    /// All selected modes for bulk editing; always contains `selected_mode_index`
+    pub selected_modes: std::collections::BTreeSet<usize>,
+    /// Undo/redo history of genome edits
+    pub history: super::history::GenomeHistory,
+    /// Repairs made to the last loaded genome, shown until dismissed
+    pub load_warnings: Vec<String>,
+    /// Mode copied with "Copy Mode", kept across genome loads so it can be pasted elsewhere
+    pub mode_clipboard: Option<ModeSettings>,
+    /// Filter for the editor's mode list
+    pub mode_list_filter: ModeListFilter,
+}
+
+impl CurrentGenome {
+    /// Select a single mode, clearing any multi-selection
+    pub fn select_single_mode(&mut self, index: usize) {
+        self.selected_mode_index = index as i32;
+        self.selected_modes.clear();
+        self.selected_modes.insert(index);
+    }
+
+    /// Ctrl+click behaviour: add or remove a mode from the selection
+    pub fn toggle_mode_selection(&mut self, index: usize) {
+        if self.selected_modes.remove(&index) {
+            // Keep at least one mode selected and keep the primary selection inside the set
+            if let Some(&first) = self.selected_modes.iter().next() {
+                if self.selected_mode_index as usize == index {
+                    self.selected_mode_index = first as i32;
+                }
+            } else {
+                self.selected_modes.insert(index);
+            }
+        } else {
+            self.selected_modes.insert(index);
+            self.selected_mode_index = index as i32;
+        }
+    }
+
+    /// Replace the genome with one loaded from outside the editor, repairing it first
+    /// so the editor and simulation never see a genome without modes
+    pub fn load_genome(&mut self, mut genome: GenomeData) {
+        self.load_warnings = genome.repair();
+        for warning in &self.load_warnings {
+            eprintln!("Genome '{}': {}", genome.name, warning);
+        }
+        self.genome = genome;
+        self.validate_selection();
+    }
+
+    /// Bring the selection back in range after modes were added, removed or replaced
+    pub fn validate_selection(&mut self) {
+        let count = self.genome.modes.len();
+        self.selected_mode_index = self.selected_mode_index.clamp(0, (count as i32 - 1).max(0));
+        self.selected_modes.retain(|&i| i < count);
+        self.selected_modes.insert(self.selected_mode_index as usize);
+    }
 }
 
 impl Default for CurrentGenome {
@@ -218,6 +387,112 @@ impl Default for CurrentGenome {
             selected_mode_index: 0,
             show_mode_glow: false,
             show_genome_graph: false,
+            file_path: None,
+            watch_file: false,
+            selected_modes: std::collections::BTreeSet::from([0]),
+            history: super::history::GenomeHistory::default(),
+            load_warnings: Vec::new(),
+            mode_clipboard: None,
+            mode_list_filter: ModeListFilter::default(),
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_saved_before_cell_type_settings_loads_defaults() {
+        let mut json = serde_json::to_value(ModeSettings::new_self_splitting(0, "Old".to_string())).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        for key in ["light_absorption_rate", "engulf_radius", "digestion_rate"] {
+            fields.remove(key);
+        }
+
+        let mode: ModeSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(mode, ModeSettings::new_self_splitting(0, "Old".to_string()));
+    }
+
+    #[test]
+    fn test_mode_list_filter_matches_name_and_cell_type() {
+        let mut mode = ModeSettings::new_self_splitting(0, "Root Photocyte".to_string());
+        mode.cell_type = 2;
+        let filter = |text: &str, cell_type| ModeListFilter { text: text.to_string(), cell_type };
+
+        assert!(filter("", None).matches(&mode));
+        assert!(filter("  photo ", None).matches(&mode));
+        assert!(!filter("stem", None).matches(&mode));
+        assert!(filter("root", Some(2)).matches(&mode));
+        assert!(!filter("root", Some(1)).matches(&mode));
+        assert!(!filter("", None).is_active());
+    }
+
+    #[test]
+    fn test_repair_adds_default_mode_to_empty_genome() {
+        let mut genome: GenomeData = serde_json::from_str(r#"{"name": "Empty", "initial_mode": 0, "modes": []}"#).unwrap();
+
+        let warnings = genome.repair();
+
+        assert_eq!(genome.modes.len(), 1);
+        assert_eq!(genome.initial_mode, 0);
+        assert_eq!(genome.modes[0].child_a.mode_number, 0);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_repair_clamps_initial_mode() {
+        let mut genome = GenomeData { initial_mode: 5, ..GenomeData::default() };
+        assert_eq!(genome.repair().len(), 1);
+        assert_eq!(genome.initial_mode, 0);
+
+        genome.initial_mode = -2;
+        assert_eq!(genome.repair().len(), 1);
+        assert_eq!(genome.initial_mode, 0);
+    }
+
+    #[test]
+    fn test_repair_clamps_child_mode_numbers() {
+        let mut genome = GenomeData::default();
+        genome.modes[0].child_a.mode_number = 7;
+        genome.modes[0].child_b.mode_number = -1;
+
+        assert_eq!(genome.repair().len(), 2);
+        assert_eq!(genome.modes[0].child_a.mode_number, 0);
+        assert_eq!(genome.modes[0].child_b.mode_number, 0);
+    }
+
+    #[test]
+    fn test_repair_leaves_valid_genome_untouched() {
+        let mut genome = GenomeData::default();
+        assert!(genome.repair().is_empty());
+        assert_eq!(genome, GenomeData::default());
+    }
+
+    #[test]
+    fn test_load_genome_clamps_selection_and_records_warnings() {
+        let mut current = CurrentGenome::default();
+        current.genome.modes.push(ModeSettings::new_self_splitting(1, "Mode 1".to_string()));
+        current.select_single_mode(1);
+
+        current.load_genome(GenomeData { name: "Empty".to_string(), initial_mode: 3, modes: Vec::new() });
+
+        assert_eq!(current.genome.modes.len(), 1);
+        assert_eq!(current.genome.initial_mode, 0);
+        assert_eq!(current.selected_mode_index, 0);
+        assert_eq!(current.selected_modes, std::collections::BTreeSet::from([0]));
+        assert_eq!(current.load_warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_load_from_file_round_trips_single_mode_genome() {
+        let path = std::env::temp_dir().join(format!("biospheres_single_mode_{}.json", std::process::id()));
+        GenomeData::default().save_to_file(&path).unwrap();
+        let loaded = GenomeData::load_from_file(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let mut current = CurrentGenome::default();
+        current.load_genome(loaded.unwrap());
+        assert_eq!(current.genome, GenomeData::default());
+        assert!(current.load_warnings.is_empty());
+    }
+}
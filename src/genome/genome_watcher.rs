@@ -0,0 +1,117 @@
+use super::{CurrentGenome, GenomeData, GenomeNodeGraph};
+use notify::{RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+
+/// How long the file must be quiet before reloading; editors often write in several steps
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How long the reload toast stays on screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(2);
+
+/// Watches the current genome file and reloads it when it changes on disk
+#[derive(Default)]
+pub struct GenomeFileWatcher {
+    watched_path: Option<PathBuf>,
+    watcher: Option<notify::RecommendedWatcher>,
+    receiver: Option<Receiver<notify::Result<notify::Event>>>,
+    /// Time of the most recent change that hasn't been reloaded yet
+    pending_since: Option<Instant>,
+    /// Last reload result for the toast (message, is_error, when)
+    toast: Option<(String, bool, Instant)>,
+}
+
+impl GenomeFileWatcher {
+    /// Start or stop watching to match the genome's file path and live reload flag,
+    /// then reload the genome if the file changed
+    pub fn update(&mut self, current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph) {
+        let wanted = if current_genome.watch_file { current_genome.file_path.clone() } else { None };
+        if wanted != self.watched_path {
+            self.stop();
+            if let Some(path) = wanted {
+                if let Err(e) = self.watch(&path) {
+                    eprintln!("Failed to watch genome file {}: {}", path.display(), e);
+                    current_genome.watch_file = false;
+                }
+            }
+        }
+
+        if self.poll_changed() {
+            if let Some(path) = self.watched_path.clone() {
+                self.reload(&path, current_genome, node_graph);
+            }
+        }
+    }
+
+    /// Current toast message and how far through its lifetime it is (0..1)
+    pub fn toast(&self) -> Option<(&str, bool, f32)> {
+        let (message, is_error, shown_at) = self.toast.as_ref()?;
+        let progress = shown_at.elapsed().as_secs_f32() / TOAST_DURATION.as_secs_f32();
+        (progress < 1.0).then_some((message.as_str(), *is_error, progress))
+    }
+
+    fn watch(&mut self, path: &Path) -> notify::Result<()> {
+        let (sender, receiver) = channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        // Watch the directory: many editors save by writing a temp file and renaming it over the original
+        let directory = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+        self.watched_path = Some(path.to_path_buf());
+        self.watcher = Some(watcher);
+        self.receiver = Some(receiver);
+        self.pending_since = None;
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.watcher = None;
+        self.receiver = None;
+        self.watched_path = None;
+        self.pending_since = None;
+    }
+
+    /// Drain file events; returns true once the watched file has changed and then been quiet for the debounce period
+    fn poll_changed(&mut self) -> bool {
+        let (Some(receiver), Some(path)) = (self.receiver.as_ref(), self.watched_path.as_ref()) else {
+            return false;
+        };
+        let file_name = path.file_name();
+
+        for event in receiver.try_iter().flatten() {
+            let touches_file = event.paths.iter().any(|p| p.file_name() == file_name);
+            if touches_file && (event.kind.is_modify() || event.kind.is_create()) {
+                self.pending_since = Some(Instant::now());
+            }
+        }
+
+        match self.pending_since {
+            Some(since) if since.elapsed() >= RELOAD_DEBOUNCE => {
+                self.pending_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn reload(&mut self, path: &Path, current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph) {
+        match GenomeData::load_from_file(path) {
+            Ok(genome) => {
+                println!("Reloaded genome from {}", path.display());
+                current_genome.load_genome(genome);
+                // Node positions are restored by mode name during the rebuild
+                node_graph.mark_for_rebuild();
+                self.toast = Some(match current_genome.load_warnings.first() {
+                    Some(warning) => (format!("Reloaded {} with repairs: {}", current_genome.genome.name, warning), true, Instant::now()),
+                    None => (format!("Reloaded {}", current_genome.genome.name), false, Instant::now()),
+                });
+            }
+            Err(e) => {
+                // Keep the current genome; the file may be mid-edit
+                eprintln!("Failed to reload genome from {}: {}", path.display(), e);
+                self.toast = Some((format!("Reload failed: {}", e), true, Instant::now()));
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use super::GenomeData;
+
+/// Maximum number of undo steps kept
+const MAX_UNDO_STEPS: usize = 100;
+
+/// Snapshot-based undo/redo for the genome being edited.
+/// Changes are committed once no widget is active, so one slider drag or one bulk edit is one step.
+#[derive(Default)]
+pub struct GenomeHistory {
+    undo_stack: Vec<GenomeData>,
+    redo_stack: Vec<GenomeData>,
+    /// Genome as of the last committed step
+    committed: Option<GenomeData>,
+}
+
+impl GenomeHistory {
+    /// Record the genome after this frame's edits. Pass `interacting = true` while a widget is
+    /// still being edited so the whole interaction collapses into a single step.
+    pub fn track(&mut self, genome: &GenomeData, interacting: bool) {
+        let Some(committed) = &self.committed else {
+            self.committed = Some(genome.clone());
+            return;
+        };
+        if interacting || committed == genome {
+            return;
+        }
+
+        if let Some(previous) = self.committed.replace(genome.clone()) {
+            self.undo_stack.push(previous);
+        }
+        if self.undo_stack.len() > MAX_UNDO_STEPS {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Restore the previous step; returns false if there is nothing to undo
+    pub fn undo(&mut self, genome: &mut GenomeData) -> bool {
+        let Some(previous) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack.push(std::mem::replace(genome, previous.clone()));
+        self.committed = Some(previous);
+        true
+    }
+
+    /// Re-apply the last undone step; returns false if there is nothing to redo
+    pub fn redo(&mut self, genome: &mut GenomeData) -> bool {
+        let Some(next) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push(std::mem::replace(genome, next.clone()));
+        self.committed = Some(next);
+        true
+    }
+}
@@ -0,0 +1,132 @@
+use super::GenomeData;
+use base64::Engine;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Version byte at the start of every compact genome string. Bump it when the payload changes
+/// shape, and teach `import_compact` to migrate or reject the old versions.
+pub const COMPACT_FORMAT_VERSION: u8 = 1;
+
+/// Failures while reading or writing a genome file
+#[derive(Debug, Error)]
+pub enum GenomeError {
+    #[error("Could not access {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("{path} is not a valid genome file: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("Could not serialize genome: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+impl GenomeError {
+    pub(crate) fn io(path: &Path, source: std::io::Error) -> Self {
+        GenomeError::Io { path: path.to_path_buf(), source }
+    }
+}
+
+/// Write the genome to `path` as pretty-printed JSON
+pub fn save_genome(genome: &GenomeData, path: &Path) -> Result<(), GenomeError> {
+    let json = serde_json::to_string_pretty(genome)?;
+    std::fs::write(path, json).map_err(|source| GenomeError::io(path, source))
+}
+
+/// Read a genome from a JSON file. The result is returned as stored; load it through
+/// `CurrentGenome::load_genome`, which repairs out-of-range mode references.
+pub fn load_genome(path: &Path) -> Result<GenomeData, GenomeError> {
+    let json = std::fs::read_to_string(path).map_err(|source| GenomeError::io(path, source))?;
+    serde_json::from_str(&json).map_err(|source| GenomeError::Parse { path: path.to_path_buf(), source })
+}
+
+/// Failures while decoding a compact genome string
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("The genome string is empty")]
+    Empty,
+
+    #[error("Not a genome string: {0}")]
+    Encoding(#[from] base64::DecodeError),
+
+    #[error("Genome string version {0} is not supported (this build reads version {COMPACT_FORMAT_VERSION})")]
+    UnsupportedVersion(u8),
+
+    #[error("The genome string is damaged: {0}")]
+    Decompress(#[from] std::io::Error),
+
+    #[error("The genome string does not contain a valid genome: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+/// Encode the genome as a short, copy-pasteable string: a version byte followed by
+/// gzip-compressed JSON, all base64 encoded
+pub fn export_compact(genome: &GenomeData) -> String {
+    let json = serde_json::to_vec(genome).expect("genome data always serializes");
+    let mut encoder = GzEncoder::new(vec![COMPACT_FORMAT_VERSION], Compression::best());
+    encoder.write_all(&json).expect("writing to memory cannot fail");
+    let bytes = encoder.finish().expect("writing to memory cannot fail");
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+/// Decode a string made by `export_compact`. Like `load_genome`, the genome is returned unrepaired.
+pub fn import_compact(text: &str) -> Result<GenomeData, ImportError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(ImportError::Empty);
+    }
+    let bytes = base64::engine::general_purpose::STANDARD.decode(text)?;
+    let (&version, payload) = bytes.split_first().ok_or(ImportError::Empty)?;
+    if version != COMPACT_FORMAT_VERSION {
+        return Err(ImportError::UnsupportedVersion(version));
+    }
+    let mut json = Vec::new();
+    GzDecoder::new(payload).read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{ModeSettings, Vec3};
+
+    fn multi_mode_genome() -> GenomeData {
+        let mut genome = GenomeData { name: "Shared".to_string(), initial_mode: 1, modes: Vec::new() };
+        for index in 0..3 {
+            let mut mode = ModeSettings::new_self_splitting(index, format!("Mode {}", index));
+            mode.split_mass = 1.5 + index as f32 * 0.37;
+            mode.split_mass_min = Some(1.1);
+            mode.child_b.mode_number = (index + 1) % 3;
+            mode.color = Vec3::new(0.1, 0.7, 0.3);
+            genome.modes.push(mode);
+        }
+        genome
+    }
+
+    #[test]
+    fn test_compact_round_trip_is_lossless() {
+        let genome = multi_mode_genome();
+        let text = export_compact(&genome);
+
+        assert!(!text.contains(char::is_whitespace));
+        assert_eq!(import_compact(&format!("  {}\n", text)).unwrap(), genome);
+    }
+
+    #[test]
+    fn test_compact_import_rejects_bad_input() {
+        assert!(matches!(import_compact("   "), Err(ImportError::Empty)));
+        assert!(matches!(import_compact("not base64!"), Err(ImportError::Encoding(_))));
+
+        let mut bytes = base64::engine::general_purpose::STANDARD.decode(export_compact(&multi_mode_genome())).unwrap();
+        bytes[0] = COMPACT_FORMAT_VERSION + 1;
+        let future = base64::engine::general_purpose::STANDARD.encode(bytes);
+        assert!(matches!(import_compact(&future), Err(ImportError::UnsupportedVersion(v)) if v == COMPACT_FORMAT_VERSION + 1));
+    }
+}
@@ -1,5 +1,15 @@
+pub mod analysis;
+pub mod autosave;
+pub mod diff;
+pub mod file_io;
 pub mod genome_data;
+pub mod genome_watcher;
+pub mod history;
+pub mod io;
 pub mod node_graph;
+pub mod text_format;
+pub mod validation;
 
 pub use genome_data::*;
-pub use node_graph::*;
\ No newline at end of file
+pub use node_graph::*;
+pub use validation::{validate, Severity, ValidationFix, ValidationIssue};
\ No newline at end of file
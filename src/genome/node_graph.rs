@@ -1,4 +1,32 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Distance between neighbouring node origins in automatic layouts
+const NODE_SPACING_X: f32 = 250.0;
+const NODE_SPACING_Y: f32 = 200.0;
+/// Editor-space origin of automatic layouts
+const START_X: f32 = 50.0;
+const START_Y: f32 = 50.0;
+
+/// Automatic arrangement of the genome graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphLayout {
+    /// Modes in index order, four per row
+    #[default]
+    Grid,
+    /// Division tree from the initial mode downwards
+    Tree,
+}
+
+impl GraphLayout {
+    pub const ALL: [GraphLayout; 2] = [GraphLayout::Grid, GraphLayout::Tree];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphLayout::Grid => "Grid",
+            GraphLayout::Tree => "Tree",
+        }
+    }
+}
 
 /// Node graph representation of genome modes
 pub struct GenomeNodeGraph {
@@ -94,12 +122,16 @@ impl GenomeNodeGraph {
         self.needs_layout = true;
     }
 
+    /// Arrange the nodes with the given layout
+    pub fn calculate_layout(&mut self, layout: GraphLayout, initial_mode: i32) {
+        match layout {
+            GraphLayout::Grid => self.calculate_grid_layout(),
+            GraphLayout::Tree => self.calculate_tree_layout(initial_mode),
+        }
+    }
+
     /// Calculate automatic layout for nodes in a grid pattern
     pub fn calculate_grid_layout(&mut self) {
-        const NODE_SPACING_X: f32 = 250.0;
-        const NODE_SPACING_Y: f32 = 200.0;
-        const START_X: f32 = 50.0;
-        const START_Y: f32 = 50.0;
         const COLUMNS: usize = 4;
 
         let mut sorted_nodes: Vec<i32> = self.node_to_mode.keys().copied().collect();
@@ -116,6 +148,76 @@ impl GenomeNodeGraph {
         self.needs_layout = false;
     }
 
+    /// Lay the nodes out as a top-down tree rooted at the initial mode: each mode sits one row
+    /// below the first mode that divides into it, with Child A's subtree left of Child B's so
+    /// tree links never cross. Links back up the tree and self-links add no rows or columns.
+    /// Modes the initial mode can never reach are stacked in a column to the right.
+    pub fn calculate_tree_layout(&mut self, initial_mode: i32) {
+        let mut modes: Vec<usize> = self.node_to_mode.values().copied().collect();
+        modes.sort_unstable();
+        let Some(&first_mode) = modes.first() else {
+            self.needs_layout = false;
+            return;
+        };
+        let root = usize::try_from(initial_mode)
+            .ok()
+            .and_then(|mode| self.get_node_for_mode(mode))
+            .or_else(|| self.get_node_for_mode(first_mode));
+
+        // Child A before Child B, so siblings keep their pin order
+        let mut outgoing: HashMap<i32, Vec<(bool, i32)>> = HashMap::new();
+        for &(from, to, is_child_a) in &self.links {
+            if from != to {
+                outgoing.entry(from).or_default().push((!is_child_a, to));
+            }
+        }
+        for targets in outgoing.values_mut() {
+            targets.sort_unstable();
+        }
+
+        // Breadth-first spanning tree, so every mode sits at its shortest division depth
+        let mut tree_children: HashMap<i32, Vec<i32>> = HashMap::new();
+        let mut depth: HashMap<i32, usize> = HashMap::new();
+        let mut queue = VecDeque::new();
+        if let Some(root) = root {
+            depth.insert(root, 0);
+            queue.push_back(root);
+        }
+        while let Some(node) = queue.pop_front() {
+            for &(_, child) in outgoing.get(&node).into_iter().flatten() {
+                if !depth.contains_key(&child) {
+                    depth.insert(child, depth[&node] + 1);
+                    tree_children.entry(node).or_default().push(child);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        // Leaves take one column each; a parent is centred over its children's columns
+        let mut next_column = 0.0;
+        if let Some(root) = root {
+            let positions = &mut self.node_positions;
+            place_subtree(root, 0, &tree_children, &mut next_column, &mut |node, column, row| {
+                positions.insert(node, (START_X + column * NODE_SPACING_X, START_Y + row as f32 * NODE_SPACING_Y));
+            });
+        }
+
+        let reached: HashSet<i32> = depth.keys().copied().collect();
+        let unreachable_x = START_X + next_column.max(1.0) * NODE_SPACING_X;
+        let mut row = 0;
+        for mode in modes {
+            let Some(node) = self.get_node_for_mode(mode) else {
+                continue;
+            };
+            if !reached.contains(&node) {
+                self.node_positions.insert(node, (unreachable_x, START_Y + row as f32 * NODE_SPACING_Y));
+                row += 1;
+            }
+        }
+
+        self.needs_layout = false;
+    }
+
     /// Get position for a node
     pub fn get_node_position(&self, node_id: i32) -> Option<(f32, f32)> {
         self.node_positions.get(&node_id).copied()
@@ -125,4 +227,65 @@ impl GenomeNodeGraph {
     pub fn set_node_position(&mut self, node_id: i32, x: f32, y: f32) {
         self.node_positions.insert(node_id, (x, y));
     }
-}
\ No newline at end of file
+}
+/// Place a spanning-tree node and its descendants, handing out leaf columns left to right
+fn place_subtree(
+    node: i32,
+    row: usize,
+    tree_children: &HashMap<i32, Vec<i32>>,
+    next_column: &mut f32,
+    place: &mut impl FnMut(i32, f32, usize),
+) -> f32 {
+    let column = match tree_children.get(&node) {
+        Some(children) if !children.is_empty() => {
+            let columns: Vec<f32> = children
+                .iter()
+                .map(|&child| place_subtree(child, row + 1, tree_children, next_column, place))
+                .collect();
+            (columns[0] + columns[columns.len() - 1]) * 0.5
+        }
+        _ => {
+            let column = *next_column;
+            *next_column += 1.0;
+            column
+        }
+    };
+    place(node, column, row);
+    column
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Graph with one node per mode and the given (parent, child, is_child_a) links between modes
+    fn graph(mode_count: usize, links: &[(usize, usize, bool)]) -> GenomeNodeGraph {
+        let mut graph = GenomeNodeGraph::default();
+        for mode in 0..mode_count {
+            graph.create_node(mode);
+        }
+        for &(from, to, is_child_a) in links {
+            let (from, to) = (graph.get_node_for_mode(from).unwrap(), graph.get_node_for_mode(to).unwrap());
+            graph.add_link(from, to, is_child_a);
+        }
+        graph
+    }
+
+    fn position(graph: &GenomeNodeGraph, mode: usize) -> (f32, f32) {
+        graph.get_node_position(graph.get_node_for_mode(mode).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_tree_layout_places_children_below_and_orphans_aside() {
+        // 0 -> A:1, B:2; 1 -> A:1 (self), B:0 (back up); 3 is never reached
+        let mut graph = graph(4, &[(0, 1, true), (0, 2, false), (1, 1, true), (1, 0, false)]);
+        graph.calculate_tree_layout(0);
+
+        let (root, a, b, orphan) = (position(&graph, 0), position(&graph, 1), position(&graph, 2), position(&graph, 3));
+        assert!(a.1 > root.1 && (a.1 - b.1).abs() < 1e-3, "children share the row below the root");
+        assert!(a.0 < b.0, "Child A sits left of Child B");
+        assert!((root.0 - (a.0 + b.0) * 0.5).abs() < 1e-3, "parent is centred over its children");
+        assert!(orphan.0 > b.0, "unreachable modes go in a column to the right");
+        assert!(!graph.needs_layout);
+    }
+}
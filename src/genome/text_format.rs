@@ -0,0 +1,565 @@
+//! Compact text format for hand-authoring genomes.
+//!
+//! ```text
+//! genome "Two Tissue"
+//! initial = Stem
+//!
+//! mode Stem {
+//!     type = test  split_mass = 2.0  split_interval = 8
+//!     childA = Stem  childB = Leaf
+//!     color = 0.2, 0.8, 0.3
+//! }
+//!
+//! mode Leaf { type = flagellocyte  swim_force = 0.8  adhesion.break_force = 12 }
+//! ```
+//!
+//! Every `ModeSettings` field can be set by its field name; `adhesion.<field>` and
+//! `child_a.<field>` / `child_b.<field>` reach the nested settings. Mode references accept
+//! a mode name or index, and `#` starts a comment. Unset fields keep the defaults of
+//! `ModeSettings::new_self_splitting`, so children split into their own mode by default.
+
+use super::{GenomeData, ModeSettings, Quat, Vec3};
+use thiserror::Error;
+
+/// A syntax or value error in genome text, with the 1-based line it was found on
+#[derive(Debug, Error)]
+#[error("line {line}: {message}")]
+pub struct GenomeParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+fn error<T>(line: usize, message: impl Into<String>) -> Result<T, GenomeParseError> {
+    Err(GenomeParseError { line, message: message.into() })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// Bare word: keyword, key, mode name or number
+    Word(String),
+    /// Double-quoted string
+    Str(String),
+    Equals,
+    Comma,
+    OpenBrace,
+    CloseBrace,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, GenomeParseError> {
+    let mut tokens = Vec::new();
+    for (line_index, line_text) in text.lines().enumerate() {
+        let line = line_index + 1;
+        let mut chars = line_text.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            let kind = match c {
+                '#' => break,
+                c if c.is_whitespace() => {
+                    chars.next();
+                    continue;
+                }
+                '=' | ',' | '{' | '}' => {
+                    chars.next();
+                    match c {
+                        '=' => TokenKind::Equals,
+                        ',' => TokenKind::Comma,
+                        '{' => TokenKind::OpenBrace,
+                        _ => TokenKind::CloseBrace,
+                    }
+                }
+                '"' => {
+                    chars.next();
+                    let mut value = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(c) => value.push(c),
+                            None => return error(line, "unterminated string"),
+                        }
+                    }
+                    TokenKind::Str(value)
+                }
+                _ => {
+                    let mut word = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if c.is_whitespace() || matches!(c, '=' | ',' | '{' | '}' | '"' | '#') {
+                            break;
+                        }
+                        word.push(c);
+                        chars.next();
+                    }
+                    TokenKind::Word(word)
+                }
+            };
+            tokens.push(Token { kind, line });
+        }
+    }
+    Ok(tokens)
+}
+
+/// Reference to another mode, resolved once every mode name is known
+#[derive(Debug, Clone)]
+enum ModeRef {
+    Name(String),
+    Index(i32),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RefField {
+    ChildA,
+    ChildB,
+    ModeAAfterSplits,
+    ModeBAfterSplits,
+}
+
+struct PendingRef {
+    mode: usize,
+    field: RefField,
+    target: ModeRef,
+    line: usize,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    last_line: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos).map(|t| &t.kind)
+    }
+
+    fn expect(&mut self, kind: TokenKind, what: &str) -> Result<usize, GenomeParseError> {
+        match self.next() {
+            Some(token) if token.kind == kind => Ok(token.line),
+            Some(token) => error(token.line, format!("expected {}", what)),
+            None => error(self.last_line, format!("expected {} before end of input", what)),
+        }
+    }
+
+    /// A bare word or quoted string
+    fn name(&mut self, what: &str) -> Result<(String, usize), GenomeParseError> {
+        match self.next() {
+            Some(Token { kind: TokenKind::Word(s) | TokenKind::Str(s), line }) => Ok((s, line)),
+            Some(token) => error(token.line, format!("expected {}", what)),
+            None => error(self.last_line, format!("expected {} before end of input", what)),
+        }
+    }
+
+    /// One or more comma-separated values
+    fn values(&mut self) -> Result<(Vec<String>, usize), GenomeParseError> {
+        let (first, line) = self.name("a value")?;
+        let mut values = vec![first];
+        while self.peek() == Some(&TokenKind::Comma) {
+            self.pos += 1;
+            values.push(self.name("a value after ','")?.0);
+        }
+        Ok((values, line))
+    }
+}
+
+fn single<'a>(key: &str, values: &'a [String], line: usize) -> Result<&'a str, GenomeParseError> {
+    match values {
+        [value] => Ok(value),
+        _ => error(line, format!("'{}' takes a single value", key)),
+    }
+}
+
+fn parse_f32(key: &str, values: &[String], line: usize) -> Result<f32, GenomeParseError> {
+    let value = single(key, values, line)?;
+    value
+        .parse()
+        .or_else(|_| error(line, format!("'{}' expects a number, got '{}'", key, value)))
+}
+
+fn parse_optional_f32(key: &str, values: &[String], line: usize) -> Result<Option<f32>, GenomeParseError> {
+    if single(key, values, line)? == "none" {
+        return Ok(None);
+    }
+    parse_f32(key, values, line).map(Some)
+}
+
+fn parse_i32(key: &str, values: &[String], line: usize) -> Result<i32, GenomeParseError> {
+    let value = single(key, values, line)?;
+    value
+        .parse()
+        .or_else(|_| error(line, format!("'{}' expects a whole number, got '{}'", key, value)))
+}
+
+fn parse_bool(key: &str, values: &[String], line: usize) -> Result<bool, GenomeParseError> {
+    match single(key, values, line)? {
+        "true" | "yes" | "on" => Ok(true),
+        "false" | "no" | "off" => Ok(false),
+        other => error(line, format!("'{}' expects true or false, got '{}'", key, other)),
+    }
+}
+
+fn parse_floats<const N: usize>(key: &str, values: &[String], line: usize) -> Result<[f32; N], GenomeParseError> {
+    if values.len() != N {
+        return error(line, format!("'{}' expects {} comma-separated numbers", key, N));
+    }
+    let mut out = [0.0; N];
+    for (slot, value) in out.iter_mut().zip(values) {
+        *slot = parse_f32(key, std::slice::from_ref(value), line)?;
+    }
+    Ok(out)
+}
+
+fn parse_vec3(key: &str, values: &[String], line: usize) -> Result<Vec3, GenomeParseError> {
+    let [x, y, z] = parse_floats::<3>(key, values, line)?;
+    Ok(Vec3::new(x, y, z))
+}
+
+fn parse_cell_type(key: &str, values: &[String], line: usize) -> Result<i32, GenomeParseError> {
+    match single(key, values, line)?.to_ascii_lowercase().as_str() {
+        "test" => Ok(0),
+        "flagellocyte" => Ok(1),
+        "photocyte" => Ok(2),
+        "phagocyte" => Ok(3),
+        _ => parse_i32(key, values, line),
+    }
+}
+
+fn parse_mode_ref(key: &str, values: &[String], line: usize) -> Result<ModeRef, GenomeParseError> {
+    let value = single(key, values, line)?;
+    if value == "none" {
+        return Ok(ModeRef::Index(-1));
+    }
+    Ok(match value.parse() {
+        Ok(index) => ModeRef::Index(index),
+        Err(_) => ModeRef::Name(value.to_string()),
+    })
+}
+
+/// Apply one `key = value` assignment inside a mode block
+fn assign(
+    mode: &mut ModeSettings,
+    mode_index: usize,
+    key: &str,
+    values: &[String],
+    line: usize,
+    refs: &mut Vec<PendingRef>,
+) -> Result<(), GenomeParseError> {
+    let mut reference = |field| -> Result<(), GenomeParseError> {
+        let target = parse_mode_ref(key, values, line)?;
+        refs.push(PendingRef { mode: mode_index, field, target, line });
+        Ok(())
+    };
+
+    // Accept the camelCase child spelling and the `type` shorthand
+    let normalized = key
+        .replacen("childA", "child_a", 1)
+        .replacen("childB", "child_b", 1);
+    let normalized = match normalized.as_str() {
+        "type" => "cell_type",
+        "child_a" => "child_a.mode",
+        "child_b" => "child_b.mode",
+        other => other,
+    };
+
+    let a = &mut mode.adhesion_settings;
+    match normalized {
+        "default_name" => mode.default_name = single(key, values, line)?.to_string(),
+        "cell_type" => mode.cell_type = parse_cell_type(key, values, line)?,
+        "color" => mode.color = parse_vec3(key, values, line)?,
+        "opacity" => mode.opacity = parse_f32(key, values, line)?,
+        "emissive" => mode.emissive = parse_f32(key, values, line)?,
+
+        "split_mass" => mode.split_mass = parse_f32(key, values, line)?,
+        "split_mass_min" => mode.split_mass_min = parse_optional_f32(key, values, line)?,
+        "split_interval" => mode.split_interval = parse_f32(key, values, line)?,
+        "split_interval_min" => mode.split_interval_min = parse_optional_f32(key, values, line)?,
+        "split_ratio" => mode.split_ratio = parse_f32(key, values, line)?,
+        "max_splits" => mode.max_splits = parse_i32(key, values, line)?,
+        "mode_a_after_splits" => reference(RefField::ModeAAfterSplits)?,
+        "mode_b_after_splits" => reference(RefField::ModeBAfterSplits)?,
+
+        "nutrient_gain_rate" => mode.nutrient_gain_rate = parse_f32(key, values, line)?,
+        "max_cell_size" => mode.max_cell_size = parse_f32(key, values, line)?,
+        "nutrient_priority" => mode.nutrient_priority = parse_f32(key, values, line)?,
+        "prioritize_when_low" => mode.prioritize_when_low = parse_bool(key, values, line)?,
+
+        "swim_force" => mode.swim_force = parse_f32(key, values, line)?,
+        "light_absorption_rate" => mode.light_absorption_rate = parse_f32(key, values, line)?,
+        "engulf_radius" => mode.engulf_radius = parse_f32(key, values, line)?,
+        "digestion_rate" => mode.digestion_rate = parse_f32(key, values, line)?,
+
+        "parent_split_direction" => mode.parent_split_direction = parse_vec3(key, values, line)?,
+        "enable_parent_angle_snapping" => mode.enable_parent_angle_snapping = parse_bool(key, values, line)?,
+        "split_angle_jitter" => mode.split_angle_jitter = parse_f32(key, values, line)?,
+        "terminal" => mode.terminal = parse_bool(key, values, line)?,
+        "terminal_stops_nutrients" => mode.terminal_stops_nutrients = parse_bool(key, values, line)?,
+
+        "max_adhesions" => mode.max_adhesions = parse_i32(key, values, line)?,
+        "min_adhesions" => mode.min_adhesions = parse_i32(key, values, line)?,
+        "parent_make_adhesion" => mode.parent_make_adhesion = parse_bool(key, values, line)?,
+
+        "adhesion.can_break" => a.can_break = parse_bool(key, values, line)?,
+        "adhesion.break_force" => a.break_force = parse_f32(key, values, line)?,
+        "adhesion.rest_length" => a.rest_length = parse_f32(key, values, line)?,
+        "adhesion.auto_rest_length" => a.auto_rest_length = parse_bool(key, values, line)?,
+        "adhesion.linear_spring_stiffness" => a.linear_spring_stiffness = parse_f32(key, values, line)?,
+        "adhesion.linear_spring_damping" => a.linear_spring_damping = parse_f32(key, values, line)?,
+        "adhesion.orientation_spring_stiffness" => a.orientation_spring_stiffness = parse_f32(key, values, line)?,
+        "adhesion.orientation_spring_damping" => a.orientation_spring_damping = parse_f32(key, values, line)?,
+        "adhesion.max_angular_deviation" => a.max_angular_deviation = parse_f32(key, values, line)?,
+        "adhesion.enable_twist_constraint" => a.enable_twist_constraint = parse_bool(key, values, line)?,
+        "adhesion.twist_constraint_stiffness" => a.twist_constraint_stiffness = parse_f32(key, values, line)?,
+        "adhesion.twist_constraint_damping" => a.twist_constraint_damping = parse_f32(key, values, line)?,
+
+        "child_a.mode" => reference(RefField::ChildA)?,
+        "child_b.mode" => reference(RefField::ChildB)?,
+        "child_a.orientation" | "child_b.orientation" => {
+            let [x, y, z, w] = parse_floats::<4>(key, values, line)?;
+            let child = if normalized.starts_with("child_a") { &mut mode.child_a } else { &mut mode.child_b };
+            child.orientation = Quat { x, y, z, w };
+        }
+        "child_a.keep_adhesion" => mode.child_a.keep_adhesion = parse_bool(key, values, line)?,
+        "child_b.keep_adhesion" => mode.child_b.keep_adhesion = parse_bool(key, values, line)?,
+        "child_a.enable_angle_snapping" => mode.child_a.enable_angle_snapping = parse_bool(key, values, line)?,
+        "child_b.enable_angle_snapping" => mode.child_b.enable_angle_snapping = parse_bool(key, values, line)?,
+
+        _ => return error(line, format!("unknown mode setting '{}'", key)),
+    }
+    Ok(())
+}
+
+fn resolve(target: &ModeRef, names: &[String], line: usize) -> Result<i32, GenomeParseError> {
+    match target {
+        ModeRef::Index(index) if (-1..names.len() as i32).contains(index) => Ok(*index),
+        ModeRef::Index(index) => error(line, format!("mode index {} is out of range", index)),
+        ModeRef::Name(name) => match names.iter().position(|n| n == name) {
+            Some(index) => Ok(index as i32),
+            None => error(line, format!("no mode named '{}'", name)),
+        },
+    }
+}
+
+/// Compile genome text into `GenomeData`
+pub fn parse_genome_text(text: &str) -> Result<GenomeData, GenomeParseError> {
+    let tokens = tokenize(text)?;
+    let last_line = text.lines().count().max(1);
+    let mut parser = Parser { tokens, pos: 0, last_line };
+
+    let mut genome_name = None;
+    let mut initial: Option<(ModeRef, usize)> = None;
+    let mut modes: Vec<ModeSettings> = Vec::new();
+    let mut refs = Vec::new();
+
+    while let Some(token) = parser.next() {
+        let TokenKind::Word(keyword) = token.kind else {
+            return error(token.line, "expected 'genome', 'initial' or 'mode'");
+        };
+        match keyword.as_str() {
+            "genome" => {
+                if parser.peek() == Some(&TokenKind::Equals) {
+                    parser.pos += 1;
+                }
+                genome_name = Some(parser.name("a genome name")?.0);
+            }
+            "initial" => {
+                parser.expect(TokenKind::Equals, "'=' after 'initial'")?;
+                let (values, line) = parser.values()?;
+                initial = Some((parse_mode_ref("initial", &values, line)?, line));
+            }
+            "mode" => {
+                let (name, line) = parser.name("a mode name")?;
+                if modes.iter().any(|m| m.name == name) {
+                    return error(line, format!("mode '{}' is defined twice", name));
+                }
+                let mode_index = modes.len();
+                let mut mode = ModeSettings::new_self_splitting(mode_index as i32, name);
+
+                parser.expect(TokenKind::OpenBrace, "'{' after the mode name")?;
+                loop {
+                    if parser.peek() == Some(&TokenKind::CloseBrace) {
+                        parser.pos += 1;
+                        break;
+                    }
+                    let (key, _) = parser.name("a setting name or '}'")?;
+                    parser.expect(TokenKind::Equals, &format!("'=' after '{}'", key))?;
+                    let (values, line) = parser.values()?;
+                    assign(&mut mode, mode_index, &key, &values, line, &mut refs)?;
+                }
+                modes.push(mode);
+            }
+            other => return error(token.line, format!("expected 'genome', 'initial' or 'mode', got '{}'", other)),
+        }
+    }
+
+    if modes.is_empty() {
+        return error(last_line, "the genome defines no modes");
+    }
+
+    let names: Vec<String> = modes.iter().map(|m| m.name.clone()).collect();
+    for PendingRef { mode, field, target, line } in refs {
+        let index = resolve(&target, &names, line)?;
+        if index < 0 && matches!(field, RefField::ChildA | RefField::ChildB) {
+            return error(line, "a child mode cannot be 'none'");
+        }
+        let mode = &mut modes[mode];
+        match field {
+            RefField::ChildA => mode.child_a.mode_number = index,
+            RefField::ChildB => mode.child_b.mode_number = index,
+            // -1 means "no mode change after max splits"
+            RefField::ModeAAfterSplits => mode.mode_a_after_splits = index,
+            RefField::ModeBAfterSplits => mode.mode_b_after_splits = index,
+        }
+    }
+
+    let initial_mode = match initial {
+        Some((target, line)) => {
+            let index = resolve(&target, &names, line)?;
+            if index < 0 {
+                return error(line, "the initial mode cannot be 'none'");
+            }
+            index
+        }
+        None => 0,
+    };
+
+    Ok(GenomeData {
+        name: genome_name.unwrap_or_else(|| "Imported Genome".to_string()),
+        initial_mode,
+        modes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error_line(text: &str) -> (usize, String) {
+        let error = parse_genome_text(text).unwrap_err();
+        (error.line, error.message)
+    }
+
+    /// Leaf values of a serialized mode by dotted path, e.g. `adhesion_settings.break_force`
+    fn leaves(prefix: &str, value: &serde_json::Value, out: &mut Vec<(String, serde_json::Value)>) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (key, field) in fields {
+                    let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                    leaves(&path, field, out);
+                }
+            }
+            leaf => out.push((prefix.to_string(), leaf.clone())),
+        }
+    }
+
+    #[test]
+    fn test_request_example_compiles_to_genome() {
+        let text = "genome \"Two Tissue\"\n\
+                    initial = Stem\n\
+                    mode Stem { type=test split_mass=2.0 split_interval=8 childA=Stem childB=Leaf }\n\
+                    mode Leaf { type = flagellocyte  swim_force = 0.8  # fast swimmer\n\
+                        adhesion.break_force = 12 }\n";
+        let genome = parse_genome_text(text).unwrap();
+
+        let mut stem = ModeSettings::new_self_splitting(0, "Stem".to_string());
+        stem.split_mass = 2.0;
+        stem.split_interval = 8.0;
+        stem.child_b.mode_number = 1;
+        let mut leaf = ModeSettings::new_self_splitting(1, "Leaf".to_string());
+        leaf.cell_type = 1;
+        leaf.swim_force = 0.8;
+        leaf.adhesion_settings.break_force = 12.0;
+        assert_eq!(genome, GenomeData { name: "Two Tissue".to_string(), initial_mode: 0, modes: vec![stem, leaf] });
+    }
+
+    #[test]
+    fn test_mode_references_by_name_index_none_and_forward() {
+        let text = "initial = Leaf\n\
+                    mode Stem { childA = Leaf  childB = 0  mode_a_after_splits = none  mode_b_after_splits = 1 }\n\
+                    mode Leaf { }\n";
+        let genome = parse_genome_text(text).unwrap();
+        let stem = &genome.modes[0];
+        assert_eq!(genome.initial_mode, 1);
+        assert_eq!(genome.name, "Imported Genome");
+        assert_eq!((stem.child_a.mode_number, stem.child_b.mode_number), (1, 0));
+        assert_eq!((stem.mode_a_after_splits, stem.mode_b_after_splits), (-1, 1));
+        // Unset children split into their own mode
+        assert_eq!(genome.modes[1].child_a.mode_number, 1);
+
+        assert_eq!(parse_error_line("mode A {}\nmode B { childA = Missing }").0, 2);
+        assert_eq!(parse_error_line("mode A {\n  childB = 5\n}").0, 2);
+        assert_eq!(parse_error_line("mode A {\n  childA = none\n}").0, 2);
+        assert_eq!(parse_error_line("\ninitial = none\nmode A {}").0, 2);
+    }
+
+    #[test]
+    fn test_errors_report_their_line() {
+        let (line, message) = parse_error_line("mode A { }\n\nmode A { }");
+        assert_eq!(line, 3);
+        assert!(message.contains("defined twice"), "{}", message);
+
+        let (line, message) = parse_error_line("mode A {\n  split_mass = 2\n  bogus = 1\n}");
+        assert_eq!(line, 3);
+        assert!(message.contains("'bogus'"), "{}", message);
+
+        let (line, message) = parse_error_line("mode A { }\ngenome \"Unfinished\nmode B { }");
+        assert_eq!(line, 2);
+        assert!(message.contains("unterminated string"), "{}", message);
+
+        let (line, message) = parse_error_line("mode A {\n  split_mass = 2\n  split_ratio = 0.4\n");
+        assert_eq!(line, 3);
+        assert!(message.contains("before end of input"), "{}", message);
+
+        let (line, message) = parse_error_line("mode A {\n\n  split_mass = lots\n}");
+        assert_eq!(line, 3);
+        assert!(message.contains("expects a number"), "{}", message);
+    }
+
+    #[test]
+    fn test_every_mode_setting_can_be_set() {
+        // One non-default value for every field; a new ModeSettings field without a key fails this test
+        let text = "mode A {
+            default_name = Renamed  type = photocyte  color = 0.1, 0.2, 0.3  opacity = 0.5  emissive = 0.25
+            split_mass = 3.5  split_mass_min = 1.5  split_interval = 4  split_interval_min = 2
+            split_ratio = 0.3  max_splits = 5  mode_a_after_splits = B  mode_b_after_splits = 1
+            terminal = true  terminal_stops_nutrients = yes
+            nutrient_gain_rate = 0.7  max_cell_size = 2.5  nutrient_priority = 3  prioritize_when_low = off
+            swim_force = 1.5  light_absorption_rate = 0.9  engulf_radius = 0.8  digestion_rate = 1.1
+            parent_split_direction = 10, 20, 30  enable_parent_angle_snapping = true  split_angle_jitter = 15
+            max_adhesions = 4  min_adhesions = 1  parent_make_adhesion = true
+            adhesion.can_break = true  adhesion.break_force = 25  adhesion.rest_length = 1.2
+            adhesion.auto_rest_length = true  adhesion.linear_spring_stiffness = 80
+            adhesion.linear_spring_damping = 4  adhesion.orientation_spring_stiffness = 7
+            adhesion.orientation_spring_damping = 3  adhesion.max_angular_deviation = 45
+            adhesion.enable_twist_constraint = true  adhesion.twist_constraint_stiffness = 6
+            adhesion.twist_constraint_damping = 2.5
+            childA = B  childB = 1
+            child_a.orientation = 0.1, 0.2, 0.3, 0.9  child_b.orientation = 0.2, 0.1, 0.4, 0.8
+            child_a.keep_adhesion = true  child_b.keep_adhesion = true
+            child_a.enable_angle_snapping = true  child_b.enable_angle_snapping = true
+        }
+        mode B { }";
+        let genome = parse_genome_text(text).unwrap();
+
+        let mut parsed = Vec::new();
+        leaves("", &serde_json::to_value(&genome.modes[0]).unwrap(), &mut parsed);
+        let mut defaults = Vec::new();
+        leaves("", &serde_json::to_value(ModeSettings::new_self_splitting(0, "A".to_string())).unwrap(), &mut defaults);
+
+        assert_eq!(parsed.len(), defaults.len());
+        let unchanged: Vec<&str> = parsed
+            .iter()
+            .zip(&defaults)
+            .filter(|((path, value), (_, default))| path != "name" && value == default)
+            .map(|((path, _), _)| path.as_str())
+            .collect();
+        assert!(unchanged.is_empty(), "fields the text format could not set: {:?}", unchanged);
+    }
+}
@@ -0,0 +1,240 @@
+use super::analysis::{split_deadlocks, SplitDeadlock};
+use super::{GenomeData, ModeSettings};
+
+/// Split mass / interval ranges narrower than this are treated as a single value
+const COLLAPSED_RANGE_EPSILON: f32 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The genome runs, but probably not the way it was meant to
+    Warning,
+    /// Part of the genome can never do anything
+    Error,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+        }
+    }
+
+    pub fn color(&self) -> [f32; 4] {
+        match self {
+            Severity::Warning => [1.0, 0.8, 0.2, 1.0],
+            Severity::Error => [1.0, 0.35, 0.35, 1.0],
+        }
+    }
+}
+
+/// The obvious correction for a validation issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationFix {
+    /// Run `GenomeData::repair` to add a mode or clamp dangling mode references
+    Repair,
+    /// Raise max adhesions above min adhesions
+    RaiseMaxAdhesions(usize),
+    /// Raise max adhesions so a cell bonded to its sibling can still split
+    AllowSiblingBond(usize),
+    /// Drop the split mass range and keep the fixed split mass
+    ClearSplitMassRange(usize),
+    /// Drop the split interval range and keep the fixed split interval
+    ClearSplitIntervalRange(usize),
+    /// Restore the default nutrient gain rate
+    DefaultNutrientGain(usize),
+}
+
+impl ValidationFix {
+    /// Tooltip for the "Fix" button
+    pub fn description(&self) -> &'static str {
+        match self {
+            ValidationFix::Repair => "Add a default mode or point missing mode references at the nearest existing mode",
+            ValidationFix::RaiseMaxAdhesions(_) => "Set max connections to one more than min connections",
+            ValidationFix::AllowSiblingBond(_) => "Set max connections to 2, leaving room for a bond beside the sibling bond",
+            ValidationFix::ClearSplitMassRange(_) => "Remove the range and keep the fixed split mass",
+            ValidationFix::ClearSplitIntervalRange(_) => "Remove the range and keep the fixed split interval",
+            ValidationFix::DefaultNutrientGain(_) => "Restore the default nutrient gain rate",
+        }
+    }
+
+    pub fn apply(&self, genome: &mut GenomeData) {
+        match *self {
+            ValidationFix::Repair => {
+                genome.repair();
+            }
+            ValidationFix::RaiseMaxAdhesions(index) => {
+                if let Some(mode) = genome.modes.get_mut(index) {
+                    mode.max_adhesions = mode.min_adhesions + 1;
+                }
+            }
+            ValidationFix::AllowSiblingBond(index) => {
+                if let Some(mode) = genome.modes.get_mut(index) {
+                    mode.max_adhesions = mode.max_adhesions.max(mode.min_adhesions + 1).max(2);
+                }
+            }
+            ValidationFix::ClearSplitMassRange(index) => {
+                if let Some(mode) = genome.modes.get_mut(index) {
+                    mode.split_mass_min = None;
+                }
+            }
+            ValidationFix::ClearSplitIntervalRange(index) => {
+                if let Some(mode) = genome.modes.get_mut(index) {
+                    mode.split_interval_min = None;
+                }
+            }
+            ValidationFix::DefaultNutrientGain(index) => {
+                if let Some(mode) = genome.modes.get_mut(index) {
+                    mode.nutrient_gain_rate = ModeSettings::new_self_splitting(0, String::new()).nutrient_gain_rate;
+                }
+            }
+        }
+    }
+}
+
+/// A problem found by `validate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// Mode the issue belongs to, or `None` for genome-wide issues
+    pub mode_index: Option<usize>,
+    pub message: String,
+    pub fix: Option<ValidationFix>,
+}
+
+impl ValidationIssue {
+    fn new(severity: Severity, mode_index: Option<usize>, message: String, fix: Option<ValidationFix>) -> Self {
+        Self { severity, mode_index, message, fix }
+    }
+}
+
+/// Contradictory or dead settings in the genome, errors first.
+/// Nothing is changed; apply an issue's `fix` to correct it.
+pub fn validate(genome: &GenomeData) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    if genome.modes.is_empty() {
+        issues.push(ValidationIssue::new(
+            Severity::Error,
+            None,
+            "Genome has no modes".to_string(),
+            Some(ValidationFix::Repair),
+        ));
+        return issues;
+    }
+
+    let last = genome.modes.len() as i32 - 1;
+    if !(0..=last).contains(&genome.initial_mode) {
+        issues.push(ValidationIssue::new(
+            Severity::Error,
+            None,
+            format!("Initial mode {} does not exist", genome.initial_mode),
+            Some(ValidationFix::Repair),
+        ));
+    }
+
+    for (index, mode) in genome.modes.iter().enumerate() {
+        for (label, child) in [("A", &mode.child_a), ("B", &mode.child_b)] {
+            if !(0..=last).contains(&child.mode_number) {
+                issues.push(ValidationIssue::new(
+                    Severity::Error,
+                    Some(index),
+                    format!("Child {} points to missing mode {}", label, child.mode_number),
+                    Some(ValidationFix::Repair),
+                ));
+            }
+        }
+
+        for deadlock in split_deadlocks(genome, index) {
+            let (severity, fix) = match deadlock {
+                SplitDeadlock::ConnectionRange { .. } => (Severity::Error, ValidationFix::RaiseMaxAdhesions(index)),
+                // The sibling bond can break, so a few cells may still split
+                SplitDeadlock::SiblingBondFillsMax { .. } => (Severity::Warning, ValidationFix::AllowSiblingBond(index)),
+                SplitDeadlock::NoNutrientGain { .. } => (Severity::Warning, ValidationFix::DefaultNutrientGain(index)),
+            };
+            issues.push(ValidationIssue::new(
+                severity,
+                Some(index),
+                format!("Can never split: {}", deadlock.description()),
+                Some(fix),
+            ));
+        }
+
+        if let Some(message) = range_problem("Split mass", mode.split_mass_min, mode.split_mass) {
+            issues.push(ValidationIssue::new(Severity::Warning, Some(index), message, Some(ValidationFix::ClearSplitMassRange(index))));
+        }
+        if let Some(message) = range_problem("Split interval", mode.split_interval_min, mode.split_interval) {
+            issues.push(ValidationIssue::new(Severity::Warning, Some(index), message, Some(ValidationFix::ClearSplitIntervalRange(index))));
+        }
+    }
+
+    issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity));
+    issues
+}
+
+/// Describe an inverted or zero-width `min..max` range
+fn range_problem(label: &str, min: Option<f32>, max: f32) -> Option<String> {
+    let min = min?;
+    if min > max + COLLAPSED_RANGE_EPSILON {
+        Some(format!("{} range is inverted ({:.2} > {:.2}); only the maximum is used", label, min, max))
+    } else if (max - min).abs() < COLLAPSED_RANGE_EPSILON {
+        Some(format!("{} range has collapsed to a single value ({:.2})", label, max))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn single_mode_genome() -> GenomeData {
+        GenomeData {
+            name: "Test".to_string(),
+            initial_mode: 0,
+            modes: vec![ModeSettings::new_self_splitting(0, "Mode 0".to_string())],
+        }
+    }
+
+    #[test]
+    fn test_default_genome_is_valid() {
+        assert!(validate(&single_mode_genome()).is_empty());
+    }
+
+    #[test]
+    fn test_fixes_resolve_their_issues() {
+        let mut genome = single_mode_genome();
+        genome.initial_mode = 3;
+        let mode = &mut genome.modes[0];
+        mode.min_adhesions = 4;
+        mode.max_adhesions = 2;
+        mode.split_mass_min = Some(mode.split_mass + 1.0);
+        mode.nutrient_gain_rate = 0.0;
+
+        let issues = validate(&genome);
+        assert_eq!(issues.len(), 4);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues.last().map(|issue| issue.severity), Some(Severity::Warning));
+
+        for issue in &issues {
+            issue.fix.expect("every issue here has a fix").apply(&mut genome);
+        }
+        assert!(validate(&genome).is_empty());
+    }
+
+    #[test]
+    fn test_sibling_bond_filling_max_connections_is_flagged() {
+        let mut genome = single_mode_genome();
+        let mode = &mut genome.modes[0];
+        mode.parent_make_adhesion = true;
+        mode.min_adhesions = 0;
+        mode.max_adhesions = 1;
+
+        let issues = validate(&genome);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(split_deadlocks(&genome, 0), vec![SplitDeadlock::SiblingBondFillsMax { max: 1 }]);
+        assert!(issues[0].message.contains("sibling"));
+
+        issues[0].fix.unwrap().apply(&mut genome);
+        assert!(validate(&genome).is_empty());
+    }
+}
@@ -1,20 +1,73 @@
 use biospheres::scene::BasicScene;
+use biospheres::simulation::benchmark::{BenchmarkOptions, run_headless_benchmark};
+use biospheres::simulation::scenes::SceneLibrary;
+use biospheres::ui::system_tray::{SystemTray, TrayAction};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use winit::{
     event::*,
-    event_loop::EventLoop,
+    event_loop::{ControlFlow, EventLoop},
     window::{Window, WindowBuilder},
 };
 
+/// How often the simulation steps while the window is hidden to the tray
+const HIDDEN_UPDATE_INTERVAL: Duration = Duration::from_millis(16);
+
 struct App {
     window: Arc<Window>,
     scene: BasicScene,
+    /// Created once the event loop runs, as macOS requires
+    tray: Option<SystemTray>,
+    window_visible: bool,
+}
+
+/// Save everything that is written on a normal exit
+fn save_before_exit(scene: &mut BasicScene) {
+    scene.save_scenes();
+    scene.save_settings();
+    scene.finish_telemetry();
+}
+
+/// Report a fatal startup problem in a native dialog, since there may be no console, then exit
+fn exit_with_startup_error(error: &dyn std::fmt::Display) -> ! {
+    eprintln!("Startup failed: {}", error);
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Error)
+        .set_title("BioSpheres could not start")
+        .set_description(format!(
+            "{}\n\nBioSpheres needs a GPU with Vulkan, Metal, DirectX 12 or OpenGL support. \
+             Updating your graphics drivers often fixes this.",
+            error
+        ))
+        .set_buttons(rfd::MessageButtons::Ok)
+        .show();
+    std::process::exit(1);
 }
 
 fn main() {
+    biospheres::app_info::install_panic_hook();
+    
+    let benchmark = BenchmarkOptions::from_args(std::env::args().skip(1));
+    let benchmark_genome = benchmark.as_ref().map(|options| {
+        options.genome().unwrap_or_else(|e| {
+            eprintln!("Failed to load benchmark genome: {}", e);
+            std::process::exit(1);
+        })
+    });
+    
+    // Simulation-only benchmark: no window, just the report
+    if let (Some(options), Some(genome)) = (&benchmark, &benchmark_genome) {
+        if !options.include_rendering {
+            // Same physics as the windowed benchmark: the active scene's
+            let scenes = SceneLibrary::load_from_file(&SceneLibrary::default_path());
+            println!("{}", run_headless_benchmark(genome, &scenes.active_scene().physics, options.steps).to_json());
+            return;
+        }
+    }
+    
     println!("BioSpheres starting...");
     
-    let event_loop = EventLoop::new().unwrap();
+    let event_loop = EventLoop::new().unwrap_or_else(|e| exit_with_startup_error(&e));
     
     let window = Arc::new(
         WindowBuilder::new()
@@ -22,17 +75,24 @@ fn main() {
             .with_inner_size(winit::dpi::LogicalSize::new(1280, 720))
             .with_maximized(true)
             .build(&event_loop)
-            .unwrap()
+            .unwrap_or_else(|e| exit_with_startup_error(&e))
     );
     
     // Create basic scene
-    let scene = pollster::block_on(BasicScene::new(window.clone()));
+    let mut scene = pollster::block_on(BasicScene::new(window.clone()))
+        .unwrap_or_else(|e| exit_with_startup_error(&e));
+    if let (Some(options), Some(genome)) = (benchmark, benchmark_genome) {
+        scene.start_benchmark(options, genome);
+    }
     println!("Scene initialized successfully");
     
-    let mut app = App { window, scene };
+    let mut app = App { window, scene, tray: None, window_visible: true };
     
     event_loop.run(move |event, elwt| {
         match event {
+            Event::NewEvents(StartCause::Init) => {
+                app.tray = Some(SystemTray::new());
+            }
             Event::WindowEvent { event, .. } => {
                 // Let the scene handle input first
                 if app.scene.handle_input(&event) {
@@ -43,11 +103,16 @@ fn main() {
                 match event {
                     WindowEvent::CloseRequested => {
                         println!("Close requested, exiting...");
+                        save_before_exit(&mut app.scene);
                         elwt.exit();
                     }
                     WindowEvent::Resized(physical_size) => {
                         app.scene.resize(physical_size);
                     }
+                    WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                        // Moved to a monitor with a different DPI; a Resized event follows
+                        app.scene.set_display_scale(scale_factor as f32);
+                    }
                     WindowEvent::RedrawRequested => {
                         // Render the scene with UI
                         match app.scene.render(&app.window) {
@@ -82,7 +147,32 @@ fn main() {
                 }
             }
             Event::AboutToWait => {
-                app.window.request_redraw();
+                if let Some(tray) = app.tray.as_mut() {
+                    match tray.poll() {
+                        Some(TrayAction::ToggleWindow) => {
+                            app.window_visible = !app.window_visible;
+                            app.window.set_visible(app.window_visible);
+                        }
+                        Some(TrayAction::TogglePause) => app.scene.toggle_pause(),
+                        Some(TrayAction::Quit) => {
+                            println!("Quit requested from the tray, exiting...");
+                            save_before_exit(&mut app.scene);
+                            elwt.exit();
+                            return;
+                        }
+                        None => {}
+                    }
+                    tray.update_labels(app.window_visible, app.scene.is_paused());
+                }
+                
+                // A hidden window gets no redraws, so step the simulation directly
+                if app.window_visible {
+                    elwt.set_control_flow(ControlFlow::Wait);
+                    app.window.request_redraw();
+                } else {
+                    app.scene.update_hidden();
+                    elwt.set_control_flow(ControlFlow::WaitUntil(Instant::now() + HIDDEN_UPDATE_INTERVAL));
+                }
             }
             _ => {}
         }
@@ -1 +1,716 @@
 // Cell rendering
+use crate::cell::adhesion::adhesion_components;
+use crate::genome::GenomeData;
+use crate::simulation::cpu_sim::CpuSimulation;
+use crate::simulation::nutrient_system;
+use glam::{Mat4, Vec3};
+use std::collections::VecDeque;
+use wgpu::util::DeviceExt;
+
+pub(crate) const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Finest sphere mesh kept on the GPU (0 = icosahedron)
+const MAX_SPHERE_SUBDIVISIONS: u32 = 3;
+
+/// Per-instance data uploaded for every drawn sphere
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct CellInstance {
+    pub position: [f32; 3],
+    pub radius: f32,
+    /// Authored sRGB color and opacity
+    pub color: [f32; 4],
+    /// Glow added on top of lighting, as a multiple of the base color
+    pub emissive: f32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct CameraUniform {
+    view_proj: [[f32; 4]; 4],
+    camera_position: [f32; 4],
+    light_direction: [f32; 4],
+    /// x: decode authored sRGB colors to linear before lighting,
+    /// y: encode the result to sRGB in the shader (surface is not an sRGB format)
+    color_options: [f32; 4],
+}
+
+/// Which cell property drives the sphere color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum CellColorBy {
+    /// The genome mode's color
+    #[default]
+    Mode,
+    /// Gradient from the lightest to the heaviest cell in the colony
+    Mass,
+    /// Gradient from the youngest to the oldest cell in the colony
+    Age,
+    /// Stored nutrients between minimum mass (red) and split mass (green)
+    Nutrients,
+    /// A distinct color per group of cells linked by adhesions
+    Colony,
+}
+
+impl CellColorBy {
+    pub fn all() -> &'static [CellColorBy] {
+        &[CellColorBy::Mode, CellColorBy::Mass, CellColorBy::Age, CellColorBy::Nutrients, CellColorBy::Colony]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CellColorBy::Mode => "Mode Color",
+            CellColorBy::Mass => "Mass",
+            CellColorBy::Age => "Age",
+            CellColorBy::Nutrients => "Nutrient Level",
+            CellColorBy::Colony => "Colony",
+        }
+    }
+}
+
+/// Blue-green-yellow ramp for t in 0..1
+fn gradient_color(t: f32) -> [f32; 3] {
+    let low = Vec3::new(0.15, 0.2, 0.8);
+    let mid = Vec3::new(0.1, 0.75, 0.55);
+    let high = Vec3::new(1.0, 0.9, 0.15);
+    let t = t.clamp(0.0, 1.0);
+    let color = if t < 0.5 { low.lerp(mid, t * 2.0) } else { mid.lerp(high, t * 2.0 - 1.0) };
+    color.to_array()
+}
+
+/// Red-yellow-green ramp for a fill level in 0..1
+pub(crate) fn fill_color(level: f32) -> [f32; 3] {
+    let level = level.clamp(0.0, 1.0);
+    [(2.0 - 2.0 * level).min(1.0), (2.0 * level).min(1.0), 0.1]
+}
+
+/// Well-separated color for a component label; hues step by the golden ratio
+fn label_color(label: u32) -> [f32; 3] {
+    let hue = (label as f64 * 0.618_033_988_75).fract() as f32 * 6.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    let (r, g, b) = match hue as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    // Lift the darkest channel so no colony renders near-black
+    [0.15 + 0.85 * r, 0.15 + 0.85 * g, 0.15 + 0.85 * b]
+}
+
+/// Position of `value` within `range`, 0.5 when the range is empty
+fn normalize(value: f32, (min, max): (f32, f32)) -> f32 {
+    if max - min > f32::EPSILON { (value - min) / (max - min) } else { 0.5 }
+}
+
+/// Build instance data for every cell, colored according to `color_by`
+pub fn cell_instances(
+    simulation: &CpuSimulation,
+    genome: &GenomeData,
+    color_by: CellColorBy,
+    min_cell_mass: f32,
+) -> Vec<CellInstance> {
+    let range = |value: fn(&crate::cell::types::Cell) -> f32| {
+        simulation
+            .cells
+            .iter()
+            .map(value)
+            .fold((f32::MAX, f32::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)))
+    };
+    let mass_range = range(|cell| cell.mass);
+    let age_range = range(|cell| cell.age);
+    let colonies = if color_by == CellColorBy::Colony {
+        adhesion_components(&simulation.cells, &simulation.adhesions)
+    } else {
+        Vec::new()
+    };
+
+    simulation
+        .cells
+        .iter()
+        .enumerate()
+        .map(|(index, cell)| {
+            let mode = simulation.mode_of(genome, cell);
+            let rgb = match color_by {
+                CellColorBy::Mode => [mode.color.x, mode.color.y, mode.color.z],
+                CellColorBy::Mass => gradient_color(normalize(cell.mass, mass_range)),
+                CellColorBy::Age => gradient_color(normalize(cell.age, age_range)),
+                CellColorBy::Nutrients => {
+                    fill_color(nutrient_system::stored_fraction(cell.mass, min_cell_mass, mode.split_mass))
+                }
+                CellColorBy::Colony => label_color(colonies[index]),
+            };
+            CellInstance {
+                position: cell.position.to_array(),
+                radius: cell.radius(),
+                color: [rgb[0], rgb[1], rgb[2], mode.opacity],
+                emissive: mode.emissive,
+            }
+        })
+        .collect()
+}
+
+/// Sphere mesh detail, traded against frame time on large colonies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum CellQuality {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl CellQuality {
+    pub fn all() -> &'static [CellQuality] {
+        &[CellQuality::Low, CellQuality::Medium, CellQuality::High]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CellQuality::Low => "Low",
+            CellQuality::Medium => "Medium",
+            CellQuality::High => "High",
+        }
+    }
+
+    /// Icosphere subdivisions for cells near the camera
+    pub fn subdivisions(&self) -> u32 {
+        match self {
+            CellQuality::Low => 1,
+            CellQuality::Medium => 2,
+            CellQuality::High => 3,
+        }
+    }
+
+    /// Icosphere subdivisions for cells beyond the LOD distance
+    pub fn far_subdivisions(&self) -> u32 {
+        self.subdivisions() - 1
+    }
+}
+
+/// When cells are drawn as flat camera-facing impostors instead of sphere meshes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ImpostorMode {
+    Off,
+    /// Switch to impostors above the cell count threshold
+    #[default]
+    Auto,
+    Always,
+}
+
+impl ImpostorMode {
+    pub fn all() -> &'static [ImpostorMode] {
+        &[ImpostorMode::Off, ImpostorMode::Auto, ImpostorMode::Always]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImpostorMode::Off => "Off",
+            ImpostorMode::Auto => "Auto",
+            ImpostorMode::Always => "Always",
+        }
+    }
+}
+
+/// Cell mesh detail and level-of-detail options
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CellLodSettings {
+    pub quality: CellQuality,
+    /// Draw cells beyond `lod_distance` from the camera with a coarser mesh
+    pub distance_lod: bool,
+    pub lod_distance: f32,
+    pub impostors: ImpostorMode,
+    /// Cell count above which `ImpostorMode::Auto` switches to impostors
+    pub impostor_threshold: usize,
+}
+
+impl Default for CellLodSettings {
+    fn default() -> Self {
+        Self {
+            quality: CellQuality::Medium,
+            distance_lod: true,
+            lod_distance: 60.0,
+            impostors: ImpostorMode::Auto,
+            impostor_threshold: 20_000,
+        }
+    }
+}
+
+impl CellLodSettings {
+    /// Whether `cell_count` cells are drawn as impostors
+    pub fn use_impostors(&self, cell_count: usize) -> bool {
+        match self.impostors {
+            ImpostorMode::Off => false,
+            ImpostorMode::Auto => cell_count > self.impostor_threshold,
+            ImpostorMode::Always => true,
+        }
+    }
+}
+
+/// Onion-skin display options
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OnionSkinSettings {
+    pub enabled: bool,
+    /// Number of ghost snapshots kept
+    pub trail_length: usize,
+    /// Simulation steps between snapshots
+    pub steps_between: u32,
+    /// Opacity of the most recent ghost; older ghosts fade towards zero
+    pub opacity: f32,
+}
+
+impl Default for OnionSkinSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            trail_length: 8,
+            steps_between: 6,
+            opacity: 0.35,
+        }
+    }
+}
+
+/// Rolling history of cell snapshots used to draw fading ghosts of earlier steps
+#[derive(Default)]
+pub struct GhostTrail {
+    snapshots: VecDeque<Vec<CellInstance>>,
+    steps_since_snapshot: u32,
+}
+
+impl GhostTrail {
+    /// Called after every simulation step; stores a snapshot every `steps_between` steps
+    pub fn record_step(&mut self, instances: impl FnOnce() -> Vec<CellInstance>, settings: &OnionSkinSettings) {
+        if !settings.enabled {
+            self.clear();
+            return;
+        }
+
+        self.steps_since_snapshot += 1;
+        if self.steps_since_snapshot < settings.steps_between.max(1) {
+            return;
+        }
+        self.steps_since_snapshot = 0;
+
+        self.snapshots.push_front(instances());
+        self.snapshots.truncate(settings.trail_length);
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.steps_since_snapshot = 0;
+    }
+
+    /// Ghost instances, oldest first so newer ghosts blend on top
+    pub fn ghost_instances(&self, settings: &OnionSkinSettings) -> Vec<CellInstance> {
+        if !settings.enabled {
+            return Vec::new();
+        }
+        let count = self.snapshots.len().min(settings.trail_length);
+        let mut ghosts = Vec::new();
+        for (age, snapshot) in self.snapshots.iter().take(count).enumerate().rev() {
+            let fade = 1.0 - age as f32 / (count + 1) as f32;
+            ghosts.extend(snapshot.iter().map(|instance| CellInstance {
+                color: [instance.color[0], instance.color[1], instance.color[2], settings.opacity * fade],
+                ..*instance
+            }));
+        }
+        ghosts
+    }
+}
+
+/// Unit icosphere with the given number of subdivisions
+pub(crate) fn icosphere(subdivisions: u32) -> (Vec<[f32; 3]>, Vec<u32>) {
+    let t = (1.0 + 5.0_f32.sqrt()) / 2.0;
+    let mut vertices: Vec<Vec3> = [
+        [-1.0, t, 0.0], [1.0, t, 0.0], [-1.0, -t, 0.0], [1.0, -t, 0.0],
+        [0.0, -1.0, t], [0.0, 1.0, t], [0.0, -1.0, -t], [0.0, 1.0, -t],
+        [t, 0.0, -1.0], [t, 0.0, 1.0], [-t, 0.0, -1.0], [-t, 0.0, 1.0],
+    ]
+    .iter()
+    .map(|v| Vec3::from_array(*v).normalize())
+    .collect();
+    let mut triangles: Vec<[u32; 3]> = vec![
+        [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+        [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+        [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+        [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        let mut midpoints = std::collections::HashMap::new();
+        let mut midpoint = |a: u32, b: u32, vertices: &mut Vec<Vec3>| -> u32 {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                vertices.push(((vertices[a as usize] + vertices[b as usize]) * 0.5).normalize());
+                vertices.len() as u32 - 1
+            })
+        };
+        let mut next = Vec::with_capacity(triangles.len() * 4);
+        for [a, b, c] in triangles {
+            let ab = midpoint(a, b, &mut vertices);
+            let bc = midpoint(b, c, &mut vertices);
+            let ca = midpoint(c, a, &mut vertices);
+            next.extend([[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]);
+        }
+        triangles = next;
+    }
+
+    (
+        vertices.iter().map(|v| v.to_array()).collect(),
+        triangles.into_iter().flatten().collect(),
+    )
+}
+
+/// Location of one icosphere level inside the shared sphere buffers
+#[derive(Debug, Clone, Copy)]
+struct SphereMesh {
+    base_vertex: i32,
+    first_index: u32,
+    index_count: u32,
+}
+
+impl SphereMesh {
+    fn indices(&self) -> std::ops::Range<u32> {
+        self.first_index..self.first_index + self.index_count
+    }
+}
+
+/// Draws cells as instanced spheres or impostors, plus translucent onion-skin ghosts
+pub struct CellRenderer {
+    solid_pipeline: wgpu::RenderPipeline,
+    ghost_pipeline: wgpu::RenderPipeline,
+    impostor_pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    /// One entry per subdivision level, 0..=MAX_SPHERE_SUBDIVISIONS
+    meshes: Vec<SphereMesh>,
+    instance_buffer: wgpu::Buffer,
+    instance_capacity: usize,
+    camera_buffer: wgpu::Buffer,
+    camera_bind_group: wgpu::BindGroup,
+    depth_view: wgpu::TextureView,
+    /// The surface encodes linear output to sRGB on write
+    surface_is_srgb: bool,
+}
+
+impl CellRenderer {
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Cell Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../assets/shaders/cells.wgsl").into()),
+        });
+
+        let camera_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Cell Camera Buffer"),
+            size: std::mem::size_of::<CameraUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let camera_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Cell Camera Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Cell Camera Bind Group"),
+            layout: &camera_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Cell Pipeline Layout"),
+            bind_group_layouts: &[&camera_layout],
+            push_constant_ranges: &[],
+        });
+
+        let instance_layout = wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<CellInstance>() as u64,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32, 3 => Float32x4, 4 => Float32],
+        };
+        let vertex_layouts = [
+            wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<[f32; 3]>() as u64,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float32x3],
+            },
+            instance_layout.clone(),
+        ];
+
+        let create_pipeline = |label: &str, blend: Option<wgpu::BlendState>, depth_write: bool| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &vertex_layouts,
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_format,
+                        blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    cull_mode: Some(wgpu::Face::Back),
+                    ..Default::default()
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: DEPTH_FORMAT,
+                    depth_write_enabled: depth_write,
+                    depth_compare: wgpu::CompareFunction::Less,
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let solid_pipeline = create_pipeline("Cell Solid Pipeline", None, true);
+        // Ghosts test against solid cells but don't occlude each other
+        let ghost_pipeline = create_pipeline("Cell Ghost Pipeline", Some(wgpu::BlendState::ALPHA_BLENDING), false);
+
+        // Impostors generate their quad from the vertex index, so instances sit in slot 0
+        let impostor_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Cell Impostor Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_impostor"),
+                buffers: &[instance_layout],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_impostor"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // Every subdivision level shares one vertex and one index buffer
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let mut meshes = Vec::new();
+        for subdivisions in 0..=MAX_SPHERE_SUBDIVISIONS {
+            let (level_vertices, level_indices) = icosphere(subdivisions);
+            meshes.push(SphereMesh {
+                base_vertex: vertices.len() as i32,
+                first_index: indices.len() as u32,
+                index_count: level_indices.len() as u32,
+            });
+            vertices.extend(level_vertices);
+            indices.extend(level_indices);
+        }
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Cell Sphere Vertices"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Cell Sphere Indices"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        let instance_capacity = 1024;
+        let instance_buffer = Self::create_instance_buffer(device, instance_capacity);
+
+        Self {
+            solid_pipeline,
+            ghost_pipeline,
+            impostor_pipeline,
+            vertex_buffer,
+            index_buffer,
+            meshes,
+            instance_buffer,
+            instance_capacity,
+            camera_buffer,
+            camera_bind_group,
+            depth_view: Self::create_depth_view(device, width, height),
+            surface_is_srgb: surface_format.is_srgb(),
+        }
+    }
+
+    fn create_instance_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Cell Instance Buffer"),
+            size: (capacity * std::mem::size_of::<CellInstance>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn create_depth_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+        device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("Cell Depth Texture"),
+                size: wgpu::Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: DEPTH_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    }
+
+    /// Depth buffer from the last cell pass, for overlays that should be hidden behind cells
+    pub fn depth_view(&self) -> &wgpu::TextureView {
+        &self.depth_view
+    }
+
+    /// Recreate the depth buffer to match the surface
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        self.depth_view = Self::create_depth_view(device, width, height);
+    }
+
+    /// Draw solid cells, then blended ghosts, over the existing contents of `view`.
+    /// `legacy_colors` reproduces the old output, which lit authored colors as if they were
+    /// linear and ignored emissive, for comparing existing genomes. `lod` picks the sphere
+    /// detail per cell, or impostors for the whole colony; ghosts always use meshes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        view_proj: Mat4,
+        camera_position: Vec3,
+        cells: &[CellInstance],
+        ghosts: &[CellInstance],
+        legacy_colors: bool,
+        lod: &CellLodSettings,
+    ) {
+        let impostors = lod.use_impostors(cells.len());
+        let near_mesh = self.meshes[lod.quality.subdivisions() as usize];
+        let far_mesh = self.meshes[lod.quality.far_subdivisions() as usize];
+
+        // Near cells first, so each mesh level draws one contiguous instance range
+        let sorted;
+        let (cells, near_count) = if !impostors && lod.distance_lod && far_mesh.index_count < near_mesh.index_count {
+            let max_distance_sq = lod.lod_distance * lod.lod_distance;
+            let (near, far): (Vec<CellInstance>, Vec<CellInstance>) = cells
+                .iter()
+                .partition(|cell| Vec3::from(cell.position).distance_squared(camera_position) <= max_distance_sq);
+            let near_count = near.len();
+            sorted = [near, far].concat();
+            (sorted.as_slice(), near_count)
+        } else {
+            (cells, cells.len())
+        };
+
+        let total = cells.len() + ghosts.len();
+        if total > self.instance_capacity {
+            self.instance_capacity = total.next_power_of_two();
+            self.instance_buffer = Self::create_instance_buffer(device, self.instance_capacity);
+        }
+        if !cells.is_empty() {
+            queue.write_buffer(&self.instance_buffer, 0, bytemuck::cast_slice(cells));
+        }
+        if !ghosts.is_empty() {
+            let offset = std::mem::size_of_val(cells) as u64;
+            queue.write_buffer(&self.instance_buffer, offset, bytemuck::cast_slice(ghosts));
+        }
+
+        let camera = CameraUniform {
+            view_proj: view_proj.to_cols_array_2d(),
+            camera_position: camera_position.extend(1.0).to_array(),
+            light_direction: Vec3::new(-0.4, -1.0, -0.3).normalize().extend(0.0).to_array(),
+            color_options: [
+                if legacy_colors { 0.0 } else { 1.0 },
+                if legacy_colors || self.surface_is_srgb { 0.0 } else { 1.0 },
+                0.0,
+                0.0,
+            ],
+        };
+        queue.write_buffer(&self.camera_buffer, 0, bytemuck::bytes_of(&camera));
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Cell Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        pass.set_bind_group(0, &self.camera_bind_group, &[]);
+
+        if impostors && !cells.is_empty() {
+            pass.set_pipeline(&self.impostor_pipeline);
+            pass.set_vertex_buffer(0, self.instance_buffer.slice(..));
+            pass.draw(0..6, 0..cells.len() as u32);
+        }
+
+        pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+        pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+
+        if !impostors && !cells.is_empty() {
+            pass.set_pipeline(&self.solid_pipeline);
+            if near_count > 0 {
+                pass.draw_indexed(near_mesh.indices(), near_mesh.base_vertex, 0..near_count as u32);
+            }
+            if near_count < cells.len() {
+                pass.draw_indexed(far_mesh.indices(), far_mesh.base_vertex, near_count as u32..cells.len() as u32);
+            }
+        }
+        if !ghosts.is_empty() {
+            pass.set_pipeline(&self.ghost_pipeline);
+            pass.draw_indexed(near_mesh.indices(), near_mesh.base_vertex, cells.len() as u32..total as u32);
+        }
+    }
+}
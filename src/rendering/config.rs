@@ -0,0 +1,183 @@
+// Render settings edited in the Rendering Controls panel
+
+/// How the bloom glow is combined with the lit scene
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum BloomComposite {
+    /// Adds bloom on top; brighter but can wash out
+    #[default]
+    Additive,
+    /// Preserves overall brightness
+    EnergyConserving,
+}
+
+impl BloomComposite {
+    pub fn all() -> &'static [BloomComposite] {
+        &[BloomComposite::Additive, BloomComposite::EnergyConserving]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BloomComposite::Additive => "Additive",
+            BloomComposite::EnergyConserving => "Energy Conserving",
+        }
+    }
+}
+
+/// Appearance of the world boundary sphere
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct WorldSphereSettings {
+    pub opacity: f32,
+    pub color: [f32; 3],
+    /// Emissive intensity of the Fresnel edge glow
+    pub edge_glow: f32,
+}
+
+impl Default for WorldSphereSettings {
+    fn default() -> Self {
+        Self {
+            opacity: 0.1,
+            color: [0.5, 0.5, 0.5],
+            edge_glow: 0.1,
+        }
+    }
+}
+
+/// Volumetric fog parameters
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct FogSettings {
+    pub enabled: bool,
+    pub density: f32,
+    pub absorption: f32,
+    pub scattering: f32,
+    pub ambient: f32,
+    pub color: [f32; 3],
+}
+
+impl Default for FogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            density: 0.5,
+            absorption: 0.3,
+            scattering: 0.7,
+            ambient: 0.05,
+            color: [0.2, 0.3, 0.4],
+        }
+    }
+}
+
+/// Emissive bloom parameters
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct BloomSettings {
+    pub enabled: bool,
+    pub intensity: f32,
+    /// Boost for the soft, wide (low frequency) part of the glow
+    pub low_freq_boost: f32,
+    /// Brightness threshold; higher values make only the brightest emissives bloom
+    pub high_pass: f32,
+    pub composite: BloomComposite,
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            intensity: 0.3,
+            low_freq_boost: 0.7,
+            high_pass: 0.6,
+            composite: BloomComposite::Additive,
+        }
+    }
+}
+
+/// Scene render settings, persisted next to the UI settings.
+/// Debug gizmo toggles live in `GizmoSettings`.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RenderConfig {
+    pub wireframe: bool,
+    pub world_sphere: WorldSphereSettings,
+    pub fog: FogSettings,
+    pub bloom: BloomSettings,
+}
+
+impl RenderConfig {
+    /// Save render settings to file
+    pub fn save_to_file(&self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load render settings from file, falling back to default if file doesn't exist or is invalid
+    pub fn load_from_file(path: &std::path::Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                eprintln!("Failed to parse render settings file: {}. Using defaults.", e);
+                Self::default()
+            }),
+            // File doesn't exist or can't be read, use defaults
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Get the default render settings file path
+    pub fn default_path() -> std::path::PathBuf {
+        std::path::PathBuf::from("render_settings.json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(config: &RenderConfig) -> RenderConfig {
+        let json = serde_json::to_string(config).unwrap();
+        let loaded: RenderConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), json, "serialization is not stable");
+        loaded
+    }
+
+    #[test]
+    fn test_every_field_round_trips() {
+        let edits: Vec<fn(&mut RenderConfig)> = vec![
+            |c| c.wireframe = !c.wireframe,
+            |c| c.world_sphere.opacity = 0.75,
+            |c| c.world_sphere.color = [0.1, 0.9, 0.3],
+            |c| c.world_sphere.edge_glow = 0.4,
+            |c| c.fog.enabled = !c.fog.enabled,
+            |c| c.fog.density = 0.9,
+            |c| c.fog.absorption = 0.05,
+            |c| c.fog.scattering = 0.15,
+            |c| c.fog.ambient = 0.09,
+            |c| c.fog.color = [0.8, 0.1, 0.2],
+            |c| c.bloom.enabled = !c.bloom.enabled,
+            |c| c.bloom.intensity = 0.95,
+            |c| c.bloom.low_freq_boost = 0.2,
+            |c| c.bloom.high_pass = 0.1,
+            |c| c.bloom.composite = BloomComposite::EnergyConserving,
+        ];
+
+        let mut all_flipped = RenderConfig::default();
+        for edit in &edits {
+            let mut config = RenderConfig::default();
+            edit(&mut config);
+            assert_ne!(config, RenderConfig::default());
+            assert_eq!(round_trip(&config), config);
+            edit(&mut all_flipped);
+        }
+        assert_eq!(round_trip(&all_flipped), all_flipped);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let config: RenderConfig = serde_json::from_str(r#"{"wireframe": true, "fog": {"density": 0.2}}"#).unwrap();
+        assert!(config.wireframe);
+        assert_eq!(config.fog.density, 0.2);
+        assert_eq!(config.fog.color, FogSettings::default().color);
+        assert_eq!(config.bloom, BloomSettings::default());
+    }
+}
@@ -1 +1,383 @@
 // Debug rendering utilities
+use crate::cell::division::split_direction;
+use crate::genome::GenomeData;
+use crate::simulation::cpu_sim::CpuSimulation;
+use crate::simulation::nutrient_system;
+use super::cells::fill_color;
+use glam::{Mat4, Vec3};
+use rayon::prelude::*;
+
+/// Line segments per split-plane ring
+const RING_SEGMENTS: usize = 24;
+
+/// Gizmo axes and rings extend this far past the cell surface
+const GIZMO_SCALE: f32 = 1.2;
+
+/// Which debug gizmos to draw, and the colony size above which they are skipped
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GizmoSettings {
+    pub show_orientation: bool,
+    pub show_split_planes: bool,
+    pub show_adhesions: bool,
+    /// Bar over each cell: stored nutrients, tinted by the local nutrient concentration
+    pub show_nutrient_field: bool,
+    /// Gizmos are skipped entirely above this many cells to keep the UI responsive
+    pub max_cells: usize,
+}
+
+impl Default for GizmoSettings {
+    fn default() -> Self {
+        Self {
+            show_orientation: false,
+            show_split_planes: false,
+            show_adhesions: false,
+            show_nutrient_field: false,
+            max_cells: 20_000,
+        }
+    }
+}
+
+impl GizmoSettings {
+    pub fn any_enabled(&self) -> bool {
+        self.show_orientation || self.show_split_planes || self.show_adhesions || self.show_nutrient_field
+    }
+
+    /// Whether gizmos are switched on but suppressed by the cell cap
+    pub fn over_cap(&self, cell_count: usize) -> bool {
+        self.any_enabled() && cell_count > self.max_cells
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LineVertex {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+}
+
+impl LineVertex {
+    fn new(position: Vec3, color: [f32; 4]) -> Self {
+        Self { position: position.to_array(), color }
+    }
+}
+
+/// Line-list vertices for every gizmo type, one batch per type
+#[derive(Debug, Default)]
+pub struct GizmoLines {
+    pub orientation: Vec<LineVertex>,
+    pub split_planes: Vec<LineVertex>,
+    pub adhesions: Vec<LineVertex>,
+    pub nutrients: Vec<LineVertex>,
+}
+
+/// Build all enabled gizmo lines for the colony. Each batch is built in parallel across cells.
+pub fn build_gizmo_lines(simulation: &CpuSimulation, genome: &GenomeData, settings: &GizmoSettings, min_cell_mass: f32) -> GizmoLines {
+    if !settings.any_enabled() || settings.over_cap(simulation.cells.len()) || genome.modes.is_empty() {
+        return GizmoLines::default();
+    }
+
+    let orientation = || {
+        if !settings.show_orientation {
+            return Vec::new();
+        }
+        simulation
+            .cells
+            .par_iter()
+            .flat_map_iter(|cell| {
+                let length = cell.radius() * GIZMO_SCALE;
+                let axes = [
+                    (Vec3::Z, [0.2, 0.4, 1.0, 1.0]),
+                    (Vec3::X, [0.2, 1.0, 0.2, 1.0]),
+                    (Vec3::Y, [1.0, 0.2, 0.2, 1.0]),
+                ];
+                axes.into_iter().flat_map(move |(axis, color)| {
+                    [
+                        LineVertex::new(cell.position, color),
+                        LineVertex::new(cell.position + cell.rotation * axis * length, color),
+                    ]
+                })
+            })
+            .collect()
+    };
+
+    let split_planes = || {
+        if !settings.show_split_planes {
+            return Vec::new();
+        }
+        simulation
+            .cells
+            .par_iter()
+            .flat_map_iter(|cell| {
+                let mode = simulation.mode_of(genome, cell);
+                let normal = cell.rotation * split_direction(mode.parent_split_direction.x, mode.parent_split_direction.y);
+                let (u, v) = normal.any_orthonormal_pair();
+                let radius = cell.radius() * GIZMO_SCALE;
+                let color = [1.0, 0.85, 0.2, 1.0];
+                let point = move |i: usize| {
+                    let angle = i as f32 / RING_SEGMENTS as f32 * std::f32::consts::TAU;
+                    cell.position + (u * angle.cos() + v * angle.sin()) * radius
+                };
+                (0..RING_SEGMENTS).flat_map(move |i| [LineVertex::new(point(i), color), LineVertex::new(point(i + 1), color)])
+            })
+            .collect()
+    };
+
+    let adhesions = || {
+        if !settings.show_adhesions {
+            return Vec::new();
+        }
+        let position_of: std::collections::HashMap<u32, Vec3> =
+            simulation.cells.iter().map(|c| (c.id, c.position)).collect();
+        simulation
+            .adhesions
+            .par_iter()
+            .filter_map(|adhesion| {
+                let a = *position_of.get(&adhesion.cell_a)?;
+                let b = *position_of.get(&adhesion.cell_b)?;
+                let color = genome.modes.get(adhesion.mode_index).map_or([1.0; 3], |m| [m.color.x, m.color.y, m.color.z]);
+                let color = [color[0], color[1], color[2], 0.9];
+                Some([LineVertex::new(a, color), LineVertex::new(b, color)])
+            })
+            .flatten_iter()
+            .collect()
+    };
+
+    // A horizontal bar just above each cell, filled red to green like the Nutrient Level tint,
+    // fading towards grey where the medium holds fewer nutrients than the ambient level
+    let nutrients = || {
+        if !settings.show_nutrient_field {
+            return Vec::new();
+        }
+        simulation
+            .cells
+            .par_iter()
+            .flat_map_iter(|cell| {
+                let mode = simulation.mode_of(genome, cell);
+                let fill = nutrient_system::stored_fraction(cell.mass, min_cell_mass, mode.split_mass);
+                let concentration = (nutrient_system::sample(cell.position) / nutrient_system::AMBIENT_CONCENTRATION).clamp(0.0, 1.0);
+                let [r, g, b] = fill_color(fill);
+                let grey = 0.35;
+                let filled = [
+                    grey + (r - grey) * concentration,
+                    grey + (g - grey) * concentration,
+                    grey + (b - grey) * concentration,
+                    1.0,
+                ];
+                let empty = [0.2, 0.2, 0.2, 0.8];
+
+                let radius = cell.radius();
+                let left = cell.position + Vec3::new(-radius, radius * GIZMO_SCALE, 0.0);
+                let split = left + Vec3::X * (2.0 * radius * fill);
+                let right = left + Vec3::X * (2.0 * radius);
+                [
+                    LineVertex::new(left, filled),
+                    LineVertex::new(split, filled),
+                    LineVertex::new(split, empty),
+                    LineVertex::new(right, empty),
+                ]
+            })
+            .collect()
+    };
+
+    let ((orientation, split_planes), (adhesions, nutrients)) =
+        rayon::join(|| rayon::join(orientation, split_planes), || rayon::join(adhesions, nutrients));
+    GizmoLines {
+        orientation,
+        split_planes,
+        adhesions,
+        nutrients,
+    }
+}
+
+/// One dynamic vertex buffer, refilled every frame and drawn in a single call
+struct LineBatch {
+    label: &'static str,
+    buffer: wgpu::Buffer,
+    capacity: usize,
+    vertex_count: u32,
+}
+
+impl LineBatch {
+    fn new(device: &wgpu::Device, label: &'static str) -> Self {
+        let capacity = 4096;
+        Self {
+            label,
+            buffer: Self::create_buffer(device, label, capacity),
+            capacity,
+            vertex_count: 0,
+        }
+    }
+
+    fn create_buffer(device: &wgpu::Device, label: &'static str, capacity: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size: (capacity * std::mem::size_of::<LineVertex>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn upload(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, vertices: &[LineVertex]) {
+        if vertices.len() > self.capacity {
+            self.capacity = vertices.len().next_power_of_two();
+            self.buffer = Self::create_buffer(device, self.label, self.capacity);
+        }
+        if !vertices.is_empty() {
+            queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(vertices));
+        }
+        self.vertex_count = vertices.len() as u32;
+    }
+}
+
+/// Draws debug gizmos as depth-tested lines, one draw call per gizmo type
+pub struct GizmoRenderer {
+    pipeline: wgpu::RenderPipeline,
+    camera_buffer: wgpu::Buffer,
+    camera_bind_group: wgpu::BindGroup,
+    batches: [LineBatch; 4],
+}
+
+impl GizmoRenderer {
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat, depth_format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Gizmo Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../assets/shaders/gizmos.wgsl").into()),
+        });
+
+        let camera_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Gizmo Camera Buffer"),
+            size: std::mem::size_of::<[[f32; 4]; 4]>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let camera_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Gizmo Camera Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Gizmo Camera Bind Group"),
+            layout: &camera_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Gizmo Pipeline Layout"),
+            bind_group_layouts: &[&camera_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Gizmo Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<LineVertex>() as u64,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x4],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::LineList,
+                ..Default::default()
+            },
+            // Hidden behind cells, but gizmos don't occlude each other
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: depth_format,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            camera_buffer,
+            camera_bind_group,
+            batches: [
+                LineBatch::new(device, "Gizmo Orientation Lines"),
+                LineBatch::new(device, "Gizmo Split Plane Lines"),
+                LineBatch::new(device, "Gizmo Adhesion Lines"),
+                LineBatch::new(device, "Gizmo Nutrient Lines"),
+            ],
+        }
+    }
+
+    /// Upload this frame's gizmo lines and draw them over `view`, depth-tested against the cells
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        depth_view: &wgpu::TextureView,
+        view_proj: Mat4,
+        lines: &GizmoLines,
+    ) {
+        let sources = [&lines.orientation, &lines.split_planes, &lines.adhesions, &lines.nutrients];
+        for (batch, vertices) in self.batches.iter_mut().zip(sources) {
+            batch.upload(device, queue, vertices);
+        }
+        if self.batches.iter().all(|b| b.vertex_count == 0) {
+            return;
+        }
+        queue.write_buffer(&self.camera_buffer, 0, bytemuck::bytes_of(&view_proj.to_cols_array_2d()));
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Gizmo Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        for batch in self.batches.iter().filter(|b| b.vertex_count > 0) {
+            pass.set_vertex_buffer(0, batch.buffer.slice(..));
+            pass.draw(0..batch.vertex_count, 0..1);
+        }
+    }
+}
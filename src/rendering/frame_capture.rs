@@ -0,0 +1,604 @@
+// Frame capture and recording of the rendered viewport
+use crossbeam_channel::{Receiver, Sender};
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Instant;
+
+/// Directory F12 screenshots are written to, relative to the data directory
+pub const SCREENSHOT_DIR: &str = "screenshots";
+
+/// Timestamped PNG path in the screenshot directory
+pub fn timestamped_screenshot_path() -> PathBuf {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    Path::new(SCREENSHOT_DIR).join(format!("screenshot_{}.png", millis))
+}
+
+/// Output format for a finished recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingFormat {
+    /// Animated GIF
+    Gif,
+    /// Numbered PNG files in a directory
+    PngSequence,
+}
+
+impl RecordingFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RecordingFormat::Gif => "Animated GIF",
+            RecordingFormat::PngSequence => "PNG Sequence",
+        }
+    }
+}
+
+/// User-facing recording options
+#[derive(Debug, Clone)]
+pub struct RecordingSettings {
+    /// Frames captured per second of wall-clock time
+    pub fps: u32,
+    /// Recording stops automatically after this many seconds
+    pub max_duration: f32,
+    pub format: RecordingFormat,
+    /// GIF file path, or directory for a PNG sequence
+    pub output_path: PathBuf,
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self {
+            fps: 15,
+            max_duration: 10.0,
+            format: RecordingFormat::Gif,
+            output_path: PathBuf::from("recording.gif"),
+        }
+    }
+}
+
+/// Progress of the background encoder, shown in the UI
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncoderStatus {
+    Idle,
+    Encoding { frames_done: usize, frames_total: usize },
+    Finished(PathBuf),
+    Failed(String),
+}
+
+/// A captured frame waiting for its GPU buffer to be mapped
+struct PendingReadback {
+    buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    is_bgra: bool,
+    map_requested: bool,
+    /// Set by the map callback: `Ok` once the buffer can be read, `Err` if mapping failed
+    mapped: Arc<Mutex<Option<Result<(), String>>>>,
+}
+
+/// Message sent from the render thread to the encoder worker
+enum WorkerMessage {
+    Frame {
+        width: u32,
+        height: u32,
+        is_bgra: bool,
+        data: Vec<u8>,
+    },
+    Finish {
+        format: RecordingFormat,
+        output_path: PathBuf,
+        fps: u32,
+    },
+}
+
+/// Records the surface texture at a fixed rate.
+/// Readbacks are queued on the GPU and polled each frame; encoding happens on a worker thread
+/// so recording never blocks the render thread.
+pub struct FrameRecorder {
+    pub settings: RecordingSettings,
+    recording: bool,
+    started_at: Option<Instant>,
+    last_capture: Option<Instant>,
+    frames_captured: usize,
+    pending: Vec<PendingReadback>,
+    /// Recording stopped; Finish goes to the worker once `pending` has drained
+    finishing: bool,
+    sender: Option<Sender<WorkerMessage>>,
+    worker: Option<JoinHandle<()>>,
+    status: Arc<Mutex<EncoderStatus>>,
+}
+
+impl Default for FrameRecorder {
+    fn default() -> Self {
+        Self {
+            settings: RecordingSettings::default(),
+            recording: false,
+            started_at: None,
+            last_capture: None,
+            frames_captured: 0,
+            pending: Vec::new(),
+            finishing: false,
+            sender: None,
+            worker: None,
+            status: Arc::new(Mutex::new(EncoderStatus::Idle)),
+        }
+    }
+}
+
+impl FrameRecorder {
+    /// Whether frames are currently being captured
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Seconds since recording started
+    pub fn elapsed(&self) -> f32 {
+        self.started_at.map(|t| t.elapsed().as_secs_f32()).unwrap_or(0.0)
+    }
+
+    /// Number of frames captured in the current recording
+    pub fn frames_captured(&self) -> usize {
+        self.frames_captured
+    }
+
+    /// Current state of the background encoder
+    pub fn status(&self) -> EncoderStatus {
+        self.status.lock().clone()
+    }
+
+    /// Whether a previous recording is still being encoded
+    pub fn is_encoding(&self) -> bool {
+        matches!(*self.status.lock(), EncoderStatus::Encoding { .. })
+    }
+
+    /// Start capturing frames. Ignored while a previous recording is still encoding.
+    pub fn start(&mut self) {
+        if self.recording || self.is_encoding() {
+            return;
+        }
+
+        // Reap the previous worker, if any
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+
+        let temp_dir = std::env::temp_dir().join(format!(
+            "biospheres_recording_{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        ));
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let status = self.status.clone();
+        *status.lock() = EncoderStatus::Idle;
+        self.worker = Some(std::thread::spawn(move || {
+            run_encoder_worker(receiver, temp_dir, status);
+        }));
+        self.sender = Some(sender);
+
+        self.recording = true;
+        self.started_at = Some(Instant::now());
+        self.last_capture = None;
+        self.frames_captured = 0;
+        println!("Recording started ({} fps, max {:.0}s)", self.settings.fps, self.settings.max_duration);
+    }
+
+    /// Stop capturing and hand the captured frames to the encoder.
+    /// Encoding starts once `poll` has forwarded the readbacks still in flight.
+    pub fn stop(&mut self) {
+        if !self.recording {
+            return;
+        }
+        self.recording = false;
+        self.started_at = None;
+
+        if self.sender.is_some() {
+            *self.status.lock() = EncoderStatus::Encoding {
+                frames_done: 0,
+                frames_total: self.frames_captured,
+            };
+            self.finishing = true;
+        }
+        println!("Recording stopped after {} frames", self.frames_captured);
+    }
+
+    /// Tell the worker to encode, once every captured frame has been forwarded
+    fn finish_if_drained(&mut self) {
+        if !self.finishing || !self.pending.is_empty() {
+            return;
+        }
+        self.finishing = false;
+        *self.status.lock() = EncoderStatus::Encoding {
+            frames_done: 0,
+            frames_total: self.frames_captured,
+        };
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(WorkerMessage::Finish {
+                format: self.settings.format,
+                output_path: self.settings.output_path.clone(),
+                fps: self.settings.fps,
+            });
+        }
+    }
+
+    /// Queue a copy of the surface texture if a frame is due. Call before submitting the encoder.
+    pub fn capture_frame(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+    ) {
+        if !self.recording {
+            return;
+        }
+
+        if self.elapsed() >= self.settings.max_duration {
+            self.stop();
+            return;
+        }
+
+        let frame_interval = 1.0 / self.settings.fps.max(1) as f32;
+        let now = Instant::now();
+        if let Some(last) = self.last_capture {
+            if now.duration_since(last).as_secs_f32() < frame_interval {
+                return;
+            }
+        }
+
+        let Some(is_bgra) = is_bgra_format(texture.format()) else {
+            eprintln!("Recording not supported for surface format {:?}", texture.format());
+            self.stop();
+            return;
+        };
+
+        let readback = queue_texture_readback(device, encoder, texture, is_bgra);
+        self.pending.push(readback);
+        self.last_capture = Some(now);
+        self.frames_captured += 1;
+    }
+
+    /// Request mapping of readbacks queued this frame. Call after submitting the encoder.
+    pub fn after_submit(&mut self) {
+        for readback in self.pending.iter_mut() {
+            readback.request_map();
+        }
+    }
+
+    /// Forward finished readbacks to the encoder worker, then start encoding
+    /// if the recording has stopped. Call once per frame.
+    pub fn poll(&mut self, device: &wgpu::Device) {
+        if self.pending.is_empty() {
+            self.finish_if_drained();
+            return;
+        }
+
+        let _ = device.poll(wgpu::PollType::Poll);
+
+        // Frames must reach the worker in order, so stop at the first one that isn't ready
+        while let Some(readback) = self.pending.first() {
+            let Some(map_result) = readback.map_result() else {
+                break;
+            };
+            let readback = self.pending.remove(0);
+            if let Err(e) = map_result {
+                // A frame that can't be read back is dropped so the rest of the recording still encodes
+                eprintln!("Dropped a recorded frame: {}", e);
+                self.frames_captured -= 1;
+                continue;
+            }
+            let data = read_mapped_rows(&readback);
+            if let Some(sender) = self.sender.as_ref() {
+                let _ = sender.send(WorkerMessage::Frame {
+                    width: readback.width,
+                    height: readback.height,
+                    is_bgra: readback.is_bgra,
+                    data,
+                });
+            }
+        }
+        self.finish_if_drained();
+    }
+}
+
+/// One-shot capture of the surface texture to a PNG file.
+/// Mapping the GPU copy is asynchronous, so the file is written by a later `poll`,
+/// usually on the next frame, rather than when the capture is requested.
+#[derive(Default)]
+pub struct ScreenshotCapture {
+    /// File the next presented frame is saved to
+    requested: Option<PathBuf>,
+    pending: Option<(PendingReadback, PathBuf)>,
+}
+
+impl ScreenshotCapture {
+    /// Save the next presented frame to `path`. Replaces an earlier request that hasn't been captured yet.
+    pub fn request(&mut self, path: PathBuf) {
+        self.requested = Some(path);
+    }
+
+    /// Queue a copy of the surface texture if a screenshot was requested. Call before submitting the encoder.
+    pub fn capture_frame(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+    ) -> Result<(), String> {
+        if self.pending.is_some() {
+            return Ok(()); // Wait for the previous screenshot to be written
+        }
+        let Some(path) = self.requested.take() else {
+            return Ok(());
+        };
+        let is_bgra = is_bgra_format(texture.format())
+            .ok_or_else(|| format!("Screenshots not supported for surface format {:?}", texture.format()))?;
+        self.pending = Some((queue_texture_readback(device, encoder, texture, is_bgra), path));
+        Ok(())
+    }
+
+    /// Request mapping of a readback queued this frame. Call after submitting the encoder.
+    pub fn after_submit(&mut self) {
+        if let Some((readback, _)) = self.pending.as_mut() {
+            readback.request_map();
+        }
+    }
+
+    /// Write the screenshot once its readback is mapped. Call once per frame.
+    /// Returns the saved path, or the error, when a screenshot finishes.
+    pub fn poll(&mut self, device: &wgpu::Device) -> Option<Result<PathBuf, String>> {
+        let (readback, _) = self.pending.as_ref()?;
+        let _ = device.poll(wgpu::PollType::Poll);
+        let map_result = readback.map_result()?;
+
+        let (readback, path) = self.pending.take()?;
+        if let Err(e) = map_result {
+            return Some(Err(format!("Failed to read back the frame: {}", e)));
+        }
+        let mut data = read_mapped_rows(&readback);
+        if readback.is_bgra {
+            bgra_to_rgba(&mut data);
+        }
+        let result = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(|e| e.to_string())
+            .and_then(|()| write_png(&path, readback.width, readback.height, &data).map_err(|e| e.to_string()));
+        Some(result.map(|()| path))
+    }
+}
+
+/// Whether a surface format is BGRA rather than RGBA, or `None` if it can't be captured
+fn is_bgra_format(format: wgpu::TextureFormat) -> Option<bool> {
+    match format {
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => Some(true),
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => Some(false),
+        _ => None,
+    }
+}
+
+impl PendingReadback {
+    /// Start mapping the buffer once its copy has been submitted
+    fn request_map(&mut self) {
+        if self.map_requested {
+            return;
+        }
+        let mapped = self.mapped.clone();
+        self.buffer.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+            *mapped.lock() = Some(result.map_err(|e| e.to_string()));
+        });
+        self.map_requested = true;
+    }
+
+    /// Outcome of mapping, or `None` while it is still in flight
+    fn map_result(&self) -> Option<Result<(), String>> {
+        self.mapped.lock().clone()
+    }
+}
+
+/// Copy a texture into a mappable buffer, with each row padded to wgpu's
+/// 256-byte copy alignment. The copy is recorded into `encoder`.
+fn queue_texture_readback(
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    texture: &wgpu::Texture,
+    is_bgra: bool,
+) -> PendingReadback {
+    let width = texture.width();
+    let height = texture.height();
+    let unpadded_bytes_per_row = width * 4;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Frame Capture Buffer"),
+        size: (padded_bytes_per_row * height) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+
+    PendingReadback {
+        buffer,
+        width,
+        height,
+        padded_bytes_per_row,
+        is_bgra,
+        map_requested: false,
+        mapped: Arc::new(Mutex::new(None)),
+    }
+}
+
+/// Copy the mapped rows out of the buffer, dropping the row padding
+fn read_mapped_rows(readback: &PendingReadback) -> Vec<u8> {
+    let row_bytes = (readback.width * 4) as usize;
+    let mut data = Vec::with_capacity(row_bytes * readback.height as usize);
+    {
+        let mapped = readback.buffer.slice(..).get_mapped_range();
+        for row in mapped.chunks(readback.padded_bytes_per_row as usize) {
+            data.extend_from_slice(&row[..row_bytes]);
+        }
+    }
+    readback.buffer.unmap();
+    data
+}
+
+/// Swap the red and blue channels in place
+fn bgra_to_rgba(data: &mut [u8]) {
+    for pixel in data.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+}
+
+/// Write an RGBA8 image to a PNG file
+pub fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    let file = std::fs::File::create(path)?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(rgba)?;
+    Ok(())
+}
+
+/// Read an RGBA8 PNG file back into memory
+fn read_png(path: &Path) -> Result<(u32, u32, Vec<u8>), Box<dyn std::error::Error>> {
+    let decoder = png::Decoder::new(std::io::BufReader::new(std::fs::File::open(path)?));
+    let mut reader = decoder.read_info()?;
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data)?;
+    data.truncate(info.buffer_size());
+    Ok((info.width, info.height, data))
+}
+
+/// Worker loop: writes frames to a temp directory, then encodes them on Finish
+fn run_encoder_worker(
+    receiver: Receiver<WorkerMessage>,
+    temp_dir: PathBuf,
+    status: Arc<Mutex<EncoderStatus>>,
+) {
+    if let Err(e) = std::fs::create_dir_all(&temp_dir) {
+        *status.lock() = EncoderStatus::Failed(format!("Failed to create temp directory: {}", e));
+        return;
+    }
+
+    let mut frame_paths: Vec<PathBuf> = Vec::new();
+    let mut frame_size: Option<(u32, u32)> = None;
+
+    for message in receiver.iter() {
+        match message {
+            WorkerMessage::Frame { width, height, is_bgra, mut data } => {
+                // All frames of a recording must share the size of the first one
+                if *frame_size.get_or_insert((width, height)) != (width, height) {
+                    continue;
+                }
+                if is_bgra {
+                    bgra_to_rgba(&mut data);
+                }
+                let path = temp_dir.join(format!("frame_{:05}.png", frame_paths.len()));
+                match write_png(&path, width, height, &data) {
+                    Ok(()) => frame_paths.push(path),
+                    Err(e) => eprintln!("Failed to write frame {}: {}", path.display(), e),
+                }
+            }
+            WorkerMessage::Finish { format, output_path, fps } => {
+                let result = match format {
+                    RecordingFormat::Gif => encode_gif(&frame_paths, &output_path, fps, &status),
+                    RecordingFormat::PngSequence => copy_png_sequence(&frame_paths, &output_path, &status),
+                };
+                *status.lock() = match result {
+                    Ok(()) => {
+                        println!("Recording saved to {}", output_path.display());
+                        EncoderStatus::Finished(output_path)
+                    }
+                    Err(e) => EncoderStatus::Failed(e.to_string()),
+                };
+                break;
+            }
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+/// Encode captured PNG frames into an animated GIF
+fn encode_gif(
+    frame_paths: &[PathBuf],
+    output_path: &Path,
+    fps: u32,
+    status: &Mutex<EncoderStatus>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(first) = frame_paths.first() else {
+        return Err("No frames were captured".into());
+    };
+    let (width, height, _) = read_png(first)?;
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(format!("Frames are {}x{}, but GIF images can be at most 65535 pixels on a side", width, height).into());
+    };
+
+    let file = std::fs::File::create(output_path)?;
+    let mut encoder = gif::Encoder::new(std::io::BufWriter::new(file), width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    // GIF delays are in hundredths of a second
+    let delay = (100 / fps.max(1)) as u16;
+    for (i, path) in frame_paths.iter().enumerate() {
+        let (_, _, mut rgba) = read_png(path)?;
+        let mut frame = gif::Frame::from_rgba_speed(width, height, &mut rgba, 10);
+        frame.delay = delay;
+        encoder.write_frame(&frame)?;
+        *status.lock() = EncoderStatus::Encoding {
+            frames_done: i + 1,
+            frames_total: frame_paths.len(),
+        };
+    }
+    Ok(())
+}
+
+/// Copy captured PNG frames into the output directory
+fn copy_png_sequence(
+    frame_paths: &[PathBuf],
+    output_dir: &Path,
+    status: &Mutex<EncoderStatus>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if frame_paths.is_empty() {
+        return Err("No frames were captured".into());
+    }
+    std::fs::create_dir_all(output_dir)?;
+    for (i, path) in frame_paths.iter().enumerate() {
+        if let Some(name) = path.file_name() {
+            std::fs::copy(path, output_dir.join(name))?;
+        }
+        *status.lock() = EncoderStatus::Encoding {
+            frames_done: i + 1,
+            frames_total: frame_paths.len(),
+        };
+    }
+    Ok(())
+}
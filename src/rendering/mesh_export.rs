@@ -0,0 +1,129 @@
+// Export the current colony as a static mesh (OBJ or PLY)
+use super::cells::{icosphere, CellInstance};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Highest icosphere subdivision offered for export
+pub const MAX_EXPORT_SUBDIVISIONS: u32 = 4;
+
+/// Mesh file format for colony export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeshFormat {
+    Obj,
+    Ply,
+}
+
+impl MeshFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MeshFormat::Obj => "OBJ",
+            MeshFormat::Ply => "PLY",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MeshFormat::Obj => "obj",
+            MeshFormat::Ply => "ply",
+        }
+    }
+}
+
+/// Write every cell as a sphere into a single merged mesh with per-vertex mode colors.
+/// `subdivisions` controls sphere tessellation (0 = icosahedron, 20 faces per cell).
+pub fn export_colony_mesh(
+    path: &Path,
+    format: MeshFormat,
+    cells: &[CellInstance],
+    subdivisions: u32,
+) -> std::io::Result<()> {
+    let (sphere_vertices, sphere_indices) = icosphere(subdivisions.min(MAX_EXPORT_SUBDIVISIONS));
+    let mut writer = BufWriter::new(std::fs::File::create(path)?);
+
+    match format {
+        MeshFormat::Obj => write_obj(&mut writer, cells, &sphere_vertices, &sphere_indices)?,
+        MeshFormat::Ply => write_ply(&mut writer, cells, &sphere_vertices, &sphere_indices)?,
+    }
+    writer.flush()
+}
+
+fn transform(cell: &CellInstance, vertex: &[f32; 3]) -> [f32; 3] {
+    [
+        cell.position[0] + vertex[0] * cell.radius,
+        cell.position[1] + vertex[1] * cell.radius,
+        cell.position[2] + vertex[2] * cell.radius,
+    ]
+}
+
+/// OBJ with the common `v x y z r g b` vertex color extension (read by Blender and MeshLab)
+fn write_obj(
+    writer: &mut impl Write,
+    cells: &[CellInstance],
+    sphere_vertices: &[[f32; 3]],
+    sphere_indices: &[u32],
+) -> std::io::Result<()> {
+    writeln!(writer, "# Biospheres colony export: {} cells", cells.len())?;
+    for (i, cell) in cells.iter().enumerate() {
+        writeln!(writer, "o cell_{}", i)?;
+        let [r, g, b, _] = cell.color;
+        for vertex in sphere_vertices {
+            let [x, y, z] = transform(cell, vertex);
+            writeln!(writer, "v {} {} {} {} {} {}", x, y, z, r, g, b)?;
+        }
+        for vertex in sphere_vertices {
+            writeln!(writer, "vn {} {} {}", vertex[0], vertex[1], vertex[2])?;
+        }
+        // OBJ indices are 1-based and global across the file
+        let base = (i * sphere_vertices.len()) as u32 + 1;
+        for triangle in sphere_indices.chunks_exact(3) {
+            let (a, b, c) = (triangle[0] + base, triangle[1] + base, triangle[2] + base);
+            writeln!(writer, "f {a}//{a} {b}//{b} {c}//{c}")?;
+        }
+    }
+    Ok(())
+}
+
+/// ASCII PLY with per-vertex normals and 8-bit colors
+fn write_ply(
+    writer: &mut impl Write,
+    cells: &[CellInstance],
+    sphere_vertices: &[[f32; 3]],
+    sphere_indices: &[u32],
+) -> std::io::Result<()> {
+    let vertex_count = cells.len() * sphere_vertices.len();
+    let face_count = cells.len() * sphere_indices.len() / 3;
+
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format ascii 1.0")?;
+    writeln!(writer, "comment Biospheres colony export: {} cells", cells.len())?;
+    writeln!(writer, "element vertex {}", vertex_count)?;
+    for property in ["x", "y", "z", "nx", "ny", "nz"] {
+        writeln!(writer, "property float {}", property)?;
+    }
+    for property in ["red", "green", "blue"] {
+        writeln!(writer, "property uchar {}", property)?;
+    }
+    writeln!(writer, "element face {}", face_count)?;
+    writeln!(writer, "property list uchar uint vertex_indices")?;
+    writeln!(writer, "end_header")?;
+
+    for cell in cells {
+        let [r, g, b] = [cell.color[0], cell.color[1], cell.color[2]]
+            .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        for vertex in sphere_vertices {
+            let [x, y, z] = transform(cell, vertex);
+            writeln!(
+                writer,
+                "{} {} {} {} {} {} {} {} {}",
+                x, y, z, vertex[0], vertex[1], vertex[2], r, g, b
+            )?;
+        }
+    }
+    for i in 0..cells.len() {
+        let base = (i * sphere_vertices.len()) as u32;
+        for triangle in sphere_indices.chunks_exact(3) {
+            writeln!(writer, "3 {} {} {}", triangle[0] + base, triangle[1] + base, triangle[2] + base)?;
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,10 @@
 pub mod adhesion_lines;
 pub mod boundary_crossing;
 pub mod cells;
+pub mod config;
 pub mod debug;
 pub mod flagellocyte_mesh;
+pub mod frame_capture;
+pub mod mesh_export;
 pub mod skybox;
 pub mod volumetric_fog;
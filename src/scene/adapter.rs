@@ -0,0 +1,91 @@
+use super::SceneInitError;
+
+/// Which class of GPU to ask for on hybrid-graphics systems
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum GpuPowerPreference {
+    /// Let the driver decide
+    #[default]
+    Default,
+    /// Usually the discrete GPU
+    HighPerformance,
+    /// Usually the integrated GPU
+    LowPower,
+}
+
+impl GpuPowerPreference {
+    pub fn all() -> &'static [GpuPowerPreference] {
+        &[
+            GpuPowerPreference::Default,
+            GpuPowerPreference::HighPerformance,
+            GpuPowerPreference::LowPower,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GpuPowerPreference::Default => "System Default",
+            GpuPowerPreference::HighPerformance => "High Performance",
+            GpuPowerPreference::LowPower => "Low Power",
+        }
+    }
+
+    fn to_wgpu(self) -> wgpu::PowerPreference {
+        match self {
+            GpuPowerPreference::Default => wgpu::PowerPreference::default(),
+            GpuPowerPreference::HighPerformance => wgpu::PowerPreference::HighPerformance,
+            GpuPowerPreference::LowPower => wgpu::PowerPreference::LowPower,
+        }
+    }
+}
+
+/// Human-readable adapter description, e.g. "NVIDIA GeForce RTX 3060 (Vulkan)"
+pub fn adapter_label(info: &wgpu::AdapterInfo) -> String {
+    format!("{} ({:?})", info.name, info.backend)
+}
+
+/// Labels of every adapter that can present to the surface
+pub fn compatible_adapter_labels(instance: &wgpu::Instance, surface: &wgpu::Surface) -> Vec<String> {
+    instance
+        .enumerate_adapters(wgpu::Backends::all())
+        .into_iter()
+        .filter(|adapter| adapter.is_surface_supported(surface))
+        .map(|adapter| adapter_label(&adapter.get_info()))
+        .collect()
+}
+
+/// Pick the adapter named by `preferred_adapter` if it is present and can drive the surface,
+/// otherwise request one by power preference, falling back to a software adapter last
+pub async fn select_adapter(
+    instance: &wgpu::Instance,
+    surface: &wgpu::Surface<'_>,
+    power_preference: GpuPowerPreference,
+    preferred_adapter: Option<&str>,
+) -> Result<wgpu::Adapter, SceneInitError> {
+    if let Some(wanted) = preferred_adapter {
+        let chosen = instance
+            .enumerate_adapters(wgpu::Backends::all())
+            .into_iter()
+            .find(|adapter| adapter_label(&adapter.get_info()) == wanted && adapter.is_surface_supported(surface));
+        match chosen {
+            Some(adapter) => return Ok(adapter),
+            None => eprintln!("Preferred adapter '{}' not available, choosing automatically", wanted),
+        }
+    }
+
+    let options = wgpu::RequestAdapterOptions {
+        power_preference: power_preference.to_wgpu(),
+        compatible_surface: Some(surface),
+        force_fallback_adapter: false,
+    };
+    match instance.request_adapter(&options).await {
+        Ok(adapter) => Ok(adapter),
+        Err(e) => {
+            eprintln!("No hardware adapter available ({}), trying fallback adapter", e);
+            let fallback = wgpu::RequestAdapterOptions {
+                force_fallback_adapter: true,
+                ..options
+            };
+            Ok(instance.request_adapter(&fallback).await?)
+        }
+    }
+}
@@ -1,26 +1,63 @@
 use std::sync::Arc;
 use wgpu;
 use winit::window::Window;
-use winit::event::WindowEvent;
+use winit::event::{ElementState, WindowEvent};
+use winit::keyboard::PhysicalKey;
 use crate::ui::{
+    about::render_about_dialog,
     imgui_integration::{ImguiManager, ImguiConfig},
-    GlobalUiState,
-    scene_manager::{SceneManagerState, render_scene_manager_window, render_scene_manager_content},
+    GlobalUiState, DEBUG_OVERLAY_KEY, SCREENSHOT_KEY, TOGGLE_UI_KEY,
+    debug_info::render_debug_overlay,
+    scene_manager::{SceneManagerState, SceneRequest, render_instability_warning, render_scene_manager_window, render_scene_manager_content},
     time_scrubber::{TimeScrubberState, render_time_scrubber, render_time_scrubber_content},
-    rendering_controls::{render_controls_ui, render_controls_content},
-    performance_monitor::{PerformanceMonitor, render_performance_window, render_performance_content, update_performance_metrics},
-    genome_editor::{render_genome_editor_window, render_genome_editor_content, GenomeGraphState},
+    rendering_controls::{render_controls_ui, render_controls_content, render_recording_indicator},
+    performance_monitor::{PerformanceMonitor, UPDATE_INTERVAL_MAX, UPDATE_INTERVAL_MIN, render_performance_window, render_performance_content, update_performance_metrics},
+    genome_editor::{render_genome_editor_window, render_genome_editor_content, render_genome_reload_toast, render_autosave_restore_prompt, GenomeGraphState},
     cell_inspector::{CellInspectorState, render_cell_inspector_window, render_cell_inspector_content},
     theme_editor::{ThemeEditorState, render_theme_editor_window, render_theme_editor_content},
-    camera_settings::{CameraSettingsState, render_camera_settings_window, render_camera_settings_content},
+    camera_settings::{render_camera_settings_window, render_camera_settings_content},
     lighting_settings::{LightingSettingsState, render_lighting_settings_window, render_lighting_settings_content},
     edge_resize::{EdgeResizableWindow, EdgeResizeState},
-    main_menu_bar::render_main_menu_bar,
+    main_menu_bar::{MenuRequests, render_main_menu_bar},
+    measurement_hud::render_measurement_hud,
+    parameter_sweep::{ParameterSweepState, render_parameter_sweep_window, render_parameter_sweep_content},
+    mass_histogram::{MassHistogramState, render_mass_histogram_window, render_mass_histogram_content},
+    physics_settings::{render_physics_settings_window, render_physics_settings_content},
+    settings::{FontRebuildDebounce, FontSettings, render_settings_window, render_settings_content},
     imgui_style::{ImguiThemeState, apply_imgui_style},
+    camera::{CameraView, OrbitCamera, bounding_sphere, camera_view_key, handle_camera_bookmark_keys, handle_camera_keys, ViewportCameraInput},
+    imgui_panel::{dockspace_over_main_viewport, saved_layout_exists},
 };
-use crate::simulation::SimulationState;
-use crate::genome::{CurrentGenome, GenomeNodeGraph};
+use crate::simulation::{SimulationMode, SimulationState, SimulationStats, cpu_sim::{CpuSimulation, StepCounters}};
+use crate::simulation::benchmark::{BenchmarkOptions, BenchmarkRecorder};
+use crate::simulation::clock::FixedStepClock;
+use crate::simulation::gpu_physics::{GpuPhysics, GpuPhysicsSupport};
+use crate::simulation::preview_sim::{MAX_PREVIEW_STEPS_PER_FRAME, PreviewSeek};
+use crate::simulation::scenes::SceneLibrary;
+use crate::rendering::frame_capture::{FrameRecorder, ScreenshotCapture, timestamped_screenshot_path};
+use crate::rendering::mesh_export::{MeshFormat, export_colony_mesh};
+use crate::rendering::cells::{CellRenderer, GhostTrail, OnionSkinSettings, cell_instances, DEPTH_FORMAT};
+use crate::rendering::config::RenderConfig;
+use crate::rendering::debug::{GizmoRenderer, build_gizmo_lines};
+use crate::genome::{CurrentGenome, GenomeData, GenomeNodeGraph, analysis::{division_blocker, initial_mode_growth_blocker}, autosave::GenomeAutoSaver, file_io::GenomeFileIo, genome_watcher::GenomeFileWatcher};
+use std::path::Path;
 use std::time::Instant;
+use super::SceneInitError;
+use super::adapter::{adapter_label, compatible_adapter_labels, select_adapter};
+
+/// Upper bound on physics steps per frame so a slow frame can't spiral
+const MAX_STEPS_PER_FRAME: u32 = 32;
+/// Weight of the newest frame in the smoothed achieved speed
+const ACHIEVED_SPEED_SMOOTHING: f32 = 0.05;
+
+/// Simulated seconds between copies of the colony kept for rolling back a blow-up
+const STABLE_SNAPSHOT_INTERVAL: f32 = 1.0;
+
+/// Upper bound on physics steps per frame while seeking to a scrubber time
+const MAX_SEEK_STEPS_PER_FRAME: u32 = 256;
+
+/// Minimum time between automatic saves of changed UI, theme and render settings
+const SETTINGS_SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
 /// Basic scene that renders a simple background color with ImGui UI
 /// This provides the foundation for the complete UI layout
@@ -43,11 +80,52 @@ pub struct BasicScene {
     current_genome: CurrentGenome,
     node_graph: GenomeNodeGraph,
     graph_state: GenomeGraphState,
+    genome_watcher: GenomeFileWatcher,
+    genome_file_io: GenomeFileIo,
+    genome_autosaver: GenomeAutoSaver,
     cell_inspector_state: CellInspectorState,
     theme_editor_state: ThemeEditorState,
-    camera_settings_state: CameraSettingsState,
     lighting_settings_state: LightingSettingsState,
     imgui_theme_state: ImguiThemeState,
+    parameter_sweep_state: ParameterSweepState,
+    mass_histogram_state: MassHistogramState,
+    /// Waits for font setting changes to settle before rebuilding the font atlas
+    font_rebuild: FontRebuildDebounce,
+    /// Monitor scale factor of the window, applied to the UI before the next frame
+    display_scale: f32,
+    
+    // Adapters the user can pick from in Options (takes effect on restart)
+    available_adapters: Vec<String>,
+    
+    // Camera (the camera itself is saved in `global_ui_state`)
+    /// Mouse drag state for orbiting and panning in the viewport
+    viewport_camera_input: ViewportCameraInput,
+    /// Build the default dock layout on the next frame (no saved imgui.ini)
+    apply_default_dock_layout: bool,
+    
+    // Live simulation and cell rendering
+    cpu_simulation: CpuSimulation,
+    /// Compute pipelines for the GPU scene, built the first time it runs
+    gpu_physics: Option<GpuPhysics>,
+    /// Genome the running colony follows; edits reach it through an explicit apply
+    applied_genome: GenomeData,
+    step_clock: FixedStepClock,
+    /// Colony figures shown in the performance monitor, refreshed after each step
+    simulation_stats: SimulationStats,
+    cell_renderer: CellRenderer,
+    gizmo_renderer: GizmoRenderer,
+    /// Windowed benchmark in progress (see `start_benchmark`)
+    benchmark: Option<(BenchmarkOptions, BenchmarkRecorder)>,
+    onion_skin: OnionSkinSettings,
+    /// Wireframe, world sphere, fog and bloom settings from Rendering Controls
+    render_config: RenderConfig,
+    ghost_trail: GhostTrail,
+    /// Recent colony that passed the stability check, restored by "Roll Back"
+    stable_snapshot: Option<CpuSimulation>,
+    
+    // Viewport recording
+    frame_recorder: FrameRecorder,
+    screenshot: ScreenshotCapture,
     
     // Edge resize states for all windows
     cell_inspector_resize: EdgeResizeState,
@@ -59,6 +137,10 @@ pub struct BasicScene {
     time_scrubber_resize: EdgeResizeState,
     performance_monitor_resize: EdgeResizeState,
     rendering_controls_resize: EdgeResizeState,
+    parameter_sweep_resize: EdgeResizeState,
+    mass_histogram_resize: EdgeResizeState,
+    settings_resize: EdgeResizeState,
+    physics_settings_resize: EdgeResizeState,
     
     // Cursor state for edge resizing
     pending_cursor: Option<imgui::MouseCursor>,
@@ -70,11 +152,15 @@ pub struct BasicScene {
     // Settings persistence
     previous_ui_state: GlobalUiState,
     previous_theme_state: ImguiThemeState,
+    previous_render_config: RenderConfig,
+    last_settings_save: Instant,
+    /// A settings save failed and was reported; further failures stay quiet until one succeeds
+    settings_save_failed: bool,
 }
 
 impl BasicScene {
     /// Create a new BasicScene with the given window
-    pub async fn new(window: Arc<Window>) -> Self {
+    pub async fn new(window: Arc<Window>) -> Result<Self, SceneInitError> {
         let size = window.inner_size();
         
         // Create wgpu instance
@@ -84,17 +170,23 @@ impl BasicScene {
         });
         
         // Create surface
-        let surface = instance.create_surface(window.clone()).unwrap();
-        
-        // Request adapter
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::default(),
-                compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
-            })
-            .await
-            .unwrap();
+        let surface = instance.create_surface(window.clone())?;
+        
+        // Settings are needed up front for the adapter choice
+        let global_ui_state = GlobalUiState::load_from_file(&GlobalUiState::default_settings_path());
+        
+        // Request adapter: the saved choice, else by power preference, else the software fallback
+        let adapter = select_adapter(
+            &instance,
+            &surface,
+            global_ui_state.gpu_power_preference,
+            global_ui_state.preferred_adapter.as_deref(),
+        )
+        .await?;
+        let available_adapters = compatible_adapter_labels(&instance, &surface);
+        let adapter_info = adapter.get_info();
+        let adapter_name = adapter_label(&adapter_info);
+        println!("Using graphics adapter: {}", adapter_name);
         
         // Request device and queue
         let (device, queue) = adapter
@@ -107,11 +199,17 @@ impl BasicScene {
                     trace: Default::default(),
                 },
             )
-            .await
-            .unwrap();
+            .await?;
+        let gpu_support = GpuPhysicsSupport::probe(&adapter, &device);
+        if let Some(reason) = gpu_support.unsupported_reason() {
+            println!("GPU physics unavailable: {}", reason);
+        }
         
         // Get surface capabilities and configure
         let surface_caps = surface.get_capabilities(&adapter);
+        if surface_caps.formats.is_empty() {
+            return Err(SceneInitError::UnsupportedSurface);
+        }
         let surface_format = surface_caps
             .formats
             .iter()
@@ -119,8 +217,14 @@ impl BasicScene {
             .find(|f| f.is_srgb())
             .unwrap_or(surface_caps.formats[0]);
         
+        // Allow copying the surface texture when the platform supports it (used for recording)
+        let mut surface_usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+        if surface_caps.usages.contains(wgpu::TextureUsages::COPY_SRC) {
+            surface_usage |= wgpu::TextureUsages::COPY_SRC;
+        }
+        
         let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage: surface_usage,
             format: surface_format,
             width: size.width,
             height: size.height,
@@ -132,40 +236,59 @@ impl BasicScene {
         
         surface.configure(&device, &config);
         
-        // Default background color - dark gray
-        let background_color = wgpu::Color {
-            r: 0.1,
-            g: 0.1,
-            b: 0.15,
-            a: 1.0,
-        };
         
         // Initialize ImGui
-        let imgui_config = ImguiConfig::default();
+        let font_settings = FontSettings::from_ui_state(&global_ui_state);
+        let mut imgui_config = ImguiConfig::default();
+        font_settings.apply_to(&mut imgui_config);
+        // First run: dock the panels into a default layout on the first frame
+        let apply_default_dock_layout = !saved_layout_exists(imgui_config.ini_filename.as_deref());
         let imgui_manager = ImguiManager::new(&device, &queue, surface_format, imgui_config);
         
-        // Ensure default imgui.ini layout is created
-        crate::ui::imgui_panel::ensure_default_imgui_ini();
-        
         // Initialize UI state - load from files if they exist
-        let global_ui_state = GlobalUiState::load_from_file(&GlobalUiState::default_settings_path());
         let imgui_theme_state = ImguiThemeState::load_from_file(&ImguiThemeState::default_theme_path());
+        let render_config = RenderConfig::load_from_file(&RenderConfig::default_path());
         
         // Clone for tracking changes
         let previous_ui_state = global_ui_state.clone();
         let previous_theme_state = imgui_theme_state.clone();
+        let previous_render_config = render_config.clone();
         
-        let scene_manager_state = SceneManagerState::default();
+        // Start in the scene that was active last session
+        let scene_manager_state = SceneManagerState {
+            scenes: SceneLibrary::load_from_file(&SceneLibrary::default_path()),
+            ..Default::default()
+        };
+        let active_scene = scene_manager_state.scenes.active_scene().clone();
         let time_scrubber_state = TimeScrubberState::default();
-        let performance_monitor = PerformanceMonitor::default();
-        let simulation_state = SimulationState::default();
-        let current_genome = CurrentGenome::default();
+        let mut performance_monitor = PerformanceMonitor::default();
+        performance_monitor.adapter_name = adapter_name;
+        performance_monitor.adapter_info = Some(adapter_info);
+        performance_monitor.scene_name = active_scene.name;
+        performance_monitor.perf_update_interval = global_ui_state.perf_update_interval.clamp(UPDATE_INTERVAL_MIN, UPDATE_INTERVAL_MAX);
+        performance_monitor.set_history_size(global_ui_state.perf_history_size);
+        let current_genome = CurrentGenome {
+            genome: active_scene.genome,
+            ..Default::default()
+        };
+        let simulation_state = SimulationState {
+            growth_blocker: initial_mode_growth_blocker(&current_genome.genome),
+            spawn_config: active_scene.spawn,
+            physics_config: active_scene.physics,
+            gpu_support,
+            ..Default::default()
+        };
         let cell_inspector_state = CellInspectorState::default();
         let theme_editor_state = ThemeEditorState::default();
-        let camera_settings_state = CameraSettingsState::default();
         let lighting_settings_state = LightingSettingsState::default();
+        let background_color = Self::lighting_background(&lighting_settings_state);
+        let cpu_simulation = CpuSimulation::spawn(&current_genome.genome, &simulation_state.spawn_config);
+        let applied_genome = current_genome.genome.clone();
+        let genome_autosaver = GenomeAutoSaver::new(&current_genome);
+        let cell_renderer = CellRenderer::new(&device, surface_format, config.width, config.height);
+        let gizmo_renderer = GizmoRenderer::new(&device, surface_format, DEPTH_FORMAT);
         
-        Self {
+        Ok(Self {
             surface,
             device,
             queue,
@@ -180,11 +303,34 @@ impl BasicScene {
             current_genome,
             node_graph: GenomeNodeGraph::default(),
             graph_state: GenomeGraphState::default(),
+            genome_watcher: GenomeFileWatcher::default(),
+            genome_file_io: GenomeFileIo::default(),
+            genome_autosaver,
             cell_inspector_state,
             theme_editor_state,
-            camera_settings_state,
             lighting_settings_state,
             imgui_theme_state,
+            parameter_sweep_state: ParameterSweepState::default(),
+            mass_histogram_state: MassHistogramState::default(),
+            font_rebuild: FontRebuildDebounce::new(font_settings),
+            display_scale: window.scale_factor() as f32,
+            available_adapters,
+            viewport_camera_input: ViewportCameraInput::default(),
+            apply_default_dock_layout,
+            cpu_simulation,
+            gpu_physics: None,
+            applied_genome,
+            step_clock: FixedStepClock::default(),
+            simulation_stats: SimulationStats::default(),
+            cell_renderer,
+            gizmo_renderer,
+            benchmark: None,
+            onion_skin: OnionSkinSettings::default(),
+            render_config,
+            ghost_trail: GhostTrail::default(),
+            stable_snapshot: None,
+            frame_recorder: FrameRecorder::default(),
+            screenshot: ScreenshotCapture::default(),
             cell_inspector_resize: EdgeResizeState::default(),
             genome_editor_resize: EdgeResizeState::default(),
             camera_settings_resize: EdgeResizeState::default(),
@@ -194,12 +340,19 @@ impl BasicScene {
             time_scrubber_resize: EdgeResizeState::default(),
             performance_monitor_resize: EdgeResizeState::default(),
             rendering_controls_resize: EdgeResizeState::default(),
+            parameter_sweep_resize: EdgeResizeState::default(),
+            mass_histogram_resize: EdgeResizeState::default(),
+            settings_resize: EdgeResizeState::default(),
+            physics_settings_resize: EdgeResizeState::default(),
             pending_cursor: None,
             cursor_priority: 0,
             last_frame_time: Instant::now(),
             previous_ui_state,
             previous_theme_state,
-        }
+            previous_render_config,
+            last_settings_save: Instant::now(),
+            settings_save_failed: false,
+        })
     }
     
     /// Get reference to the device
@@ -217,17 +370,52 @@ impl BasicScene {
         self.config.format
     }
     
-    /// Set the background color
+    /// Set the background color. Replaced on the next frame by the color derived from
+    /// the lighting settings.
     pub fn set_background_color(&mut self, color: wgpu::Color) {
         self.background_color = color;
     }
     
+    /// Clear color for the lighting settings (ambient light plus skybox tint)
+    fn lighting_background(lighting: &LightingSettingsState) -> wgpu::Color {
+        let [r, g, b] = lighting.background_color();
+        wgpu::Color { r: r as f64, g: g as f64, b: b as f64, a: 1.0 }
+    }
+    
+    /// Set the window's monitor scale factor; fonts and style sizes follow on the next frame
+    pub fn set_display_scale(&mut self, scale: f32) {
+        self.display_scale = scale;
+    }
+    
     /// Resize the surface
     pub fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
         if new_size.width > 0 && new_size.height > 0 {
             self.config.width = new_size.width;
             self.config.height = new_size.height;
             self.surface.configure(&self.device, &self.config);
+            self.cell_renderer.resize(&self.device, new_size.width, new_size.height);
+        }
+    }
+    
+    pub fn is_paused(&self) -> bool {
+        self.simulation_state.paused
+    }
+    
+    /// Pause or resume the live simulation, as the Play/Pause buttons do
+    pub fn toggle_pause(&mut self) {
+        self.simulation_state.paused = !self.simulation_state.paused;
+    }
+    
+    /// Keep the simulation running without drawing, while the window is hidden to the tray.
+    /// A running benchmark waits for the window to come back, since it times rendering too.
+    pub fn update_hidden(&mut self) {
+        let now = Instant::now();
+        let delta_time = now.duration_since(self.last_frame_time).as_secs_f32();
+        self.last_frame_time = now;
+        
+        if self.benchmark.is_none() {
+            self.step_simulation(delta_time);
+            self.simulation_stats = SimulationStats::new(&self.cpu_simulation, &self.simulation_state);
         }
     }
     
@@ -242,6 +430,53 @@ impl BasicScene {
         
         // Update performance metrics
         update_performance_metrics(&mut self.performance_monitor, delta_time, current_time);
+        // Saved with the settings so the chosen smoothing survives restarts
+        self.global_ui_state.perf_history_size = self.performance_monitor.history_size();
+        self.global_ui_state.perf_update_interval = self.performance_monitor.perf_update_interval;
+        
+        // Track the selected cell with the camera if follow is enabled
+        self.update_follow_camera(delta_time);
+        
+        // Apply finished background saves and loads
+        self.genome_file_io.poll(&mut self.current_genome, &mut self.node_graph);
+        
+        // Switch scenes or save the running setup as a new one
+        self.handle_scene_request();
+        
+        // Reload the genome if its watched file changed on disk; a background load takes precedence
+        if !self.genome_file_io.is_loading() {
+            self.genome_watcher.update(&mut self.current_genome, &mut self.node_graph);
+        }
+        
+        // Advance the live simulation, or the benchmark workload when one is running
+        let benchmark_finished = if self.benchmark.is_some() {
+            self.step_benchmark(delta_time)
+        } else {
+            self.step_simulation(delta_time);
+            false
+        };
+        self.simulation_stats = SimulationStats::new(&self.cpu_simulation, &self.simulation_state);
+        
+        // Keep the inspected cell and its neighbour list in step with the colony
+        self.cell_inspector_state.sync_with_simulation(&self.cpu_simulation, &self.applied_genome);
+        
+        // Rebucket cell masses only while someone is looking at the histogram
+        if self.global_ui_state.show_mass_histogram {
+            self.mass_histogram_state.update(delta_time, &self.cpu_simulation, &self.applied_genome);
+        }
+        
+        // Autosave the genome if it changed since the last autosave
+        if self.global_ui_state.autosave_enabled {
+            self.genome_autosaver.update(delta_time, self.global_ui_state.autosave_interval_secs, &self.current_genome);
+        }
+        
+        // Hand any finished frame readbacks to the recording encoder
+        self.frame_recorder.poll(&self.device);
+        match self.screenshot.poll(&self.device) {
+            Some(Ok(path)) => println!("Screenshot saved to {}", path.display()),
+            Some(Err(e)) => eprintln!("Failed to save screenshot: {}", e),
+            None => {}
+        }
         
         // Get the current frame
         let output = self.surface.get_current_texture()?;
@@ -256,7 +491,8 @@ impl BasicScene {
                 label: Some("Render Encoder"),
             });
         
-        // Create render pass that clears to background color
+        // Create render pass that clears to the background from the lighting settings
+        self.background_color = Self::lighting_background(&self.lighting_settings_state);
         {
             let _render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Background Render Pass"),
@@ -274,8 +510,64 @@ impl BasicScene {
             });
         }
         
+        // Draw cells and onion-skin ghosts over the background
+        {
+            let cells = cell_instances(
+                &self.cpu_simulation,
+                &self.applied_genome,
+                self.global_ui_state.cell_color_by,
+                self.simulation_state.physics_config.min_cell_mass,
+            );
+            let ghosts = self.ghost_trail.ghost_instances(&self.onion_skin);
+            let aspect = self.config.width as f32 / self.config.height.max(1) as f32;
+            self.cell_renderer.render(
+                &self.device,
+                &self.queue,
+                &mut encoder,
+                &view,
+                self.global_ui_state.camera.view_projection(aspect),
+                self.global_ui_state.camera.position(),
+                &cells,
+                &ghosts,
+                self.global_ui_state.legacy_color_handling,
+                &self.global_ui_state.cell_lod,
+            );
+            
+            let gizmo_lines = build_gizmo_lines(
+                &self.cpu_simulation,
+                &self.applied_genome,
+                &self.global_ui_state.gizmos,
+                self.simulation_state.physics_config.min_cell_mass,
+            );
+            self.gizmo_renderer.render(
+                &self.device,
+                &self.queue,
+                &mut encoder,
+                &view,
+                self.cell_renderer.depth_view(),
+                self.global_ui_state.camera.view_projection(aspect),
+                &gizmo_lines,
+            );
+        }
+        
+        // Rebuild the font atlas once the font settings have stopped changing
+        if let Some(font_settings) = self.font_rebuild.poll(FontSettings::from_ui_state(&self.global_ui_state), Instant::now()) {
+            // Only the font fields are read from this config
+            let mut font_config = ImguiConfig::default();
+            font_settings.apply_to(&mut font_config);
+            self.imgui_manager.update_font_config(&self.device, &self.queue, &font_config);
+        }
+        
+        // Rebuild fonts for a new monitor scale before the frame starts, so no frame
+        // draws text from the old atlas with the new style sizes or the other way round
+        self.imgui_manager.update_display_scale(&self.device, &self.queue, self.display_scale);
+        
+        // Apply ImGui theme and styling before the frame starts
+        let display_scale = self.imgui_manager.display_scale();
+        apply_imgui_style(self.imgui_manager.context_mut(), &mut self.imgui_theme_state, self.global_ui_state.ui_scale, display_scale);
+        
         // Prepare ImGui frame and render UI windows
-        let (cursor_requests, manual_save_requested, exit_requested) = {
+        let (cursor_requests, menu_requests) = {
             let ui = self.imgui_manager.prepare_frame(window);
             
             // Collect cursor requests from all windows
@@ -283,17 +575,27 @@ impl BasicScene {
             
             // Create a dockspace that covers the entire viewport
             // This allows windows to be docked anywhere in the application
-            ui.dockspace_over_main_viewport();
+            dockspace_over_main_viewport(ui, &mut self.apply_default_dock_layout);
             
-            // Apply ImGui theme and styling
-            apply_imgui_style(ui, &mut self.imgui_theme_state, self.global_ui_state.ui_scale);
+            // Viewport camera keys and bookmark hotkeys
+            handle_camera_keys(ui, &mut self.global_ui_state.camera);
+            handle_camera_bookmark_keys(ui, &mut self.global_ui_state.camera, &mut self.global_ui_state.camera_bookmarks);
+            let camera_view_key = camera_view_key(ui);
+            
+            // Presentation mode hides the menu bar and every window; the viewport keeps rendering
+            let show_ui = !self.global_ui_state.ui_hidden;
             
             // Render main menu bar at the top
-            let (manual_save_requested, mut exit_requested) = render_main_menu_bar(ui, &mut self.global_ui_state, &mut self.simulation_state, &mut self.imgui_theme_state);
+            let mut menu_requests = if show_ui {
+                render_main_menu_bar(ui, &mut self.global_ui_state, &mut self.simulation_state, &mut self.imgui_theme_state, &self.available_adapters)
+            } else {
+                MenuRequests::default()
+            };
+            menu_requests.camera_view = menu_requests.camera_view.or(camera_view_key);
             
             // Render all UI windows inline to avoid borrow checker issues
             // Scene Manager
-            if self.global_ui_state.show_scene_manager {
+            if show_ui && self.global_ui_state.show_scene_manager {
                 if self.global_ui_state.windows_locked {
                     if render_scene_manager_window(
                         ui,
@@ -301,7 +603,7 @@ impl BasicScene {
                         &mut self.simulation_state,
                         &self.global_ui_state,
                     ) {
-                        exit_requested = true;
+                        menu_requests.exit = true;
                     }
                 } else {
                     let mut cursor_to_set = None;
@@ -312,7 +614,7 @@ impl BasicScene {
                         .min_size([250.0, 150.0])
                         .build(ui, |cursor| cursor_to_set = cursor, || {
                             if render_scene_manager_content(ui, &mut self.scene_manager_state, &mut self.simulation_state) {
-                                exit_requested = true;
+                                menu_requests.exit = true;
                             }
                         });
                     cursor_requests.push((cursor_to_set, 10));
@@ -320,7 +622,7 @@ impl BasicScene {
             }
             
             // Time Scrubber
-            if self.global_ui_state.show_time_scrubber {
+            if show_ui && self.global_ui_state.show_time_scrubber {
                 if self.global_ui_state.windows_locked {
                     render_time_scrubber(
                         ui,
@@ -343,9 +645,9 @@ impl BasicScene {
             }
             
             // Rendering Controls
-            if self.global_ui_state.show_rendering_controls {
+            if show_ui && self.global_ui_state.show_rendering_controls {
                 if self.global_ui_state.windows_locked {
-                    render_controls_ui(ui, &mut self.global_ui_state);
+                    render_controls_ui(ui, &mut self.global_ui_state, &mut self.frame_recorder, &mut self.onion_skin, &mut self.render_config, self.cpu_simulation.cell_count());
                 } else {
                     let mut cursor_to_set = None;
                     EdgeResizableWindow::new("Rendering Controls", &mut self.rendering_controls_resize)
@@ -354,16 +656,16 @@ impl BasicScene {
                         .border_size(6.0)
                         .min_size([250.0, 200.0])
                         .build(ui, |cursor| cursor_to_set = cursor, || {
-                            render_controls_content(ui, &mut self.global_ui_state);
+                            render_controls_content(ui, &mut self.global_ui_state, &mut self.frame_recorder, &mut self.onion_skin, &mut self.render_config, self.cpu_simulation.cell_count());
                         });
                     cursor_requests.push((cursor_to_set, 10));
                 }
             }
             
             // Advanced Performance Monitor
-            if self.global_ui_state.show_performance_monitor {
+            if show_ui && self.global_ui_state.show_performance_monitor {
                 if self.global_ui_state.windows_locked {
-                    render_performance_window(ui, &self.performance_monitor, &self.global_ui_state);
+                    render_performance_window(ui, &mut self.performance_monitor, &self.simulation_stats, &self.global_ui_state);
                 } else {
                     let mut cursor_to_set = None;
                     EdgeResizableWindow::new("Performance Monitor", &mut self.performance_monitor_resize)
@@ -372,20 +674,22 @@ impl BasicScene {
                         .border_size(6.0)
                         .min_size([300.0, 200.0])
                         .build(ui, |cursor| cursor_to_set = cursor, || {
-                            render_performance_content(ui, &self.performance_monitor);
+                            render_performance_content(ui, &mut self.performance_monitor, &self.simulation_stats);
                         });
                     cursor_requests.push((cursor_to_set, 10));
                 }
             }
             
             // Cell Inspector
-            if self.global_ui_state.show_cell_inspector {
+            if show_ui && self.global_ui_state.show_cell_inspector {
                 if self.global_ui_state.windows_locked {
                     render_cell_inspector_window(
                         ui,
                         &mut self.cell_inspector_state,
-                        &self.current_genome,
+                        &self.cpu_simulation,
+                        &self.applied_genome,
                         &self.global_ui_state,
+                        self.simulation_state.physics_config.min_cell_mass,
                     );
                 } else {
                     let mut cursor_to_set = None;
@@ -395,14 +699,24 @@ impl BasicScene {
                         .border_size(6.0)
                         .min_size([200.0, 150.0])
                         .build(ui, |cursor| cursor_to_set = cursor, || {
-                            render_cell_inspector_content(ui, &mut self.cell_inspector_state, &self.current_genome);
+                            render_cell_inspector_content(ui, &mut self.cell_inspector_state, &self.cpu_simulation, &self.applied_genome, self.global_ui_state.number_format(), self.simulation_state.physics_config.min_cell_mass);
                         });
                     cursor_requests.push((cursor_to_set, 10));
                 }
             }
             
+            // "Edit Mode" in the inspector jumps to the cell's mode in the Genome Editor
+            let focus_genome_editor = match self.cell_inspector_state.edit_mode_requested.take() {
+                Some(mode_index) => {
+                    self.current_genome.select_single_mode(mode_index);
+                    self.global_ui_state.show_genome_editor = true;
+                    true
+                }
+                None => false,
+            };
+            
             // Genome Editor
-            if self.global_ui_state.show_genome_editor {
+            if show_ui && self.global_ui_state.show_genome_editor {
                 if self.global_ui_state.windows_locked {
                     render_genome_editor_window(
                         ui,
@@ -411,6 +725,8 @@ impl BasicScene {
                         &self.global_ui_state,
                         &mut self.node_graph,
                         &mut self.graph_state,
+                        &mut self.genome_file_io,
+                        self.cpu_simulation.mode_counts(),
                     );
                 } else {
                     let mut cursor_to_set = None;
@@ -420,19 +736,25 @@ impl BasicScene {
                         .border_size(6.0)
                         .min_size([400.0, 300.0])
                         .build(ui, |cursor| cursor_to_set = cursor, || {
-                            render_genome_editor_content(ui, &mut self.current_genome, &mut self.simulation_state, &mut self.node_graph, &mut self.graph_state);
+                            render_genome_editor_content(ui, &mut self.current_genome, &mut self.simulation_state, &mut self.node_graph, &mut self.graph_state, &mut self.genome_file_io, self.cpu_simulation.mode_counts(), self.global_ui_state.number_format());
                         });
                     cursor_requests.push((cursor_to_set, 10));
                 }
             }
+            if focus_genome_editor {
+                // The window exists by now, even if it was hidden until this frame
+                unsafe { imgui::sys::igSetWindowFocus_Str(c"Genome Editor".as_ptr()) };
+            }
             
             // Camera Settings
-            if self.global_ui_state.show_camera_settings {
+            if show_ui && self.global_ui_state.show_camera_settings {
+                let aspect = self.config.width as f32 / self.config.height.max(1) as f32;
                 if self.global_ui_state.windows_locked {
                     render_camera_settings_window(
                         ui,
-                        &mut self.camera_settings_state,
-                        &self.global_ui_state,
+                        &mut self.global_ui_state,
+                        self.simulation_state.physics_config.world_radius,
+                        aspect,
                     );
                 } else {
                     let mut cursor_to_set = None;
@@ -442,18 +764,24 @@ impl BasicScene {
                         .border_size(6.0)
                         .min_size([300.0, 200.0])
                         .build(ui, |cursor| cursor_to_set = cursor, || {
-                            render_camera_settings_content(ui, &mut self.camera_settings_state);
+                            render_camera_settings_content(
+                                ui,
+                                &mut self.global_ui_state.camera,
+                                self.simulation_state.physics_config.world_radius,
+                                aspect,
+                            );
                         });
                     cursor_requests.push((cursor_to_set, 10));
                 }
             }
             
             // Theme Editor
-            if self.global_ui_state.show_theme_editor {
+            if show_ui && self.global_ui_state.show_theme_editor {
                 if self.global_ui_state.windows_locked {
                     render_theme_editor_window(
                         ui,
                         &mut self.theme_editor_state,
+                        &mut self.imgui_theme_state,
                         &self.global_ui_state,
                     );
                 } else {
@@ -464,14 +792,14 @@ impl BasicScene {
                         .border_size(6.0)
                         .min_size([250.0, 200.0])
                         .build(ui, |cursor| cursor_to_set = cursor, || {
-                            render_theme_editor_content(ui, &mut self.theme_editor_state);
+                            render_theme_editor_content(ui, &mut self.theme_editor_state, &mut self.imgui_theme_state);
                         });
                     cursor_requests.push((cursor_to_set, 10));
                 }
             }
             
             // Lighting Settings
-            if self.global_ui_state.show_lighting_settings {
+            if show_ui && self.global_ui_state.show_lighting_settings {
                 if self.global_ui_state.windows_locked {
                     render_lighting_settings_window(
                         ui,
@@ -492,15 +820,156 @@ impl BasicScene {
                 }
             }
             
-            (cursor_requests, manual_save_requested, exit_requested)
+            // Parameter Sweep
+            if show_ui && self.global_ui_state.show_parameter_sweep {
+                if self.global_ui_state.windows_locked {
+                    render_parameter_sweep_window(
+                        ui,
+                        &mut self.parameter_sweep_state,
+                        &self.current_genome,
+                        &self.simulation_state,
+                        &self.global_ui_state,
+                    );
+                } else {
+                    let mut cursor_to_set = None;
+                    EdgeResizableWindow::new("Parameter Sweep", &mut self.parameter_sweep_resize)
+                        .size([420.0, 520.0], imgui::Condition::FirstUseEver)
+                        .position([1000.0, 200.0], imgui::Condition::FirstUseEver)
+                        .border_size(6.0)
+                        .min_size([300.0, 250.0])
+                        .build(ui, |cursor| cursor_to_set = cursor, || {
+                            render_parameter_sweep_content(ui, &mut self.parameter_sweep_state, &self.current_genome, &self.simulation_state);
+                        });
+                    cursor_requests.push((cursor_to_set, 10));
+                }
+            }
+            
+            // Mass Histogram
+            if show_ui && self.global_ui_state.show_mass_histogram {
+                if self.global_ui_state.windows_locked {
+                    render_mass_histogram_window(ui, &mut self.mass_histogram_state, &self.global_ui_state);
+                } else {
+                    let mut cursor_to_set = None;
+                    EdgeResizableWindow::new("Mass Histogram", &mut self.mass_histogram_resize)
+                        .size([380.0, 300.0], imgui::Condition::FirstUseEver)
+                        .position([1000.0, 300.0], imgui::Condition::FirstUseEver)
+                        .border_size(6.0)
+                        .min_size([250.0, 180.0])
+                        .build(ui, |cursor| cursor_to_set = cursor, || {
+                            render_mass_histogram_content(ui, &mut self.mass_histogram_state, self.global_ui_state.number_format());
+                        });
+                    cursor_requests.push((cursor_to_set, 10));
+                }
+            }
+            
+            // Settings
+            if show_ui && self.global_ui_state.show_settings {
+                if self.global_ui_state.windows_locked {
+                    render_settings_window(ui, &mut self.global_ui_state);
+                } else {
+                    let mut cursor_to_set = None;
+                    EdgeResizableWindow::new("Settings", &mut self.settings_resize)
+                        .size([360.0, 220.0], imgui::Condition::FirstUseEver)
+                        .position([1000.0, 200.0], imgui::Condition::FirstUseEver)
+                        .border_size(6.0)
+                        .min_size([250.0, 150.0])
+                        .build(ui, |cursor| cursor_to_set = cursor, || {
+                            render_settings_content(ui, &mut self.global_ui_state);
+                        });
+                    cursor_requests.push((cursor_to_set, 10));
+                }
+            }
+            
+            // Physics Settings
+            if show_ui && self.global_ui_state.show_physics_settings {
+                if self.global_ui_state.windows_locked {
+                    render_physics_settings_window(ui, &mut self.simulation_state.physics_config, &self.global_ui_state);
+                } else {
+                    let mut cursor_to_set = None;
+                    EdgeResizableWindow::new("Physics Settings", &mut self.physics_settings_resize)
+                        .size([380.0, 340.0], imgui::Condition::FirstUseEver)
+                        .position([1000.0, 440.0], imgui::Condition::FirstUseEver)
+                        .border_size(6.0)
+                        .min_size([280.0, 200.0])
+                        .build(ui, |cursor| cursor_to_set = cursor, || {
+                            render_physics_settings_content(ui, &mut self.simulation_state.physics_config);
+                        });
+                    cursor_requests.push((cursor_to_set, 10));
+                }
+            }
+            
+            // Frame and colony counters, top-left
+            let debug_overlay_height = if show_ui && self.global_ui_state.show_debug_overlay {
+                render_debug_overlay(ui, &self.performance_monitor, &self.simulation_stats)
+            } else {
+                0.0
+            };
+            
+            // Measurement HUD overlay
+            if show_ui && self.global_ui_state.show_measurement_hud {
+                render_measurement_hud(
+                    ui,
+                    self.global_ui_state.measurement_hud_corner,
+                    debug_overlay_height,
+                    self.simulation_state.current_time,
+                    self.cpu_simulation.cell_count(),
+                    self.performance_monitor.display_fps,
+                    self.global_ui_state.number_format(),
+                );
+            }
+            
+            if show_ui {
+                // Recording indicator overlay
+                render_recording_indicator(ui, &self.frame_recorder);
+                
+                // Explain an automatic pause after the physics blew up
+                render_instability_warning(ui, &mut self.simulation_state);
+                
+                render_about_dialog(ui, &mut self.global_ui_state.show_about, self.performance_monitor.adapter_info.as_ref());
+                
+                // Offer to restore an autosave from a previous session
+                if !self.genome_file_io.is_loading() {
+                    render_autosave_restore_prompt(ui, &mut self.genome_autosaver, &mut self.current_genome, &mut self.node_graph);
+                }
+                
+                // Genome live reload toast
+                render_genome_reload_toast(ui, &self.genome_watcher);
+            }
+            
+            // Record genome edits for undo once the widget being edited is released
+            self.current_genome.history.track(&self.current_genome.genome, ui.is_any_item_active());
+            
+            (cursor_requests, menu_requests)
         };
         
         // Handle manual save request
-        if manual_save_requested {
+        if menu_requests.manual_save {
             self.save_settings();
+            self.save_scenes();
             println!("Settings saved manually");
         }
         
+        // Handle mesh export request
+        if let Some(format) = menu_requests.export_mesh {
+            self.export_mesh(format);
+        }
+        
+        // Handle screenshot request from the menu
+        if menu_requests.screenshot {
+            let picked = rfd::FileDialog::new()
+                .add_filter("PNG Image", &["png"])
+                .set_file_name("screenshot.png")
+                .save_file();
+            if let Some(path) = picked {
+                self.capture_screenshot(&path);
+            }
+        }
+        
+        // Handle reset view / axis snap request
+        if let Some(view) = menu_requests.camera_view {
+            self.snap_camera(view);
+        }
+        
         // Process cursor requests with priority
         // Reset cursor state each frame to start fresh
         let _prev_cursor = self.pending_cursor;
@@ -525,22 +994,447 @@ impl BasicScene {
         // Check for settings changes and save if needed
         self.check_and_save_settings();
         
-        Ok((output, view, encoder, exit_requested))
+        // Keep the running setup of the active scene and any unsaved settings for next session
+        if menu_requests.exit {
+            self.save_scenes();
+            self.save_settings();
+            self.finish_telemetry();
+        }
+        
+        Ok((output, view, encoder, menu_requests.exit || benchmark_finished))
     }
 
     
     /// Present the frame
-    pub fn present(&self, output: wgpu::SurfaceTexture, encoder: wgpu::CommandEncoder) {
+    pub fn present(&mut self, output: wgpu::SurfaceTexture, mut encoder: wgpu::CommandEncoder) {
+        // Queue a copy of this frame if recording or taking a screenshot
+        if self.config.usage.contains(wgpu::TextureUsages::COPY_SRC) {
+            self.frame_recorder.capture_frame(&self.device, &mut encoder, &output.texture);
+            if let Err(e) = self.screenshot.capture_frame(&self.device, &mut encoder, &output.texture) {
+                eprintln!("Failed to save screenshot: {}", e);
+            }
+        }
+        
         // Submit the command buffer
         self.queue.submit(std::iter::once(encoder.finish()));
+        self.frame_recorder.after_submit();
+        self.screenshot.after_submit();
         
         // Present the frame
         output.present();
     }
     
+    /// Get reference to the orbit camera
+    pub fn camera(&self) -> &OrbitCamera {
+        &self.global_ui_state.camera
+    }
+    
+    /// Run fixed physics steps for the elapsed frame time, scaled by the speed multiplier
+    fn step_simulation(&mut self, delta_time: f32) {
+        // GPU mode needs compute support; without it the same colony carries on in the CPU scene
+        self.simulation_state.apply_gpu_fallback();
+        if self.simulation_state.mode == SimulationMode::Gpu && self.gpu_physics.is_none() {
+            self.gpu_physics = Some(GpuPhysics::new(&self.device, &self.queue));
+        }
+        
+        // The sim can't run backwards, so seeking to an earlier time restarts the colony
+        let dt = self.simulation_state.physics_config.fixed_timestep;
+        if let Some(target) = self.simulation_state.target_time {
+            if target < self.cpu_simulation.time - dt * 0.5 {
+                self.simulation_state.needs_respawn = true;
+            }
+        }
+        
+        // Restart the colony from the spawn configuration with the edited genome
+        if self.simulation_state.needs_respawn {
+            self.simulation_state.needs_respawn = false;
+            self.respawn_colony();
+        }
+        
+        // Hot-apply the edited genome; this works while paused too
+        if self.simulation_state.genome_apply_requested {
+            self.simulation_state.genome_apply_requested = false;
+            self.cpu_simulation.apply_genome(&self.applied_genome, self.simulation_state.genome_apply_mode);
+            self.applied_genome = self.current_genome.genome.clone();
+            self.simulation_state.growth_blocker = initial_mode_growth_blocker(&self.applied_genome);
+        }
+        self.simulation_state.genome_has_unapplied_changes = self.applied_genome != self.current_genome.genome;
+        
+        // Go back to the last colony that passed the stability check; stays paused so settings can be fixed
+        if self.simulation_state.rollback_requested {
+            self.simulation_state.rollback_requested = false;
+            if let Some(snapshot) = &self.stable_snapshot {
+                self.cpu_simulation = snapshot.clone();
+                self.simulation_state.current_time = self.cpu_simulation.time;
+                self.simulation_state.instability = None;
+                self.ghost_trail.clear();
+            }
+        }
+        
+        // The genome preview jumps ahead in a tight loop without the live scene's bookkeeping
+        if self.simulation_state.mode == SimulationMode::Preview {
+            if let Some(target) = self.simulation_state.target_time {
+                self.fast_forward_preview(target);
+                return;
+            }
+        }
+        
+        // Seek to a time picked on the scrubber, spread over frames; this works while paused too
+        if let Some(target) = self.simulation_state.target_time {
+            self.simulation_state.is_resimulating = true;
+            self.step_clock.reset();
+            let remaining = ((target - self.cpu_simulation.time) / dt - 0.5).ceil().max(0.0) as usize;
+            let mut frame_counters = StepCounters::default();
+            self.advance_steps(remaining.min(MAX_SEEK_STEPS_PER_FRAME as usize), &mut frame_counters);
+            let arrived = self.cpu_simulation.time + dt * 0.5 >= target;
+            if arrived || self.simulation_state.instability.is_some() {
+                self.simulation_state.target_time = None;
+                self.simulation_state.is_resimulating = false;
+            }
+            self.performance_monitor.sim_counters = frame_counters;
+            self.simulation_state.current_time = self.cpu_simulation.time;
+            return;
+        }
+        
+        if self.simulation_state.paused {
+            self.step_clock.reset();
+            let mut frame_counters = StepCounters::default();
+            if std::mem::take(&mut self.simulation_state.step_requested) {
+                self.advance_steps(1, &mut frame_counters);
+                self.simulation_state.current_time = self.cpu_simulation.time;
+            }
+            self.performance_monitor.sim_counters = frame_counters;
+            return;
+        }
+        self.simulation_state.step_requested = false;
+        
+        let steps = self.step_clock.steps_due(delta_time * self.simulation_state.speed_multiplier, dt, MAX_STEPS_PER_FRAME);
+        let mut frame_counters = StepCounters::default();
+        if !self.advance_steps(steps, &mut frame_counters) {
+            self.step_clock.reset();
+        }
+        self.simulation_state.speed_capped = steps >= MAX_STEPS_PER_FRAME as usize;
+        if delta_time > 0.0 {
+            let frame_speed = steps as f32 * dt / delta_time;
+            let achieved = &mut self.simulation_state.achieved_speed;
+            *achieved += (frame_speed - *achieved) * ACHIEVED_SPEED_SMOOTHING;
+        }
+        
+        self.performance_monitor.sim_counters = frame_counters;
+        self.simulation_state.current_time = self.cpu_simulation.time;
+    }
+    
+    /// Run the genome preview towards `target`, at most `MAX_PREVIEW_STEPS_PER_FRAME` steps per frame.
+    /// `is_resimulating` stays set until the target is reached or a step blows up.
+    fn fast_forward_preview(&mut self, target: f32) {
+        let seek = match self.simulation_state.preview_seek {
+            Some(seek) if seek.target_time == target => seek,
+            _ => {
+                // Trail points from before the jump would be drawn as one long streak
+                self.ghost_trail.clear();
+                PreviewSeek::new(self.cpu_simulation.time, target)
+            }
+        };
+        self.simulation_state.preview_seek = Some(seek);
+        self.simulation_state.is_resimulating = true;
+        self.step_clock.reset();
+        
+        let result = seek.run(
+            &mut self.cpu_simulation,
+            &self.applied_genome,
+            &self.simulation_state.physics_config,
+            &mut self.simulation_state.event_log,
+            MAX_PREVIEW_STEPS_PER_FRAME,
+        );
+        let finished = match result {
+            Ok(arrived) => arrived,
+            Err(instability) => {
+                eprintln!("Simulation unstable at t={:.2}s (cell {}): {}", instability.time, instability.cell_id, instability.likely_cause);
+                self.simulation_state.instability = Some(instability);
+                self.simulation_state.paused = true;
+                true
+            }
+        };
+        if finished {
+            self.simulation_state.target_time = None;
+            self.simulation_state.preview_seek = None;
+            self.simulation_state.is_resimulating = false;
+        }
+        self.performance_monitor.sim_counters = StepCounters::default();
+        self.simulation_state.current_time = self.cpu_simulation.time;
+    }
+    
+    /// Run physics steps, keeping the rollback snapshot and ghost trail up to date.
+    /// Returns false, leaving the simulation paused, if a step blew up.
+    fn advance_steps(&mut self, steps: usize, frame_counters: &mut StepCounters) -> bool {
+        let genome = &self.applied_genome;
+        let color_by = self.global_ui_state.cell_color_by;
+        let min_cell_mass = self.simulation_state.physics_config.min_cell_mass;
+        let stable_snapshot = &mut self.stable_snapshot;
+        let stable_snapshot_time = &mut self.simulation_state.stable_snapshot_time;
+        let ghost_trail = &mut self.ghost_trail;
+        let onion_skin = &self.onion_skin;
+        let telemetry = &mut self.simulation_state.telemetry;
+        let after_step = |simulation: &CpuSimulation| {
+            if stable_snapshot.as_ref().is_none_or(|snapshot| simulation.time - snapshot.time >= STABLE_SNAPSHOT_INTERVAL) {
+                *stable_snapshot = Some(simulation.clone());
+                *stable_snapshot_time = Some(simulation.time);
+            }
+            ghost_trail.record_step(|| cell_instances(simulation, genome, color_by, min_cell_mass), onion_skin);
+            frame_counters.accumulate(&simulation.last_step_counters);
+            telemetry.record_step(simulation, genome);
+        };
+        let config = &self.simulation_state.physics_config;
+        let events = &mut self.simulation_state.event_log;
+        let result = match self.gpu_physics.as_mut().filter(|_| self.simulation_state.mode == SimulationMode::Gpu) {
+            Some(gpu) => self.cpu_simulation.advance_on_gpu(gpu, genome, config, events, steps, after_step),
+            None => self.cpu_simulation.advance(genome, config, events, steps, after_step),
+        };
+        
+        // A GPU that stops working hands the colony to the CPU scene with a warning
+        if let Some(reason) = self.gpu_physics.as_ref().and_then(GpuPhysics::failure) {
+            self.simulation_state.gpu_support = GpuPhysicsSupport::Unsupported(reason.to_string());
+            self.simulation_state.apply_gpu_fallback();
+            self.gpu_physics = None;
+        }
+        
+        // Stop at the first blow-up instead of rendering NaN garbage
+        if let Err(instability) = result {
+            eprintln!("Simulation unstable at t={:.2}s (cell {}): {}", instability.time, instability.cell_id, instability.likely_cause);
+            self.simulation_state.instability = Some(instability);
+            self.simulation_state.paused = true;
+            return false;
+        }
+        true
+    }
+    
+    /// Swap in the benchmark colony and drive it every frame until `options.steps` are done.
+    /// The edited genome is left untouched.
+    pub fn start_benchmark(&mut self, options: BenchmarkOptions, genome: GenomeData) {
+        self.applied_genome = genome;
+        self.cpu_simulation = CpuSimulation::new(&self.applied_genome);
+        self.simulation_state.event_log.clear();
+        self.ghost_trail.clear();
+        self.stable_snapshot = None;
+        self.benchmark = Some((options, BenchmarkRecorder::default()));
+    }
+    
+    /// Run one frame's worth of benchmark steps at full speed; prints the report and
+    /// returns true once the run is complete or the colony blew up
+    fn step_benchmark(&mut self, delta_time: f32) -> bool {
+        let Some((options, recorder)) = &mut self.benchmark else {
+            return false;
+        };
+        recorder.record_frame(std::time::Duration::from_secs_f32(delta_time));
+        
+        let remaining = options.steps - recorder.steps_recorded();
+        let mut last = Instant::now();
+        let result = self.cpu_simulation.advance(
+            &self.applied_genome,
+            &self.simulation_state.physics_config,
+            &mut self.simulation_state.event_log,
+            remaining.min(MAX_STEPS_PER_FRAME as usize),
+            |simulation| {
+                let now = Instant::now();
+                recorder.record_step(now - last, simulation.cell_count());
+                last = now;
+            },
+        );
+        self.simulation_state.current_time = self.cpu_simulation.time;
+        
+        if let Err(instability) = result {
+            eprintln!("Benchmark colony blew up at t={:.2}s: {}", instability.time, instability.likely_cause);
+        } else if recorder.steps_recorded() < options.steps {
+            return false;
+        }
+        println!("{}", recorder.report(self.cpu_simulation.cell_count()).to_json());
+        self.benchmark = None;
+        true
+    }
+    
+    /// Copy the running genome, spawn and physics settings into the active scene
+    fn capture_active_scene(&mut self) {
+        let scene = self.scene_manager_state.scenes.active_scene_mut();
+        scene.genome = self.current_genome.genome.clone();
+        scene.spawn = self.simulation_state.spawn_config.clone();
+        scene.physics = self.simulation_state.physics_config.clone();
+    }
+    
+    /// Apply a scene switch or save-as-new requested from the Scene Manager, then persist the list
+    fn handle_scene_request(&mut self) {
+        match self.scene_manager_state.scene_request.take() {
+            Some(SceneRequest::Switch(index)) if index < self.scene_manager_state.scenes.scenes.len() => {
+                self.capture_active_scene();
+                self.scene_manager_state.scenes.active = index;
+                let scene = self.scene_manager_state.scenes.active_scene().clone();
+                self.current_genome.load_genome(scene.genome);
+                self.current_genome.select_single_mode(0);
+                self.current_genome.file_path = None;
+                self.current_genome.watch_file = false;
+                self.node_graph.mark_for_rebuild();
+                self.simulation_state.spawn_config = scene.spawn;
+                self.simulation_state.physics_config = scene.physics;
+                self.respawn_colony();
+                self.cell_inspector_state.clear_selection();
+                println!("Switched to scene '{}'", scene.name);
+            }
+            Some(SceneRequest::SaveAsNew(name)) => {
+                self.capture_active_scene();
+                let mut scene = self.scene_manager_state.scenes.active_scene().clone();
+                scene.name = name;
+                self.scene_manager_state.scenes.add(scene);
+            }
+            Some(SceneRequest::Switch(_)) | None => {
+                if !self.scene_manager_state.scenes_changed {
+                    return;
+                }
+            }
+        }
+        self.scene_manager_state.scenes_changed = false;
+        self.performance_monitor.scene_name = self.scene_manager_state.scenes.active_scene().name.clone();
+        self.save_scenes();
+    }
+    
+    /// Flush and close the telemetry CSV, if one is being written; call before exiting
+    pub fn finish_telemetry(&mut self) {
+        if let Err(e) = self.simulation_state.telemetry.stop() {
+            eprintln!("Failed to finish telemetry log: {}", e);
+        }
+    }
+    
+    /// Write the scene list, including the running setup of the active scene
+    pub fn save_scenes(&mut self) {
+        self.capture_active_scene();
+        if let Err(e) = self.scene_manager_state.scenes.save_to_file(&SceneLibrary::default_path()) {
+            eprintln!("Failed to save scenes: {}", e);
+        }
+    }
+    
+    /// Replace the colony with a fresh spawn of the edited genome
+    fn respawn_colony(&mut self) {
+        self.applied_genome = self.current_genome.genome.clone();
+        let spawn = &self.simulation_state.spawn_config;
+        self.cpu_simulation = CpuSimulation::spawn(&self.applied_genome, spawn);
+        println!("Spawned colony with seed {}", spawn.seed);
+        self.simulation_state.growth_blocker = self
+            .applied_genome
+            .modes
+            .get(spawn.mode_index(&self.applied_genome))
+            .and_then(division_blocker);
+        self.simulation_state.event_log.clear();
+        self.simulation_state.current_time = 0.0;
+        self.simulation_state.instability = None;
+        self.simulation_state.stable_snapshot_time = None;
+        self.stable_snapshot = None;
+        self.ghost_trail.clear();
+        self.step_clock.reset();
+    }
+    
+    /// Save the next presented frame as a PNG. The GPU copy is read back asynchronously,
+    /// so the file is written a frame or so later; failures are logged like other file errors.
+    pub fn capture_screenshot(&mut self, path: &Path) {
+        if !self.config.usage.contains(wgpu::TextureUsages::COPY_SRC) {
+            eprintln!("Failed to save screenshot: this surface can't be copied from");
+            return;
+        }
+        self.screenshot.request(path.to_path_buf());
+    }
+    
+    /// Ask for a destination and write the current colony as a mesh file
+    fn export_mesh(&self, format: MeshFormat) {
+        let picked = rfd::FileDialog::new()
+            .set_title("Export Colony Mesh")
+            .add_filter(format.name(), &[format.extension()])
+            .set_file_name(format!("colony.{}", format.extension()))
+            .save_file();
+        let Some(path) = picked else {
+            return;
+        };
+        
+        let cells = cell_instances(
+            &self.cpu_simulation,
+            &self.applied_genome,
+            self.global_ui_state.cell_color_by,
+            self.simulation_state.physics_config.min_cell_mass,
+        );
+        match export_colony_mesh(&path, format, &cells, self.global_ui_state.mesh_export_subdivisions) {
+            Ok(()) => println!("Exported {} cells to {}", cells.len(), path.display()),
+            Err(e) => eprintln!("Failed to export mesh to {}: {}", path.display(), e),
+        }
+    }
+    
+    /// Fly the camera to a preset view, framing the live cells for a reset
+    fn snap_camera(&mut self, view: CameraView) {
+        let colony_bounds = if view == CameraView::Reset {
+            bounding_sphere(self.cpu_simulation.cells.iter().map(|cell| (cell.position, cell.radius())))
+        } else {
+            None
+        };
+        let aspect = self.config.width as f32 / self.config.height.max(1) as f32;
+        self.global_ui_state.camera.snap_to(view, colony_bounds, aspect);
+    }
+
+    /// Move the camera target towards the selected cell while follow is enabled.
+    /// A bookmark flight takes priority over following.
+    fn update_follow_camera(&mut self, delta_time: f32) {
+        let camera = &mut self.global_ui_state.camera;
+        if camera.is_transitioning() {
+            camera.update_transition(delta_time);
+            return;
+        }
+        if !self.cell_inspector_state.follow_selected {
+            return;
+        }
+        
+        let selected = self.cell_inspector_state.selected_cell;
+        if let Some(view) = selected.and_then(|id| self.cpu_simulation.cell_view(&self.applied_genome, id)) {
+            self.global_ui_state.camera.follow(view.cell.position, delta_time);
+        }
+    }
+    
     /// Handle input events for ImGui
     pub fn handle_input(&mut self, event: &WindowEvent) -> bool {
-        self.imgui_manager.handle_event(event)
+        // Presentation mode toggle works whether or not imgui has focus
+        if let WindowEvent::KeyboardInput { event: key_event, .. } = event {
+            if key_event.state == ElementState::Pressed
+                && !key_event.repeat
+                && key_event.physical_key == PhysicalKey::Code(TOGGLE_UI_KEY)
+            {
+                self.global_ui_state.toggle_ui_hidden();
+            }
+            if key_event.state == ElementState::Pressed
+                && !key_event.repeat
+                && key_event.physical_key == PhysicalKey::Code(SCREENSHOT_KEY)
+            {
+                self.capture_screenshot(&timestamped_screenshot_path());
+            }
+            if key_event.state == ElementState::Pressed
+                && !key_event.repeat
+                && key_event.physical_key == PhysicalKey::Code(DEBUG_OVERLAY_KEY)
+            {
+                self.global_ui_state.show_debug_overlay = !self.global_ui_state.show_debug_overlay;
+            }
+        }
+        let consumed = self.imgui_manager.handle_event(event);
+        
+        // Orbit, pan and zoom with the mouse; presses and scrolls over a panel stay with imgui
+        let viewport_height = self.config.height as f32;
+        self.viewport_camera_input.handle_event(event, consumed, &mut self.global_ui_state.camera, viewport_height);
+
+        // A click that didn't turn into an orbit selects the cell under the cursor
+        if let Some(cursor) = self.viewport_camera_input.take_click() {
+            let (width, height) = (self.config.width.max(1) as f32, self.config.height.max(1) as f32);
+            let ndc = [cursor[0] / width * 2.0 - 1.0, 1.0 - cursor[1] / height * 2.0];
+            let ray = self.global_ui_state.camera.screen_ray(ndc, width / height);
+            match self.cpu_simulation.pick_cell(&ray) {
+                Some(id) => {
+                    self.cell_inspector_state.selected_cell = Some(id);
+                    self.global_ui_state.show_cell_inspector = true;
+                }
+                None => self.cell_inspector_state.clear_selection(),
+            }
+        }
+        consumed
     }
     
     /// Set cursor with priority (higher priority wins)
@@ -584,30 +1478,67 @@ impl BasicScene {
         if let Err(e) = self.imgui_theme_state.save_to_file(&ImguiThemeState::default_theme_path()) {
             eprintln!("Failed to save theme settings: {}", e);
         }
+
+        // Save render settings
+        if let Err(e) = self.render_config.save_to_file(&RenderConfig::default_path()) {
+            eprintln!("Failed to save render settings: {}", e);
+        }
     }
 
-    /// Check if settings have changed and save them if so
+    /// Save changed UI, theme and render settings, at most once per `SETTINGS_SAVE_INTERVAL`.
+    /// A failing save (e.g. a write-protected file) is reported once and retried quietly.
     fn check_and_save_settings(&mut self) {
+        let ui_changed = self.global_ui_state != self.previous_ui_state;
+        let theme_changed = self.imgui_theme_state.current_theme != self.previous_theme_state.current_theme;
+        let render_changed = self.render_config != self.previous_render_config;
+        if !(ui_changed || theme_changed || render_changed) || self.last_settings_save.elapsed() < SETTINGS_SAVE_INTERVAL {
+            return;
+        }
+        self.last_settings_save = Instant::now();
+
         let mut settings_changed = false;
+        let mut errors = Vec::new();
 
         // Check if UI state changed
-        if self.global_ui_state != self.previous_ui_state {
-            if let Err(e) = self.global_ui_state.save_to_file(&GlobalUiState::default_settings_path()) {
-                eprintln!("Failed to save UI settings: {}", e);
-            } else {
-                self.previous_ui_state = self.global_ui_state.clone();
-                settings_changed = true;
+        if ui_changed {
+            match self.global_ui_state.save_to_file(&GlobalUiState::default_settings_path()) {
+                Ok(()) => {
+                    self.previous_ui_state = self.global_ui_state.clone();
+                    settings_changed = true;
+                }
+                Err(e) => errors.push(format!("Failed to save UI settings: {}", e)),
             }
         }
 
         // Check if theme state changed
-        if self.imgui_theme_state.current_theme != self.previous_theme_state.current_theme {
-            if let Err(e) = self.imgui_theme_state.save_to_file(&ImguiThemeState::default_theme_path()) {
-                eprintln!("Failed to save theme settings: {}", e);
-            } else {
-                self.previous_theme_state = self.imgui_theme_state.clone();
-                settings_changed = true;
+        if theme_changed {
+            match self.imgui_theme_state.save_to_file(&ImguiThemeState::default_theme_path()) {
+                Ok(()) => {
+                    self.previous_theme_state = self.imgui_theme_state.clone();
+                    settings_changed = true;
+                }
+                Err(e) => errors.push(format!("Failed to save theme settings: {}", e)),
+            }
+        }
+
+        // Check if render settings changed
+        if render_changed {
+            match self.render_config.save_to_file(&RenderConfig::default_path()) {
+                Ok(()) => {
+                    self.previous_render_config = self.render_config.clone();
+                    settings_changed = true;
+                }
+                Err(e) => errors.push(format!("Failed to save render settings: {}", e)),
+            }
+        }
+
+        if errors.is_empty() {
+            self.settings_save_failed = false;
+        } else if !self.settings_save_failed {
+            for error in &errors {
+                eprintln!("{} (further failures will not be reported)", error);
             }
+            self.settings_save_failed = true;
         }
 
         if settings_changed {
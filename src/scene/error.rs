@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+/// Failures while setting up the GPU for the scene
+#[derive(Debug, Error)]
+pub enum SceneInitError {
+    #[error("Could not create a rendering surface for the window: {0}")]
+    CreateSurface(#[from] wgpu::CreateSurfaceError),
+
+    #[error("No compatible graphics adapter was found (also tried the software fallback): {0}")]
+    NoAdapter(#[from] wgpu::RequestAdapterError),
+
+    #[error("The graphics adapter refused to create a device: {0}")]
+    RequestDevice(#[from] wgpu::RequestDeviceError),
+
+    #[error("The graphics adapter cannot present to this window")]
+    UnsupportedSurface,
+}
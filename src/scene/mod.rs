@@ -1,3 +1,6 @@
+pub mod adapter;
 pub mod basic_scene;
+pub mod error;
 
 pub use basic_scene::BasicScene;
+pub use error::SceneInitError;
@@ -0,0 +1,237 @@
+// Fixed-workload benchmark runs for performance baselines
+use super::cpu_sim::{CpuSimulation, run_headless_steps};
+use super::physics_config::PhysicsConfig;
+use crate::genome::{GenomeData, io::{GenomeError, load_genome}};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Steps run when `--benchmark` is given without a count
+pub const DEFAULT_BENCHMARK_STEPS: usize = 5000;
+
+/// What a benchmark run should do, from the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchmarkOptions {
+    pub steps: usize,
+    /// Run inside the window so the report includes GPU rendering cost
+    pub include_rendering: bool,
+    /// Genome file to run instead of the standard benchmark colony
+    pub genome_path: Option<PathBuf>,
+}
+
+impl BenchmarkOptions {
+    /// Parse `--benchmark [steps]`, `--benchmark-render [steps]` or `--headless`, optionally with
+    /// `--ticks <steps>` and `--genome <file>`; None when no benchmark flag is present
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Option<Self> {
+        let mut mode = None;
+        let mut steps = None;
+        let mut genome_path = None;
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            let include_rendering = match arg.as_str() {
+                "--benchmark" | "--headless" => Some(false),
+                "--benchmark-render" => Some(true),
+                "--ticks" => None,
+                "--genome" => {
+                    genome_path = args.next().map(PathBuf::from).or(genome_path);
+                    continue;
+                }
+                _ => continue,
+            };
+            // The step count may follow the benchmark flag directly or come from --ticks
+            if let Some(count) = args.next_if(|next| next.parse::<usize>().is_ok()) {
+                steps = count.parse().ok();
+            }
+            mode = include_rendering.or(mode);
+        }
+        mode.map(|include_rendering| Self {
+            steps: steps.unwrap_or(DEFAULT_BENCHMARK_STEPS),
+            include_rendering,
+            genome_path,
+        })
+    }
+
+    /// Genome to benchmark: the `--genome` file, repaired like any loaded genome, or the standard colony
+    pub fn genome(&self) -> Result<GenomeData, GenomeError> {
+        let Some(path) = &self.genome_path else {
+            return Ok(benchmark_genome());
+        };
+        let mut genome = load_genome(path)?;
+        for warning in genome.repair() {
+            eprintln!("Genome '{}': {}", genome.name, warning);
+        }
+        Ok(genome)
+    }
+}
+
+/// Standard colony: a single fast self-splitting mode that bonds its children,
+/// so the run exercises growth, collisions and adhesions up to the cell cap
+pub fn benchmark_genome() -> GenomeData {
+    let mut genome = GenomeData {
+        name: "Benchmark".to_string(),
+        ..GenomeData::default()
+    };
+    let mode = &mut genome.modes[0];
+    mode.split_interval = 1.0;
+    mode.nutrient_gain_rate = 1.0;
+    mode.parent_make_adhesion = true;
+    genome
+}
+
+/// Collects step and frame timings during a benchmark
+#[derive(Debug, Default)]
+pub struct BenchmarkRecorder {
+    step_times: Vec<Duration>,
+    frame_times: Vec<Duration>,
+    peak_cells: usize,
+}
+
+impl BenchmarkRecorder {
+    pub fn record_step(&mut self, duration: Duration, cell_count: usize) {
+        self.step_times.push(duration);
+        self.peak_cells = self.peak_cells.max(cell_count);
+    }
+
+    pub fn record_frame(&mut self, duration: Duration) {
+        self.frame_times.push(duration);
+    }
+
+    pub fn steps_recorded(&self) -> usize {
+        self.step_times.len()
+    }
+
+    pub fn report(&self, final_cells: usize) -> BenchmarkReport {
+        let step_seconds: f64 = self.step_times.iter().map(Duration::as_secs_f64).sum();
+        BenchmarkReport {
+            steps: self.step_times.len(),
+            steps_per_second: if step_seconds > 0.0 { self.step_times.len() as f64 / step_seconds } else { 0.0 },
+            step_ms: TimingStats::from_durations(&self.step_times),
+            frame_ms: (!self.frame_times.is_empty()).then(|| TimingStats::from_durations(&self.frame_times)),
+            peak_cells: self.peak_cells,
+            final_cells,
+            peak_memory_bytes: peak_memory_bytes(),
+        }
+    }
+}
+
+/// Distribution of a set of timings, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct TimingStats {
+    pub min: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl TimingStats {
+    fn from_durations(durations: &[Duration]) -> Self {
+        Self::from_millis(durations.iter().map(|d| d.as_secs_f64() * 1000.0))
+    }
+
+    /// Summarise samples that are already in milliseconds (or any other unit)
+    pub fn from_millis(samples: impl IntoIterator<Item = f64>) -> Self {
+        let mut ms: Vec<f64> = samples.into_iter().collect();
+        ms.sort_by(f64::total_cmp);
+        let percentile = |p: f64| {
+            if ms.is_empty() {
+                return 0.0;
+            }
+            ms[((ms.len() - 1) as f64 * p).round() as usize]
+        };
+        Self {
+            min: ms.first().copied().unwrap_or(0.0),
+            mean: if ms.is_empty() { 0.0 } else { ms.iter().sum::<f64>() / ms.len() as f64 },
+            p50: percentile(0.5),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+            max: ms.last().copied().unwrap_or(0.0),
+        }
+    }
+}
+
+/// Result of a benchmark run, printed as JSON
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct BenchmarkReport {
+    pub steps: usize,
+    pub steps_per_second: f64,
+    pub step_ms: TimingStats,
+    /// Whole-frame timings including rendering; only for windowed runs
+    pub frame_ms: Option<TimingStats>,
+    pub peak_cells: usize,
+    pub final_cells: usize,
+    /// Peak resident memory of the process, where the platform reports it
+    pub peak_memory_bytes: Option<u64>,
+}
+
+impl BenchmarkReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Run a colony for `steps` physics steps without a window, stopping early if it blows up.
+/// `config` should be the active scene's physics, as the windowed benchmark uses.
+pub fn run_headless_benchmark(genome: &GenomeData, config: &PhysicsConfig, steps: usize) -> BenchmarkReport {
+    let mut recorder = BenchmarkRecorder::default();
+    let mut last = Instant::now();
+    let result = run_headless_steps(genome, config, steps, |simulation: &CpuSimulation| {
+        let now = Instant::now();
+        recorder.record_step(now - last, simulation.cell_count());
+        last = now;
+    });
+    recorder.report(result.final_population)
+}
+
+/// Peak resident set size from /proc on Linux
+#[cfg(target_os = "linux")]
+fn peak_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_memory_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Option<BenchmarkOptions> {
+        BenchmarkOptions::from_args(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn test_parse_benchmark_flags() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("--ticks 100 --genome a.json"), None);
+
+        let legacy = parse("--benchmark-render 200").unwrap();
+        assert_eq!((legacy.steps, legacy.include_rendering, legacy.genome_path), (200, true, None));
+
+        let headless = parse("--headless --genome foo.json --ticks 10000").unwrap();
+        assert_eq!(headless.steps, 10000);
+        assert!(!headless.include_rendering);
+        assert_eq!(headless.genome_path, Some(PathBuf::from("foo.json")));
+
+        assert_eq!(parse("--headless").unwrap().steps, DEFAULT_BENCHMARK_STEPS);
+    }
+
+    #[test]
+    fn test_genome_file_without_modes_is_repaired() {
+        let path = std::env::temp_dir().join(format!("biospheres_benchmark_genome_{}.json", std::process::id()));
+        let empty = GenomeData { name: "Empty".to_string(), initial_mode: 3, modes: Vec::new() };
+        std::fs::write(&path, serde_json::to_string(&empty).unwrap()).unwrap();
+        let options = BenchmarkOptions { steps: 10, include_rendering: false, genome_path: Some(path.clone()) };
+
+        let genome = options.genome().unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(genome.modes.len(), 1);
+        assert_eq!(genome.initial_mode, 0);
+        assert_eq!(run_headless_benchmark(&genome, &PhysicsConfig::default(), 10).steps, 10);
+    }
+}
@@ -1 +1,53 @@
 // Cell memory allocation management
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Source of colony ids; every spawned colony gets a fresh one
+static NEXT_COLONY: AtomicU64 = AtomicU64::new(1);
+
+/// Names one cell for as long as it lives: the colony it belongs to and its id within it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CellId {
+    pub colony: u64,
+    pub id: u32,
+}
+
+/// Hands out cell ids for one colony.
+/// Ids only ever increase, so the id of a dead cell is never given to a newborn, and
+/// `(colony, id)` names a single cell even across respawns, which restart ids at zero.
+#[derive(Debug, Clone)]
+pub struct CellIdAllocator {
+    colony: u64,
+    next_id: u32,
+}
+
+impl CellIdAllocator {
+    /// Start a new colony whose first `reserved` ids are already taken by its seed cells
+    pub fn new(reserved: u32) -> Self {
+        Self {
+            colony: NEXT_COLONY.fetch_add(1, Ordering::Relaxed),
+            next_id: reserved,
+        }
+    }
+
+    /// Id of the colony these cell ids belong to
+    pub fn colony(&self) -> u64 {
+        self.colony
+    }
+
+    /// Next unused cell id
+    pub fn allocate(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Number of ids handed out so far, including the reserved ones
+    pub fn issued(&self) -> u32 {
+        self.next_id
+    }
+}
+
+/// Bytes reserved by a cell buffer, counting spare capacity as well as live entries
+pub fn buffer_bytes<T>(buffer: &Vec<T>) -> usize {
+    buffer.capacity() * std::mem::size_of::<T>()
+}
@@ -1 +1,47 @@
 // Simulation clock and timing
+
+/// Turns elapsed frame time into a whole number of fixed physics steps.
+/// At most `max_steps` run per call; any backlog beyond that is dropped rather than
+/// carried over, so one slow frame can't snowball into ever slower ones.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FixedStepClock {
+    accumulator: f32,
+}
+
+impl FixedStepClock {
+    /// Forget any partial step, e.g. after a pause or respawn
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+
+    /// Steps of length `dt` due after `elapsed` more seconds of (already speed-scaled) time
+    pub fn steps_due(&mut self, elapsed: f32, dt: f32, max_steps: u32) -> usize {
+        if dt <= 0.0 {
+            return 0;
+        }
+        self.accumulator += elapsed.max(0.0);
+        let due = (self.accumulator / dt).floor();
+        if due >= max_steps as f32 {
+            self.accumulator = 0.0;
+            return max_steps as usize;
+        }
+        self.accumulator -= due * dt;
+        due as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_carry_remainder_and_cap_backlog() {
+        let mut clock = FixedStepClock::default();
+        assert_eq!(clock.steps_due(0.025, 0.01, 32), 2);
+        assert_eq!(clock.steps_due(0.005, 0.01, 32), 1);
+
+        // A huge frame runs the cap and then starts fresh
+        assert_eq!(clock.steps_due(100.0, 0.01, 32), 32);
+        assert_eq!(clock.steps_due(0.0, 0.01, 32), 0);
+    }
+}
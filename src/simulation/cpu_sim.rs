@@ -1 +1,1254 @@
 // CPU simulation loop
+use super::cell_allocation::{CellId, CellIdAllocator};
+use super::double_buffer::DoubleBuffer;
+use super::events::{EventLog, SimulationEventKind};
+use super::gpu_physics::GpuPhysics;
+use super::initial_state::{cell_seed, SpawnConfig};
+use super::nutrient_system;
+use super::physics_config::{Integrator, PhysicsConfig};
+use crate::cell::adhesion_forces::{is_spring_stable, max_stable_stiffness};
+use crate::cell::adhesion_zones::classify_bond_direction;
+use crate::cell::division::{hash_unit, jitter_split_direction, split_direction};
+use crate::cell::types::{AdhesionConnection, Cell, CellType};
+use crate::genome::{AdhesionSettings, GenomeData, ModeSettings};
+use glam::{IVec3, Quat, Vec3};
+use std::collections::{HashMap, HashSet};
+
+/// Mass a flagellocyte spends per unit of swim force per second
+const SWIM_MASS_COST: f32 = 0.02;
+
+/// Offsets of the 27 grid buckets around (and including) a cell's own bucket
+const NEIGHBOR_OFFSETS: [IVec3; 27] = {
+    let mut offsets = [IVec3::ZERO; 27];
+    let mut i = 0;
+    while i < 27 {
+        offsets[i] = IVec3::new(i as i32 % 3 - 1, (i as i32 / 3) % 3 - 1, i as i32 / 9 - 1);
+        i += 1;
+    }
+    offsets
+};
+
+/// Work done by the simulation, for explaining frame cost in the performance monitor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct StepCounters {
+    /// Force and integration passes executed
+    pub substeps: u32,
+    /// Cell pairs checked for overlap after the grid broadphase
+    pub collision_pairs_tested: u32,
+    /// Adhesion springs evaluated
+    pub adhesion_constraints: u32,
+    /// Per-cell nutrient uptakes applied (the only nutrient flow the CPU sim models so far)
+    pub nutrient_transfers: u32,
+}
+
+impl StepCounters {
+    /// Add another set of counters to this one
+    pub fn accumulate(&mut self, other: &StepCounters) {
+        self.substeps += other.substeps;
+        self.collision_pairs_tested += other.collision_pairs_tested;
+        self.adhesion_constraints += other.adhesion_constraints;
+        self.nutrient_transfers += other.nutrient_transfers;
+    }
+}
+
+/// How an edited genome is applied to a colony that is already running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GenomeApplyMode {
+    /// Existing cells switch to the new mode parameters immediately
+    #[default]
+    UpdateExisting,
+    /// Existing cells keep the old parameters; only cells born afterwards use the new genome
+    NewbornsOnly,
+}
+
+impl GenomeApplyMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GenomeApplyMode::UpdateExisting => "Update Existing Cells",
+            GenomeApplyMode::NewbornsOnly => "Newborn Cells Only",
+        }
+    }
+}
+
+/// A cell on the other end of an adhesion and the current state of the spring
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdhesionPartner {
+    pub cell_id: u32,
+    pub mode_index: usize,
+    /// Current distance between the two cell centers
+    pub distance: f32,
+    pub rest_length: f32,
+    /// Deviation from the rest length relative to the rest length; positive when stretched
+    pub strain: f32,
+    /// Spring force along the link, including damping; positive when pulling the cells together
+    pub force: f32,
+    /// Force at which the link snaps, or `None` if it can't break
+    pub break_force: Option<f32>,
+}
+
+impl AdhesionPartner {
+    /// Share of the break force the link is carrying, or `None` if it can't break
+    pub fn break_load(&self) -> Option<f32> {
+        self.break_force.map(|break_force| self.force.abs() / break_force.max(f32::EPSILON))
+    }
+}
+
+/// Read-only view of one live cell, borrowed from the simulation's cell buffer for inspection
+#[derive(Debug, Clone, Copy)]
+pub struct CellView<'a> {
+    pub id: CellId,
+    /// Position in the cell buffer
+    pub index: usize,
+    pub cell: &'a Cell,
+    /// Mode settings the cell follows, from the genome revision it was born under
+    pub mode: &'a ModeSettings,
+    pub adhesion_count: usize,
+    /// Simulation time at which the cell was created
+    pub birth_time: f32,
+}
+
+/// Half-line through the world, used to pick the cell under the cursor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    /// Unit length
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Distance along the ray to where it enters the sphere, or `None` if it misses.
+    /// A ray starting inside the sphere hits at distance 0.
+    pub fn sphere_hit(&self, center: Vec3, radius: f32) -> Option<f32> {
+        let to_center = center - self.origin;
+        let along = to_center.dot(self.direction);
+        let closest_sq = to_center.length_squared() - along * along;
+        let radius_sq = radius * radius;
+        if closest_sq > radius_sq {
+            return None;
+        }
+        let half_chord = (radius_sq - closest_sq).sqrt();
+        let (entry, exit) = (along - half_chord, along + half_chord);
+        if exit < 0.0 {
+            return None; // Sphere is behind the origin
+        }
+        Some(entry.max(0.0))
+    }
+}
+
+/// Speed above which a cell is treated as having blown up
+pub const RUNAWAY_SPEED: f32 = 1.0e4;
+
+/// A cell whose state shows the integration has blown up
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instability {
+    /// Simulated time the blow-up was detected
+    pub time: f32,
+    pub cell_id: u32,
+    /// Position or velocity became NaN or infinite; otherwise the speed passed `RUNAWAY_SPEED`
+    pub non_finite: bool,
+    /// Most likely culprit, phrased as something to change
+    pub likely_cause: String,
+}
+
+/// Rest length of the spring between two cells under the given settings
+pub(super) fn adhesion_rest_length(settings: &AdhesionSettings, a: &Cell, b: &Cell) -> f32 {
+    if settings.auto_rest_length {
+        a.radius() + b.radius()
+    } else {
+        settings.rest_length
+    }
+}
+
+/// Spring between two linked cells: unit direction from `a` to `b`, center distance and
+/// force magnitude along that direction (positive pulls the cells together)
+fn adhesion_spring(settings: &AdhesionSettings, a: &Cell, b: &Cell) -> (Vec3, f32, f32) {
+    let rest_length = adhesion_rest_length(settings, a, b);
+    spring_between(settings, rest_length, &CellMotion::of(a), &CellMotion::of(b))
+}
+
+/// `adhesion_spring` for the motion state of a substep
+fn spring_between(settings: &AdhesionSettings, rest_length: f32, a: &CellMotion, b: &CellMotion) -> (Vec3, f32, f32) {
+    let delta = b.position - a.position;
+    let distance = delta.length();
+    let direction = if distance > 1e-6 { delta / distance } else { Vec3::Y };
+    let stretch = distance - rest_length;
+    let closing_speed = (b.velocity - a.velocity).dot(direction);
+    let magnitude = settings.linear_spring_stiffness * stretch + settings.linear_spring_damping * closing_speed;
+    (direction, distance, magnitude)
+}
+
+/// Lookups shared by every division in a step, so a division only visits its own adhesions
+struct DivisionLookup {
+    /// Cell id to index in `cells`
+    index_of: HashMap<u32, usize>,
+    /// Cell id to the indices of its adhesions in `adhesions`
+    adhesions_of: HashMap<u32, Vec<usize>>,
+    /// Adhesions dropped by a division this step, removed once all divisions are done
+    removed: Vec<bool>,
+}
+
+impl DivisionLookup {
+    fn new(cells: &[Cell], adhesions: &[AdhesionConnection]) -> Self {
+        let mut lookup = Self {
+            index_of: cells.iter().enumerate().map(|(i, c)| (c.id, i)).collect(),
+            adhesions_of: HashMap::new(),
+            removed: vec![false; adhesions.len()],
+        };
+        for (i, adhesion) in adhesions.iter().enumerate() {
+            lookup.link(adhesion.cell_a, i);
+            lookup.link(adhesion.cell_b, i);
+        }
+        lookup
+    }
+
+    fn link(&mut self, cell_id: u32, adhesion_index: usize) {
+        self.adhesions_of.entry(cell_id).or_default().push(adhesion_index);
+    }
+
+    /// Register an adhesion appended to `adhesions`
+    fn add(&mut self, cell_a: u32, cell_b: u32, adhesion_index: usize) {
+        self.removed.push(false);
+        self.link(cell_a, adhesion_index);
+        self.link(cell_b, adhesion_index);
+    }
+}
+
+/// The part of a cell that changes every substep
+#[derive(Debug, Clone, Copy)]
+struct CellMotion {
+    position: Vec3,
+    velocity: Vec3,
+    acceleration: Vec3,
+}
+
+impl CellMotion {
+    fn of(cell: &Cell) -> Self {
+        Self {
+            position: cell.position,
+            velocity: cell.velocity,
+            acceleration: cell.acceleration,
+        }
+    }
+}
+
+/// Where a cell splits within an optional `min..max` range, given a uniform roll in [0, 1)
+fn split_threshold(min: Option<f32>, max: f32, roll: f32) -> f32 {
+    match min {
+        Some(min) if min < max => min + (max - min) * roll,
+        _ => max,
+    }
+}
+
+/// Genome that governs a given revision: older revisions are retired copies, the newest is `current`
+fn genome_revision<'a>(retired: &'a [GenomeData], current: &'a GenomeData, generation: u32) -> &'a GenomeData {
+    retired.get(generation as usize).unwrap_or(current)
+}
+
+/// Deterministic CPU simulation of a colony grown from a genome.
+/// Iteration order only depends on cell order and all randomness comes from the spawn seed,
+/// so the same genome, seed and config always produce the same run.
+#[derive(Debug, Clone)]
+pub struct CpuSimulation {
+    pub cells: Vec<Cell>,
+    pub adhesions: Vec<AdhesionConnection>,
+    /// Simulated time in seconds
+    pub time: f32,
+    /// Counters for the most recent step
+    pub last_step_counters: StepCounters,
+    ids: CellIdAllocator,
+    /// Earlier genomes still followed by cells born before a newborns-only apply
+    retired_genomes: Vec<GenomeData>,
+    /// Live cells per mode index, refreshed after every step
+    mode_counts: HashMap<usize, u32>,
+    /// Run seed from the spawn configuration
+    seed: u32,
+    /// Positions and velocities during the substep loop, in cell order. Forces read the
+    /// front buffer and integration writes the back one, so no cell sees a neighbour's
+    /// half-updated state. `cells` is authoritative between steps.
+    motion: DoubleBuffer<Vec<CellMotion>>,
+}
+
+impl CpuSimulation {
+    /// Start from a single seed cell in the genome's initial mode
+    pub fn new(genome: &GenomeData) -> Self {
+        Self::spawn(genome, &SpawnConfig::default())
+    }
+
+    /// Start from the colony described by a spawn configuration
+    pub fn spawn(genome: &GenomeData, spawn: &SpawnConfig) -> Self {
+        let cells = spawn.spawn_cells(genome);
+        let mut simulation = Self {
+            ids: CellIdAllocator::new(cells.len() as u32),
+            cells,
+            adhesions: Vec::new(),
+            time: 0.0,
+            last_step_counters: StepCounters::default(),
+            retired_genomes: Vec::new(),
+            mode_counts: HashMap::new(),
+            seed: spawn.seed,
+            motion: DoubleBuffer::default(),
+        };
+        simulation.count_modes();
+        simulation
+    }
+
+    /// Seed the colony was spawned with
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Identifies this colony; a respawned colony reuses cell ids but never a colony id
+    pub fn colony(&self) -> u64 {
+        self.ids.colony()
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Approximate heap memory held by the cell and adhesion buffers
+    pub fn allocated_bytes(&self) -> usize {
+        super::cell_allocation::buffer_bytes(&self.cells) + super::cell_allocation::buffer_bytes(&self.adhesions)
+    }
+
+    /// Live cells per mode index as of the last step; modes without cells are absent
+    pub fn mode_counts(&self) -> &HashMap<usize, u32> {
+        &self.mode_counts
+    }
+
+    fn count_modes(&mut self) {
+        self.mode_counts.clear();
+        for cell in &self.cells {
+            *self.mode_counts.entry(cell.mode_index).or_default() += 1;
+        }
+    }
+
+    /// Sum of all cell masses
+    pub fn total_mass(&self) -> f32 {
+        self.cells.iter().map(|c| c.mass).sum()
+    }
+
+    /// Advance the simulation by one fixed timestep
+    pub fn step(&mut self, genome: &GenomeData, config: &PhysicsConfig, events: &mut EventLog) {
+        self.step_on(None, genome, config, events);
+    }
+
+    /// One fixed timestep, running the force and integration substeps on `gpu` when given.
+    /// Growth, division and death always run here. If the GPU fails, the step finishes on the CPU.
+    fn step_on(&mut self, gpu: Option<&mut GpuPhysics>, genome: &GenomeData, config: &PhysicsConfig, events: &mut EventLog) {
+        if genome.modes.is_empty() {
+            return;
+        }
+        let dt = config.fixed_timestep;
+        let substeps = config.substep_count();
+        self.last_step_counters = StepCounters {
+            substeps,
+            ..Default::default()
+        };
+
+        self.grow_cells(genome, config, dt);
+        self.feed_phagocytes(genome, dt);
+        self.divide_cells(genome, config, events);
+
+        // Growth and division run once per step; forces are re-evaluated every substep
+        if !gpu.is_some_and(|gpu| gpu.run_substeps(self, genome, config, events)) {
+            self.load_motion();
+            for _ in 0..substeps {
+                let forces = self.compute_forces(genome, config, events);
+                self.integrate(&forces, genome, config, config.substep_dt());
+                self.motion.swap();
+            }
+            self.store_motion();
+        }
+
+        self.remove_dead_cells(config, events);
+        self.count_modes();
+        self.time += dt;
+    }
+
+    /// Advance exactly one fixed timestep and check the result, so a paused colony can be stepped
+    /// frame by frame. Returns the blow-up if the step made the colony unstable.
+    pub fn step_once(&mut self, genome: &GenomeData, config: &PhysicsConfig, events: &mut EventLog) -> Option<Instability> {
+        self.step(genome, config, events);
+        self.check_stability(genome, config)
+    }
+
+    /// Like `advance`, with the force and integration substeps of every step run on the GPU
+    pub fn advance_on_gpu(
+        &mut self,
+        gpu: &mut GpuPhysics,
+        genome: &GenomeData,
+        config: &PhysicsConfig,
+        events: &mut EventLog,
+        steps: usize,
+        mut after_step: impl FnMut(&CpuSimulation),
+    ) -> Result<(), Instability> {
+        for _ in 0..steps {
+            self.step_on(Some(&mut *gpu), genome, config, events);
+            if let Some(instability) = self.check_stability(genome, config) {
+                return Err(instability);
+            }
+            after_step(self);
+        }
+        Ok(())
+    }
+
+    /// Run up to `steps` fixed steps, calling `after_step` after each one. The window and
+    /// headless runs both advance the colony through here. Stops at the first step that makes
+    /// the colony unstable and returns that blow-up.
+    pub fn advance(
+        &mut self,
+        genome: &GenomeData,
+        config: &PhysicsConfig,
+        events: &mut EventLog,
+        steps: usize,
+        mut after_step: impl FnMut(&CpuSimulation),
+    ) -> Result<(), Instability> {
+        for _ in 0..steps {
+            if let Some(instability) = self.step_once(genome, config, events) {
+                return Err(instability);
+            }
+            after_step(self);
+        }
+        Ok(())
+    }
+
+    fn mode(genome: &GenomeData, mode_index: usize) -> &ModeSettings {
+        &genome.modes[mode_index.min(genome.modes.len() - 1)]
+    }
+
+    /// Revision number of the current genome; newborn cells follow this one
+    fn current_generation(&self) -> u32 {
+        self.retired_genomes.len() as u32
+    }
+
+    /// Spring settings of an adhesion, from the genome revision it was made under
+    pub(super) fn adhesion_settings<'a>(&'a self, genome: &'a GenomeData, adhesion: &AdhesionConnection) -> &'a AdhesionSettings {
+        &Self::mode(genome_revision(&self.retired_genomes, genome, adhesion.genome_generation), adhesion.mode_index).adhesion_settings
+    }
+
+    /// Mode settings a cell follows, taking genome revisions into account
+    pub fn mode_of<'a>(&'a self, genome: &'a GenomeData, cell: &Cell) -> &'a ModeSettings {
+        Self::mode(genome_revision(&self.retired_genomes, genome, cell.genome_generation), cell.mode_index)
+    }
+
+    /// Look up a live cell by id
+    pub fn cell_by_id(&self, id: u32) -> Option<&Cell> {
+        self.cells.iter().find(|c| c.id == id)
+    }
+
+    /// Colony-qualified id of a cell in this simulation
+    pub fn cell_id(&self, id: u32) -> CellId {
+        CellId { colony: self.colony(), id }
+    }
+
+    /// The cell whose sphere the ray hits first; where spheres overlap the hit closest to the ray origin wins
+    pub fn pick_cell(&self, ray: &Ray) -> Option<CellId> {
+        self.cells
+            .iter()
+            .filter_map(|cell| ray.sphere_hit(cell.position, cell.radius()).map(|distance| (distance, cell.id)))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, id)| self.cell_id(id))
+    }
+
+    /// Read-only view of a live cell, or `None` if it died or belongs to a replaced colony
+    pub fn cell_view<'a>(&'a self, genome: &'a GenomeData, id: CellId) -> Option<CellView<'a>> {
+        if id.colony != self.colony() || genome.modes.is_empty() {
+            return None;
+        }
+        let index = self.cells.iter().position(|c| c.id == id.id)?;
+        let cell = &self.cells[index];
+        Some(CellView {
+            id,
+            index,
+            cell,
+            mode: self.mode_of(genome, cell),
+            adhesion_count: self.adhesions.iter().filter(|a| a.cell_a == id.id || a.cell_b == id.id).count(),
+            birth_time: self.time - cell.age,
+        })
+    }
+
+    /// Live adhesion links of the cell with the given id, in adhesion order
+    pub fn connections_for(&self, genome: &GenomeData, cell_id: u32) -> Vec<AdhesionPartner> {
+        let Some(cell) = self.cell_by_id(cell_id) else {
+            return Vec::new();
+        };
+        self.adhesions
+            .iter()
+            .filter_map(|adhesion| {
+                let partner_id = if adhesion.cell_a == cell_id {
+                    adhesion.cell_b
+                } else if adhesion.cell_b == cell_id {
+                    adhesion.cell_a
+                } else {
+                    return None;
+                };
+                let partner = self.cell_by_id(partner_id)?;
+                let settings = &Self::mode(genome_revision(&self.retired_genomes, genome, adhesion.genome_generation), adhesion.mode_index).adhesion_settings;
+                let rest_length = adhesion_rest_length(settings, cell, partner);
+                let (_, distance, force) = adhesion_spring(settings, cell, partner);
+                Some(AdhesionPartner {
+                    cell_id: partner_id,
+                    mode_index: partner.mode_index,
+                    distance,
+                    rest_length,
+                    strain: (distance - rest_length) / rest_length.max(f32::EPSILON),
+                    force,
+                    break_force: settings.can_break.then_some(settings.break_force),
+                })
+            })
+            .collect()
+    }
+
+    /// Look for a cell whose position or velocity has blown up, and guess why
+    pub fn check_stability(&self, genome: &GenomeData, config: &PhysicsConfig) -> Option<Instability> {
+        let cell = self.cells.iter().find(|cell| {
+            !cell.position.is_finite() || !cell.velocity.is_finite() || cell.velocity.length() > RUNAWAY_SPEED
+        })?;
+        Some(Instability {
+            time: self.time,
+            cell_id: cell.id,
+            non_finite: !cell.position.is_finite() || !cell.velocity.is_finite(),
+            likely_cause: self.likely_instability_cause(genome, config, cell),
+        })
+    }
+
+    /// Check the forces acting on a blown-up cell against the explicit stability limit
+    fn likely_instability_cause(&self, genome: &GenomeData, config: &PhysicsConfig, cell: &Cell) -> String {
+        let dt = config.substep_dt();
+        let mass_of = |c: &Cell| if c.mass.is_finite() { c.mass.max(config.min_cell_mass) } else { config.min_cell_mass };
+        for adhesion in self.adhesions.iter().filter(|a| a.cell_a == cell.id || a.cell_b == cell.id) {
+            let other_id = if adhesion.cell_a == cell.id { adhesion.cell_b } else { adhesion.cell_a };
+            let Some(other) = self.cell_by_id(other_id) else { continue };
+            let mode = Self::mode(genome_revision(&self.retired_genomes, genome, adhesion.genome_generation), adhesion.mode_index);
+            let settings = &mode.adhesion_settings;
+            let (mass_a, mass_b) = (mass_of(cell), mass_of(other));
+            if !is_spring_stable(settings.linear_spring_stiffness, settings.linear_spring_damping, mass_a, mass_b, dt) {
+                return format!(
+                    "Adhesion spring of mode '{}' is too stiff for the {:.4}s timestep (stiffness {:.1}, damping {:.2}); \
+                     try stiffness below {:.1}, more substeps or a smaller timestep",
+                    mode.name,
+                    dt,
+                    settings.linear_spring_stiffness,
+                    settings.linear_spring_damping,
+                    max_stable_stiffness(settings.linear_spring_damping, mass_a, mass_b, dt),
+                );
+            }
+        }
+
+        let mass = mass_of(cell);
+        if !is_spring_stable(config.collision_stiffness, 0.0, mass, mass, dt) {
+            return format!(
+                "Collision stiffness {:.1} is too high for the {:.4}s timestep; try below {:.1}",
+                config.collision_stiffness,
+                dt,
+                max_stable_stiffness(0.0, mass, mass, dt),
+            );
+        }
+        if config.integrator == Integrator::Explicit {
+            return "Explicit Euler adds energy to every spring each step; switch to Semi-Implicit Euler".to_string();
+        }
+        "Forces grew without bound; lower spring stiffness or the timestep".to_string()
+    }
+
+    /// Switch the colony to an edited genome. `previous` is the genome the colony was running.
+    /// Cell and adhesion lists are left untouched, so indices and links stay valid;
+    /// out-of-range mode indices are clamped on lookup.
+    pub fn apply_genome(&mut self, previous: &GenomeData, mode: GenomeApplyMode) {
+        match mode {
+            GenomeApplyMode::UpdateExisting => {
+                self.retired_genomes.clear();
+                for cell in &mut self.cells {
+                    cell.genome_generation = 0;
+                }
+                for adhesion in &mut self.adhesions {
+                    adhesion.genome_generation = 0;
+                }
+            }
+            GenomeApplyMode::NewbornsOnly => {
+                // Cells on the old current revision now point at this retired copy
+                self.retired_genomes.push(previous.clone());
+            }
+        }
+    }
+
+    /// Mass gain, aging, and flagellocyte swimming. Terminal cells only age,
+    /// and keep growing unless their mode also stops nutrients. Photocytes add absorbed light to their gain.
+    fn grow_cells(&mut self, genome: &GenomeData, config: &PhysicsConfig, dt: f32) {
+        for cell in &mut self.cells {
+            let mode = Self::mode(genome_revision(&self.retired_genomes, genome, cell.genome_generation), cell.mode_index);
+            cell.age += dt;
+            if mode.terminal && mode.terminal_stops_nutrients {
+                continue;
+            }
+            let gain_rate = mode.passive_gain_rate_at(nutrient_system::sample(cell.position));
+            cell.mass = (cell.mass + gain_rate * dt).min(mode.split_mass.max(cell.mass));
+            if gain_rate != 0.0 {
+                self.last_step_counters.nutrient_transfers += 1;
+            }
+
+            if !mode.terminal && CellType::from_index(mode.cell_type) == CellType::Flagellocyte {
+                let heading = cell.rotation * Vec3::Z;
+                cell.velocity += heading * (mode.swim_force / cell.mass.max(config.min_cell_mass)) * dt;
+                cell.mass -= mode.swim_force * SWIM_MASS_COST * dt;
+            }
+        }
+    }
+
+    /// Phagocytes drain mass from every non-phagocyte cell within their engulf radius.
+    /// Prey is scanned linearly per phagocyte, which is fine while phagocytes are a small part of the colony.
+    fn feed_phagocytes(&mut self, genome: &GenomeData, dt: f32) {
+        let is_phagocyte = |mode: &ModeSettings| CellType::from_index(mode.cell_type) == CellType::Phagocyte;
+        let hunters: Vec<usize> = (0..self.cells.len())
+            .filter(|&i| {
+                let mode = self.mode_of(genome, &self.cells[i]);
+                is_phagocyte(mode) && mode.digestion_rate > 0.0 && !(mode.terminal && mode.terminal_stops_nutrients)
+            })
+            .collect();
+        if hunters.is_empty() {
+            return;
+        }
+
+        let mut mass_change = vec![0.0_f32; self.cells.len()];
+        let mut transfers = 0;
+        for &h in &hunters {
+            let hunter = &self.cells[h];
+            let mode = self.mode_of(genome, hunter);
+            for (p, prey) in self.cells.iter().enumerate() {
+                if p == h || is_phagocyte(self.mode_of(genome, prey)) {
+                    continue;
+                }
+                let reach = hunter.radius() + prey.radius() + mode.engulf_radius;
+                if hunter.position.distance_squared(prey.position) < reach * reach {
+                    let bite = (mode.digestion_rate * dt).min(prey.mass + mass_change[p]).max(0.0);
+                    mass_change[p] -= bite;
+                    mass_change[h] += bite;
+                    transfers += 1;
+                }
+            }
+        }
+
+        for &h in &hunters {
+            let split_mass = self.mode_of(genome, &self.cells[h]).split_mass;
+            let cell = &mut self.cells[h];
+            cell.mass = (cell.mass + mass_change[h]).min(split_mass.max(cell.mass));
+            mass_change[h] = 0.0;
+        }
+        for (cell, change) in self.cells.iter_mut().zip(mass_change) {
+            cell.mass += change;
+        }
+        self.last_step_counters.nutrient_transfers += transfers;
+    }
+
+    /// Split every cell that is ready to divide
+    fn divide_cells(&mut self, genome: &GenomeData, config: &PhysicsConfig, events: &mut EventLog) {
+        let mut adhesion_counts: HashMap<u32, usize> = HashMap::new();
+        for adhesion in &self.adhesions {
+            *adhesion_counts.entry(adhesion.cell_a).or_default() += 1;
+            *adhesion_counts.entry(adhesion.cell_b).or_default() += 1;
+        }
+
+        let mut lookup = DivisionLookup::new(&self.cells, &self.adhesions);
+
+        // Only cells that existed at the start of this step may split
+        let existing = self.cells.len();
+        for index in 0..existing {
+            if self.cells.len() >= config.max_cells {
+                break;
+            }
+            let cell = &self.cells[index];
+            let mode = self.mode_of(genome, cell);
+            if mode.terminal {
+                continue;
+            }
+            let connections = adhesion_counts.get(&cell.id).copied().unwrap_or(0) as i32;
+
+            // Each cell rolls once where in the split ranges it divides
+            let seed = cell_seed(self.seed, cell.id);
+            let split_interval = split_threshold(mode.split_interval_min, mode.split_interval, hash_unit(seed, 3));
+            let split_mass = split_threshold(mode.split_mass_min, mode.split_mass, hash_unit(seed, 4));
+
+            let ready = cell.age >= split_interval
+                && cell.mass >= split_mass
+                && cell.mass * mode.split_ratio.min(1.0 - mode.split_ratio) >= config.min_cell_mass
+                && (mode.max_splits < 0 || cell.split_count < mode.max_splits)
+                && connections < mode.max_adhesions
+                && connections >= mode.min_adhesions;
+            if ready {
+                self.divide(index, genome, events, &mut lookup);
+            }
+        }
+
+        if lookup.removed.contains(&true) {
+            let mut removed = lookup.removed.into_iter();
+            self.adhesions.retain(|_| !removed.next().unwrap_or(false));
+        }
+    }
+
+    /// Replace the cell at `index` with Child A and append Child B
+    fn divide(&mut self, index: usize, genome: &GenomeData, events: &mut EventLog, lookup: &mut DivisionLookup) {
+        let parent = self.cells[index].clone();
+        let generation = self.current_generation();
+        let mode = Self::mode(genome_revision(&self.retired_genomes, genome, parent.genome_generation), parent.mode_index);
+
+        let local_split = jitter_split_direction(
+            split_direction(mode.parent_split_direction.x, mode.parent_split_direction.y),
+            mode.split_angle_jitter,
+            cell_seed(self.seed, parent.id),
+        );
+        let world_split = parent.rotation * local_split;
+        let offset = world_split * parent.radius() * 0.5;
+
+        let make_child = |id: u32, settings: &crate::genome::ChildSettings, mass: f32, position: Vec3| {
+            let mode_index = (settings.mode_number.max(0) as usize).min(genome.modes.len() - 1);
+            let orientation = Quat::from_xyzw(
+                settings.orientation.x,
+                settings.orientation.y,
+                settings.orientation.z,
+                settings.orientation.w,
+            )
+            .normalize();
+            Cell {
+                id,
+                mode_index,
+                position,
+                velocity: parent.velocity,
+                acceleration: parent.acceleration,
+                rotation: (parent.rotation * orientation).normalize(),
+                mass,
+                age: 0.0,
+                // Splits are counted per mode; a mode change starts a fresh count
+                split_count: if mode_index == parent.mode_index { parent.split_count + 1 } else { 0 },
+                // Children are born now, so they follow the current genome
+                genome_generation: generation,
+            }
+        };
+
+        let child_a_id = self.ids.allocate();
+        let child_b_id = self.ids.allocate();
+
+        let child_a = make_child(child_a_id, &mode.child_a, parent.mass * mode.split_ratio, parent.position + offset);
+        let child_b = make_child(child_b_id, &mode.child_b, parent.mass * (1.0 - mode.split_ratio), parent.position - offset);
+
+        // Hand the parent's adhesions to the children by zone. The first heir takes over the
+        // adhesion in place, a second heir gets a copy, and an adhesion nobody keeps is dropped.
+        let inverse_rotation = parent.rotation.inverse();
+        for adhesion_index in lookup.adhesions_of.remove(&parent.id).unwrap_or_default() {
+            if lookup.removed[adhesion_index] {
+                continue;
+            }
+            let adhesion = self.adhesions[adhesion_index];
+            let partner = if adhesion.cell_a == parent.id { adhesion.cell_b } else { adhesion.cell_a };
+            let mut heirs = Vec::with_capacity(2);
+            if let Some(&partner_index) = lookup.index_of.get(&partner) {
+                let bond = inverse_rotation * (self.cells[partner_index].position - parent.position);
+                let zone = classify_bond_direction(bond, local_split);
+                if mode.child_a.keep_adhesion && zone.inherited_by_child_a() {
+                    heirs.push(child_a_id);
+                }
+                if mode.child_b.keep_adhesion && zone.inherited_by_child_b() {
+                    heirs.push(child_b_id);
+                }
+            }
+            match heirs.as_slice() {
+                [] => lookup.removed[adhesion_index] = true,
+                [first, rest @ ..] => {
+                    self.adhesions[adhesion_index] = AdhesionConnection { cell_a: *first, cell_b: partner, ..adhesion };
+                    lookup.link(*first, adhesion_index);
+                    for &heir in rest {
+                        self.adhesions.push(AdhesionConnection { cell_a: heir, cell_b: partner, ..adhesion });
+                        lookup.add(heir, partner, self.adhesions.len() - 1);
+                    }
+                }
+            }
+        }
+
+        if mode.parent_make_adhesion {
+            self.adhesions.push(AdhesionConnection {
+                cell_a: child_a_id,
+                cell_b: child_b_id,
+                mode_index: parent.mode_index,
+                genome_generation: parent.genome_generation,
+            });
+            lookup.add(child_a_id, child_b_id, self.adhesions.len() - 1);
+        }
+
+        events.record(self.time, SimulationEventKind::Split, parent.id);
+        lookup.index_of.remove(&parent.id);
+        lookup.index_of.insert(child_a_id, index);
+        lookup.index_of.insert(child_b_id, self.cells.len());
+        self.cells[index] = child_a;
+        self.cells.push(child_b);
+    }
+
+    /// Collision, adhesion spring, and boundary forces. Breaks overstretched adhesions.
+    fn compute_forces(&mut self, genome: &GenomeData, config: &PhysicsConfig, events: &mut EventLog) -> Vec<Vec3> {
+        let mut forces = vec![Vec3::ZERO; self.cells.len()];
+        let motion = self.motion.read();
+
+        // Uniform grid broadphase; buckets are filled in cell order so results are deterministic
+        let max_radius = self.cells.iter().map(|c| c.radius()).fold(0.0_f32, f32::max);
+        let bucket_size = (max_radius * 2.0).max(0.1);
+        let bucket_of = |p: Vec3| (p / bucket_size).floor().as_ivec3();
+        let mut buckets: HashMap<IVec3, Vec<usize>> = HashMap::new();
+        for (i, state) in motion.iter().enumerate() {
+            buckets.entry(bucket_of(state.position)).or_default().push(i);
+        }
+
+        let mut pairs_tested = 0;
+        for (i, (cell, state)) in self.cells.iter().zip(motion).enumerate() {
+            let home = bucket_of(state.position);
+            for offset in NEIGHBOR_OFFSETS {
+                let Some(bucket) = buckets.get(&(home + offset)) else { continue };
+                for &j in bucket.iter().filter(|&&j| j > i) {
+                    pairs_tested += 1;
+                    let other = &self.cells[j];
+                    let delta = motion[j].position - state.position;
+                    let distance = delta.length();
+                    let overlap = cell.radius() + other.radius() - distance;
+                    if overlap > 0.0 {
+                        let normal = if distance > 1e-6 { delta / distance } else { Vec3::Y };
+                        let force = normal * overlap * config.collision_stiffness;
+                        forces[i] -= force;
+                        forces[j] += force;
+                    }
+                }
+            }
+
+            // Keep cells inside the world sphere
+            let penetration = state.position.length() + cell.radius() - config.world_radius;
+            if penetration > 0.0 {
+                forces[i] -= state.position.normalize_or_zero() * penetration * config.collision_stiffness;
+            }
+        }
+
+        self.last_step_counters.collision_pairs_tested += pairs_tested;
+        self.last_step_counters.adhesion_constraints += self.adhesions.len() as u32;
+
+        // Adhesion springs
+        let index_of: HashMap<u32, usize> = self.cells.iter().enumerate().map(|(i, c)| (c.id, i)).collect();
+        let cells = &self.cells;
+        let retired = &self.retired_genomes;
+        let time = self.time;
+        self.adhesions.retain(|adhesion| {
+            let (Some(&a), Some(&b)) = (index_of.get(&adhesion.cell_a), index_of.get(&adhesion.cell_b)) else {
+                return false;
+            };
+            let settings = &Self::mode(genome_revision(retired, genome, adhesion.genome_generation), adhesion.mode_index).adhesion_settings;
+            let rest_length = adhesion_rest_length(settings, &cells[a], &cells[b]);
+            let (direction, _, magnitude) = spring_between(settings, rest_length, &motion[a], &motion[b]);
+
+            if settings.can_break && magnitude.abs() > settings.break_force {
+                events.record(time, SimulationEventKind::AdhesionBreak, adhesion.cell_a);
+                return false;
+            }
+            forces[a] += direction * magnitude;
+            forces[b] -= direction * magnitude;
+            true
+        });
+
+        forces
+    }
+
+    /// Load the cells' positions and velocities into the front motion buffer before the substeps
+    fn load_motion(&mut self) {
+        let state = self.motion.front_mut();
+        state.clear();
+        state.extend(self.cells.iter().map(CellMotion::of));
+    }
+
+    /// Copy the result of the last substep back into the cells and end the tick,
+    /// so the next step can load the front buffer again
+    fn store_motion(&mut self) {
+        for (cell, state) in self.cells.iter_mut().zip(self.motion.read()) {
+            cell.position = state.position;
+            cell.velocity = state.velocity;
+            cell.acceleration = state.acceleration;
+        }
+        self.motion.swap();
+    }
+
+    /// Advance velocities and positions with the configured integrator and exponential velocity damping.
+    /// Reads the front motion buffer and writes the back one; the caller swaps them.
+    fn integrate(&mut self, forces: &[Vec3], genome: &GenomeData, config: &PhysicsConfig, dt: f32) {
+        let damping = (-config.velocity_damping * dt).exp();
+        let (current, next) = self.motion.split();
+        next.clear();
+        for ((cell, state), force) in self.cells.iter().zip(current).zip(forces) {
+            let mut state = *state;
+            // Terminal cells are anchored in place; neighbours still collide with them
+            if Self::mode(genome_revision(&self.retired_genomes, genome, cell.genome_generation), cell.mode_index).terminal {
+                state.velocity = Vec3::ZERO;
+                state.acceleration = Vec3::ZERO;
+                next.push(state);
+                continue;
+            }
+            let acceleration = *force / cell.mass.max(config.min_cell_mass);
+            match config.integrator {
+                Integrator::Explicit => {
+                    state.position += state.velocity * dt;
+                    state.velocity = (state.velocity + acceleration * dt) * damping;
+                }
+                Integrator::SemiImplicit => {
+                    state.velocity = (state.velocity + acceleration * dt) * damping;
+                    state.position += state.velocity * dt;
+                }
+                Integrator::Verlet => {
+                    // Finish last step's velocity update now that the new acceleration is known, then drift
+                    state.velocity = (state.velocity + 0.5 * (state.acceleration + acceleration) * dt) * damping;
+                    state.position += state.velocity * dt + 0.5 * acceleration * dt * dt;
+                }
+            }
+            state.acceleration = acceleration;
+            next.push(state);
+        }
+    }
+
+    /// Remove cells that fell below the minimum mass, along with their adhesions
+    fn remove_dead_cells(&mut self, config: &PhysicsConfig, events: &mut EventLog) {
+        let time = self.time;
+        let mut dead = HashSet::new();
+        self.cells.retain(|cell| {
+            if cell.mass < config.min_cell_mass {
+                events.record(time, SimulationEventKind::Death, cell.id);
+                dead.insert(cell.id);
+                false
+            } else {
+                true
+            }
+        });
+        if !dead.is_empty() {
+            self.adhesions.retain(|a| !dead.contains(&a.cell_a) && !dead.contains(&a.cell_b));
+        }
+    }
+}
+
+/// Summary of a headless run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeadlessResult {
+    pub final_time: f32,
+    pub final_population: usize,
+    pub total_mass: f32,
+    pub steps: usize,
+}
+
+/// Run a simulation without rendering for `duration` seconds of simulated time
+pub fn run_headless(genome: &GenomeData, config: &PhysicsConfig, duration: f32) -> HeadlessResult {
+    let steps = (duration / config.fixed_timestep).ceil().max(0.0) as usize;
+    run_headless_steps(genome, config, steps, |_| {})
+}
+
+/// Run `steps` fixed steps from a seed cell, calling `after_step` after each one.
+/// Stops early if the colony blows up; `steps` in the result counts the steps that ran.
+pub fn run_headless_steps(
+    genome: &GenomeData,
+    config: &PhysicsConfig,
+    steps: usize,
+    mut after_step: impl FnMut(&CpuSimulation),
+) -> HeadlessResult {
+    let mut simulation = CpuSimulation::new(genome);
+    let mut events = EventLog::default();
+    let mut steps_run = 0;
+    let _ = simulation.advance(genome, config, &mut events, steps, |simulation| {
+        steps_run += 1;
+        after_step(simulation);
+    });
+    HeadlessResult {
+        final_time: simulation.time,
+        final_population: simulation.cell_count(),
+        total_mass: simulation.total_mass(),
+        steps: steps_run,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two unit-mass cells joined by a stiff, undamped spring, stretched and let go.
+    /// Returns the largest distance from the rest length seen over `steps` steps.
+    fn max_spring_stretch(integrator: Integrator, steps: usize) -> f32 {
+        let mut genome = GenomeData::default();
+        let mode = &mut genome.modes[0];
+        mode.nutrient_gain_rate = 0.0;
+        mode.adhesion_settings.linear_spring_stiffness = 200.0;
+        mode.adhesion_settings.linear_spring_damping = 0.0;
+        mode.adhesion_settings.can_break = false;
+        mode.adhesion_settings.auto_rest_length = false;
+        mode.adhesion_settings.rest_length = 2.0;
+        let rest_length = mode.adhesion_settings.rest_length;
+
+        let config = PhysicsConfig {
+            collision_stiffness: 0.0,
+            velocity_damping: 0.0,
+            world_radius: 1.0e9,
+            max_cells: 2,
+            integrator,
+            ..PhysicsConfig::default()
+        };
+
+        let mut simulation = CpuSimulation::spawn(&genome, &SpawnConfig::default());
+        simulation.cells = vec![
+            Cell::new(0, 0, Vec3::new(-1.5, 0.0, 0.0), 1.0),
+            Cell::new(1, 0, Vec3::new(1.5, 0.0, 0.0), 1.0),
+        ];
+        simulation.adhesions = vec![AdhesionConnection { cell_a: 0, cell_b: 1, mode_index: 0, genome_generation: 0 }];
+
+        let mut events = EventLog::default();
+        let mut max_stretch = 0.0_f32;
+        for _ in 0..steps {
+            simulation.step(&genome, &config, &mut events);
+            let distance = simulation.cells[0].position.distance(simulation.cells[1].position);
+            max_stretch = max_stretch.max((distance - rest_length).abs());
+        }
+        max_stretch
+    }
+
+    #[test]
+    fn test_semi_implicit_spring_stays_bounded_where_explicit_diverges() {
+        let initial_stretch = 1.0;
+        let explicit = max_spring_stretch(Integrator::Explicit, 200);
+        let semi_implicit = max_spring_stretch(Integrator::SemiImplicit, 200);
+        let verlet = max_spring_stretch(Integrator::Verlet, 200);
+
+        assert!(explicit > 100.0 * initial_stretch, "explicit stretch {explicit} should blow up");
+        assert!(semi_implicit < 1.5 * initial_stretch, "semi-implicit stretch {semi_implicit} should stay bounded");
+        assert!(verlet < 1.5 * initial_stretch, "verlet stretch {verlet} should stay bounded");
+    }
+
+    #[test]
+    fn test_dead_cell_ids_are_never_reused() {
+        let mut genome = GenomeData::default();
+        genome.modes[0].split_interval = 0.5;
+        genome.modes[0].split_mass = 1.0;
+        genome.modes[0].nutrient_gain_rate = 4.0;
+        let config = PhysicsConfig::default();
+        let mut events = EventLog::default();
+        let spawn = SpawnConfig { pattern: crate::simulation::initial_state::SpawnPattern::Cluster, count: 4, ..SpawnConfig::default() };
+        let mut simulation = CpuSimulation::spawn(&genome, &spawn);
+        simulation.step(&genome, &config, &mut events);
+
+        // Starve one cell so its slot in the cell list is freed, then let the rest keep dividing
+        let dead_id = simulation.cells[0].id;
+        simulation.cells[0].mass = 0.0;
+        let mut seen: std::collections::HashSet<u32> = simulation.cells.iter().map(|c| c.id).collect();
+        for _ in 0..120 {
+            simulation.step(&genome, &config, &mut events);
+            for cell in &simulation.cells {
+                assert_ne!(cell.id, dead_id, "dead cell id was handed to a newborn");
+                seen.insert(cell.id);
+            }
+        }
+
+        assert!(simulation.ids.issued() > spawn.count as u32, "no cell divided after the death");
+        let ids: std::collections::HashSet<u32> = simulation.cells.iter().map(|c| c.id).collect();
+        assert_eq!(ids.len(), simulation.cell_count(), "live cells share an id");
+        assert!(seen.iter().all(|&id| id < simulation.ids.issued()));
+    }
+
+    #[test]
+    fn test_substeps_read_last_substep_state_for_every_cell() {
+        // A symmetric row of colliding cells must stay symmetric; updating cells in place
+        // while later ones still read positions would push the row off-centre
+        let mut genome = GenomeData::default();
+        genome.modes[0].nutrient_gain_rate = 0.0;
+        let config = PhysicsConfig { velocity_damping: 0.0, substeps: 4, ..PhysicsConfig::default() };
+        let mut simulation = CpuSimulation::spawn(&genome, &SpawnConfig::default());
+        simulation.cells = vec![
+            Cell::new(0, 0, Vec3::new(-0.5, 0.0, 0.0), 1.0),
+            Cell::new(1, 0, Vec3::ZERO, 1.0),
+            Cell::new(2, 0, Vec3::new(0.5, 0.0, 0.0), 1.0),
+        ];
+
+        let mut events = EventLog::default();
+        for _ in 0..5 {
+            simulation.step(&genome, &config, &mut events);
+        }
+
+        let position = |id| simulation.cell_by_id(id).unwrap().position;
+        assert!(position(2).x > 0.5, "overlapping cells were not pushed apart");
+        assert!((position(0).x + position(2).x).abs() < 1e-5, "outer cells moved unevenly");
+        assert!(position(1).length() < 1e-5, "middle cell drifted off-centre");
+    }
+
+    #[test]
+    fn test_phagocyte_digests_neighbouring_cells() {
+        let mut genome = GenomeData::default();
+        genome.modes.push(ModeSettings::new_self_splitting(1, "Prey".to_string()));
+        for mode in &mut genome.modes {
+            mode.nutrient_gain_rate = 0.0;
+            mode.split_mass = 10.0;
+        }
+        genome.modes[0].cell_type = CellType::Phagocyte.index();
+        genome.modes[0].digestion_rate = 1.0;
+
+        let config = PhysicsConfig { collision_stiffness: 0.0, ..PhysicsConfig::default() };
+        let mut simulation = CpuSimulation::spawn(&genome, &SpawnConfig::default());
+        simulation.cells = vec![
+            Cell::new(0, 0, Vec3::ZERO, 2.0),
+            Cell::new(1, 1, Vec3::new(1.0, 0.0, 0.0), 2.0),
+            Cell::new(2, 1, Vec3::new(50.0, 0.0, 0.0), 2.0),
+        ];
+
+        let mut events = EventLog::default();
+        for _ in 0..10 {
+            simulation.step(&genome, &config, &mut events);
+        }
+
+        let mass = |id| simulation.cell_by_id(id).unwrap().mass;
+        assert!(mass(0) > 2.0, "phagocyte did not gain mass");
+        assert!(mass(1) < 2.0, "touching prey was not digested");
+        assert_eq!(mass(2), 2.0, "distant prey was digested");
+        assert!((mass(0) + mass(1) - 4.0).abs() < 1e-4, "digestion should move mass, not create it");
+    }
+
+    #[test]
+    fn test_respawned_colony_gets_new_colony_id() {
+        let genome = GenomeData::default();
+        let first = CpuSimulation::new(&genome);
+        let second = CpuSimulation::new(&genome);
+        assert_eq!(first.cells[0].id, second.cells[0].id);
+        assert_ne!(first.colony(), second.colony());
+    }
+
+    #[test]
+    fn test_cell_view_only_finds_live_cells_of_this_colony() {
+        let genome = GenomeData::default();
+        let mut simulation = CpuSimulation::new(&genome);
+        let id = simulation.cell_id(simulation.cells[0].id);
+
+        let view = simulation.cell_view(&genome, id).expect("seed cell is alive");
+        assert_eq!(view.cell.id, id.id);
+        assert_eq!(view.index, 0);
+
+        let other_colony = CpuSimulation::new(&genome);
+        assert!(other_colony.cell_view(&genome, id).is_none());
+
+        simulation.cells.clear();
+        assert!(simulation.cell_view(&genome, id).is_none());
+    }
+
+    #[test]
+    fn test_step_once_advances_one_timestep() {
+        let genome = GenomeData::default();
+        let config = PhysicsConfig::default();
+        let mut simulation = CpuSimulation::new(&genome);
+        let mut events = EventLog::default();
+        assert!(simulation.step_once(&genome, &config, &mut events).is_none());
+        assert!((simulation.time - config.fixed_timestep).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_substeps_split_a_step_without_speeding_up_time() {
+        let genome = GenomeData::default();
+        let config = PhysicsConfig { substeps: 4, ..PhysicsConfig::default() };
+        let mut simulation = CpuSimulation::new(&genome);
+        simulation.step(&genome, &config, &mut EventLog::default());
+        assert!((simulation.time - config.fixed_timestep).abs() < 1e-6);
+        assert_eq!(simulation.last_step_counters.substeps, 4);
+
+        let unclamped = PhysicsConfig { substeps: 0, ..PhysicsConfig::default() };
+        assert_eq!(unclamped.substep_dt(), unclamped.fixed_timestep);
+    }
+
+    #[test]
+    fn test_connections_report_spring_force_and_break_load() {
+        let mut genome = GenomeData::default();
+        let settings = &mut genome.modes[0].adhesion_settings;
+        settings.can_break = true;
+        settings.linear_spring_damping = 0.0;
+        let (rest_length, stiffness, break_force) = (settings.rest_length, settings.linear_spring_stiffness, settings.break_force);
+
+        let mut simulation = CpuSimulation::new(&genome);
+        simulation.cells = vec![
+            Cell::new(0, 0, Vec3::ZERO, 1.0),
+            Cell::new(1, 0, Vec3::new(rest_length + 0.1, 0.0, 0.0), 1.0),
+        ];
+        simulation.adhesions = vec![AdhesionConnection { cell_a: 0, cell_b: 1, mode_index: 0, genome_generation: 0 }];
+
+        let connections = simulation.connections_for(&genome, 0);
+        assert_eq!(connections.len(), 1);
+        let link = &connections[0];
+        assert_eq!(link.cell_id, 1);
+        assert!((link.force - stiffness * 0.1).abs() < 1e-3);
+        assert!((link.break_load().unwrap() - stiffness * 0.1 / break_force).abs() < 1e-3);
+        assert!(simulation.connections_for(&genome, 7).is_empty());
+    }
+
+    fn seeded_trajectories(seed: u32, steps: usize) -> Vec<Vec<Vec3>> {
+        let mut genome = GenomeData::default();
+        let mode = &mut genome.modes[0];
+        mode.split_interval = 1.0;
+        mode.split_interval_min = Some(0.5);
+        mode.split_mass = 1.5;
+        mode.split_mass_min = Some(1.0);
+        mode.split_angle_jitter = 30.0;
+        let config = PhysicsConfig::default();
+        let spawn = SpawnConfig { pattern: crate::simulation::initial_state::SpawnPattern::Scatter, count: 4, seed, ..SpawnConfig::default() };
+
+        let mut simulation = CpuSimulation::spawn(&genome, &spawn);
+        let mut events = EventLog::default();
+        (0..steps)
+            .map(|_| {
+                simulation.step(&genome, &config, &mut events);
+                simulation.cells.iter().map(|cell| cell.position).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_same_seed_replays_the_same_run() {
+        let steps = 300;
+        let run = seeded_trajectories(7, steps);
+        assert_eq!(run, seeded_trajectories(7, steps));
+        assert!(run.last().unwrap().len() > 4, "colony should have divided");
+        assert_ne!(run, seeded_trajectories(8, steps));
+    }
+
+    #[test]
+    fn test_divisions_pass_adhesions_only_to_live_cells() {
+        let mut genome = GenomeData::default();
+        let mode = &mut genome.modes[0];
+        mode.split_interval = 1.0;
+        mode.split_mass = 1.5;
+        mode.split_mass_min = Some(1.0);
+        mode.parent_make_adhesion = true;
+        mode.child_a.keep_adhesion = true;
+        mode.child_b.keep_adhesion = true;
+        mode.max_adhesions = 20;
+        mode.adhesion_settings.can_break = false;
+
+        let config = PhysicsConfig::default();
+        let mut simulation = CpuSimulation::new(&genome);
+        let mut events = EventLog::default();
+        for _ in 0..1200 {
+            simulation.step(&genome, &config, &mut events);
+        }
+
+        let live: HashSet<u32> = simulation.cells.iter().map(|c| c.id).collect();
+        assert!(live.len() > 4, "colony should have divided");
+        assert!(simulation.adhesions.len() >= live.len() - 1, "sibling bonds were lost");
+        for adhesion in &simulation.adhesions {
+            assert!(live.contains(&adhesion.cell_a) && live.contains(&adhesion.cell_b), "adhesion to a parent that divided");
+            assert_ne!(adhesion.cell_a, adhesion.cell_b);
+        }
+    }
+
+    #[test]
+    fn test_pick_cell_prefers_the_closest_of_overlapping_spheres() {
+        let genome = GenomeData::default();
+        let mut simulation = CpuSimulation::new(&genome);
+        simulation.cells = vec![
+            Cell::new(0, 0, Vec3::new(0.0, 0.0, -5.0), 1.0),
+            Cell::new(1, 0, Vec3::new(0.0, 0.0, -4.2), 1.0),
+            Cell::new(2, 0, Vec3::new(3.0, 0.0, -2.0), 1.0),
+        ];
+        let ray = Ray { origin: Vec3::ZERO, direction: Vec3::NEG_Z };
+        assert_eq!(simulation.pick_cell(&ray), Some(simulation.cell_id(1)));
+
+        let miss = Ray { origin: Vec3::ZERO, direction: Vec3::Y };
+        assert_eq!(simulation.pick_cell(&miss), None);
+        let behind = Ray { origin: Vec3::ZERO, direction: Vec3::Z };
+        assert_eq!(simulation.pick_cell(&behind), None);
+    }
+}
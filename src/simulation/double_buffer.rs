@@ -1 +1,128 @@
 // Double buffering for simulation state
+#[cfg(debug_assertions)]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Two copies of some state: a tick reads the front buffer and writes the back one,
+/// then `swap` makes the freshly written state the front.
+///
+/// Debug builds panic if the front buffer is written after it was read in the same tick,
+/// which would make part of the tick see this tick's data and part see last tick's.
+#[derive(Debug)]
+pub struct DoubleBuffer<T> {
+    buffers: [T; 2],
+    /// Index of the buffer `read` returns
+    front: usize,
+    /// The front buffer was read since the last swap. Atomic so the owner stays `Sync`.
+    #[cfg(debug_assertions)]
+    front_read: AtomicBool,
+}
+
+impl<T> DoubleBuffer<T> {
+    pub fn new(front: T, back: T) -> Self {
+        Self {
+            buffers: [front, back],
+            front: 0,
+            #[cfg(debug_assertions)]
+            front_read: AtomicBool::new(false),
+        }
+    }
+
+    /// State from the previous tick
+    pub fn read(&self) -> &T {
+        #[cfg(debug_assertions)]
+        self.front_read.store(true, Ordering::Relaxed);
+        &self.buffers[self.front]
+    }
+
+    /// State being built this tick
+    pub fn write(&mut self) -> &mut T {
+        &mut self.buffers[1 - self.front]
+    }
+
+    /// Read the front and write the back at the same time, for a tick that needs both
+    pub fn split(&mut self) -> (&T, &mut T) {
+        #[cfg(debug_assertions)]
+        self.front_read.store(true, Ordering::Relaxed);
+        let [first, second] = &mut self.buffers;
+        if self.front == 0 {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+
+    /// Overwrite the front buffer directly, e.g. to load a snapshot between ticks.
+    /// Must not be called after the front was read in the current tick.
+    pub fn front_mut(&mut self) -> &mut T {
+        #[cfg(debug_assertions)]
+        assert!(
+            !self.front_read.load(Ordering::Relaxed),
+            "DoubleBuffer: front buffer written after it was read this tick; write to the back buffer instead"
+        );
+        &mut self.buffers[self.front]
+    }
+
+    /// Make the back buffer the front one and start a new tick. Nothing is copied.
+    pub fn swap(&mut self) {
+        self.front = 1 - self.front;
+        #[cfg(debug_assertions)]
+        self.front_read.store(false, Ordering::Relaxed);
+    }
+}
+
+impl<T: Clone> Clone for DoubleBuffer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            buffers: self.buffers.clone(),
+            front: self.front,
+            #[cfg(debug_assertions)]
+            front_read: AtomicBool::new(self.front_read.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<T: Default> Default for DoubleBuffer<T> {
+    fn default() -> Self {
+        Self::new(T::default(), T::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_reads_front_and_writes_back() {
+        let mut buffer = DoubleBuffer::new(vec![1, 2, 3], Vec::new());
+        for _ in 0..3 {
+            let (front, back) = buffer.split();
+            back.clear();
+            back.extend(front.iter().map(|value| value * 2));
+            let written = buffer.write().clone();
+            assert_ne!(buffer.read(), &written, "writes must not show up in read() before the swap");
+            buffer.swap();
+        }
+        assert_eq!(buffer.read(), &vec![8, 16, 24]);
+    }
+
+    #[test]
+    fn test_swap_flips_buffers_without_cloning() {
+        // Vec isn't copied by a swap, so the heap allocations just trade places
+        let mut buffer = DoubleBuffer::new(vec![1], vec![2]);
+        let front = buffer.read().as_ptr();
+        let back = buffer.write().as_ptr();
+        buffer.swap();
+        assert_eq!(buffer.read().as_ptr(), back);
+        assert_eq!(buffer.write().as_ptr(), front);
+        assert_eq!(buffer.read(), &vec![2]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "front buffer written after it was read")]
+    fn test_writing_front_after_reading_it_panics() {
+        let mut buffer = DoubleBuffer::new(0, 0);
+        let _ = *buffer.read();
+        *buffer.front_mut() = 1;
+    }
+}
@@ -0,0 +1,93 @@
+// Notable simulation events recorded for the timeline
+
+/// Kind of notable event that happened during the simulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SimulationEventKind {
+    /// A cell reached its split mass and divided
+    Split,
+    /// A cell died (ran out of mass)
+    Death,
+    /// An adhesion connection broke
+    AdhesionBreak,
+}
+
+impl SimulationEventKind {
+    /// Human-readable name for tooltips
+    pub fn name(&self) -> &'static str {
+        match self {
+            SimulationEventKind::Split => "Split",
+            SimulationEventKind::Death => "Death",
+            SimulationEventKind::AdhesionBreak => "Adhesion Break",
+        }
+    }
+
+    /// Marker color on the time scrubber
+    pub fn color(&self) -> [f32; 4] {
+        match self {
+            SimulationEventKind::Split => [0.3, 0.9, 0.3, 1.0],
+            SimulationEventKind::Death => [0.9, 0.25, 0.25, 1.0],
+            SimulationEventKind::AdhesionBreak => [1.0, 0.7, 0.1, 1.0],
+        }
+    }
+}
+
+/// A single recorded event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulationEvent {
+    /// Simulation time the event happened at (seconds)
+    pub time: f32,
+    pub kind: SimulationEventKind,
+    /// Cell the event happened to
+    pub cell_id: u32,
+}
+
+/// Bounded log of simulation events, kept sorted by time
+pub struct EventLog {
+    events: Vec<SimulationEvent>,
+    /// Maximum number of events kept; oldest are dropped first
+    pub capacity: usize,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self {
+            events: Vec::new(),
+            capacity: 10_000,
+        }
+    }
+}
+
+impl EventLog {
+    /// Record a new event
+    pub fn record(&mut self, time: f32, kind: SimulationEventKind, cell_id: u32) {
+        let event = SimulationEvent { time, kind, cell_id };
+        // Events almost always arrive in order, so this is usually a push
+        let idx = self.events.partition_point(|e| e.time <= time);
+        self.events.insert(idx, event);
+
+        if self.events.len() > self.capacity {
+            let overflow = self.events.len() - self.capacity;
+            self.events.drain(..overflow);
+        }
+    }
+
+    /// All recorded events, sorted by time
+    pub fn events(&self) -> &[SimulationEvent] {
+        &self.events
+    }
+
+    /// Drop events after the given time (used when the simulation is rewound)
+    pub fn truncate_after(&mut self, time: f32) {
+        let idx = self.events.partition_point(|e| e.time <= time);
+        self.events.truncate(idx);
+    }
+
+    /// Remove all events
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
@@ -1 +1,620 @@
 // GPU-accelerated physics simulation
+use super::cpu_sim::{adhesion_rest_length, CpuSimulation};
+use super::events::{EventLog, SimulationEventKind};
+use super::physics_config::{Integrator, PhysicsConfig};
+use crate::genome::GenomeData;
+use glam::Vec3;
+use std::collections::HashMap;
+
+/// Storage buffers the physics kernels bind at once: cells, adhesions, the per-cell adhesion
+/// lists, motion in, motion out and adhesion forces
+const REQUIRED_STORAGE_BUFFERS: u32 = 6;
+/// Threads per workgroup used by the physics kernels, matching `@workgroup_size` in the shader
+const REQUIRED_WORKGROUP_SIZE: u32 = 64;
+/// Smallest number of cells and adhesions the buffers are sized for; they double when outgrown
+const MIN_BUFFER_CAPACITY: usize = 256;
+
+/// Whether the adapter can run GPU physics, probed once at startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GpuPhysicsSupport {
+    Supported,
+    /// Why GPU physics can't run, shown in the Scene Manager
+    Unsupported(String),
+}
+
+impl Default for GpuPhysicsSupport {
+    fn default() -> Self {
+        GpuPhysicsSupport::Unsupported("No graphics adapter has been probed".to_string())
+    }
+}
+
+impl GpuPhysicsSupport {
+    /// Check the adapter's compute capabilities and the limits the device was created with
+    pub fn probe(adapter: &wgpu::Adapter, device: &wgpu::Device) -> Self {
+        Self::check(adapter.get_downlevel_capabilities().flags, &device.limits())
+    }
+
+    /// Check compute capabilities against what the physics kernels need
+    pub fn check(flags: wgpu::DownlevelFlags, limits: &wgpu::Limits) -> Self {
+        if !flags.contains(wgpu::DownlevelFlags::COMPUTE_SHADERS) {
+            return GpuPhysicsSupport::Unsupported("The graphics adapter has no compute shader support".to_string());
+        }
+        if limits.max_storage_buffers_per_shader_stage < REQUIRED_STORAGE_BUFFERS {
+            return GpuPhysicsSupport::Unsupported(format!(
+                "The graphics adapter allows {} storage buffers per shader stage; GPU physics needs {}",
+                limits.max_storage_buffers_per_shader_stage, REQUIRED_STORAGE_BUFFERS
+            ));
+        }
+        if limits.max_compute_invocations_per_workgroup < REQUIRED_WORKGROUP_SIZE
+            || limits.max_compute_workgroup_size_x < REQUIRED_WORKGROUP_SIZE
+        {
+            return GpuPhysicsSupport::Unsupported(format!(
+                "The graphics adapter's compute workgroups are smaller than {} threads",
+                REQUIRED_WORKGROUP_SIZE
+            ));
+        }
+        GpuPhysicsSupport::Supported
+    }
+
+    pub fn is_supported(&self) -> bool {
+        matches!(self, GpuPhysicsSupport::Supported)
+    }
+
+    pub fn unsupported_reason(&self) -> Option<&str> {
+        match self {
+            GpuPhysicsSupport::Supported => None,
+            GpuPhysicsSupport::Unsupported(reason) => Some(reason),
+        }
+    }
+}
+
+/// Substep parameters, laid out as `Params` in the shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuParams {
+    cell_count: u32,
+    adhesion_count: u32,
+    integrator: u32,
+    _padding: u32,
+    dt: f32,
+    damping: f32,
+    collision_stiffness: f32,
+    world_radius: f32,
+}
+
+/// Position, velocity and last acceleration of one cell
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuMotion {
+    position: [f32; 4],
+    velocity: [f32; 4],
+    acceleration: [f32; 4],
+}
+
+/// What the kernels need from a cell besides its motion
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuCell {
+    radius: f32,
+    inverse_mass: f32,
+    anchored: u32,
+    _padding: u32,
+}
+
+/// Adhesion between two cell indices with its spring settings resolved
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuAdhesion {
+    cell_a: u32,
+    cell_b: u32,
+    can_break: u32,
+    _padding: u32,
+    rest_length: f32,
+    stiffness: f32,
+    damping: f32,
+    break_force: f32,
+}
+
+const MOTION_SIZE: u64 = std::mem::size_of::<GpuMotion>() as u64;
+const ADHESION_FORCE_SIZE: u64 = std::mem::size_of::<[f32; 4]>() as u64;
+
+/// Colony-sized buffers, recreated when the colony outgrows them
+struct ColonyBuffers {
+    cell_capacity: usize,
+    adhesion_capacity: usize,
+    cells: wgpu::Buffer,
+    adhesions: wgpu::Buffer,
+    cell_adhesions: wgpu::Buffer,
+    /// Ping-pong motion buffers; substeps read one and write the other
+    motion: [wgpu::Buffer; 2],
+    adhesion_forces: wgpu::Buffer,
+    /// Final motion followed by the adhesion forces, copied back after the substeps
+    readback: wgpu::Buffer,
+    /// Bind group `i` reads `motion[i]` and writes the other
+    bind_groups: [wgpu::BindGroup; 2],
+}
+
+/// Runs the force and integration substeps of a `CpuSimulation` step in compute shaders.
+///
+/// The colony stays in the `CpuSimulation` between steps: every step uploads the cells and
+/// adhesions, runs the substeps on the GPU and reads the motion and broken adhesions back.
+/// Growth, division and death stay on the CPU, and switching between the CPU and GPU scenes
+/// carries the colony over without a reset.
+pub struct GpuPhysics {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    layout: wgpu::BindGroupLayout,
+    spring_pipeline: wgpu::ComputePipeline,
+    integrate_pipeline: wgpu::ComputePipeline,
+    params: wgpu::Buffer,
+    buffers: Option<ColonyBuffers>,
+    /// Why the GPU stopped stepping; later steps run on the CPU
+    failure: Option<String>,
+}
+
+impl GpuPhysics {
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("GPU Physics Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../assets/shaders/gpu_physics.wgsl").into()),
+        });
+
+        let storage_entry = |binding: u32, read_only: bool| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("GPU Physics Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_entry(1, true),
+                storage_entry(2, true),
+                storage_entry(3, true),
+                storage_entry(4, true),
+                storage_entry(5, false),
+                storage_entry(6, false),
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("GPU Physics Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = |label: &str, entry_point: &str| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let spring_pipeline = pipeline("GPU Adhesion Spring Pipeline", "adhesion_springs");
+        let integrate_pipeline = pipeline("GPU Integrate Pipeline", "integrate_cells");
+
+        let params = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Physics Params"),
+            size: std::mem::size_of::<GpuParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            device: device.clone(),
+            queue: queue.clone(),
+            layout,
+            spring_pipeline,
+            integrate_pipeline,
+            params,
+            buffers: None,
+            failure: None,
+        }
+    }
+
+    /// Why GPU stepping stopped, if it did. Steps after a failure run on the CPU.
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.as_deref()
+    }
+
+    /// Run one step's force and integration substeps for `simulation` on the GPU and write the
+    /// result back. Returns false, leaving the colony untouched, if the GPU can't; the caller
+    /// then runs the substeps on the CPU.
+    pub(super) fn run_substeps(
+        &mut self,
+        simulation: &mut CpuSimulation,
+        genome: &GenomeData,
+        config: &PhysicsConfig,
+        events: &mut EventLog,
+    ) -> bool {
+        if self.failure.is_some() {
+            return false;
+        }
+        match self.try_run_substeps(simulation, genome, config, events) {
+            Ok(()) => true,
+            Err(error) => {
+                eprintln!("GPU physics failed, continuing on the CPU: {}", error);
+                self.failure = Some(error);
+                false
+            }
+        }
+    }
+
+    fn try_run_substeps(
+        &mut self,
+        simulation: &mut CpuSimulation,
+        genome: &GenomeData,
+        config: &PhysicsConfig,
+        events: &mut EventLog,
+    ) -> Result<(), String> {
+        let cell_count = simulation.cells.len();
+        if cell_count == 0 {
+            return Ok(());
+        }
+        let index_of: HashMap<u32, usize> = simulation.cells.iter().enumerate().map(|(i, c)| (c.id, i)).collect();
+        simulation.adhesions.retain(|a| index_of.contains_key(&a.cell_a) && index_of.contains_key(&a.cell_b));
+        let adhesion_count = simulation.adhesions.len();
+
+        let cells: Vec<GpuCell> = simulation
+            .cells
+            .iter()
+            .map(|cell| GpuCell {
+                radius: cell.radius(),
+                inverse_mass: 1.0 / cell.mass.max(config.min_cell_mass),
+                anchored: simulation.mode_of(genome, cell).terminal as u32,
+                _padding: 0,
+            })
+            .collect();
+        let motion: Vec<GpuMotion> = simulation
+            .cells
+            .iter()
+            .map(|cell| GpuMotion {
+                position: cell.position.extend(1.0).to_array(),
+                velocity: cell.velocity.extend(0.0).to_array(),
+                acceleration: cell.acceleration.extend(0.0).to_array(),
+            })
+            .collect();
+        let adhesions: Vec<GpuAdhesion> = simulation
+            .adhesions
+            .iter()
+            .map(|adhesion| {
+                let (a, b) = (index_of[&adhesion.cell_a], index_of[&adhesion.cell_b]);
+                let settings = simulation.adhesion_settings(genome, adhesion);
+                GpuAdhesion {
+                    cell_a: a as u32,
+                    cell_b: b as u32,
+                    can_break: settings.can_break as u32,
+                    _padding: 0,
+                    rest_length: adhesion_rest_length(settings, &simulation.cells[a], &simulation.cells[b]),
+                    stiffness: settings.linear_spring_stiffness,
+                    damping: settings.linear_spring_damping,
+                    break_force: settings.break_force,
+                }
+            })
+            .collect();
+        let cell_adhesions = cell_adhesion_lists(cell_count, &adhesions);
+        let adhesion_forces = vec![[0.0, 0.0, 0.0, 1.0_f32]; adhesion_count];
+
+        let substeps = config.substep_count();
+        let dt = config.substep_dt();
+        let params = GpuParams {
+            cell_count: cell_count as u32,
+            adhesion_count: adhesion_count as u32,
+            integrator: match config.integrator {
+                Integrator::Explicit => 0,
+                Integrator::SemiImplicit => 1,
+                Integrator::Verlet => 2,
+            },
+            _padding: 0,
+            dt,
+            damping: (-config.velocity_damping * dt).exp(),
+            collision_stiffness: config.collision_stiffness,
+            world_radius: config.world_radius,
+        };
+
+        self.reserve(cell_count, adhesion_count);
+        let buffers = self.buffers.as_ref().expect("buffers were just reserved");
+        self.queue.write_buffer(&self.params, 0, bytemuck::bytes_of(&params));
+        self.queue.write_buffer(&buffers.cells, 0, bytemuck::cast_slice(&cells));
+        self.queue.write_buffer(&buffers.cell_adhesions, 0, bytemuck::cast_slice(&cell_adhesions));
+        self.queue.write_buffer(&buffers.motion[0], 0, bytemuck::cast_slice(&motion));
+        if adhesion_count > 0 {
+            self.queue.write_buffer(&buffers.adhesions, 0, bytemuck::cast_slice(&adhesions));
+            self.queue.write_buffer(&buffers.adhesion_forces, 0, bytemuck::cast_slice(&adhesion_forces));
+        }
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("GPU Physics Encoder"),
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("GPU Physics Substeps"),
+                timestamp_writes: None,
+            });
+            for substep in 0..substeps as usize {
+                pass.set_bind_group(0, &buffers.bind_groups[substep % 2], &[]);
+                if adhesion_count > 0 {
+                    pass.set_pipeline(&self.spring_pipeline);
+                    pass.dispatch_workgroups(workgroups(adhesion_count), 1, 1);
+                }
+                pass.set_pipeline(&self.integrate_pipeline);
+                pass.dispatch_workgroups(workgroups(cell_count), 1, 1);
+            }
+        }
+        let motion_bytes = cell_count as u64 * MOTION_SIZE;
+        let forces_offset = buffers.cell_capacity as u64 * MOTION_SIZE;
+        let forces_bytes = adhesion_count as u64 * ADHESION_FORCE_SIZE;
+        let final_motion = &buffers.motion[substeps as usize % 2];
+        encoder.copy_buffer_to_buffer(final_motion, 0, &buffers.readback, 0, motion_bytes);
+        if adhesion_count > 0 {
+            encoder.copy_buffer_to_buffer(&buffers.adhesion_forces, 0, &buffers.readback, forces_offset, forces_bytes);
+        }
+        self.queue.submit(Some(encoder.finish()));
+
+        // Block until the substeps are done; the next step needs their result
+        let slice = buffers.readback.slice(..forces_offset + forces_bytes.max(ADHESION_FORCE_SIZE));
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::PollType::Wait).map_err(|e| format!("waiting for the GPU failed: {}", e))?;
+        receiver
+            .recv()
+            .map_err(|_| "the GPU never answered the readback".to_string())?
+            .map_err(|e| format!("reading physics results back failed: {}", e))?;
+        {
+            let data = slice.get_mapped_range();
+            let motion: &[GpuMotion] = bytemuck::cast_slice(&data[..motion_bytes as usize]);
+            for (cell, state) in simulation.cells.iter_mut().zip(motion) {
+                cell.position = Vec3::from_slice(&state.position);
+                cell.velocity = Vec3::from_slice(&state.velocity);
+                cell.acceleration = Vec3::from_slice(&state.acceleration);
+            }
+
+            // Adhesions the spring kernel broke, recorded like the CPU does
+            let forces: &[[f32; 4]] = bytemuck::cast_slice(&data[forces_offset as usize..(forces_offset + forces_bytes) as usize]);
+            let time = simulation.time;
+            let mut intact = forces.iter().map(|force| force[3] != 0.0);
+            simulation.adhesions.retain(|adhesion| {
+                let keep = intact.next().unwrap_or(true);
+                if !keep {
+                    events.record(time, SimulationEventKind::AdhesionBreak, adhesion.cell_a);
+                }
+                keep
+            });
+        }
+        buffers.readback.unmap();
+
+        let counters = &mut simulation.last_step_counters;
+        let pairs = (cell_count * cell_count.saturating_sub(1) / 2) as u32;
+        counters.collision_pairs_tested = counters.collision_pairs_tested.saturating_add(pairs.saturating_mul(substeps));
+        counters.adhesion_constraints += adhesion_count as u32 * substeps;
+        Ok(())
+    }
+
+    /// Make sure the colony buffers hold at least this many cells and adhesions
+    fn reserve(&mut self, cell_count: usize, adhesion_count: usize) {
+        if let Some(buffers) = &self.buffers {
+            if buffers.cell_capacity >= cell_count && buffers.adhesion_capacity >= adhesion_count {
+                return;
+            }
+        }
+        let cell_capacity = cell_count.max(MIN_BUFFER_CAPACITY).next_power_of_two();
+        let adhesion_capacity = adhesion_count.max(MIN_BUFFER_CAPACITY).next_power_of_two();
+
+        let storage = |label: &str, size: u64, usage: wgpu::BufferUsages| {
+            self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | usage,
+                mapped_at_creation: false,
+            })
+        };
+        let cells = storage("GPU Physics Cells", (cell_capacity * std::mem::size_of::<GpuCell>()) as u64, wgpu::BufferUsages::empty());
+        let adhesions = storage(
+            "GPU Physics Adhesions",
+            (adhesion_capacity * std::mem::size_of::<GpuAdhesion>()) as u64,
+            wgpu::BufferUsages::empty(),
+        );
+        let cell_adhesions = storage(
+            "GPU Physics Cell Adhesions",
+            ((cell_capacity + 1 + adhesion_capacity * 2) * std::mem::size_of::<u32>()) as u64,
+            wgpu::BufferUsages::empty(),
+        );
+        let motion = [
+            storage("GPU Physics Motion A", cell_capacity as u64 * MOTION_SIZE, wgpu::BufferUsages::COPY_SRC),
+            storage("GPU Physics Motion B", cell_capacity as u64 * MOTION_SIZE, wgpu::BufferUsages::COPY_SRC),
+        ];
+        let adhesion_forces = storage(
+            "GPU Physics Adhesion Forces",
+            adhesion_capacity as u64 * ADHESION_FORCE_SIZE,
+            wgpu::BufferUsages::COPY_SRC,
+        );
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Physics Readback"),
+            size: cell_capacity as u64 * MOTION_SIZE + adhesion_capacity as u64 * ADHESION_FORCE_SIZE,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = |read: &wgpu::Buffer, write: &wgpu::Buffer| {
+            self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("GPU Physics Bind Group"),
+                layout: &self.layout,
+                entries: &[
+                    wgpu::BindGroupEntry { binding: 0, resource: self.params.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 1, resource: cells.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 2, resource: adhesions.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 3, resource: cell_adhesions.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 4, resource: read.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 5, resource: write.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 6, resource: adhesion_forces.as_entire_binding() },
+                ],
+            })
+        };
+        let bind_groups = [bind_group(&motion[0], &motion[1]), bind_group(&motion[1], &motion[0])];
+
+        self.buffers = Some(ColonyBuffers {
+            cell_capacity,
+            adhesion_capacity,
+            cells,
+            adhesions,
+            cell_adhesions,
+            motion,
+            adhesion_forces,
+            readback,
+            bind_groups,
+        });
+    }
+}
+
+/// Workgroups needed to cover `count` invocations
+fn workgroups(count: usize) -> u32 {
+    (count as u32).div_ceil(REQUIRED_WORKGROUP_SIZE)
+}
+
+/// Each cell's adhesions as `cell_count + 1` offsets followed by the entries they index.
+/// An entry is `adhesion_index * 2`, plus one when the cell is the adhesion's `cell_b`.
+fn cell_adhesion_lists(cell_count: usize, adhesions: &[GpuAdhesion]) -> Vec<u32> {
+    let mut offsets = vec![0_u32; cell_count + 1];
+    for adhesion in adhesions {
+        offsets[adhesion.cell_a as usize + 1] += 1;
+        offsets[adhesion.cell_b as usize + 1] += 1;
+    }
+    for i in 0..cell_count {
+        offsets[i + 1] += offsets[i];
+    }
+    let mut entries = vec![0_u32; adhesions.len() * 2];
+    let mut next = offsets[..cell_count].to_vec();
+    for (index, adhesion) in adhesions.iter().enumerate() {
+        for (cell, side) in [(adhesion.cell_a, 0), (adhesion.cell_b, 1)] {
+            entries[next[cell as usize] as usize] = index as u32 * 2 + side;
+            next[cell as usize] += 1;
+        }
+    }
+    offsets.extend(entries);
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::types::{AdhesionConnection, Cell};
+
+    #[test]
+    fn test_support_check_needs_compute_shaders_and_limits() {
+        let limits = wgpu::Limits::default();
+        assert!(GpuPhysicsSupport::check(wgpu::DownlevelFlags::all(), &limits).is_supported());
+        assert!(!GpuPhysicsSupport::check(wgpu::DownlevelFlags::empty(), &limits).is_supported());
+        // WebGL2-class adapters have no storage buffers at all
+        let downlevel = wgpu::Limits::downlevel_webgl2_defaults();
+        let support = GpuPhysicsSupport::check(wgpu::DownlevelFlags::all(), &downlevel);
+        assert!(support.unsupported_reason().is_some_and(|reason| reason.contains("storage buffers")));
+    }
+
+    #[test]
+    fn test_cell_adhesion_lists_index_both_ends() {
+        let adhesion = |cell_a, cell_b| GpuAdhesion { cell_a, cell_b, ..bytemuck::Zeroable::zeroed() };
+        let lists = cell_adhesion_lists(3, &[adhesion(0, 2), adhesion(2, 1)]);
+        // Offsets 0, 1, 2, 4 then cell 0: adhesion 0 as a; cell 1: adhesion 1 as b; cell 2: adhesion 0 as b, adhesion 1 as a
+        assert_eq!(lists, vec![0, 1, 2, 4, 0, 3, 1, 2]);
+    }
+
+    /// A device to run the kernels on, or None on machines without a usable adapter
+    fn test_device() -> Option<(wgpu::Adapter, wgpu::Device, wgpu::Queue)> {
+        let instance = wgpu::Instance::default();
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default())).ok()?;
+        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).ok()?;
+        Some((adapter, device, queue))
+    }
+
+    #[test]
+    fn test_gpu_substeps_follow_the_cpu_solver() {
+        let Some((adapter, device, queue)) = test_device() else {
+            eprintln!("no graphics adapter, skipping the GPU physics comparison");
+            return;
+        };
+        if !GpuPhysicsSupport::probe(&adapter, &device).is_supported() {
+            return;
+        }
+
+        let mut genome = GenomeData::default();
+        let mode = &mut genome.modes[0];
+        mode.split_interval = 1.0;
+        mode.split_mass = 1.5;
+        mode.split_mass_min = Some(1.0);
+        mode.parent_make_adhesion = true;
+        mode.child_a.keep_adhesion = true;
+        mode.child_b.keep_adhesion = true;
+        let mut gpu_physics = GpuPhysics::new(&device, &queue);
+
+        for &integrator in Integrator::all() {
+            let config = PhysicsConfig { substeps: 3, integrator, ..PhysicsConfig::default() };
+            let mut cpu = CpuSimulation::new(&genome);
+            let mut gpu = cpu.clone();
+            let (mut cpu_events, mut gpu_events) = (EventLog::default(), EventLog::default());
+            cpu.advance(&genome, &config, &mut cpu_events, 600, |_| {}).unwrap();
+            gpu.advance_on_gpu(&mut gpu_physics, &genome, &config, &mut gpu_events, 600, |_| {}).unwrap();
+
+            assert_eq!(gpu_physics.failure(), None);
+            assert!(cpu.cell_count() > 1, "colony should have divided");
+            assert_eq!(gpu.cell_count(), cpu.cell_count());
+            assert_eq!(gpu.adhesions.len(), cpu.adhesions.len());
+            for (gpu_cell, cpu_cell) in gpu.cells.iter().zip(&cpu.cells) {
+                assert_eq!(gpu_cell.id, cpu_cell.id);
+                assert!(
+                    gpu_cell.position.distance(cpu_cell.position) < 1e-2,
+                    "{:?}: {} vs {}",
+                    integrator,
+                    gpu_cell.position,
+                    cpu_cell.position
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_gpu_breaks_overstretched_adhesions() {
+        let Some((adapter, device, queue)) = test_device() else {
+            return;
+        };
+        if !GpuPhysicsSupport::probe(&adapter, &device).is_supported() {
+            return;
+        }
+
+        let mut genome = GenomeData::default();
+        let mode = &mut genome.modes[0];
+        mode.nutrient_gain_rate = 0.0;
+        mode.adhesion_settings.can_break = true;
+        mode.adhesion_settings.break_force = 1.0;
+        let mut simulation = CpuSimulation::new(&genome);
+        simulation.cells = vec![
+            Cell::new(0, 0, Vec3::new(-3.0, 0.0, 0.0), 1.0),
+            Cell::new(1, 0, Vec3::new(3.0, 0.0, 0.0), 1.0),
+        ];
+        simulation.adhesions = vec![AdhesionConnection { cell_a: 0, cell_b: 1, mode_index: 0, genome_generation: 0 }];
+
+        let mut gpu_physics = GpuPhysics::new(&device, &queue);
+        let mut events = EventLog::default();
+        simulation.advance_on_gpu(&mut gpu_physics, &genome, &PhysicsConfig::default(), &mut events, 1, |_| {}).unwrap();
+
+        assert_eq!(gpu_physics.failure(), None);
+        assert!(simulation.adhesions.is_empty());
+        assert!(events.events().iter().any(|event| event.kind == SimulationEventKind::AdhesionBreak && event.cell_id == 0));
+    }
+}
@@ -1 +1,112 @@
 // Initial simulation state setup
+use crate::cell::division::hash_unit;
+use crate::cell::types::Cell;
+use crate::genome::GenomeData;
+use glam::Vec3;
+
+/// Mass of every cell placed at spawn
+pub const SEED_CELL_MASS: f32 = 1.0;
+
+/// How the starting colony is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum SpawnPattern {
+    /// One cell at the origin
+    #[default]
+    SingleSeed,
+    /// `count` cells packed tightly around the origin
+    Cluster,
+    /// `count` cells spread uniformly through a sphere of `radius`
+    Scatter,
+}
+
+impl SpawnPattern {
+    pub fn all() -> &'static [SpawnPattern] {
+        &[SpawnPattern::SingleSeed, SpawnPattern::Cluster, SpawnPattern::Scatter]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpawnPattern::SingleSeed => "Single Seed Cell",
+            SpawnPattern::Cluster => "Cluster",
+            SpawnPattern::Scatter => "Random Scatter",
+        }
+    }
+}
+
+/// Starting colony used when the simulation is (re)spawned
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SpawnConfig {
+    pub pattern: SpawnPattern,
+    /// Number of cells for cluster and scatter patterns
+    pub count: usize,
+    /// Scatter sphere radius
+    pub radius: f32,
+    /// Mode of the spawned cells; negative uses the genome's initial mode
+    pub initial_mode: i32,
+    /// Seed for placement, split ranges and split jitter, so the same config, genome and
+    /// physics settings always replay the same run
+    pub seed: u32,
+}
+
+impl Default for SpawnConfig {
+    fn default() -> Self {
+        Self {
+            pattern: SpawnPattern::SingleSeed,
+            count: 8,
+            radius: 10.0,
+            initial_mode: -1,
+            seed: 1,
+        }
+    }
+}
+
+impl SpawnConfig {
+    /// Mode index spawned cells start in, clamped to the genome
+    pub fn mode_index(&self, genome: &GenomeData) -> usize {
+        let mode = if self.initial_mode < 0 { genome.initial_mode } else { self.initial_mode };
+        (mode.max(0) as usize).min(genome.modes.len().saturating_sub(1))
+    }
+
+    /// Starting cells with ids 0..n
+    pub fn spawn_cells(&self, genome: &GenomeData) -> Vec<Cell> {
+        let mode_index = self.mode_index(genome);
+        let seed_radius = SEED_CELL_MASS.cbrt();
+        let (count, radius) = match self.pattern {
+            SpawnPattern::SingleSeed => return vec![Cell::new(0, mode_index, Vec3::ZERO, SEED_CELL_MASS)],
+            // Roughly the radius of a ball holding `count` touching cells
+            SpawnPattern::Cluster => (self.count, seed_radius * (self.count as f32).cbrt() * 1.2),
+            SpawnPattern::Scatter => (self.count, self.radius),
+        };
+
+        (0..count.max(1) as u32)
+            .map(|id| {
+                let position = random_in_ball(cell_seed(self.seed, id)) * radius;
+                Cell::new(id, mode_index, position, SEED_CELL_MASS)
+            })
+            .collect()
+    }
+}
+
+/// Per-cell random seed derived from the run seed
+pub(crate) fn cell_seed(seed: u32, cell_id: u32) -> u32 {
+    seed.wrapping_mul(0x2545_F491) ^ cell_id
+}
+
+/// Fresh seed from the clock, for the "Randomize" button
+pub fn random_seed() -> u32 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or(0);
+    (nanos ^ (nanos >> 32)) as u32
+}
+
+/// Uniform random point in the unit ball
+fn random_in_ball(seed: u32) -> Vec3 {
+    let cos_theta = 1.0 - 2.0 * hash_unit(seed, 0);
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let phi = hash_unit(seed, 1) * std::f32::consts::TAU;
+    let r = hash_unit(seed, 2).cbrt();
+    Vec3::new(sin_theta * phi.cos(), cos_theta, sin_theta * phi.sin()) * r
+}
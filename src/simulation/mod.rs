@@ -1,35 +1,126 @@
 pub mod adhesion_inheritance;
+pub mod benchmark;
 pub mod cell_allocation;
 pub mod clock;
 pub mod cpu_physics;
 pub mod cpu_sim;
 pub mod double_buffer;
+pub mod events;
 pub mod gpu_physics;
 pub mod initial_state;
 pub mod nutrient_system;
+pub mod parameter_sweep;
 pub mod physics_config;
 pub mod preview_sim;
+pub mod scenes;
 pub mod synchronized_nutrients;
+pub mod telemetry;
 
 /// Current simulation mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum SimulationMode {
     Cpu,
+    /// Needs an adapter with compute support; falls back to `Cpu` without one
+    Gpu,
     #[default]
     Preview,
 }
 
+impl SimulationMode {
+    pub const ALL: [SimulationMode; 3] = [SimulationMode::Preview, SimulationMode::Cpu, SimulationMode::Gpu];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SimulationMode::Cpu => "CPU Scene",
+            SimulationMode::Gpu => "GPU Scene",
+            SimulationMode::Preview => "Genome Editor",
+        }
+    }
+}
+
+/// Live figures about the running colony, refreshed every frame for the performance monitor
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SimulationStats {
+    pub live_cell_count: usize,
+    /// Adhesion bonds between live cells
+    pub adhesion_count: usize,
+    /// Cell cap from the physics config
+    pub max_capacity: usize,
+    /// Simulated seconds
+    pub sim_time: f32,
+    /// Heap memory held by the cell and adhesion buffers
+    pub approx_memory_bytes: usize,
+    pub paused: bool,
+    pub mode: SimulationMode,
+}
+
+impl SimulationStats {
+    pub fn new(simulation: &cpu_sim::CpuSimulation, state: &SimulationState) -> Self {
+        Self {
+            live_cell_count: simulation.cell_count(),
+            adhesion_count: simulation.adhesions.len(),
+            max_capacity: state.physics_config.max_cells,
+            sim_time: state.current_time,
+            approx_memory_bytes: simulation.allocated_bytes(),
+            paused: state.paused,
+            mode: state.mode,
+        }
+    }
+
+    /// Share of the cell cap in use, in percent
+    pub fn capacity_percent(&self) -> f32 {
+        self.live_cell_count as f32 / self.max_capacity.max(1) as f32 * 100.0
+    }
+}
+
 /// Global simulation state
 pub struct SimulationState {
     pub mode: SimulationMode,
     pub paused: bool,
+    /// Set by the UI to run a single physics step while paused
+    pub step_requested: bool,
     pub target_time: Option<f32>,
     pub is_resimulating: bool,
+    /// Genome preview jump to `target_time` in progress, for the scrubber's progress bar
+    pub preview_seek: Option<preview_sim::PreviewSeek>,
+    /// Set by the UI to replace the colony on the next frame with a fresh spawn of the edited
+    /// genome from `spawn_config` (same seed): cells, adhesions, time and event log all restart
     pub needs_respawn: bool,
     /// Simulation speed multiplier (1.0 = real-time, 10.0 = 10x speed)
     pub speed_multiplier: f32,
+    /// Speed actually delivered, smoothed over recent frames. Falls below `speed_multiplier`
+    /// when the physics steps needed per frame exceed the per-frame cap.
+    pub achieved_speed: f32,
+    /// The last frame ran the most physics steps a frame allows and dropped the rest
+    pub speed_capped: bool,
     /// Current simulation time
     pub current_time: f32,
+    /// Notable events (splits, deaths, adhesion breaks) for the timeline
+    pub event_log: events::EventLog,
+    /// Integration parameters for the physics step
+    pub physics_config: physics_config::PhysicsConfig,
+    /// How "Apply Genome to Running Sim" treats existing cells
+    pub genome_apply_mode: cpu_sim::GenomeApplyMode,
+    /// Set by the UI to apply the edited genome on the next frame
+    pub genome_apply_requested: bool,
+    /// The edited genome differs from the one the colony is running
+    pub genome_has_unapplied_changes: bool,
+    /// Starting colony used by Reset Scene / Respawn
+    pub spawn_config: initial_state::SpawnConfig,
+    /// Set when the running genome's initial mode can never divide
+    pub growth_blocker: Option<crate::genome::analysis::DivisionBlocker>,
+    /// Blow-up that auto-paused the simulation, shown until dismissed
+    pub instability: Option<cpu_sim::Instability>,
+    /// Simulated time of the last snapshot that passed the stability check
+    pub stable_snapshot_time: Option<f32>,
+    /// Set by the UI to restore the last stable snapshot on the next frame
+    pub rollback_requested: bool,
+    /// Optional CSV log of colony statistics, sampled as the simulation steps
+    pub telemetry: telemetry::TelemetryLogger,
+    /// Whether the adapter can run GPU physics, probed at startup
+    pub gpu_support: gpu_physics::GpuPhysicsSupport,
+    /// Why GPU mode was refused and the CPU scene used instead, shown until dismissed
+    pub gpu_fallback: Option<String>,
 }
 
 impl Default for SimulationState {
@@ -37,11 +128,43 @@ impl Default for SimulationState {
         Self {
             mode: SimulationMode::default(),
             paused: false,
+            step_requested: false,
             target_time: None,
             is_resimulating: false,
+            preview_seek: None,
             needs_respawn: false,
             speed_multiplier: 1.0,
+            achieved_speed: 1.0,
+            speed_capped: false,
             current_time: 0.0,
+            event_log: events::EventLog::default(),
+            physics_config: physics_config::PhysicsConfig::default(),
+            genome_apply_mode: cpu_sim::GenomeApplyMode::default(),
+            genome_apply_requested: false,
+            genome_has_unapplied_changes: false,
+            spawn_config: initial_state::SpawnConfig::default(),
+            growth_blocker: None,
+            instability: None,
+            stable_snapshot_time: None,
+            rollback_requested: false,
+            telemetry: telemetry::TelemetryLogger::default(),
+            gpu_support: gpu_physics::GpuPhysicsSupport::default(),
+            gpu_fallback: None,
+        }
+    }
+}
+
+impl SimulationState {
+    /// Switch to the CPU scene if GPU mode was picked on an adapter that can't run it.
+    /// The colony is shared by both modes, so no cells are lost.
+    pub fn apply_gpu_fallback(&mut self) {
+        if self.mode != SimulationMode::Gpu {
+            return;
+        }
+        if let Some(reason) = self.gpu_support.unsupported_reason() {
+            eprintln!("GPU physics unavailable, using the CPU scene: {}", reason);
+            self.gpu_fallback = Some(reason.to_string());
+            self.mode = SimulationMode::Cpu;
         }
     }
 }
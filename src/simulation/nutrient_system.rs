@@ -1 +1,17 @@
 // Nutrient distribution system
+use glam::Vec3;
+
+/// Nutrient concentration of the medium, relative to the level genome gain rates are tuned for
+pub const AMBIENT_CONCENTRATION: f32 = 1.0;
+
+/// Nutrient concentration at `position`. The medium is uniform for now, so every point reads
+/// the ambient level; growth scales each mode's nutrient gain rate by this value, so a
+/// spatially varying field only has to change this function.
+pub fn sample(_position: Vec3) -> f32 {
+    AMBIENT_CONCENTRATION
+}
+
+/// How full a cell's nutrient store is: 0 at the minimum cell mass, 1 once it reaches its split mass
+pub fn stored_fraction(mass: f32, min_cell_mass: f32, split_mass: f32) -> f32 {
+    ((mass - min_cell_mass) / (split_mass - min_cell_mass).max(f32::EPSILON)).clamp(0.0, 1.0)
+}
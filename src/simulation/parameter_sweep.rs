@@ -0,0 +1,152 @@
+// Batch parameter sweeps over headless runs
+use super::cpu_sim::run_headless;
+use super::physics_config::PhysicsConfig;
+use crate::genome::{GenomeData, ModeSettings};
+
+/// Mode parameter that a sweep varies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SweepParameter {
+    SplitInterval,
+    SplitMass,
+    SplitRatio,
+    NutrientGainRate,
+    LinearSpringStiffness,
+}
+
+impl SweepParameter {
+    pub fn all() -> &'static [SweepParameter] {
+        &[
+            SweepParameter::SplitInterval,
+            SweepParameter::SplitMass,
+            SweepParameter::SplitRatio,
+            SweepParameter::NutrientGainRate,
+            SweepParameter::LinearSpringStiffness,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SweepParameter::SplitInterval => "Split Interval",
+            SweepParameter::SplitMass => "Split Mass",
+            SweepParameter::SplitRatio => "Split Ratio",
+            SweepParameter::NutrientGainRate => "Nutrient Gain Rate",
+            SweepParameter::LinearSpringStiffness => "Linear Spring Stiffness",
+        }
+    }
+
+    /// Write a value into a mode
+    pub fn apply(&self, mode: &mut ModeSettings, value: f32) {
+        match self {
+            SweepParameter::SplitInterval => mode.split_interval = value,
+            SweepParameter::SplitMass => mode.split_mass = value,
+            SweepParameter::SplitRatio => mode.split_ratio = value.clamp(0.01, 0.99),
+            SweepParameter::NutrientGainRate => mode.nutrient_gain_rate = value,
+            SweepParameter::LinearSpringStiffness => mode.adhesion_settings.linear_spring_stiffness = value,
+        }
+    }
+}
+
+/// Description of a sweep: one parameter, a range, and how long each run lasts
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepConfig {
+    pub parameter: SweepParameter,
+    /// Mode to modify; `None` applies the value to every mode
+    pub mode_index: Option<usize>,
+    pub start: f32,
+    pub end: f32,
+    /// Number of runs, evenly spaced from `start` to `end` inclusive
+    pub steps: usize,
+    /// Simulated seconds per run
+    pub duration: f32,
+}
+
+impl Default for SweepConfig {
+    fn default() -> Self {
+        Self {
+            parameter: SweepParameter::SplitInterval,
+            mode_index: None,
+            start: 2.0,
+            end: 20.0,
+            steps: 10,
+            duration: 60.0,
+        }
+    }
+}
+
+impl SweepConfig {
+    /// Parameter values visited by the sweep
+    pub fn values(&self) -> Vec<f32> {
+        match self.steps {
+            0 => Vec::new(),
+            1 => vec![self.start],
+            n => (0..n)
+                .map(|i| self.start + (self.end - self.start) * i as f32 / (n - 1) as f32)
+                .collect(),
+        }
+    }
+}
+
+/// Outcome of one run in a sweep
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepPoint {
+    pub value: f32,
+    pub final_population: usize,
+    pub total_mass: f32,
+}
+
+/// Run the sweep, calling `on_point` after each run (e.g. to report progress).
+/// Runs are deterministic, so the same genome and config always give the same curve.
+pub fn run_sweep(
+    genome: &GenomeData,
+    physics: &PhysicsConfig,
+    config: &SweepConfig,
+    mut on_point: impl FnMut(SweepPoint),
+) -> Vec<SweepPoint> {
+    let mut points = Vec::with_capacity(config.steps);
+    for value in config.values() {
+        let mut variant = genome.clone();
+        match config.mode_index {
+            Some(index) => {
+                if let Some(mode) = variant.modes.get_mut(index) {
+                    config.parameter.apply(mode, value);
+                }
+            }
+            None => {
+                for mode in &mut variant.modes {
+                    config.parameter.apply(mode, value);
+                }
+            }
+        }
+
+        let result = run_headless(&variant, physics, config.duration);
+        let point = SweepPoint {
+            value,
+            final_population: result.final_population,
+            total_mass: result.total_mass,
+        };
+        on_point(point);
+        points.push(point);
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_sweep_gives_identical_points() {
+        let genome = GenomeData::default();
+        let physics = PhysicsConfig::default();
+        let config = SweepConfig { start: 2.0, end: 6.0, steps: 3, duration: 15.0, ..SweepConfig::default() };
+
+        let mut reported = Vec::new();
+        let first = run_sweep(&genome, &physics, &config, |point| reported.push(point));
+        let second = run_sweep(&genome, &physics, &config, |_| {});
+
+        assert_eq!(first, reported);
+        assert_eq!(first, second);
+        assert_eq!(first.iter().map(|point| point.value).collect::<Vec<_>>(), vec![2.0, 4.0, 6.0]);
+        assert!(first.iter().any(|point| point.final_population > 1), "no run divided, so the sweep proves nothing");
+    }
+}
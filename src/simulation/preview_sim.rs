@@ -1 +1,75 @@
 // Preview simulation for genome editor
+use super::cpu_sim::{CpuSimulation, Instability};
+use super::events::EventLog;
+use super::physics_config::PhysicsConfig;
+use crate::genome::GenomeData;
+
+/// Most physics steps a preview fast-forward runs per frame; longer jumps continue on the next frame
+pub const MAX_PREVIEW_STEPS_PER_FRAME: usize = 2048;
+
+/// A jump of the genome preview to a later time. Steps run back to back without the
+/// live scene's per-step bookkeeping (rollback snapshots, ghost trail, telemetry).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewSeek {
+    /// Simulated time when the jump started
+    pub start_time: f32,
+    pub target_time: f32,
+}
+
+impl PreviewSeek {
+    pub fn new(start_time: f32, target_time: f32) -> Self {
+        Self { start_time, target_time }
+    }
+
+    /// Share of the jump done at `time`, from 0 to 1
+    pub fn progress(&self, time: f32) -> f32 {
+        let span = self.target_time - self.start_time;
+        if span <= 0.0 {
+            1.0
+        } else {
+            ((time - self.start_time) / span).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Steps left before `simulation` reaches the target
+    pub fn remaining_steps(&self, simulation: &CpuSimulation, config: &PhysicsConfig) -> usize {
+        ((self.target_time - simulation.time) / config.fixed_timestep - 0.5).ceil().max(0.0) as usize
+    }
+
+    /// Run up to `max_steps` towards the target. Returns whether the target was reached.
+    pub fn run(
+        &self,
+        simulation: &mut CpuSimulation,
+        genome: &GenomeData,
+        config: &PhysicsConfig,
+        events: &mut EventLog,
+        max_steps: usize,
+    ) -> Result<bool, Instability> {
+        let remaining = self.remaining_steps(simulation, config);
+        simulation.advance(genome, config, events, remaining.min(max_steps), |_| {})?;
+        Ok(remaining <= max_steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fast_forward_reaches_target_over_capped_frames() {
+        let genome = GenomeData::default();
+        let config = PhysicsConfig::default();
+        let mut simulation = CpuSimulation::new(&genome);
+        let mut events = EventLog::default();
+        let seek = PreviewSeek::new(0.0, config.fixed_timestep * 25.0);
+
+        let mut frames = 0;
+        while !seek.run(&mut simulation, &genome, &config, &mut events, 10).unwrap() {
+            frames += 1;
+            assert!(seek.progress(simulation.time) < 1.0);
+        }
+        assert_eq!(frames, 2);
+        assert!((simulation.time - seek.target_time).abs() < config.fixed_timestep * 0.5);
+        assert!(seek.progress(simulation.time) > 0.99);
+    }
+}
@@ -0,0 +1,112 @@
+// Named simulation configurations for quick A/B comparisons
+use super::initial_state::SpawnConfig;
+use super::physics_config::PhysicsConfig;
+use crate::genome::GenomeData;
+
+/// Everything needed to rebuild a colony from scratch
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SceneConfig {
+    pub name: String,
+    pub genome: GenomeData,
+    pub spawn: SpawnConfig,
+    pub physics: PhysicsConfig,
+}
+
+impl Default for SceneConfig {
+    fn default() -> Self {
+        Self {
+            name: "Main Simulation".to_string(),
+            genome: GenomeData::default(),
+            spawn: SpawnConfig::default(),
+            physics: PhysicsConfig::default(),
+        }
+    }
+}
+
+/// The saved scenes and which one the running colony belongs to. Never empty.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SceneLibrary {
+    pub scenes: Vec<SceneConfig>,
+    pub active: usize,
+}
+
+impl Default for SceneLibrary {
+    fn default() -> Self {
+        Self {
+            scenes: vec![SceneConfig::default()],
+            active: 0,
+        }
+    }
+}
+
+impl SceneLibrary {
+    /// Default location of the scene list, next to the UI settings
+    pub fn default_path() -> std::path::PathBuf {
+        std::path::PathBuf::from("scenes.json")
+    }
+
+    pub fn save_to_file(&self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load the scene list, falling back to a single default scene if the file is missing or invalid
+    pub fn load_from_file(path: &std::path::Path) -> Self {
+        let mut library: Self = match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                eprintln!("Failed to parse scenes file: {}. Using defaults.", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        if library.scenes.is_empty() {
+            library.scenes.push(SceneConfig::default());
+        }
+        library.active = library.active.min(library.scenes.len() - 1);
+        for scene in &mut library.scenes {
+            for warning in scene.genome.repair() {
+                eprintln!("Scene '{}': {}", scene.name, warning);
+            }
+        }
+        library
+    }
+
+    pub fn active_scene(&self) -> &SceneConfig {
+        &self.scenes[self.active]
+    }
+
+    pub fn active_scene_mut(&mut self) -> &mut SceneConfig {
+        &mut self.scenes[self.active]
+    }
+
+    /// A name not used by any scene, built from `base`
+    pub fn unique_name(&self, base: &str) -> String {
+        let taken = |name: &str| self.scenes.iter().any(|scene| scene.name == name);
+        if !taken(base) {
+            return base.to_string();
+        }
+        (2..).map(|n| format!("{} {}", base, n)).find(|name| !taken(name)).unwrap()
+    }
+
+    /// Append a scene and make it active
+    pub fn add(&mut self, mut scene: SceneConfig) {
+        scene.name = self.unique_name(&scene.name);
+        self.scenes.push(scene);
+        self.active = self.scenes.len() - 1;
+    }
+
+    /// Remove a scene other than the active one; returns false if nothing was removed
+    pub fn remove(&mut self, index: usize) -> bool {
+        if index == self.active || index >= self.scenes.len() {
+            return false;
+        }
+        self.scenes.remove(index);
+        if index < self.active {
+            self.active -= 1;
+        }
+        true
+    }
+}
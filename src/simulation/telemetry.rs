@@ -0,0 +1,184 @@
+// Time-series logging of colony statistics to CSV
+use super::cpu_sim::CpuSimulation;
+use crate::genome::GenomeData;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+/// Steps between samples unless the user picks another interval
+pub const DEFAULT_TELEMETRY_INTERVAL: u32 = 30;
+
+/// Colony figures recorded for one CSV row
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelemetrySample {
+    /// Simulated seconds
+    pub time: f32,
+    pub cell_count: usize,
+    pub total_mass: f32,
+    /// Live cells per mode index, one entry per mode column
+    pub mode_counts: Vec<u32>,
+}
+
+impl TelemetrySample {
+    /// Snapshot the colony, counting cells in the first `mode_columns` modes
+    pub fn new(simulation: &CpuSimulation, mode_columns: usize) -> Self {
+        let counts = simulation.mode_counts();
+        Self {
+            time: simulation.time,
+            cell_count: simulation.cell_count(),
+            total_mass: simulation.total_mass(),
+            mode_counts: (0..mode_columns).map(|mode| counts.get(&mode).copied().unwrap_or(0)).collect(),
+        }
+    }
+
+    pub fn average_mass(&self) -> f32 {
+        if self.cell_count == 0 {
+            0.0
+        } else {
+            self.total_mass / self.cell_count as f32
+        }
+    }
+}
+
+/// Writes a `TelemetrySample` every `interval_steps` physics steps to a buffered CSV file.
+/// Per-mode columns are fixed by the genome running when the first row is written.
+pub struct TelemetryLogger {
+    pub output_path: PathBuf,
+    /// Physics steps between samples
+    pub interval_steps: u32,
+    writer: Option<BufWriter<File>>,
+    /// Number of per-mode columns, known once the header is written
+    mode_columns: Option<usize>,
+    steps_until_sample: u32,
+    rows_written: usize,
+    /// Why logging last failed to start or stopped on its own, shown in the UI
+    last_error: Option<String>,
+}
+
+impl Default for TelemetryLogger {
+    fn default() -> Self {
+        Self {
+            output_path: PathBuf::from("telemetry.csv"),
+            interval_steps: DEFAULT_TELEMETRY_INTERVAL,
+            writer: None,
+            mode_columns: None,
+            steps_until_sample: 0,
+            rows_written: 0,
+            last_error: None,
+        }
+    }
+}
+
+impl TelemetryLogger {
+    pub fn is_logging(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Rows written since logging started
+    pub fn rows_written(&self) -> usize {
+        self.rows_written
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Create (or truncate) the output file and start sampling from the next step
+    pub fn start(&mut self) -> std::io::Result<()> {
+        self.stop()?;
+        let file = File::create(&self.output_path).inspect_err(|e| self.last_error = Some(e.to_string()))?;
+        self.writer = Some(BufWriter::new(file));
+        self.mode_columns = None;
+        self.steps_until_sample = 0;
+        self.rows_written = 0;
+        self.last_error = None;
+        println!("Logging telemetry to {}", self.output_path.display());
+        Ok(())
+    }
+
+    /// Flush and close the file. Does nothing when not logging.
+    pub fn stop(&mut self) -> std::io::Result<()> {
+        let Some(mut writer) = self.writer.take() else {
+            return Ok(());
+        };
+        writer.flush()?;
+        println!("Telemetry stopped after {} rows", self.rows_written);
+        Ok(())
+    }
+
+    /// Count a physics step and write a row when a sample is due.
+    /// A write error stops logging and is kept for the UI.
+    pub fn record_step(&mut self, simulation: &CpuSimulation, genome: &GenomeData) {
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+        if self.steps_until_sample > 0 {
+            self.steps_until_sample -= 1;
+            return;
+        }
+        self.steps_until_sample = self.interval_steps.max(1) - 1;
+
+        let result = match self.mode_columns {
+            Some(columns) => write_row(writer, &TelemetrySample::new(simulation, columns)),
+            None => {
+                let columns = genome.modes.len();
+                self.mode_columns = Some(columns);
+                write_header(writer, genome).and_then(|()| write_row(writer, &TelemetrySample::new(simulation, columns)))
+            }
+        };
+        match result {
+            Ok(()) => self.rows_written += 1,
+            Err(e) => {
+                eprintln!("Failed to write telemetry to {}: {}", self.output_path.display(), e);
+                self.writer = None;
+                self.last_error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+fn write_header(out: &mut impl Write, genome: &GenomeData) -> std::io::Result<()> {
+    write!(out, "time,cell_count,total_mass,average_mass")?;
+    for (index, mode) in genome.modes.iter().enumerate() {
+        write!(out, ",{}", csv_field(&format!("mode_{}_{}", index, mode.name)))?;
+    }
+    writeln!(out)
+}
+
+fn write_row(out: &mut impl Write, sample: &TelemetrySample) -> std::io::Result<()> {
+    write!(out, "{:.4},{},{:.4},{:.4}", sample.time, sample.cell_count, sample.total_mass, sample.average_mass())?;
+    for count in &sample.mode_counts {
+        write!(out, ",{}", count)?;
+    }
+    writeln!(out)
+}
+
+/// Quote a field if it contains a comma, quote or line break
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_and_rows_have_matching_columns() {
+        let mut genome = GenomeData::default();
+        genome.modes[0].name = "Stem, \"A\"".to_string();
+        let simulation = CpuSimulation::new(&genome);
+
+        let mut out = Vec::new();
+        write_header(&mut out, &genome).unwrap();
+        write_row(&mut out, &TelemetrySample::new(&simulation, genome.modes.len())).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "time,cell_count,total_mass,average_mass,\"mode_0_Stem, \"\"A\"\"\"");
+        assert_eq!(lines[1], "0.0000,1,1.0000,1.0000,1");
+    }
+}
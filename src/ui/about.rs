@@ -0,0 +1,73 @@
+use crate::app_info::{data_dir, CRASH_LOG_DIR, GIT_HASH, VERSION};
+use crate::genome::autosave::AUTOSAVE_DIR;
+use crate::rendering::frame_capture::SCREENSHOT_DIR;
+use imgui::{Condition, WindowFlags};
+
+/// One labelled path with a button copying it to the clipboard
+fn path_row(ui: &imgui::Ui, label: &str, path: &std::path::Path) {
+    let text = path.display().to_string();
+    ui.text(format!("{}:", label));
+    ui.same_line();
+    ui.text_disabled(&text);
+    ui.same_line();
+    if ui.small_button(format!("Copy##{}", label)) {
+        ui.set_clipboard_text(&text);
+    }
+}
+
+/// About dialog: build version, GPU adapter, and where settings, autosaves and crash logs live
+pub fn render_about_dialog(ui: &imgui::Ui, open: &mut bool, adapter_info: Option<&wgpu::AdapterInfo>) {
+    if !*open {
+        return;
+    }
+    
+    let display_size = ui.io().display_size;
+    ui.window("About BioSpheres")
+        .opened(open)
+        .position([display_size[0] * 0.5, display_size[1] * 0.4], Condition::Appearing)
+        .position_pivot([0.5, 0.5])
+        .flags(
+            WindowFlags::NO_COLLAPSE
+                | WindowFlags::ALWAYS_AUTO_RESIZE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_DOCKING,
+        )
+        .build(|| {
+            ui.text(format!("BioSpheres v{}", VERSION));
+            ui.text_disabled(format!("Commit {}", GIT_HASH));
+            
+            ui.separator();
+            ui.text("Graphics:");
+            match adapter_info {
+                Some(info) => {
+                    ui.text(format!("Adapter: {}", info.name));
+                    ui.text(format!("Backend: {:?}", info.backend));
+                    if !info.driver.is_empty() {
+                        ui.text(format!("Driver: {} {}", info.driver, info.driver_info));
+                    }
+                }
+                None => ui.text_disabled("Adapter information unavailable"),
+            }
+            
+            ui.separator();
+            ui.text("Files:");
+            let dir = data_dir();
+            path_row(ui, "Data", &dir);
+            path_row(ui, "Autosaves", &dir.join(AUTOSAVE_DIR));
+            path_row(ui, "Screenshots", &dir.join(SCREENSHOT_DIR));
+            path_row(ui, "Crash logs", &dir.join(CRASH_LOG_DIR));
+            ui.text_wrapped("If BioSpheres crashes, a report with a backtrace is written to the crash log folder. \
+                             Please attach it to your bug report.");
+            
+            ui.separator();
+            if ui.button("Copy Build Info") {
+                let adapter = adapter_info
+                    .map(|info| format!("{} ({:?})", info.name, info.backend))
+                    .unwrap_or_else(|| "unknown".to_string());
+                ui.set_clipboard_text(format!("BioSpheres v{} ({})\nAdapter: {}", VERSION, GIT_HASH, adapter));
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Copy the version, commit and adapter for a bug report");
+            }
+        });
+}
@@ -1 +1,621 @@
 // Camera control system
+use crate::simulation::cpu_sim::Ray;
+use glam::{Mat4, Vec3, Vec4};
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+
+/// How quickly the follow camera catches up to its target (higher = snappier)
+const FOLLOW_SHARPNESS: f32 = 12.0;
+
+/// Default vertical field of view in degrees
+const DEFAULT_FOV: f32 = 45.0;
+const DEFAULT_NEAR: f32 = 0.1;
+const DEFAULT_FAR: f32 = 1000.0;
+
+/// Orbit angle per pixel of mouse drag at a mouse sensitivity of 1
+const ORBIT_RADIANS_PER_PIXEL: f32 = 0.005;
+
+/// Distance multiplier per mouse wheel notch
+const ZOOM_STEP: f32 = 0.9;
+
+/// Orbit pitch limit; looking straight up or down would make the look-at up vector degenerate
+const MAX_PITCH: f32 = 89.0_f32.to_radians();
+
+/// Seconds taken to fly to a recalled bookmark
+const BOOKMARK_TRANSITION_DURATION: f32 = 0.6;
+
+/// Number of bookmark slots, bound to keys 1..9
+pub const CAMERA_BOOKMARK_SLOTS: usize = 9;
+
+/// Extra room left around the colony when framing it, as a multiple of its radius
+const FIT_MARGIN: f32 = 1.15;
+
+/// Pitch used for the top view
+const TOP_VIEW_PITCH: f32 = MAX_PITCH;
+
+/// Preset framings reachable from the View menu and hotkeys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraView {
+    /// Default orbit angle, zoomed to fit every live cell
+    Reset,
+    Front,
+    Side,
+    Top,
+}
+
+impl CameraView {
+    pub fn all() -> &'static [CameraView] {
+        &[CameraView::Reset, CameraView::Front, CameraView::Side, CameraView::Top]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CameraView::Reset => "Reset View",
+            CameraView::Front => "Front View",
+            CameraView::Side => "Side View",
+            CameraView::Top => "Top View",
+        }
+    }
+
+    pub fn shortcut(&self) -> &'static str {
+        match self {
+            CameraView::Reset => "Home",
+            CameraView::Front => "1",
+            CameraView::Side => "2",
+            CameraView::Top => "3",
+        }
+    }
+
+    fn key(&self) -> imgui::Key {
+        match self {
+            CameraView::Reset => imgui::Key::Home,
+            CameraView::Front => imgui::Key::Alpha1,
+            CameraView::Side => imgui::Key::Alpha2,
+            CameraView::Top => imgui::Key::Alpha3,
+        }
+    }
+}
+
+/// Smallest sphere centered on the bounding box of `spheres` that contains all of them
+pub fn bounding_sphere(spheres: impl IntoIterator<Item = (Vec3, f32)>) -> Option<(Vec3, f32)> {
+    let spheres: Vec<(Vec3, f32)> = spheres.into_iter().collect();
+    let (min, max) = spheres.iter().fold(None, |bounds: Option<(Vec3, Vec3)>, (center, radius)| {
+        let (lo, hi) = (*center - Vec3::splat(*radius), *center + Vec3::splat(*radius));
+        Some(match bounds {
+            Some((min, max)) => (min.min(lo), max.max(hi)),
+            None => (lo, hi),
+        })
+    })?;
+    let center = (min + max) * 0.5;
+    let radius = spheres
+        .iter()
+        .map(|(position, radius)| position.distance(center) + radius)
+        .fold(0.0, f32::max);
+    Some((center, radius))
+}
+
+/// Saved orbit camera viewpoint
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CameraBookmark {
+    pub target: [f32; 3],
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+/// In-progress flight from one viewpoint to another
+#[derive(Debug, Clone, PartialEq)]
+struct CameraTransition {
+    from: CameraBookmark,
+    to: CameraBookmark,
+    elapsed: f32,
+}
+
+/// `Vec3` as a plain `[x, y, z]` array in the settings file
+mod vec3_array {
+    use glam::Vec3;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Vec3, serializer: S) -> Result<S::Ok, S::Error> {
+        value.to_array().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec3, D::Error> {
+        <[f32; 3]>::deserialize(deserializer).map(Vec3::from_array)
+    }
+}
+
+/// Orbit camera that rotates around a target point.
+/// Pose, lens and control tuning are saved with the UI settings; a bookmark flight is not.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct OrbitCamera {
+    /// Point the camera orbits around and looks at
+    #[serde(with = "vec3_array")]
+    pub target: Vec3,
+    /// Distance from the target
+    pub distance: f32,
+    /// Horizontal orbit angle in radians
+    pub yaw: f32,
+    /// Vertical orbit angle in radians
+    pub pitch: f32,
+    /// Vertical field of view in degrees
+    pub fov: f32,
+    pub near: f32,
+    pub far: f32,
+    /// WASD/QE movement in world units per second
+    pub move_speed: f32,
+    /// Multiplier on mouse drag orbiting
+    pub mouse_sensitivity: f32,
+    #[serde(skip)]
+    transition: Option<CameraTransition>,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            target: Vec3::ZERO,
+            distance: 10.0,
+            yaw: 0.0,
+            pitch: 0.3,
+            fov: DEFAULT_FOV,
+            near: DEFAULT_NEAR,
+            far: DEFAULT_FAR,
+            move_speed: 10.0,
+            mouse_sensitivity: 1.0,
+            transition: None,
+        }
+    }
+}
+
+impl OrbitCamera {
+    /// World-space position of the camera eye
+    pub fn position(&self) -> Vec3 {
+        let offset = Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        );
+        self.target + offset * self.distance
+    }
+
+    /// Vertical field of view in radians
+    pub fn fov_y(&self) -> f32 {
+        self.fov.to_radians()
+    }
+
+    /// Unit forward, right and up vectors of the view
+    fn view_axes(&self) -> (Vec3, Vec3, Vec3) {
+        let forward = (self.target - self.position()).normalize_or(Vec3::NEG_Z);
+        let right = forward.cross(Vec3::Y).normalize_or(Vec3::X);
+        (forward, right, right.cross(forward))
+    }
+
+    /// Rotate around the target by a mouse drag in pixels, cancelling any bookmark flight
+    pub fn orbit(&mut self, drag: [f32; 2]) {
+        let step = ORBIT_RADIANS_PER_PIXEL * self.mouse_sensitivity;
+        self.yaw -= drag[0] * step;
+        self.pitch = (self.pitch + drag[1] * step).clamp(-MAX_PITCH, MAX_PITCH);
+        self.transition = None;
+    }
+
+    /// Slide the target across the view plane so the point under the cursor
+    /// follows a drag of `drag` pixels in a viewport `viewport_height` pixels tall
+    pub fn pan(&mut self, drag: [f32; 2], viewport_height: f32) {
+        let (_, right, up) = self.view_axes();
+        let units_per_pixel = 2.0 * self.distance * (self.fov_y() * 0.5).tan() / viewport_height.max(1.0);
+        self.target += (up * drag[1] - right * drag[0]) * units_per_pixel;
+        self.transition = None;
+    }
+
+    /// Move towards (positive) or away from the target by mouse wheel notches
+    pub fn zoom(&mut self, notches: f32) {
+        self.distance = (self.distance * ZOOM_STEP.powf(notches)).clamp(self.near * 2.0, self.far * 0.5);
+        self.transition = None;
+    }
+
+    /// Fly eye and target together. `direction` is in view space: x right, y up, z forward.
+    pub fn fly(&mut self, direction: Vec3, delta_time: f32) {
+        let (forward, right, _) = self.view_axes();
+        let world = right * direction.x + Vec3::Y * direction.y + forward * direction.z;
+        self.target += world.normalize_or_zero() * self.move_speed * delta_time.max(0.0);
+        self.transition = None;
+    }
+
+    /// Move the orbit target towards a point, keeping the current orbit angle and distance.
+    /// The motion is smoothed with an exponential filter so fast-moving targets don't jitter.
+    pub fn follow(&mut self, point: Vec3, delta_time: f32) {
+        let blend = 1.0 - (-FOLLOW_SHARPNESS * delta_time.max(0.0)).exp();
+        self.target = self.target.lerp(point, blend);
+    }
+
+    /// Current viewpoint as a bookmark
+    pub fn bookmark(&self) -> CameraBookmark {
+        CameraBookmark {
+            target: self.target.to_array(),
+            distance: self.distance,
+            yaw: self.yaw,
+            pitch: self.pitch,
+        }
+    }
+
+    /// Jump straight to a viewpoint, cancelling any bookmark flight
+    fn set_view(&mut self, bookmark: CameraBookmark) {
+        self.target = Vec3::from_array(bookmark.target);
+        self.distance = bookmark.distance;
+        self.yaw = bookmark.yaw;
+        self.pitch = bookmark.pitch;
+        self.transition = None;
+    }
+
+    /// Viewpoint at `yaw`/`pitch` that fits a sphere in view through this lens at the given aspect ratio
+    pub fn framing(&self, center: Vec3, radius: f32, yaw: f32, pitch: f32, aspect: f32) -> CameraBookmark {
+        let half_fov_y = self.fov_y() * 0.5;
+        let half_fov_x = (half_fov_y.tan() * aspect.max(0.01)).atan();
+        let half_fov = half_fov_y.min(half_fov_x);
+        let distance = (radius.max(0.1) * FIT_MARGIN / half_fov.sin()).clamp(self.near * 10.0, self.far * 0.5);
+        CameraBookmark {
+            target: center.to_array(),
+            distance,
+            yaw,
+            pitch,
+        }
+    }
+
+    /// Default orbit angle, zoomed out to fit a sphere of `radius` around the origin
+    pub fn frame_world(&mut self, radius: f32, aspect: f32) {
+        let default = OrbitCamera::default();
+        self.set_view(self.framing(Vec3::ZERO, radius, default.yaw, default.pitch, aspect));
+    }
+
+    /// Fly to a preset view. `colony_bounds` is the bounding sphere of the live cells,
+    /// used by `CameraView::Reset`; the axis snaps keep the current target and distance.
+    pub fn snap_to(&mut self, view: CameraView, colony_bounds: Option<(Vec3, f32)>, aspect: f32) {
+        let default = OrbitCamera::default();
+        let current = self.transition.as_ref().map(|transition| transition.to).unwrap_or_else(|| self.bookmark());
+        let bookmark = match view {
+            CameraView::Reset => match colony_bounds {
+                Some((center, radius)) => self.framing(center, radius, default.yaw, default.pitch, aspect),
+                None => default.bookmark(),
+            },
+            CameraView::Front => CameraBookmark { yaw: 0.0, pitch: 0.0, ..current },
+            CameraView::Side => CameraBookmark { yaw: std::f32::consts::FRAC_PI_2, pitch: 0.0, ..current },
+            CameraView::Top => CameraBookmark { yaw: 0.0, pitch: TOP_VIEW_PITCH, ..current },
+        };
+        self.fly_to(bookmark);
+    }
+
+    /// Start a smooth flight to a saved viewpoint
+    pub fn fly_to(&mut self, bookmark: CameraBookmark) {
+        self.transition = Some(CameraTransition {
+            from: self.bookmark(),
+            to: bookmark,
+            elapsed: 0.0,
+        });
+    }
+
+    /// Whether a bookmark flight is in progress
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Advance a bookmark flight, easing in and out and turning the short way round
+    pub fn update_transition(&mut self, delta_time: f32) {
+        let Some(transition) = &mut self.transition else {
+            return;
+        };
+        transition.elapsed += delta_time.max(0.0);
+        let t = (transition.elapsed / BOOKMARK_TRANSITION_DURATION).min(1.0);
+        let eased = t * t * (3.0 - 2.0 * t);
+        let (from, to) = (transition.from, transition.to);
+
+        let yaw_delta = (to.yaw - from.yaw + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU) - std::f32::consts::PI;
+        self.target = Vec3::from_array(from.target).lerp(Vec3::from_array(to.target), eased);
+        self.distance = from.distance + (to.distance - from.distance) * eased;
+        self.yaw = from.yaw + yaw_delta * eased;
+        self.pitch = from.pitch + (to.pitch - from.pitch) * eased;
+
+        if t >= 1.0 {
+            self.transition = None;
+        }
+    }
+
+    /// Ray from the eye through a point on screen in normalized device coordinates
+    /// (x right and y up, both -1..1)
+    pub fn screen_ray(&self, ndc: [f32; 2], aspect: f32) -> Ray {
+        let inverse = self.view_projection(aspect).inverse();
+        let unproject = |depth: f32| {
+            let point = inverse * Vec4::new(ndc[0], ndc[1], depth, 1.0);
+            point.truncate() / point.w
+        };
+        let direction = (unproject(1.0) - unproject(0.0)).normalize_or(Vec3::NEG_Z);
+        Ray { origin: self.position(), direction }
+    }
+
+    /// Combined view and perspective projection for the given aspect ratio
+    pub fn view_projection(&self, aspect: f32) -> Mat4 {
+        let view = Mat4::look_at_rh(self.position(), self.target, Vec3::Y);
+        let near = self.near.max(0.001);
+        let projection = Mat4::perspective_rh(self.fov_y(), aspect.max(0.01), near, self.far.max(near * 2.0));
+        projection * view
+    }
+}
+
+/// Ctrl+1..9 saves the current view into a slot, 1..9 flies to it.
+/// Ignored while imgui wants the keyboard, e.g. when typing into a field.
+pub fn handle_camera_bookmark_keys(
+    ui: &imgui::Ui,
+    camera: &mut OrbitCamera,
+    bookmarks: &mut [Option<CameraBookmark>; CAMERA_BOOKMARK_SLOTS],
+) {
+    if ui.io().want_capture_keyboard {
+        return;
+    }
+    const SLOT_KEYS: [imgui::Key; CAMERA_BOOKMARK_SLOTS] = [
+        imgui::Key::Keypad1,
+        imgui::Key::Keypad2,
+        imgui::Key::Keypad3,
+        imgui::Key::Keypad4,
+        imgui::Key::Keypad5,
+        imgui::Key::Keypad6,
+        imgui::Key::Keypad7,
+        imgui::Key::Keypad8,
+        imgui::Key::Keypad9,
+    ];
+    for (slot, key) in SLOT_KEYS.iter().enumerate() {
+        if !ui.is_key_pressed_no_repeat(*key) {
+            continue;
+        }
+        if ui.io().key_ctrl {
+            bookmarks[slot] = Some(camera.bookmark());
+        } else if let Some(bookmark) = bookmarks[slot] {
+            camera.fly_to(bookmark);
+        }
+    }
+}
+
+/// Home resets the view to fit the colony; 1, 2 and 3 snap to the front, side and top views.
+/// Ignored while imgui wants the keyboard.
+pub fn camera_view_key(ui: &imgui::Ui) -> Option<CameraView> {
+    if ui.io().want_capture_keyboard || ui.io().key_ctrl {
+        return None;
+    }
+    CameraView::all().iter().copied().find(|view| ui.is_key_pressed_no_repeat(view.key()))
+}
+
+/// What a mouse drag in the viewport does to the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraDrag {
+    /// Left button
+    Orbit,
+    /// Right or middle button
+    Pan,
+}
+
+impl CameraDrag {
+    fn from_button(button: MouseButton) -> Option<Self> {
+        match button {
+            MouseButton::Left => Some(CameraDrag::Orbit),
+            MouseButton::Right | MouseButton::Middle => Some(CameraDrag::Pan),
+            _ => None,
+        }
+    }
+}
+
+/// Pixels per scroll notch for touchpads that report pixel deltas
+const PIXELS_PER_SCROLL_NOTCH: f32 = 53.0;
+
+/// Left-button movement (pixels) below which a release counts as a click rather than an orbit
+const CLICK_DRAG_THRESHOLD: f32 = 4.0;
+
+/// Viewport mouse controls fed from window events: left-drag orbits, right- or middle-drag pans
+/// and the wheel zooms. Presses and scrolls over a panel are ignored, but a drag that started
+/// in the viewport keeps going when the cursor passes over one.
+/// A left press and release that barely moves is reported as a click instead.
+#[derive(Debug, Default)]
+pub struct ViewportCameraInput {
+    cursor: Option<[f32; 2]>,
+    drag: Option<CameraDrag>,
+    /// Where the current left press started, while it can still become a click
+    click_start: Option<[f32; 2]>,
+    /// Cursor position of a finished click, until taken
+    click: Option<[f32; 2]>,
+}
+
+impl ViewportCameraInput {
+    /// Feed a window event. `over_ui` is whether imgui claimed it. Returns whether the camera moved.
+    pub fn handle_event(&mut self, event: &WindowEvent, over_ui: bool, camera: &mut OrbitCamera, viewport_height: f32) -> bool {
+        match event {
+            WindowEvent::MouseInput { state, button, .. } => {
+                let Some(drag) = CameraDrag::from_button(*button) else {
+                    return false;
+                };
+                match state {
+                    ElementState::Pressed => self.press(drag, over_ui),
+                    ElementState::Released => self.release(drag),
+                }
+                false
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor_moved([position.x as f32, position.y as f32], camera, viewport_height)
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.cursor = None;
+                false
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let notches = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => *y,
+                    MouseScrollDelta::PixelDelta(position) => position.y as f32 / PIXELS_PER_SCROLL_NOTCH,
+                };
+                self.scroll(notches, over_ui, camera)
+            }
+            _ => false,
+        }
+    }
+
+    /// Start a drag, unless the press landed on a panel
+    pub fn press(&mut self, drag: CameraDrag, over_ui: bool) {
+        if !over_ui && self.drag.is_none() {
+            self.drag = Some(drag);
+            if drag == CameraDrag::Orbit {
+                self.click_start = self.cursor;
+            }
+        }
+    }
+
+    /// End the drag started by the same button; releases always count, even over a panel
+    pub fn release(&mut self, drag: CameraDrag) {
+        if self.drag == Some(drag) {
+            self.drag = None;
+            if drag == CameraDrag::Orbit {
+                self.click = self.click_start.take();
+            }
+        }
+    }
+
+    /// Cursor position of the last left click in the viewport, if it hasn't been handled yet
+    pub fn take_click(&mut self) -> Option<[f32; 2]> {
+        self.click.take()
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Orbit or pan by the cursor movement since the last event. Returns whether the camera moved.
+    pub fn cursor_moved(&mut self, position: [f32; 2], camera: &mut OrbitCamera, viewport_height: f32) -> bool {
+        let previous = self.cursor.replace(position);
+        if let Some(start) = self.click_start {
+            if (position[0] - start[0]).hypot(position[1] - start[1]) > CLICK_DRAG_THRESHOLD {
+                self.click_start = None;
+            }
+        }
+        let (Some(drag), Some(previous)) = (self.drag, previous) else {
+            return false;
+        };
+        let delta = [position[0] - previous[0], position[1] - previous[1]];
+        if delta == [0.0, 0.0] {
+            return false;
+        }
+        match drag {
+            CameraDrag::Orbit => camera.orbit(delta),
+            CameraDrag::Pan => camera.pan(delta, viewport_height),
+        }
+        true
+    }
+
+    /// Zoom by wheel notches, unless scrolling a panel. Returns whether the camera moved.
+    pub fn scroll(&mut self, notches: f32, over_ui: bool, camera: &mut OrbitCamera) -> bool {
+        if over_ui || notches == 0.0 {
+            return false;
+        }
+        camera.zoom(notches);
+        true
+    }
+}
+
+/// WASD/QE fly the viewport camera, ignored while imgui wants the keyboard
+pub fn handle_camera_keys(ui: &imgui::Ui, camera: &mut OrbitCamera) {
+    let io = ui.io();
+    if io.want_capture_keyboard || io.key_ctrl {
+        return;
+    }
+    let axis = |positive: imgui::Key, negative: imgui::Key| {
+        ui.is_key_down(positive) as i32 as f32 - ui.is_key_down(negative) as i32 as f32
+    };
+    let direction = Vec3::new(
+        axis(imgui::Key::D, imgui::Key::A),
+        axis(imgui::Key::E, imgui::Key::Q),
+        axis(imgui::Key::W, imgui::Key::S),
+    );
+    if direction != Vec3::ZERO {
+        camera.fly(direction, io.delta_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orbit_clamps_pitch_at_the_poles() {
+        let mut camera = OrbitCamera::default();
+        camera.orbit([0.0, 100_000.0]);
+        assert!(camera.pitch <= MAX_PITCH);
+        camera.orbit([0.0, -200_000.0]);
+        assert!(camera.pitch >= -MAX_PITCH);
+    }
+
+    #[test]
+    fn test_screen_ray_through_center_hits_target() {
+        let camera = OrbitCamera::default();
+        let ray = camera.screen_ray([0.0, 0.0], 16.0 / 9.0);
+        assert!((ray.origin - camera.position()).length() < 1e-4);
+        let to_target = (camera.target - camera.position()).normalize();
+        assert!(ray.direction.dot(to_target) > 0.9999);
+    }
+
+    #[test]
+    fn test_small_press_and_release_is_a_click() {
+        let mut camera = OrbitCamera::default();
+        let mut input = ViewportCameraInput::default();
+        input.cursor_moved([100.0, 100.0], &mut camera, 720.0);
+        input.press(CameraDrag::Orbit, false);
+        input.cursor_moved([101.0, 101.0], &mut camera, 720.0);
+        input.release(CameraDrag::Orbit);
+        assert_eq!(input.take_click(), Some([100.0, 100.0]));
+        assert_eq!(input.take_click(), None);
+
+        input.press(CameraDrag::Orbit, false);
+        input.cursor_moved([140.0, 101.0], &mut camera, 720.0);
+        input.release(CameraDrag::Orbit);
+        assert_eq!(input.take_click(), None);
+    }
+
+    #[test]
+    fn test_drag_orbits_and_keeps_distance() {
+        let mut camera = OrbitCamera::default();
+        let distance = camera.distance;
+        let mut input = ViewportCameraInput::default();
+        input.cursor_moved([100.0, 100.0], &mut camera, 720.0);
+        input.press(CameraDrag::Orbit, false);
+        assert!(input.cursor_moved([140.0, 110.0], &mut camera, 720.0));
+        assert_ne!(camera, OrbitCamera::default());
+        assert_eq!(camera.distance, distance);
+        input.release(CameraDrag::Orbit);
+        assert!(!input.cursor_moved([200.0, 200.0], &mut camera, 720.0));
+    }
+
+    #[test]
+    fn test_input_over_panel_is_ignored() {
+        let mut camera = OrbitCamera::default();
+        let mut input = ViewportCameraInput::default();
+        input.cursor_moved([100.0, 100.0], &mut camera, 720.0);
+        input.press(CameraDrag::Orbit, true);
+        assert!(!input.is_dragging());
+        assert!(!input.cursor_moved([150.0, 100.0], &mut camera, 720.0));
+        assert!(!input.scroll(1.0, true, &mut camera));
+        assert_eq!(camera, OrbitCamera::default());
+
+        // A drag that started in the viewport keeps going over a panel
+        input.press(CameraDrag::Pan, false);
+        assert!(input.cursor_moved([120.0, 90.0], &mut camera, 720.0));
+        input.release(CameraDrag::Orbit);
+        assert!(input.is_dragging());
+        input.release(CameraDrag::Pan);
+        assert!(!input.is_dragging());
+    }
+
+    #[test]
+    fn test_saved_camera_keeps_pose_and_lens_but_not_flight() {
+        let mut camera = OrbitCamera { fov: 60.0, move_speed: 25.0, ..OrbitCamera::default() };
+        camera.pan([30.0, -12.0], 720.0);
+        camera.zoom(2.0);
+        camera.fly_to(OrbitCamera::default().bookmark());
+
+        let json = serde_json::to_string(&camera).unwrap();
+        let restored: OrbitCamera = serde_json::from_str(&json).unwrap();
+        assert!(!restored.is_transitioning());
+        assert_eq!(restored, OrbitCamera { transition: None, ..camera });
+    }
+}
@@ -1,56 +1,57 @@
-use crate::genome::{CurrentGenome, Vec3, Quat};
+use crate::genome::GenomeData;
+use crate::simulation::cell_allocation::CellId;
+use crate::simulation::cpu_sim::{AdhesionPartner, CpuSimulation};
+use crate::simulation::nutrient_system;
+use super::number_format::NumberFormat;
 use imgui::{Condition, WindowFlags};
 
-/// Mock cell data for display purposes
-#[derive(Debug, Clone)]
-pub struct MockCellData {
-    pub cell_id: u32,
-    pub position: Vec3,
-    pub velocity: Vec3,
-    pub rotation: Quat,
-    pub angular_velocity: Vec3,
-    pub mass: f32,
-    pub radius: f32,
-    pub mode_index: usize,
-    pub birth_time: f32,
-    pub split_interval: f32,
-    pub split_mass: f32,
-    pub split_count: i32,
-    pub adhesion_count: usize,
-}
-
-impl Default for MockCellData {
-    fn default() -> Self {
-        Self {
-            cell_id: 12345,
-            position: Vec3::new(1.23, -0.45, 2.67),
-            velocity: Vec3::new(0.12, 0.03, -0.08),
-            rotation: Quat { x: 0.1, y: 0.2, z: 0.3, w: 0.9 },
-            angular_velocity: Vec3::new(0.05, -0.02, 0.01),
-            mass: 1.85,
-            radius: 0.92,
-            mode_index: 0,
-            birth_time: 5.2,
-            split_interval: 12.5,
-            split_mass: 2.0,
-            split_count: 3,
-            adhesion_count: 2,
-        }
-    }
-}
+/// Share of the break force above which a link is shown as strained
+const STRAINED_BREAK_LOAD: f32 = 0.8;
+/// Strain (relative stretch or compression) above which an unbreakable link is highlighted
+const HIGH_STRAIN: f32 = 0.25;
 
 /// Cell inspector state
+#[derive(Default)]
 pub struct CellInspectorState {
-    pub selected_cell: Option<MockCellData>,
-    pub simulation_time: f32,
+    /// Cell being inspected; cleared once it dies or its colony is replaced
+    pub selected_cell: Option<CellId>,
+    /// Lock the orbit camera onto the selected cell
+    pub follow_selected: bool,
+    /// Mode the user asked to open in the Genome Editor, consumed by the scene
+    pub edit_mode_requested: Option<usize>,
+    /// Adhesion partners of the selected cell, refreshed from the simulation
+    pub connections: Vec<AdhesionPartner>,
+    /// Neighbour the user asked to jump to, applied on the next sync
+    pub select_cell_requested: Option<u32>,
 }
 
-impl Default for CellInspectorState {
-    fn default() -> Self {
-        Self {
-            selected_cell: Some(MockCellData::default()),
-            simulation_time: 17.7,
+impl CellInspectorState {
+    /// Apply a pending neighbour jump, then refresh the connections of the selected cell.
+    /// A cell that died or belongs to a replaced colony is deselected.
+    pub fn sync_with_simulation(&mut self, simulation: &CpuSimulation, genome: &GenomeData) {
+        if let Some(id) = self.select_cell_requested.take() {
+            if simulation.cell_by_id(id).is_some() {
+                self.selected_cell = Some(simulation.cell_id(id));
+            }
         }
+
+        let Some(selected) = self.selected_cell else {
+            self.connections.clear();
+            return;
+        };
+        if simulation.cell_view(genome, selected).is_none() {
+            // Never show a different cell under the old id
+            self.clear_selection();
+            return;
+        }
+        self.connections = simulation.connections_for(genome, selected.id);
+    }
+
+    /// Deselect, which also returns the camera to free movement
+    pub fn clear_selection(&mut self) {
+        self.selected_cell = None;
+        self.follow_selected = false;
+        self.connections.clear();
     }
 }
 
@@ -58,8 +59,10 @@ impl Default for CellInspectorState {
 pub fn render_cell_inspector_window(
     ui: &imgui::Ui,
     inspector_state: &mut CellInspectorState,
-    genome: &CurrentGenome,
+    simulation: &CpuSimulation,
+    genome: &GenomeData,
     global_ui_state: &super::GlobalUiState,
+    min_cell_mass: f32,
 ) {
     // Only show if visibility is enabled
     if !global_ui_state.show_cell_inspector {
@@ -79,63 +82,87 @@ pub fn render_cell_inspector_window(
         .collapsible(true)
         .flags(flags)
         .build(|| {
-            render_cell_inspector_content(ui, inspector_state, genome);
+            render_cell_inspector_content(ui, inspector_state, simulation, genome, global_ui_state.number_format(), min_cell_mass);
         });
 }
 
-/// Render just the content of the Cell Inspector window (without the window wrapper)
+/// Render just the content of the Cell Inspector window (without the window wrapper).
+/// `genome` is the genome the simulation runs, which may differ from the one being edited.
 pub fn render_cell_inspector_content(
     ui: &imgui::Ui,
     inspector_state: &mut CellInspectorState,
-    genome: &CurrentGenome,
+    simulation: &CpuSimulation,
+    genome: &GenomeData,
+    number_format: NumberFormat,
+    min_cell_mass: f32,
 ) {
-    // Check if we have a cell selected
-    if inspector_state.selected_cell.is_none() {
+    let fmt = |value: f32| number_format.format(value);
+    
+    // Check if we have a live cell selected
+    let view = inspector_state.selected_cell.and_then(|id| simulation.cell_view(genome, id));
+    let Some(view) = view else {
+        inspector_state.clear_selection();
+        
         ui.text("Click on a cell to inspect it");
         ui.text("(or drag a cell)");
-        
-        ui.spacing();
-        ui.separator();
-        ui.spacing();
-        
-        // Button to select mock cell for demo
-        if ui.button("Select Demo Cell") {
-            inspector_state.selected_cell = Some(MockCellData::default());
-        }
         return;
-    }
-    
-    let data = inspector_state.selected_cell.as_ref().unwrap();
-    
-    // Get mode settings from genome
-    let mode = genome.genome.modes.get(data.mode_index);
-    let mode_name = mode.map(|m| m.name.as_str()).unwrap_or("Unknown");
-    let cell_type_name = mode.map(|m| get_cell_type_name(m.cell_type)).unwrap_or("Unknown");
+    };
+    let cell = view.cell;
+    let mode = view.mode;
+    let cell_type_name = get_cell_type_name(mode.cell_type);
     
     // Calculate time alive
-    let time_alive = inspector_state.simulation_time - data.birth_time;
+    let time_alive = cell.age;
     
     // === Cell Identity (always visible) ===
-    ui.text(format!("Cell Index: {}", 0));
-    ui.text(format!("Cell ID: {}", data.cell_id));
-    ui.text(format!("Mode: {} ({})", mode_name, data.mode_index));
+    ui.text(format!("Cell Index: {}", view.index));
+    ui.text(format!("Cell ID: {}", view.id.id));
+    ui.text("Mode:");
+    ui.same_line();
+    let swatch_size = ui.text_line_height();
+    ui.color_button_config("##mode_color", [mode.color.x, mode.color.y, mode.color.z, 1.0])
+        .size([swatch_size, swatch_size])
+        .build();
+    ui.same_line();
+    ui.text(format!("{} ({})", mode.name, cell.mode_index));
+    if mode.terminal {
+        ui.same_line();
+        ui.text_colored([0.6, 0.6, 1.0, 1.0], "[TERMINAL]");
+        if ui.is_item_hovered() {
+            ui.tooltip_text("This mode never splits or moves");
+        }
+    }
     ui.text(format!("Type: {}", cell_type_name));
     
+    ui.checkbox("Follow", &mut inspector_state.follow_selected);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Lock the camera onto this cell, keeping the current orbit angle and distance");
+    }
+    
+    ui.same_line();
+    if ui.button("Edit Mode") {
+        inspector_state.edit_mode_requested = Some(cell.mode_index);
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Open this cell's mode in the Genome Editor");
+    }
+    
     ui.separator();
     
     // === Key Stats (always visible) ===
     // Mass with visual bar
-    const MIN_CELL_MASS: f32 = 0.5;
-    let split_mass = data.split_mass;
+    let split_mass = mode.split_mass;
     let max_display_mass = split_mass * 2.0;
-    let mass_ratio = ((data.mass - MIN_CELL_MASS) / (max_display_mass - MIN_CELL_MASS)).clamp(0.0, 1.0);
+    let mass_ratio = ((cell.mass - min_cell_mass) / (max_display_mass - min_cell_mass)).clamp(0.0, 1.0);
     let bar_width = 16;
     let filled = (mass_ratio * bar_width as f32) as usize;
     let bar_str = format!("[{}{}]", "#".repeat(filled), "-".repeat(bar_width - filled));
     
-    // Color based on mass relative to split threshold
-    let split_ratio = (data.mass / split_mass).clamp(0.0, 2.0);
-    let bar_color = if split_ratio >= 1.0 {
+    // Color based on mass relative to split threshold; a split also needs both children above the minimum mass
+    let split_ratio = (cell.mass / split_mass).clamp(0.0, 2.0);
+    let smaller_child_fraction = mode.split_ratio.min(1.0 - mode.split_ratio);
+    let children_viable = cell.mass * smaller_child_fraction >= min_cell_mass;
+    let bar_color = if split_ratio >= 1.0 && children_viable {
         [0.0, 1.0, 0.0, 1.0] // Green - ready to split
     } else if split_ratio >= 0.5 {
         [1.0, 1.0, 0.0, 1.0] // Yellow - growing
@@ -145,12 +172,12 @@ pub fn render_cell_inspector_content(
     
     ui.text("Mass:");
     ui.same_line();
-    ui.text_colored(bar_color, format!("{:.2}", data.mass));
+    ui.text_colored(bar_color, fmt(cell.mass));
     ui.same_line();
     ui.text_colored(bar_color, bar_str);
     
-    ui.text(format!("Time Alive: {:.2}s", time_alive));
-    ui.text(format!("Split Count: {}", data.split_count));
+    ui.text(format!("Time Alive: {}s", fmt(time_alive)));
+    ui.text(format!("Split Count: {}", cell.split_count));
     
     ui.separator();
     
@@ -160,70 +187,87 @@ pub fn render_cell_inspector_content(
         
         // Nutrient Storage (Mass)
         let storage_cap = split_mass * 2.0;
-        let stored_nutrients = (data.mass - MIN_CELL_MASS).max(0.0);
-        let storage_percent = (stored_nutrients / (storage_cap - MIN_CELL_MASS) * 100.0).min(100.0);
+        let stored_nutrients = (cell.mass - min_cell_mass).max(0.0);
+        let storage_percent = (stored_nutrients / (storage_cap - min_cell_mass) * 100.0).min(100.0);
         
         ui.text("Nutrient Storage:");
         ui.same_line();
-        let storage_color = if data.mass >= storage_cap {
+        let storage_color = if cell.mass >= storage_cap {
             [0.0, 1.0, 0.0, 1.0] // Green - at cap
-        } else if data.mass >= split_mass {
+        } else if cell.mass >= split_mass {
             [0.5, 1.0, 0.0, 1.0] // Light green - ready to split
-        } else if data.mass >= MIN_CELL_MASS + (storage_cap - MIN_CELL_MASS) * 0.5 {
+        } else if cell.mass >= min_cell_mass + (storage_cap - min_cell_mass) * 0.5 {
             [1.0, 1.0, 0.0, 1.0] // Yellow - half full
-        } else if data.mass > MIN_CELL_MASS {
+        } else if cell.mass > min_cell_mass {
             [1.0, 0.5, 0.0, 1.0] // Orange - low
         } else {
             [1.0, 0.0, 0.0, 1.0] // Red - depleted
         };
-        ui.text_colored(storage_color, format!("{:.2}/{:.2} ({:.0}%)", stored_nutrients, storage_cap - MIN_CELL_MASS, storage_percent));
+        ui.text_colored(storage_color, format!("{}/{} ({:.0}%)", fmt(stored_nutrients), fmt(storage_cap - min_cell_mass), storage_percent));
+        
+        let until_split = nutrient_system::stored_fraction(cell.mass, min_cell_mass, split_mass);
+        imgui::ProgressBar::new(until_split)
+            .overlay_text(format!("{:.0}% to split", until_split * 100.0))
+            .build(ui);
+        
+        let concentration = nutrient_system::sample(cell.position);
+        ui.text(format!("Local Nutrients: {}", fmt(concentration)));
+        if ui.is_item_hovered() {
+            ui.tooltip_text(format!(
+                "Nutrient concentration of the medium at this cell, relative to the ambient level ({}). Nutrient gain scales with it.",
+                fmt(nutrient_system::AMBIENT_CONCENTRATION)
+            ));
+        }
         
         ui.spacing();
-        ui.text(format!("Current Mass: {:.3}", data.mass));
-        ui.text(format!("Split Mass: {:.2}", split_mass));
-        ui.text(format!("Storage Cap: {:.2}", storage_cap));
-        ui.text(format!("Minimum Mass: {:.2}", MIN_CELL_MASS));
-        ui.text(format!("Radius: {:.3}", data.radius));
+        ui.text(format!("Current Mass: {}", fmt(cell.mass)));
+        ui.text(format!("Split Mass: {}", fmt(split_mass)));
+        ui.text(format!("Storage Cap: {}", fmt(storage_cap)));
+        ui.text(format!("Minimum Mass: {}", fmt(min_cell_mass)));
+        ui.text(format!("Radius: {}", fmt(cell.radius())));
         
-        if let Some(mode) = mode {
-            ui.spacing();
-            if mode.cell_type == 0 {
-                ui.text(format!("Gain Rate: {:.2}/s", mode.nutrient_gain_rate));
-            } else if mode.cell_type == 1 {
-                ui.text(format!("Swim Force: {:.2}", mode.swim_force));
-                ui.text(format!("Consumption: {:.3}/s", mode.swim_force * 0.2));
-            }
-            ui.text(format!("Max Size: {:.2}", mode.max_cell_size));
-            
-            // Show base priority and boosted priority if applicable
-            let danger_threshold = 0.6;
-            let priority_boost = 10.0;
-            let is_boosted = mode.prioritize_when_low && data.mass < danger_threshold;
-            let effective_priority = if is_boosted {
-                mode.nutrient_priority * priority_boost
-            } else {
-                mode.nutrient_priority
-            };
-            
-            ui.text(format!("Base Priority: {:.2}", mode.nutrient_priority));
+        ui.spacing();
+        if mode.cell_type == 0 {
+            ui.text(format!("Gain Rate: {}/s", fmt(mode.passive_gain_rate_at(concentration))));
+        } else if mode.cell_type == 1 {
+            ui.text(format!("Swim Force: {}", fmt(mode.swim_force)));
+            ui.text(format!("Consumption: {}/s", fmt(mode.swim_force * 0.2)));
+        } else if mode.cell_type == 2 {
+            ui.text(format!("Gain Rate: {}/s", fmt(mode.passive_gain_rate_at(concentration))));
+            ui.text(format!("Light Absorption: {}/s", fmt(mode.light_absorption_rate)));
+        } else if mode.cell_type == 3 {
+            ui.text(format!("Digestion Rate: {}/s", fmt(mode.digestion_rate)));
+        }
+        ui.text(format!("Max Size: {}", fmt(mode.max_cell_size)));
+        
+        // Show base priority and boosted priority if applicable
+        let danger_threshold = 0.6;
+        let priority_boost = 10.0;
+        let is_boosted = mode.prioritize_when_low && cell.mass < danger_threshold;
+        let effective_priority = if is_boosted {
+            mode.nutrient_priority * priority_boost
+        } else {
+            mode.nutrient_priority
+        };
+        
+        ui.text(format!("Base Priority: {}", fmt(mode.nutrient_priority)));
+        if is_boosted {
+            ui.same_line();
+            ui.text_colored([1.0, 0.0, 0.0, 1.0], format!("→ {:.1} (BOOSTED!)", effective_priority));
+        }
+        
+        ui.text(format!("Protect Low: {}", if mode.prioritize_when_low { "Yes" } else { "No" }));
+        if mode.prioritize_when_low {
+            ui.same_line();
             if is_boosted {
-                ui.same_line();
-                ui.text_colored([1.0, 0.0, 0.0, 1.0], format!("→ {:.1} (BOOSTED!)", effective_priority));
-            }
-            
-            ui.text(format!("Protect Low: {}", if mode.prioritize_when_low { "Yes" } else { "No" }));
-            if mode.prioritize_when_low {
-                ui.same_line();
-                if is_boosted {
-                    ui.text_colored([1.0, 0.0, 0.0, 1.0], "(ACTIVE)");
-                } else {
-                    ui.text_colored([0.5, 0.5, 0.5, 1.0], "(inactive)");
-                }
+                ui.text_colored([1.0, 0.0, 0.0, 1.0], "(ACTIVE)");
+            } else {
+                ui.text_colored([0.5, 0.5, 0.5, 1.0], "(inactive)");
             }
-            
-            ui.text(format!("Split Ratio: {:.0}%", mode.split_ratio * 100.0));
         }
         
+        ui.text(format!("Split Ratio: {:.0}%", mode.split_ratio * 100.0));
+        
         ui.unindent();
     }
     
@@ -231,11 +275,11 @@ pub fn render_cell_inspector_content(
     if ui.collapsing_header("Position & Motion", imgui::TreeNodeFlags::DEFAULT_OPEN) {
         ui.indent();
         
-        ui.text(format!("Position: ({:.2}, {:.2}, {:.2})", 
-            data.position.x, data.position.y, data.position.z));
-        ui.text(format!("Velocity: ({:.2}, {:.2}, {:.2})", 
-            data.velocity.x, data.velocity.y, data.velocity.z));
-        ui.text(format!("Speed: {:.3}", (data.velocity.x.powi(2) + data.velocity.y.powi(2) + data.velocity.z.powi(2)).sqrt()));
+        ui.text(format!("Position: ({}, {}, {})", 
+            fmt(cell.position.x), fmt(cell.position.y), fmt(cell.position.z)));
+        ui.text(format!("Velocity: ({}, {}, {})", 
+            fmt(cell.velocity.x), fmt(cell.velocity.y), fmt(cell.velocity.z)));
+        ui.text(format!("Speed: {}", fmt(cell.velocity.length())));
         
         ui.unindent();
     }
@@ -244,11 +288,9 @@ pub fn render_cell_inspector_content(
     if ui.collapsing_header("Rotation", imgui::TreeNodeFlags::empty()) {
         ui.indent();
         
-        let euler = quat_to_euler_degrees(data.rotation);
+        let (x, y, z) = cell.rotation.to_euler(glam::EulerRot::XYZ);
         ui.text(format!("Rotation (deg): ({:.1}, {:.1}, {:.1})", 
-            euler.x, euler.y, euler.z));
-        ui.text(format!("Angular Vel: ({:.2}, {:.2}, {:.2})", 
-            data.angular_velocity.x, data.angular_velocity.y, data.angular_velocity.z));
+            x.to_degrees(), y.to_degrees(), z.to_degrees()));
         
         ui.unindent();
     }
@@ -257,102 +299,111 @@ pub fn render_cell_inspector_content(
     if ui.collapsing_header("Division", imgui::TreeNodeFlags::empty()) {
         ui.indent();
         
-        ui.text(format!("Birth Time: {:.2}s", data.birth_time));
-        ui.text(format!("Time Alive: {:.2}s", time_alive));
-        ui.text(format!("Split Interval: {:.2}s", data.split_interval));
+        ui.text(format!("Birth Time: {}s", fmt(view.birth_time)));
+        ui.text(format!("Time Alive: {}s", fmt(time_alive)));
+        ui.text(format!("Split Interval: {}s", fmt(mode.split_interval)));
         
         // Time until next split
-        let time_until_split = (data.split_interval - time_alive).max(0.0);
-        if time_until_split > 0.0 {
-            ui.text(format!("Next Split In: {:.2}s", time_until_split));
+        let time_until_split = (mode.split_interval - time_alive).max(0.0);
+        if mode.terminal {
+            ui.text_colored([0.6, 0.6, 1.0, 1.0], "Terminal: never splits");
+        } else if time_until_split > 0.0 {
+            ui.text(format!("Next Split In: {}s", fmt(time_until_split)));
+        } else if !children_viable {
+            ui.text_colored([1.0, 0.5, 0.0, 1.0], "Too light: children would be below minimum mass");
         } else {
             ui.text_colored([0.0, 1.0, 0.0, 1.0], "Ready to split!");
         }
         
-        ui.text(format!("Split Count: {}", data.split_count));
+        ui.text(format!("Split Count: {}", cell.split_count));
         
-        if let Some(mode) = mode {
-            if mode.max_splits >= 0 {
-                ui.text(format!("Max Splits: {}", mode.max_splits));
-                let remaining = (mode.max_splits - data.split_count).max(0);
-                ui.text(format!("Remaining: {}", remaining));
-            } else {
-                ui.text("Max Splits: Infinite");
-            }
-            ui.text(format!("Min Adhesions: {}", mode.min_adhesions));
+        if mode.max_splits >= 0 {
+            ui.text(format!("Max Splits: {}", mode.max_splits));
+            let remaining = (mode.max_splits - cell.split_count).max(0);
+            ui.text(format!("Remaining: {}", remaining));
+        } else {
+            ui.text("Max Splits: Infinite");
         }
+        ui.text(format!("Min Adhesions: {}", mode.min_adhesions));
         
         ui.unindent();
     }
     
     // === Adhesions ===
-    if ui.collapsing_header("Adhesions", imgui::TreeNodeFlags::empty()) {
+    if ui.collapsing_header("Adhesions", imgui::TreeNodeFlags::DEFAULT_OPEN) {
         ui.indent();
         
-        ui.text(format!("Adhesion Count: {}", data.adhesion_count));
-        if let Some(mode) = mode {
-            ui.text(format!("Max Adhesions: {}", mode.max_adhesions));
+        ui.text(format!("Adhesion Count: {} / {}", view.adhesion_count, mode.max_adhesions));
+        
+        if inspector_state.connections.is_empty() {
+            ui.text_disabled("No connections");
+        }
+        for partner in &inspector_state.connections {
+            let partner_mode = genome.modes.get(partner.mode_index).map(|m| m.name.as_str()).unwrap_or("Unknown");
+            if ui.small_button(format!("Select##neighbor_{}", partner.cell_id)) {
+                inspector_state.select_cell_requested = Some(partner.cell_id);
+            }
+            ui.same_line();
+            ui.text(format!("#{} {}", partner.cell_id, partner_mode));
+            
+            let break_load = partner.break_load();
+            let length_text = format!(
+                "Length: {} / {} rest ({:+.0}%)",
+                fmt(partner.distance),
+                fmt(partner.rest_length),
+                partner.strain * 100.0
+            );
+            let force_text = match (partner.break_force, break_load) {
+                (Some(break_force), Some(load)) => format!("Force: {} / {} break ({:.0}%)", fmt(partner.force), fmt(break_force), load * 100.0),
+                _ => format!("Force: {} (unbreakable)", fmt(partner.force)),
+            };
+            if break_load.is_some_and(|load| load > STRAINED_BREAK_LOAD) {
+                ui.text_colored([1.0, 0.3, 0.3, 1.0], length_text);
+                ui.text_colored([1.0, 0.3, 0.3, 1.0], force_text);
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Close to the break force; this link may snap soon");
+                }
+            } else if partner.strain.abs() > HIGH_STRAIN {
+                ui.text_colored([1.0, 0.5, 0.0, 1.0], length_text);
+                ui.text_disabled(force_text);
+            } else {
+                ui.text_disabled(length_text);
+                ui.text_disabled(force_text);
+            }
         }
         
         ui.unindent();
     }
     
     // === Flagellocyte-specific ===
-    if let Some(mode) = mode {
-        if mode.cell_type == 1 {
-            if ui.collapsing_header("Flagellocyte", imgui::TreeNodeFlags::DEFAULT_OPEN) {
-                ui.indent();
-                ui.text(format!("Swim Force: {:.2}", mode.swim_force));
-                ui.unindent();
-            }
+    if mode.cell_type == 1 {
+        if ui.collapsing_header("Flagellocyte", imgui::TreeNodeFlags::DEFAULT_OPEN) {
+            ui.indent();
+            ui.text(format!("Swim Force: {}", fmt(mode.swim_force)));
+            ui.unindent();
         }
     }
     
+    // === Photocyte-specific ===
+    if mode.cell_type == 2 && ui.collapsing_header("Photocyte", imgui::TreeNodeFlags::DEFAULT_OPEN) {
+        ui.indent();
+        ui.text(format!("Light Absorption Rate: {}/s", fmt(mode.light_absorption_rate)));
+        ui.unindent();
+    }
+    
+    // === Phagocyte-specific ===
+    if mode.cell_type == 3 && ui.collapsing_header("Phagocyte", imgui::TreeNodeFlags::DEFAULT_OPEN) {
+        ui.indent();
+        ui.text(format!("Engulf Radius: {}", fmt(mode.engulf_radius)));
+        ui.text(format!("Digestion Rate: {}/s", fmt(mode.digestion_rate)));
+        ui.unindent();
+    }
+    
     ui.separator();
     
     // Clear selection button
     if ui.button("Clear Selection") {
-        inspector_state.selected_cell = None;
-    }
-    
-    // Demo controls
-    ui.same_line();
-    if ui.button("Random Cell") {
-        // Generate random cell data for demo
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        
-        let mut hasher = DefaultHasher::new();
-        inspector_state.simulation_time.to_bits().hash(&mut hasher);
-        let seed = hasher.finish();
-        
-        let random_f32 = |min: f32, max: f32, offset: u64| -> f32 {
-            let mut h = DefaultHasher::new();
-            (seed + offset).hash(&mut h);
-            let r = (h.finish() % 1000) as f32 / 1000.0;
-            min + r * (max - min)
-        };
-        
-        inspector_state.selected_cell = Some(MockCellData {
-            cell_id: (seed % 99999) as u32,
-            position: Vec3::new(
-                random_f32(-5.0, 5.0, 1),
-                random_f32(-5.0, 5.0, 2),
-                random_f32(-5.0, 5.0, 3),
-            ),
-            velocity: Vec3::new(
-                random_f32(-0.5, 0.5, 4),
-                random_f32(-0.5, 0.5, 5),
-                random_f32(-0.5, 0.5, 6),
-            ),
-            mass: random_f32(0.8, 3.5, 7),
-            radius: random_f32(0.6, 1.2, 8),
-            mode_index: (seed % genome.genome.modes.len() as u64) as usize,
-            birth_time: random_f32(0.0, inspector_state.simulation_time - 1.0, 9),
-            split_count: (seed % 8) as i32,
-            adhesion_count: (seed % 6) as usize,
-            ..MockCellData::default()
-        });
+        inspector_state.clear_selection();
     }
 }
 
@@ -366,13 +417,3 @@ fn get_cell_type_name(cell_type: i32) -> &'static str {
         _ => "Unknown",
     }
 }
-
-/// Convert quaternion to euler angles in degrees
-fn quat_to_euler_degrees(quat: Quat) -> Vec3 {
-    // Simple conversion - this is just for display purposes
-    let x = (2.0 * (quat.w * quat.x + quat.y * quat.z)).atan2(1.0 - 2.0 * (quat.x * quat.x + quat.y * quat.y)).to_degrees();
-    let y = (2.0 * (quat.w * quat.y - quat.z * quat.x)).clamp(-1.0, 1.0).asin().to_degrees();
-    let z = (2.0 * (quat.w * quat.z + quat.x * quat.y)).atan2(1.0 - 2.0 * (quat.y * quat.y + quat.z * quat.z)).to_degrees();
-    
-    Vec3::new(x, y, z)
-}
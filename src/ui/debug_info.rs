@@ -1 +1,40 @@
 // Debug information display
+use imgui::{Condition, WindowFlags};
+use super::performance_monitor::PerformanceMonitor;
+use crate::simulation::SimulationStats;
+
+/// Distance from the viewport edge to the overlay
+const OVERLAY_MARGIN: f32 = 10.0;
+
+/// Draw the top-left overlay of frame and colony counters. It is pinned under the menu bar,
+/// sits behind other windows and lets clicks through to the viewport.
+/// Returns the height it takes up below the menu bar, so other top-left overlays can stack under it.
+pub fn render_debug_overlay(ui: &imgui::Ui, performance_monitor: &PerformanceMonitor, stats: &SimulationStats) -> f32 {
+    let menu_bar_height = ui.frame_height();
+    let mut height = 0.0;
+
+    ui.window("##debug_overlay")
+        .position([OVERLAY_MARGIN, menu_bar_height + OVERLAY_MARGIN], Condition::Always)
+        .bg_alpha(0.35)
+        .flags(
+            WindowFlags::NO_DECORATION
+                | WindowFlags::ALWAYS_AUTO_RESIZE
+                | WindowFlags::NO_MOVE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_FOCUS_ON_APPEARING
+                | WindowFlags::NO_BRING_TO_FRONT_ON_FOCUS
+                | WindowFlags::NO_NAV
+                | WindowFlags::NO_INPUTS
+                | WindowFlags::NO_DOCKING,
+        )
+        .build(|| {
+            ui.text(format!("Frame:     {:.2} ms", performance_monitor.display_frame_time));
+            ui.text(format!("FPS:       {:.0}", performance_monitor.display_fps));
+            ui.text(format!("Cells:     {} / {}", stats.live_cell_count, stats.max_capacity));
+            ui.text(format!("Adhesions: {}", stats.adhesion_count));
+            ui.text(format!("Sim Time:  {:.2}s{}", stats.sim_time, if stats.paused { " (paused)" } else { "" }));
+            ui.text(format!("Mode:      {}", stats.mode.name()));
+            height = ui.window_size()[1] + OVERLAY_MARGIN;
+        });
+    height
+}
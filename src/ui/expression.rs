@@ -0,0 +1,138 @@
+//! Tiny arithmetic evaluator for numeric text inputs: `+ - * /`, parentheses,
+//! unary signs and decimal or scientific literals such as `2*1.5` or `1e-3 + 0.5`.
+
+/// Evaluate `input`, or `None` if it is not a well-formed expression
+pub fn evaluate(input: &str) -> Option<f64> {
+    let mut parser = Parser { chars: input.chars().collect(), pos: 0 };
+    let value = parser.expression()?;
+    parser.skip_whitespace();
+    (parser.pos == parser.chars.len()).then_some(value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `c` if it is the next token
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// term := factor (('*' | '/') factor)*
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        loop {
+            if self.eat('*') {
+                value *= self.factor()?;
+            } else if self.eat('/') {
+                value /= self.factor()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// factor := ('+' | '-') factor | '(' expression ')' | number
+    fn factor(&mut self) -> Option<f64> {
+        if self.eat('-') {
+            return self.factor().map(|value| -value);
+        }
+        if self.eat('+') {
+            return self.factor();
+        }
+        if self.eat('(') {
+            let value = self.expression()?;
+            return self.eat(')').then_some(value);
+        }
+        self.number()
+    }
+
+    /// Digits with an optional fraction and exponent, e.g. `12`, `.5`, `1.00e-4`
+    fn number(&mut self) -> Option<f64> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '.') {
+            self.pos += 1;
+        }
+        if self.pos > start && matches!(self.peek(), Some('e' | 'E')) {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.pos += 1;
+            }
+            let exponent_start = self.pos;
+            while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+                self.pos += 1;
+            }
+            if self.pos == exponent_start {
+                // Not an exponent after all; leave the 'e' for the caller to reject
+                self.pos = mantissa_end;
+            }
+        }
+        let literal: String = self.chars[start..self.pos].iter().collect();
+        literal.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_numbers() {
+        assert_eq!(evaluate("2"), Some(2.0));
+        assert_eq!(evaluate(" 1.25 "), Some(1.25));
+        assert_eq!(evaluate(".5"), Some(0.5));
+        assert_eq!(evaluate("1.00e-4"), Some(1.0e-4));
+        assert_eq!(evaluate("-3"), Some(-3.0));
+    }
+
+    #[test]
+    fn test_precedence_and_parentheses() {
+        assert_eq!(evaluate("2*1.5"), Some(3.0));
+        assert_eq!(evaluate("1+2*3"), Some(7.0));
+        assert_eq!(evaluate("(1+2)*3"), Some(9.0));
+        assert_eq!(evaluate("8/2/2"), Some(2.0));
+        assert_eq!(evaluate("10-4-3"), Some(3.0));
+        assert_eq!(evaluate("-(2+3)*-2"), Some(10.0));
+        assert_eq!(evaluate("1e1+2"), Some(12.0));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        for input in ["", "abc", "1+", "*2", "(1+2", "1+2)", "1..2", "2(3)", "1e", "3 4"] {
+            assert_eq!(evaluate(input), None, "{:?} should not evaluate", input);
+        }
+    }
+}
@@ -1,9 +1,20 @@
-use crate::genome::{CurrentGenome, GenomeData, ModeSettings, ChildSettings, AdhesionSettings, Vec3, Quat, GenomeNodeGraph};
+use crate::genome::analysis::{division_cycle_groups, is_division_cycle_link, split_deadlocks, SplitDeadlock, NEVER_SPLIT_INTERVAL};
+use crate::genome::autosave::GenomeAutoSaver;
+use crate::genome::diff::{diff, ModeStatus};
+use crate::genome::file_io::GenomeFileIo;
+use crate::genome::io::{export_compact, import_compact};
+use crate::genome::genome_watcher::GenomeFileWatcher;
+use crate::genome::text_format;
+use crate::genome::validation::validate;
+use crate::genome::{CurrentGenome, GenomeData, ModeSettings, ChildSettings, AdhesionSettings, Vec3, Quat, GenomeNodeGraph, GraphLayout, ModeListFilter};
 use crate::simulation::SimulationState;
-use imgui::{Condition, WindowFlags, StyleColor, InputTextFlags};
+use crate::cell::{adhesion_forces, adhesion_zones, division, types::CellType};
+use imgui::{Condition, WindowFlags, StyleColor, StyleVar, InputTextFlags};
 use imnodes::{Context, EditorContext, editor, PinShape, InputPinId, OutputPinId, LinkId};
 use super::imnodes_extensions;
+use super::expression;
 use super::imgui_widgets;
+use super::number_format::NumberFormat;
 use std::cell::RefCell;
 use std::collections::HashMap;
 
@@ -24,6 +35,18 @@ pub struct GenomeGraphState {
     pub last_mouse_pos: Option<[f32; 2]>,
     pub panning_offset: [f32; 2],
     pub dragging_from_pin: Option<i32>, // Track which output pin is being dragged from
+    /// Refuse links that would point Child A and Child B at the same (non-self) mode
+    pub block_duplicate_children: bool,
+    /// Message about the last link that gave both children the same target
+    pub link_warning: Option<String>,
+    /// Mode whose node was right-clicked to open the node context menu
+    pub context_node: Option<usize>,
+    /// Editor-space position of the right-click that opened a context menu
+    pub context_position: [f32; 2],
+    /// Arrangement used by Auto Layout and for freshly built graphs
+    pub layout: GraphLayout,
+    /// Draw the overview in the bottom-right corner of the canvas
+    pub show_minimap: bool,
 }
 
 impl Default for GenomeGraphState {
@@ -34,10 +57,28 @@ impl Default for GenomeGraphState {
             last_mouse_pos: None,
             panning_offset: [0.0, 0.0],
             dragging_from_pin: None,
+            block_duplicate_children: false,
+            link_warning: None,
+            context_node: None,
+            context_position: [0.0, 0.0],
+            layout: GraphLayout::default(),
+            show_minimap: true,
         }
     }
 }
 
+/// Link color for a parent whose Child A and Child B point at the same mode (orange)
+const DUPLICATE_CHILD_LINK_COLOR: u32 = 0xFF008CFF;
+
+/// Link color for a child link on a division cycle through two or more modes (red)
+const CYCLE_LINK_COLOR: u32 = 0xFF3030E0;
+
+/// Whether both children of a mode go to the same other mode.
+/// Self-splitting (both children staying in the parent's mode) is the normal default and is not flagged.
+fn has_duplicate_children(mode: &ModeSettings, mode_index: usize) -> bool {
+    mode.child_a.mode_number == mode.child_b.mode_number && mode.child_a.mode_number != mode_index as i32
+}
+
 /// Helper function to draw a tooltip with a hoverable "?" mark
 fn help_marker(ui: &imgui::Ui, desc: &str) {
     ui.same_line();
@@ -47,13 +88,50 @@ fn help_marker(ui: &imgui::Ui, desc: &str) {
     }
 }
 
+thread_local! {
+    /// Label of the expression input whose last entry failed to evaluate; its border flashes red for a frame
+    static REJECTED_EXPRESSION_INPUT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Text input that evaluates arithmetic like `2*1.5` when Enter is pressed.
+/// Returns the result, or `None` if nothing was entered or the expression is malformed.
+fn expression_input(ui: &imgui::Ui, label: &str, mut text: String) -> Option<f64> {
+    let rejected = REJECTED_EXPRESSION_INPUT.with(|rejected| {
+        let mut rejected = rejected.borrow_mut();
+        let flash = rejected.as_deref() == Some(label);
+        if flash {
+            *rejected = None;
+        }
+        flash
+    });
+    let _flash = rejected.then(|| {
+        (
+            ui.push_style_color(StyleColor::Border, [1.0, 0.2, 0.2, 1.0]),
+            ui.push_style_var(StyleVar::FrameBorderSize(1.0)),
+        )
+    });
+
+    let entered = ui.input_text(label, &mut text)
+        .flags(InputTextFlags::AUTO_SELECT_ALL | InputTextFlags::ENTER_RETURNS_TRUE)
+        .build();
+    if !entered {
+        return None;
+    }
+    let result = expression::evaluate(&text).filter(|value| value.is_finite());
+    if result.is_none() {
+        REJECTED_EXPRESSION_INPUT.with(|rejected| *rejected.borrow_mut() = Some(label.to_string()));
+    }
+    result
+}
+
 /// Helper function to draw a slider with a text input for precise value entry
-fn slider_with_input_f32(ui: &imgui::Ui, label: &str, value: &mut f32, min: f32, max: f32, width: f32) -> bool {
+fn slider_with_input_f32(ui: &imgui::Ui, label: &str, value: &mut f32, min: f32, max: f32, width: f32, number_format: NumberFormat) -> bool {
     let mut changed = false;
 
     // Draw slider
     ui.set_next_item_width(width - 80.0);
-    if ui.slider(label, min, max, value) {
+    let display_format = number_format.imgui_format(*value);
+    if ui.slider_config(label, min, max).display_format(&display_format).build(value) {
         changed = true;
     }
 
@@ -62,15 +140,9 @@ fn slider_with_input_f32(ui: &imgui::Ui, label: &str, value: &mut f32, min: f32,
     ui.set_next_item_width(70.0);
     let input_label = format!("##input{}", label);
 
-    let mut text_buffer = format!("{:.2}", value);
-    if ui.input_text(&input_label, &mut text_buffer)
-        .flags(InputTextFlags::CHARS_DECIMAL | InputTextFlags::AUTO_SELECT_ALL | InputTextFlags::ENTER_RETURNS_TRUE)
-        .build()
-    {
-        if let Ok(new_value) = text_buffer.parse::<f32>() {
-            *value = new_value.clamp(min, max);
-            changed = true;
-        }
+    if let Some(new_value) = expression_input(ui, &input_label, number_format.format(*value)) {
+        *value = (new_value as f32).clamp(min, max);
+        changed = true;
     }
 
     changed
@@ -91,15 +163,9 @@ fn slider_with_input_i32(ui: &imgui::Ui, label: &str, value: &mut i32, min: i32,
     ui.set_next_item_width(70.0);
     let input_label = format!("##input{}", label);
 
-    let mut text_buffer = format!("{}", value);
-    if ui.input_text(&input_label, &mut text_buffer)
-        .flags(InputTextFlags::CHARS_DECIMAL | InputTextFlags::AUTO_SELECT_ALL | InputTextFlags::ENTER_RETURNS_TRUE)
-        .build()
-    {
-        if let Ok(new_value) = text_buffer.parse::<i32>() {
-            *value = new_value.clamp(min, max);
-            changed = true;
-        }
+    if let Some(new_value) = expression_input(ui, &input_label, format!("{}", value)) {
+        *value = new_value.round().clamp(min as f64, max as f64) as i32;
+        changed = true;
     }
 
     changed
@@ -240,6 +306,13 @@ fn update_mode_numbers_after_insert(genome: &mut GenomeData, insert_idx: usize)
         if mode.child_b.mode_number >= insert_idx as i32 {
             mode.child_b.mode_number += 1;
         }
+        // -1 means no switch after splits
+        if mode.mode_a_after_splits >= insert_idx as i32 {
+            mode.mode_a_after_splits += 1;
+        }
+        if mode.mode_b_after_splits >= insert_idx as i32 {
+            mode.mode_b_after_splits += 1;
+        }
     }
     
     // Update initial mode if needed
@@ -248,7 +321,551 @@ fn update_mode_numbers_after_insert(genome: &mut GenomeData, insert_idx: usize)
     }
 }
 
+/// Step the genome history back (or forward when `redo`) and refresh dependent state
+fn undo_genome_edit(current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph, redo: bool) {
+    let changed = if redo {
+        current_genome.history.redo(&mut current_genome.genome)
+    } else {
+        current_genome.history.undo(&mut current_genome.genome)
+    };
+    if changed {
+        current_genome.validate_selection();
+        node_graph.mark_for_rebuild();
+    }
+}
+
+/// Values chosen in the bulk edit popup; each setting is only applied when its checkbox is ticked
+struct BulkEditState {
+    set_color: bool,
+    color: [f32; 3],
+    set_split_interval: bool,
+    split_interval: f32,
+    set_parent_make_adhesion: bool,
+    parent_make_adhesion: bool,
+}
+
+impl Default for BulkEditState {
+    fn default() -> Self {
+        Self {
+            set_color: false,
+            color: [1.0, 1.0, 1.0],
+            set_split_interval: false,
+            split_interval: 5.0,
+            set_parent_make_adhesion: false,
+            parent_make_adhesion: true,
+        }
+    }
+}
+
+thread_local! {
+    static BULK_EDIT_STATE: RefCell<BulkEditState> = RefCell::new(BulkEditState::default());
+}
+
+/// Popup that writes the chosen settings to every selected mode in a single edit,
+/// so one undo reverts the whole bulk change
+fn render_bulk_edit_popup(ui: &imgui::Ui, current_genome: &mut CurrentGenome) {
+    let Some(_popup) = ui.begin_popup("Bulk Edit Modes") else {
+        return;
+    };
+
+    BULK_EDIT_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        ui.text(format!("Editing {} modes", current_genome.selected_modes.len()));
+        ui.separator();
+
+        ui.checkbox("##set_color", &mut state.set_color);
+        ui.same_line();
+        {
+            let _disabled = ui.begin_disabled(!state.set_color);
+            ui.color_edit3("Color", &mut state.color);
+        }
+
+        ui.checkbox("##set_split_interval", &mut state.set_split_interval);
+        ui.same_line();
+        {
+            let _disabled = ui.begin_disabled(!state.set_split_interval);
+            ui.set_next_item_width(200.0);
+            ui.slider_config("Split Interval", 1.0, 60.0)
+                .display_format("%.1fs")
+                .build(&mut state.split_interval);
+        }
+
+        ui.checkbox("##set_parent_make_adhesion", &mut state.set_parent_make_adhesion);
+        ui.same_line();
+        {
+            let _disabled = ui.begin_disabled(!state.set_parent_make_adhesion);
+            ui.checkbox("Make Adhesion", &mut state.parent_make_adhesion);
+        }
+
+        ui.separator();
+        let any_enabled = state.set_color || state.set_split_interval || state.set_parent_make_adhesion;
+        {
+            let _disabled = ui.begin_disabled(!any_enabled);
+            if ui.button("Apply") {
+                for &index in &current_genome.selected_modes {
+                    let Some(mode) = current_genome.genome.modes.get_mut(index) else {
+                        continue;
+                    };
+                    if state.set_color {
+                        mode.color = Vec3::new(state.color[0], state.color[1], state.color[2]);
+                    }
+                    if state.set_split_interval {
+                        mode.split_interval = state.split_interval;
+                        mode.split_interval_min = None;
+                    }
+                    if state.set_parent_make_adhesion {
+                        mode.parent_make_adhesion = state.parent_make_adhesion;
+                    }
+                }
+                ui.close_current_popup();
+            }
+        }
+        ui.same_line();
+        if ui.button("Cancel") {
+            ui.close_current_popup();
+        }
+    });
+}
+
+/// Hue range and coverage chosen in the gradient colors popup
+struct GradientColorsState {
+    /// Hues in degrees
+    start_hue: f32,
+    end_hue: f32,
+    saturation: f32,
+    value: f32,
+    selected_only: bool,
+}
+
+impl Default for GradientColorsState {
+    fn default() -> Self {
+        Self { start_hue: 0.0, end_hue: 360.0, saturation: 0.7, value: 0.9, selected_only: false }
+    }
+}
+
+thread_local! {
+    static GRADIENT_COLORS_STATE: RefCell<GradientColorsState> = RefCell::new(GradientColorsState::default());
+}
+
+/// Popup that recolors all (or the selected) modes along a hue range, in index order
+fn render_gradient_colors_popup(ui: &imgui::Ui, current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph) {
+    let Some(_popup) = ui.begin_popup("Gradient Colors") else {
+        return;
+    };
+
+    GRADIENT_COLORS_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let targets: Vec<usize> = if state.selected_only {
+            current_genome.selected_modes.iter().copied().collect()
+        } else {
+            (0..current_genome.genome.modes.len()).collect()
+        };
+
+        ui.set_next_item_width(200.0);
+        ui.slider_config("Start Hue", 0.0, 360.0).display_format("%.0f°").build(&mut state.start_hue);
+        ui.set_next_item_width(200.0);
+        ui.slider_config("End Hue", 0.0, 360.0).display_format("%.0f°").build(&mut state.end_hue);
+        ui.set_next_item_width(200.0);
+        ui.slider("Saturation", 0.0, 1.0, &mut state.saturation);
+        ui.set_next_item_width(200.0);
+        ui.slider("Value", 0.0, 1.0, &mut state.value);
+        ui.checkbox("Selected modes only", &mut state.selected_only);
+
+        // Preview swatches in the order the colors will be assigned
+        let draw_list = ui.get_window_draw_list();
+        let origin = ui.cursor_screen_pos();
+        let swatch_width = 200.0 / targets.len().max(1) as f32;
+        for slot in 0..targets.len() {
+            let hue = gradient_hue(state.start_hue, state.end_hue, slot, targets.len());
+            let color = hsv_to_rgb(hue, state.saturation, state.value);
+            let min = [origin[0] + slot as f32 * swatch_width, origin[1]];
+            draw_list
+                .add_rect(min, [min[0] + swatch_width, min[1] + 16.0], [color.x, color.y, color.z, 1.0])
+                .filled(true)
+                .build();
+        }
+        ui.dummy([200.0, 16.0]);
+
+        ui.separator();
+        if ui.button(format!("Apply to {} modes", targets.len())) {
+            for (slot, &index) in targets.iter().enumerate() {
+                if let Some(mode) = current_genome.genome.modes.get_mut(index) {
+                    let hue = gradient_hue(state.start_hue, state.end_hue, slot, targets.len());
+                    mode.color = hsv_to_rgb(hue, state.saturation, state.value);
+                }
+            }
+            node_graph.mark_for_rebuild();
+            ui.close_current_popup();
+        }
+        ui.same_line();
+        if ui.button("Cancel") {
+            ui.close_current_popup();
+        }
+    });
+}
+
+/// Hue in degrees for position `slot` of `count` evenly spaced between `start` and `end`.
+/// A full turn leaves out the end so the first and last modes don't share a color.
+fn gradient_hue(start: f32, end: f32, slot: usize, count: usize) -> f32 {
+    let span = end - start;
+    let steps = if span.abs() >= 360.0 { count } else { count.saturating_sub(1) };
+    if steps == 0 {
+        return start;
+    }
+    start + span * slot as f32 / steps as f32
+}
+
+/// Convert a hue in degrees plus saturation and value (0..1) to an RGB color
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> Vec3 {
+    let saturation = saturation.clamp(0.0, 1.0);
+    let value = value.clamp(0.0, 1.0);
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    Vec3::new((r + m).clamp(0.0, 1.0), (g + m).clamp(0.0, 1.0), (b + m).clamp(0.0, 1.0))
+}
+
+/// Save/Load/Watch buttons; file I/O runs in the background with a spinner while in flight
+fn render_file_buttons(ui: &imgui::Ui, current_genome: &CurrentGenome, file_io: &mut GenomeFileIo) {
+    let busy = file_io.is_busy();
+    let file_name = format!("{}.json", current_genome.genome.name);
+    let start_dir = current_genome.file_path.as_deref().and_then(|path| path.parent());
+
+    ui.same_line();
+    {
+        let _disabled = ui.begin_disabled(busy);
+        if ui.button("Save Genome") {
+            let mut dialog = rfd::FileDialog::new().add_filter("Genome", &["json"]).set_file_name(&file_name);
+            if let Some(dir) = start_dir {
+                dialog = dialog.set_directory(dir);
+            }
+            if let Some(path) = dialog.save_file() {
+                file_io.save(current_genome.genome.clone(), path);
+            }
+        }
+
+        ui.same_line();
+        if ui.button("Load Genome") {
+            if let Some(path) = rfd::FileDialog::new().add_filter("Genome", &["json"]).pick_file() {
+                file_io.load(path, false);
+            }
+        }
+
+        ui.same_line();
+        if ui.button("Watch File...") {
+            if let Some(path) = rfd::FileDialog::new().add_filter("Genome", &["json"]).pick_file() {
+                file_io.load(path, true);
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Load a genome file and reload it whenever it changes on disk");
+        }
+    }
+
+    ui.same_line();
+    if ui.button("Compare...") {
+        if let Some(path) = rfd::FileDialog::new().add_filter("Genome", &["json"]).pick_file() {
+            let other = GenomeData::load_from_file(&path).map_err(|e| e.to_string());
+            let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            GENOME_COMPARISON.with(|comparison| *comparison.borrow_mut() = Some(GenomeComparison { file_name, other, show_unchanged: false }));
+            ui.open_popup(COMPARE_POPUP);
+        }
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Show what changed between a saved genome and this one");
+    }
+    render_compare_popup(ui, &current_genome.genome);
+
+    if let Some(operation) = file_io.pending() {
+        const SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+        let frame = (ui.time() * 10.0) as usize % SPINNER.len();
+        ui.same_line();
+        ui.text_disabled(format!("{} {}...", SPINNER[frame], operation.name()));
+    } else if let Some((message, is_error)) = file_io.status() {
+        ui.same_line();
+        if is_error {
+            ui.text_colored([1.0, 0.4, 0.4, 1.0], message);
+        } else {
+            ui.text_disabled(message);
+        }
+    }
+}
+
+const COMPARE_POPUP: &str = "Compare Genomes";
+
+/// Genome picked with "Compare..." to diff against the current one
+struct GenomeComparison {
+    file_name: String,
+    other: Result<GenomeData, String>,
+    show_unchanged: bool,
+}
+
+thread_local! {
+    static GENOME_COMPARISON: RefCell<Option<GenomeComparison>> = const { RefCell::new(None) };
+}
+
+/// Modal tree of added (green), removed (red) and changed (yellow) modes and settings,
+/// from the compared file to the current genome. Recomputed each frame so edits show up live.
+fn render_compare_popup(ui: &imgui::Ui, genome: &GenomeData) {
+    let Some(_popup) = ui.modal_popup_config(COMPARE_POPUP).always_auto_resize(true).begin_popup() else {
+        return;
+    };
+    GENOME_COMPARISON.with(|comparison| {
+        let mut comparison = comparison.borrow_mut();
+        let Some(comparison) = comparison.as_mut() else {
+            ui.close_current_popup();
+            return;
+        };
+        let other = match &comparison.other {
+            Ok(other) => other,
+            Err(error) => {
+                ui.text_colored(ModeStatus::Removed.color(), format!("Could not load {}: {}", comparison.file_name, error));
+                if ui.button("Close") {
+                    ui.close_current_popup();
+                }
+                return;
+            }
+        };
+
+        let result = diff(other, genome);
+        ui.text(format!("Changes from {} to the current genome", comparison.file_name));
+        ui.text_disabled(format!(
+            "{} added, {} removed, {} changed",
+            result.count(ModeStatus::Added),
+            result.count(ModeStatus::Removed),
+            result.count(ModeStatus::Changed),
+        ));
+        ui.checkbox("Show unchanged modes", &mut comparison.show_unchanged);
+        ui.separator();
+
+        ui.child_window("GenomeDiffTree").size([620.0, 400.0]).border(true).build(|| {
+            if result.is_empty() {
+                ui.text_colored(ModeStatus::Added.color(), "No differences");
+            }
+            if !result.fields.is_empty() {
+                let _color = ui.push_style_color(StyleColor::Text, ModeStatus::Changed.color());
+                if let Some(_node) = ui.tree_node_config("Genome settings").default_open(true).push() {
+                    for change in &result.fields {
+                        ui.bullet_text(format!("{}: {} -> {}", change.path, change.old, change.new));
+                    }
+                }
+            }
+            for (i, mode) in result.modes.iter().enumerate() {
+                if mode.status == ModeStatus::Unchanged && !mode.moved() && !comparison.show_unchanged {
+                    continue;
+                }
+                let _id = ui.push_id_usize(i);
+                let mut label = format!("{}: {}", mode.status.name(), mode.name);
+                if let Some(old_name) = &mode.renamed_from {
+                    label.push_str(&format!(" (was {})", old_name));
+                }
+                if mode.moved() {
+                    if let (Some(old), Some(new)) = (mode.old_index, mode.new_index) {
+                        label.push_str(&format!(" [moved {} -> {}]", old, new));
+                    }
+                }
+                let _color = ui.push_style_color(StyleColor::Text, mode.status.color());
+                let flags = if mode.fields.is_empty() { imgui::TreeNodeFlags::LEAF } else { imgui::TreeNodeFlags::empty() };
+                if let Some(_node) = ui.tree_node_config(&label).flags(flags).push() {
+                    for change in &mode.fields {
+                        ui.bullet_text(format!("{}: {} -> {}", change.path, change.old, change.new));
+                    }
+                }
+            }
+        });
+
+        if ui.button("Close") {
+            ui.close_current_popup();
+        }
+    });
+}
+
+thread_local! {
+    /// Result of the last share string export or import (message, is_error)
+    static SHARE_STRING_STATUS: RefCell<Option<(String, bool)>> = const { RefCell::new(None) };
+}
+
+/// Copy the genome to the clipboard as a compact share string, or replace it with one from the clipboard
+fn render_share_string_buttons(ui: &imgui::Ui, current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph) {
+    let mut status = None;
+    if ui.button("Export String") {
+        ui.set_clipboard_text(export_compact(&current_genome.genome));
+        status = Some(("Genome string copied to clipboard".to_string(), false));
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Copy the genome as a short text string for pasting into chats and forums");
+    }
+
+    ui.same_line();
+    if ui.button("Import String") {
+        let text = ui.clipboard_text().unwrap_or_default();
+        status = Some(match import_compact(&text) {
+            Ok(genome) => {
+                println!("Imported genome '{}' from share string", genome.name);
+                let message = format!("Imported '{}'", genome.name);
+                current_genome.load_genome(genome);
+                current_genome.select_single_mode(0);
+                // The genome no longer matches the watched file
+                current_genome.file_path = None;
+                current_genome.watch_file = false;
+                node_graph.mark_for_rebuild();
+                (message, false)
+            }
+            Err(e) => (e.to_string(), true),
+        });
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Replace the genome with a genome string from the clipboard");
+    }
+
+    SHARE_STRING_STATUS.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        if status.is_some() {
+            *cell = status;
+        }
+        if let Some((message, is_error)) = cell.as_ref() {
+            ui.same_line();
+            if *is_error {
+                ui.text_colored([1.0, 0.4, 0.4, 1.0], message);
+            } else {
+                ui.text_disabled(message);
+            }
+        }
+    });
+}
+
+/// Repairs made to the last loaded genome, with a button to dismiss them
+fn render_load_warnings(ui: &imgui::Ui, current_genome: &mut CurrentGenome) {
+    if current_genome.load_warnings.is_empty() {
+        return;
+    }
+    for warning in &current_genome.load_warnings {
+        ui.text_colored([1.0, 0.5, 0.0, 1.0], warning);
+    }
+    if ui.small_button("Dismiss##load_warnings") {
+        current_genome.load_warnings.clear();
+    }
+}
+
+/// Collapsible list of contradictory settings found by `genome::validate`, each with a Fix button when the correction is obvious
+fn render_validation_section(ui: &imgui::Ui, current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph) {
+    let issues = validate(&current_genome.genome);
+    let header = if issues.is_empty() {
+        "Validation###genome_validation".to_string()
+    } else {
+        format!("Validation ({})###genome_validation", issues.len())
+    };
+    if !ui.collapsing_header(&header, imgui::TreeNodeFlags::empty()) {
+        return;
+    }
+
+    if issues.is_empty() {
+        ui.text_colored([0.4, 0.9, 0.4, 1.0], "No problems found");
+        return;
+    }
+
+    for (i, issue) in issues.iter().enumerate() {
+        let _id = ui.push_id_usize(i);
+        ui.text_colored(issue.severity.color(), format!("{}:", issue.severity.name()));
+        ui.same_line();
+        match issue.mode_index {
+            Some(mode_index) => {
+                let mode_name = current_genome.genome.modes.get(mode_index).map(|m| m.name.as_str()).unwrap_or("");
+                if ui.small_button(format!("[{}] {}", mode_index, mode_name)) {
+                    current_genome.select_single_mode(mode_index);
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Select this mode");
+                }
+                ui.same_line();
+                ui.text_wrapped(&issue.message);
+            }
+            None => ui.text_wrapped(&issue.message),
+        }
+        if let Some(fix) = issue.fix {
+            ui.indent();
+            if ui.small_button("Fix") {
+                fix.apply(&mut current_genome.genome);
+                node_graph.mark_for_rebuild();
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text(fix.description());
+            }
+            ui.unindent();
+        }
+    }
+}
+
+/// Text typed into the "Import from Text" pane and the result of the last import
+#[derive(Default)]
+struct TextImportState {
+    text: String,
+    error: Option<String>,
+}
+
+thread_local! {
+    static TEXT_IMPORT_STATE: RefCell<TextImportState> = RefCell::new(TextImportState::default());
+}
+
+/// Collapsible pane that compiles the genome text DSL and replaces the current genome
+fn render_text_import_pane(ui: &imgui::Ui, current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph) {
+    if !ui.collapsing_header("Import from Text", imgui::TreeNodeFlags::empty()) {
+        return;
+    }
+
+    TEXT_IMPORT_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        ui.text_disabled("mode Name { split_mass = 2.0 childA = Name childB = Other ... }");
+        ui.input_text_multiline("##genome_text", &mut state.text, [-1.0, 200.0])
+            .allow_tab_input(true)
+            .build();
+
+        if ui.button("Import") {
+            match text_format::parse_genome_text(&state.text) {
+                Ok(genome) => {
+                    println!("Imported genome '{}' from text", genome.name);
+                    current_genome.load_genome(genome);
+                    current_genome.select_single_mode(0);
+                    // The genome no longer matches the watched file
+                    current_genome.file_path = None;
+                    current_genome.watch_file = false;
+                    node_graph.mark_for_rebuild();
+                    state.error = None;
+                }
+                Err(e) => state.error = Some(e.to_string()),
+            }
+        }
+
+        ui.same_line();
+        if ui.button("Open Text File...") {
+            if let Some(path) = rfd::FileDialog::new().add_filter("Genome Text", &["txt", "genome"]).pick_file() {
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => {
+                        state.text = text;
+                        state.error = None;
+                    }
+                    Err(e) => state.error = Some(format!("Could not read {}: {}", path.display(), e)),
+                }
+            }
+        }
+
+        if let Some(error) = &state.error {
+            ui.text_colored([1.0, 0.4, 0.4, 1.0], error);
+        }
+    });
+}
+
 /// Render the genome editor window
+#[allow(clippy::too_many_arguments)]
 pub fn render_genome_editor_window(
     ui: &imgui::Ui,
     current_genome: &mut CurrentGenome,
@@ -256,6 +873,8 @@ pub fn render_genome_editor_window(
     global_ui_state: &super::GlobalUiState,
     node_graph: &mut GenomeNodeGraph,
     graph_state: &mut GenomeGraphState,
+    file_io: &mut GenomeFileIo,
+    mode_counts: &HashMap<usize, u32>,
 ) {
     // Only show if visibility is enabled
     if !global_ui_state.show_genome_editor {
@@ -275,18 +894,39 @@ pub fn render_genome_editor_window(
         .size_constraints([700.0, 500.0], [f32::MAX, f32::MAX])
         .flags(flags)
         .build(|| {
-            render_genome_editor_content(ui, current_genome, simulation_state, node_graph, graph_state);
+            render_genome_editor_content(ui, current_genome, simulation_state, node_graph, graph_state, file_io, mode_counts, global_ui_state.number_format());
         });
 }
 
 /// Render just the content of the Genome Editor window (without the window wrapper)
+#[allow(clippy::too_many_arguments)]
 pub fn render_genome_editor_content(
     ui: &imgui::Ui,
     current_genome: &mut CurrentGenome,
-    _simulation_state: &mut SimulationState,
+    simulation_state: &mut SimulationState,
     node_graph: &mut GenomeNodeGraph,
     graph_state: &mut GenomeGraphState,
+    file_io: &mut GenomeFileIo,
+    mode_counts: &HashMap<usize, u32>,
+    number_format: NumberFormat,
 ) {
+    // The genome can be replaced from outside the editor (file reload, autosave restore)
+    current_genome.validate_selection();
+
+    // A load in flight will replace the genome, so nothing may edit it until it lands
+    let loading = file_io.is_loading();
+
+    // Undo/redo shortcuts, left to text fields while one is being typed into
+    if !loading && !ui.io().want_text_input && ui.io().key_ctrl {
+        if ui.is_key_pressed(imgui::Key::Z) {
+            undo_genome_edit(current_genome, node_graph, false);
+        } else if ui.is_key_pressed(imgui::Key::Y) {
+            undo_genome_edit(current_genome, node_graph, true);
+        }
+    }
+
+    let _load_lock = ui.begin_disabled(loading);
+
     // Genome name input
     ui.text("Genome Name:");
     ui.same_line();
@@ -296,17 +936,7 @@ pub fn render_genome_editor_content(
         current_genome.genome.name = genome_name;
     }
 
-    ui.same_line();
-    if ui.button("Save Genome") {
-        // Placeholder for save functionality
-        println!("Save genome: {}", current_genome.genome.name);
-    }
-
-    ui.same_line();
-    if ui.button("Load Genome") {
-        // Placeholder for load functionality
-        println!("Load genome");
-    }
+    render_file_buttons(ui, current_genome, file_io);
 
     ui.same_line();
     if ui.button("Genome Graph") {
@@ -319,6 +949,24 @@ pub fn render_genome_editor_content(
         ui.tooltip_text("Highlight cells of the selected mode with a pulsing glow");
     }
 
+    render_share_string_buttons(ui, current_genome, node_graph);
+
+    // Source file and live reload toggle
+    if let Some(path) = current_genome.file_path.as_ref() {
+        ui.text_disabled(format!("File: {}", path.display()));
+        ui.same_line();
+        ui.checkbox("Live Reload", &mut current_genome.watch_file);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Reload the genome and rebuild the node graph when the file changes on disk");
+        }
+    }
+
+    render_load_warnings(ui, current_genome);
+
+    render_validation_section(ui, current_genome, node_graph);
+
+    render_text_import_pane(ui, current_genome, node_graph);
+
     ui.separator();
 
     // Initial mode dropdown
@@ -379,6 +1027,30 @@ pub fn render_genome_editor_content(
         if insert_idx <= selected_idx && selected_idx < current_genome.genome.modes.len() {
             current_genome.selected_mode_index = (selected_idx + 1) as i32;
         }
+        // Indices shifted, so drop any multi-selection
+        current_genome.select_single_mode(current_genome.selected_mode_index as usize);
+    }
+
+    ui.same_line();
+    if ui.button("Copy Mode") {
+        current_genome.mode_clipboard = current_genome.genome.modes.get(current_genome.selected_mode_index as usize).cloned();
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Copy the selected mode's settings, including adhesion and child orientations");
+    }
+
+    ui.same_line();
+    {
+        let _disabled = ui.begin_disabled(current_genome.mode_clipboard.is_none());
+        if ui.button("Paste Mode") {
+            paste_mode(current_genome, node_graph);
+        }
+    }
+    if ui.is_item_hovered_with_flags(imgui::ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+        ui.tooltip_text(match &current_genome.mode_clipboard {
+            Some(mode) => format!("Insert a copy of '{}' after the selected mode; its children point back to itself", mode.name),
+            None => "Copy a mode first".to_string(),
+        });
     }
 
     ui.same_line();
@@ -422,6 +1094,7 @@ pub fn render_genome_editor_content(
             if current_genome.selected_mode_index >= current_genome.genome.modes.len() as i32 {
                 current_genome.selected_mode_index = (current_genome.genome.modes.len() as i32) - 1;
             }
+            current_genome.select_single_mode(current_genome.selected_mode_index as usize);
         }
     }
     
@@ -436,176 +1109,230 @@ pub fn render_genome_editor_content(
 
     ui.same_line();
     if ui.button("Reset Mode") {
-        let selected = current_genome.selected_mode_index as usize;
-        if selected < current_genome.genome.modes.len() {
-            let mode = &mut current_genome.genome.modes[selected];
-            
-            // Save the current name and references before resetting
-            let saved_name = mode.name.clone();
-            let saved_default_name = mode.default_name.clone();
-            let saved_child_a_mode = mode.child_a.mode_number;
-            let saved_child_b_mode = mode.child_b.mode_number;
-            
-            // Reset to default settings
-            *mode = ModeSettings::new_self_splitting(selected as i32, saved_default_name.clone());
-            
-            // Restore the saved name and references
-            mode.name = saved_name;
-            mode.child_a.mode_number = saved_child_a_mode;
-            mode.child_b.mode_number = saved_child_b_mode;
+        reset_mode(&mut current_genome.genome, current_genome.selected_mode_index as usize);
+    }
+
+    ui.same_line();
+    {
+        let _disabled = ui.begin_disabled(!current_genome.history.can_undo());
+        if ui.button("Undo") {
+            undo_genome_edit(current_genome, node_graph, false);
         }
     }
+    if ui.is_item_hovered_with_flags(imgui::ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+        ui.tooltip_text("Undo the last genome edit (Ctrl+Z)");
+    }
 
-    // Mode list (left panel) - extract data first to avoid borrow issues
-    let modes_data: Vec<(String, Vec3)> = current_genome.genome.modes.iter()
-        .map(|m| (m.name.clone(), m.color))
-        .collect();
-    let mut new_selected_index = current_genome.selected_mode_index;
-    let initial_mode = current_genome.genome.initial_mode;
+    ui.same_line();
+    {
+        let _disabled = ui.begin_disabled(!current_genome.history.can_redo());
+        if ui.button("Redo") {
+            undo_genome_edit(current_genome, node_graph, true);
+        }
+    }
+    if ui.is_item_hovered_with_flags(imgui::ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+        ui.tooltip_text("Redo the last undone genome edit (Ctrl+Y)");
+    }
 
-    ui.child_window("ModeList")
-        .size([200.0, 0.0])
-        .border(true)
-        .build(|| {
-            for (i, (name, color)) in modes_data.iter().enumerate() {
-                let is_selected = i == new_selected_index as usize;
+    if current_genome.selected_modes.len() > 1 {
+        ui.same_line();
+        if ui.button(format!("Bulk Edit ({})", current_genome.selected_modes.len())) {
+            ui.open_popup("Bulk Edit Modes");
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Change settings on every selected mode at once");
+        }
+    }
+    render_bulk_edit_popup(ui, current_genome);
 
-                // Color the mode button with mode's color
-                let button_color = if is_selected {
-                    [color.x, color.y, color.z, 1.0]
-                } else {
-                    [color.x * 0.8, color.y * 0.8, color.z * 0.8, 1.0]
-                };
+    ui.same_line();
+    if ui.button("Gradient Colors") {
+        ui.open_popup("Gradient Colors");
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Give modes evenly spaced hues in index order");
+    }
+    render_gradient_colors_popup(ui, current_genome, node_graph);
 
-                let _button_style = ui.push_style_color(StyleColor::Button, button_color);
-                let _button_hovered_style = ui.push_style_color(
-                    StyleColor::ButtonHovered,
-                    [color.x * 0.9, color.y * 0.9, color.z * 0.9, 1.0]
-                );
-                let _button_active_style = ui.push_style_color(
-                    StyleColor::ButtonActive,
-                    [color.x, color.y, color.z, 1.0]
-                );
-
-                // Radio button for initial mode selection (before text color push)
-                let is_initial = initial_mode == i as i32;
-                if ui.radio_button_bool(&format!("##initial_{}", i), is_initial) {
-                    current_genome.genome.initial_mode = i as i32;
-                }
+    // Mode list (left panel) - extract data first to avoid borrow issues
+    let selected_modes = current_genome.selected_modes.clone();
+    let filter = &current_genome.mode_list_filter;
+    // Filtered-out modes keep their indices; selected modes always stay listed
+    let modes_data: Vec<(usize, String, Vec3)> = current_genome.genome.modes.iter()
+        .enumerate()
+        .filter(|(i, m)| selected_modes.contains(i) || filter.matches(m))
+        .map(|(i, m)| (i, m.name.clone(), m.color))
+        .collect();
+    let hidden_count = current_genome.genome.modes.len() - modes_data.len();
+    let mut clicked_mode: Option<(usize, bool)> = None;
+    let initial_mode = current_genome.genome.initial_mode;
+    let item_spacing = ui.clone_style().item_spacing[0];
+
+    ui.group(|| {
+        draw_mode_list_filter(ui, &mut current_genome.mode_list_filter, hidden_count);
+        ui.child_window("ModeList")
+            .size([200.0, 0.0])
+            .border(true)
+            .build(|| {
+                for (i, name, color) in modes_data.iter() {
+                    let i = *i;
+                    let is_selected = selected_modes.contains(&i);
+
+                    // Color the mode button with mode's color
+                    let button_color = if is_selected {
+                        [color.x, color.y, color.z, 1.0]
+                    } else {
+                        [color.x * 0.8, color.y * 0.8, color.z * 0.8, 1.0]
+                    };
+
+                    let _button_style = ui.push_style_color(StyleColor::Button, button_color);
+                    let _button_hovered_style = ui.push_style_color(
+                        StyleColor::ButtonHovered,
+                        [color.x * 0.9, color.y * 0.9, color.z * 0.9, 1.0]
+                    );
+                    let _button_active_style = ui.push_style_color(
+                        StyleColor::ButtonActive,
+                        [color.x, color.y, color.z, 1.0]
+                    );
+
+                    // Radio button for initial mode selection (before text color push)
+                    let is_initial = initial_mode == i as i32;
+                    if ui.radio_button_bool(&format!("##initial_{}", i), is_initial) {
+                        current_genome.genome.initial_mode = i as i32;
+                    }
                 
-                // Tooltip must be checked immediately after the widget
-                if ui.is_item_hovered() {
-                    ui.tooltip_text("Make this mode initial");
-                }
+                    // Tooltip must be checked immediately after the widget
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Make this mode initial");
+                    }
                 
-                ui.same_line();
+                    ui.same_line();
                 
-                // Determine text color based on brightness (for button text only)
-                let brightness = color.x * 0.299 + color.y * 0.587 + color.z * 0.114;
-                let text_color = if brightness > 0.5 {
-                    [0.0, 0.0, 0.0, 1.0]
-                } else {
-                    [1.0, 1.0, 1.0, 1.0]
-                };
-                let _text_style = ui.push_style_color(StyleColor::Text, text_color);
+                    // Pick the button text color from the color actually drawn, not the theme's text color
+                    let text_color = contrasting_text_color(Vec3::new(button_color[0], button_color[1], button_color[2]));
+                    let text_style = ui.push_style_color(StyleColor::Text, text_color);
                 
-                // Mode button (slightly narrower to make room for radio button)
-                let available_width = ui.content_region_avail()[0];
-                if ui.button_with_size(name, [available_width, 0.0]) {
-                    new_selected_index = i as i32;
-                }
+                    // Live cells in this mode, shown after the button
+                    let cell_count = mode_counts.get(&i).copied().unwrap_or(0);
+                    let count_label = format!("[×{}]", cell_count);
+                    let count_width = ui.calc_text_size(&count_label)[0] + item_spacing;
+                
+                    // Mode button (slightly narrower to make room for radio button and cell count)
+                    let available_width = ui.content_region_avail()[0] - count_width;
+                    if ui.button_with_size(name, [available_width, 0.0]) {
+                        clicked_mode = Some((i, ui.io().key_ctrl));
+                    }
+                    if ui.is_item_hovered() && selected_modes.len() <= 1 {
+                        ui.tooltip_text("Ctrl+Click to select several modes for bulk editing");
+                    }
+
+                    // Draw dashed black and white outline for selected modes
+                    if is_selected {
+                        let draw_list = ui.get_window_draw_list();
+                        let min = ui.item_rect_min();
+                        let max = ui.item_rect_max();
+
+                        let dash_length = 6.0;
+                        let black_color = 0xFF000000u32;
+                        let white_color = 0xFFFFFFFFu32;
 
-                // Draw dashed black and white outline for selected mode
-                if is_selected {
-                    let draw_list = ui.get_window_draw_list();
-                    let min = ui.item_rect_min();
-                    let max = ui.item_rect_max();
-
-                    let dash_length = 6.0;
-                    let black_color = 0xFF000000u32;
-                    let white_color = 0xFFFFFFFFu32;
-
-                    // Draw top edge
-                    let mut x = min[0];
-                    while x < max[0] {
-                        let end_x = (x + dash_length).min(max[0]);
-                        draw_list
-                            .add_line([x, min[1]], [end_x, min[1]], black_color)
-                            .thickness(2.0)
-                            .build();
-                        x += dash_length;
-                        if x < max[0] {
+                        // Draw top edge
+                        let mut x = min[0];
+                        while x < max[0] {
                             let end_x = (x + dash_length).min(max[0]);
                             draw_list
-                                .add_line([x, min[1]], [end_x, min[1]], white_color)
+                                .add_line([x, min[1]], [end_x, min[1]], black_color)
                                 .thickness(2.0)
                                 .build();
                             x += dash_length;
+                            if x < max[0] {
+                                let end_x = (x + dash_length).min(max[0]);
+                                draw_list
+                                    .add_line([x, min[1]], [end_x, min[1]], white_color)
+                                    .thickness(2.0)
+                                    .build();
+                                x += dash_length;
+                            }
                         }
-                    }
 
-                    // Draw bottom edge
-                    let mut x = min[0];
-                    while x < max[0] {
-                        let end_x = (x + dash_length).min(max[0]);
-                        draw_list
-                            .add_line([x, max[1]], [end_x, max[1]], black_color)
-                            .thickness(2.0)
-                            .build();
-                        x += dash_length;
-                        if x < max[0] {
+                        // Draw bottom edge
+                        let mut x = min[0];
+                        while x < max[0] {
                             let end_x = (x + dash_length).min(max[0]);
                             draw_list
-                                .add_line([x, max[1]], [end_x, max[1]], white_color)
+                                .add_line([x, max[1]], [end_x, max[1]], black_color)
                                 .thickness(2.0)
                                 .build();
                             x += dash_length;
+                            if x < max[0] {
+                                let end_x = (x + dash_length).min(max[0]);
+                                draw_list
+                                    .add_line([x, max[1]], [end_x, max[1]], white_color)
+                                    .thickness(2.0)
+                                    .build();
+                                x += dash_length;
+                            }
                         }
-                    }
 
-                    // Draw left edge
-                    let mut y = min[1];
-                    while y < max[1] {
-                        let end_y = (y + dash_length).min(max[1]);
-                        draw_list
-                            .add_line([min[0], y], [min[0], end_y], black_color)
-                            .thickness(2.0)
-                            .build();
-                        y += dash_length;
-                        if y < max[1] {
+                        // Draw left edge
+                        let mut y = min[1];
+                        while y < max[1] {
                             let end_y = (y + dash_length).min(max[1]);
                             draw_list
-                                .add_line([min[0], y], [min[0], end_y], white_color)
+                                .add_line([min[0], y], [min[0], end_y], black_color)
                                 .thickness(2.0)
                                 .build();
                             y += dash_length;
+                            if y < max[1] {
+                                let end_y = (y + dash_length).min(max[1]);
+                                draw_list
+                                    .add_line([min[0], y], [min[0], end_y], white_color)
+                                    .thickness(2.0)
+                                    .build();
+                                y += dash_length;
+                            }
                         }
-                    }
 
-                    // Draw right edge
-                    let mut y = min[1];
-                    while y < max[1] {
-                        let end_y = (y + dash_length).min(max[1]);
-                        draw_list
-                            .add_line([max[0], y], [max[0], end_y], black_color)
-                            .thickness(2.0)
-                            .build();
-                        y += dash_length;
-                        if y < max[1] {
+                        // Draw right edge
+                        let mut y = min[1];
+                        while y < max[1] {
                             let end_y = (y + dash_length).min(max[1]);
                             draw_list
-                                .add_line([max[0], y], [max[0], end_y], white_color)
+                                .add_line([max[0], y], [max[0], end_y], black_color)
                                 .thickness(2.0)
                                 .build();
                             y += dash_length;
+                            if y < max[1] {
+                                let end_y = (y + dash_length).min(max[1]);
+                                draw_list
+                                    .add_line([max[0], y], [max[0], end_y], white_color)
+                                    .thickness(2.0)
+                                    .build();
+                                y += dash_length;
+                            }
                         }
                     }
+                
+                    drop(text_style);
+                    ui.same_line();
+                    if cell_count > 0 {
+                        ui.text(&count_label);
+                    } else {
+                        ui.text_disabled(&count_label);
+                    }
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text(format!("{} live cells in this mode", cell_count));
+                    }
                 }
-            }
-        });
+            });
+    });
 
-    // Update the selection if it changed
-    current_genome.selected_mode_index = new_selected_index;
+    // Update the selection if it changed; Ctrl+Click adds to or removes from the selection
+    match clicked_mode {
+        Some((index, true)) => current_genome.toggle_mode_selection(index),
+        Some((index, false)) => current_genome.select_single_mode(index),
+        None => {}
+    }
 
     ui.same_line();
 
@@ -616,29 +1343,110 @@ pub fn render_genome_editor_content(
     if selected_idx < all_modes_count {
         // Clone the modes list for reference
         let modes_for_ref: Vec<ModeSettings> = current_genome.genome.modes.clone();
+        let physics_dt = simulation_state.physics_config.substep_dt();
 
         if let Some(selected_mode) = current_genome.genome.modes.get_mut(selected_idx) {
-            ui.child_window("ModeSettings")
+            let copy_adhesion = ui.child_window("ModeSettings")
                 .size([0.0, 0.0])
                 .scrollable(true)
                 .build(|| {
-                    draw_mode_settings(ui, selected_mode, &modes_for_ref, selected_idx);
+                    draw_mode_settings(ui, selected_mode, &modes_for_ref, selected_idx, physics_dt, number_format)
                 });
+            if copy_adhesion == Some(true) {
+                copy_adhesion_to_all_modes(&mut current_genome.genome, selected_idx);
+            }
         }
     }
     
-    // Render genome graph window if enabled
-    if current_genome.show_genome_graph {
+    // Render genome graph window if enabled; imnodes ignores the disabled state, so hide it mid-load
+    if current_genome.show_genome_graph && !loading {
         render_genome_graph_window(ui, current_genome, node_graph, graph_state);
     }
 }
 
-/// Draw mode settings (tabbed interface)
-fn draw_mode_settings(ui: &imgui::Ui, mode: &mut ModeSettings, all_modes: &[ModeSettings], mode_index: usize) {
+/// Show a short-lived toast after the watched genome file is reloaded
+pub fn render_genome_reload_toast(ui: &imgui::Ui, watcher: &GenomeFileWatcher) {
+    let Some((message, is_error, progress)) = watcher.toast() else {
+        return;
+    };
+
+    // Fade out over the last third of the toast's lifetime
+    let alpha = ((1.0 - progress) * 3.0).min(1.0);
+    let display_size = ui.io().display_size;
+    let _alpha = ui.push_style_var(imgui::StyleVar::Alpha(alpha));
+    ui.window("##genome_reload_toast")
+        .position([display_size[0] * 0.5, display_size[1] - 60.0], Condition::Always)
+        .position_pivot([0.5, 1.0])
+        .bg_alpha(0.8)
+        .flags(
+            WindowFlags::NO_DECORATION
+                | WindowFlags::ALWAYS_AUTO_RESIZE
+                | WindowFlags::NO_MOVE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_FOCUS_ON_APPEARING
+                | WindowFlags::NO_NAV
+                | WindowFlags::NO_INPUTS
+                | WindowFlags::NO_DOCKING,
+        )
+        .build(|| {
+            let color = if is_error { [1.0, 0.4, 0.4, 1.0] } else { [0.5, 0.9, 0.5, 1.0] };
+            ui.text_colored(color, message);
+        });
+}
+
+/// Ask whether to restore an autosave left over from a previous session
+pub fn render_autosave_restore_prompt(
+    ui: &imgui::Ui,
+    autosaver: &mut GenomeAutoSaver,
+    current_genome: &mut CurrentGenome,
+    node_graph: &mut GenomeNodeGraph,
+) {
+    let Some(path) = autosaver.pending_restore().map(|p| p.to_path_buf()) else {
+        return;
+    };
+
+    let display_size = ui.io().display_size;
+    let mut restore = false;
+    let mut dismiss = false;
+    ui.window("Restore Autosave?")
+        .position([display_size[0] * 0.5, display_size[1] * 0.4], Condition::Always)
+        .position_pivot([0.5, 0.5])
+        .flags(
+            WindowFlags::NO_COLLAPSE
+                | WindowFlags::ALWAYS_AUTO_RESIZE
+                | WindowFlags::NO_MOVE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_DOCKING,
+        )
+        .build(|| {
+            ui.text("An autosaved genome newer than your last save was found:");
+            ui.text_disabled(path.display().to_string());
+            ui.separator();
+            restore = ui.button("Restore");
+            ui.same_line();
+            dismiss = ui.button("Discard");
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Keep the current genome; the autosave file is left on disk");
+            }
+        });
+
+    if restore {
+        autosaver.restore(current_genome, node_graph);
+    } else if dismiss {
+        autosaver.dismiss_restore();
+    }
+}
+
+/// Draw mode settings (tabbed interface).
+/// Returns true when the user confirmed copying this mode's adhesion settings to every other mode.
+fn draw_mode_settings(ui: &imgui::Ui, mode: &mut ModeSettings, all_modes: &[ModeSettings], mode_index: usize, physics_dt: f32, number_format: NumberFormat) -> bool {
+    let mut copy_adhesion = false;
+    let split_direction = division::split_direction(mode.parent_split_direction.x, mode.parent_split_direction.y);
+    
     if let Some(_tab_bar) = ui.tab_bar("ModeSettingsTabs") {
         // Parent Settings Tab
         if let Some(_tab) = ui.tab_item("Parent Settings") {
-            draw_parent_settings(ui, mode, all_modes, mode_index);
+            draw_parent_settings(ui, mode, all_modes, mode_index, number_format);
         }
 
         // Child A Settings Tab (Blue)
@@ -647,7 +1455,7 @@ fn draw_mode_settings(ui: &imgui::Ui, mode: &mut ModeSettings, all_modes: &[Mode
             let _child_a_color_active = ui.push_style_color(StyleColor::TabActive, [0.3, 0.5, 0.9, 1.0]);
             let _child_a_color_hovered = ui.push_style_color(StyleColor::TabHovered, [0.4, 0.6, 1.0, 1.0]);
             if let Some(_tab) = ui.tab_item("Child A Settings") {
-                draw_child_settings(ui, "Child A", &mut mode.child_a, all_modes);
+                draw_child_settings(ui, "Child A", &mut mode.child_a, all_modes, split_direction, true);
             }
         }
 
@@ -657,7 +1465,7 @@ fn draw_mode_settings(ui: &imgui::Ui, mode: &mut ModeSettings, all_modes: &[Mode
             let _child_b_color_active = ui.push_style_color(StyleColor::TabActive, [0.3, 0.8, 0.4, 1.0]);
             let _child_b_color_hovered = ui.push_style_color(StyleColor::TabHovered, [0.4, 0.9, 0.5, 1.0]);
             if let Some(_tab) = ui.tab_item("Child B Settings") {
-                draw_child_settings(ui, "Child B", &mut mode.child_b, all_modes);
+                draw_child_settings(ui, "Child B", &mut mode.child_b, all_modes, split_direction, false);
             }
         }
 
@@ -669,16 +1477,77 @@ fn draw_mode_settings(ui: &imgui::Ui, mode: &mut ModeSettings, all_modes: &[Mode
 
         if let Some(_tab) = ui.tab_item("Adhesion Settings") {
             if adhesion_tab_enabled {
-                draw_adhesion_settings(ui, &mut mode.adhesion_settings);
+                // Lightest child right after a split is the worst case for spring stability
+                let min_child_mass = mode.split_mass * mode.split_ratio.min(1.0 - mode.split_ratio);
+                draw_adhesion_settings(ui, &mut mode.adhesion_settings, physics_dt, min_child_mass, number_format);
+                ui.spacing();
+                draw_adhesion_inheritance_overview(ui, mode, split_direction);
+                ui.spacing();
+                ui.separator();
+                copy_adhesion = draw_copy_adhesion_button(ui, all_modes, mode_index);
             } else {
                 ui.text_disabled("Enable 'Parent Make Adhesion' to configure adhesion settings");
             }
         }
     }
+    copy_adhesion
+}
+
+const COPY_ADHESION_POPUP: &str = "Copy Adhesion to All Modes?";
+
+/// Button and confirmation popup for copying adhesion settings; true once confirmed
+fn draw_copy_adhesion_button(ui: &imgui::Ui, all_modes: &[ModeSettings], mode_index: usize) -> bool {
+    let other_modes = all_modes.len().saturating_sub(1);
+    {
+        let _disabled = ui.begin_disabled(other_modes == 0);
+        if ui.button("Copy Adhesion to All Modes") {
+            ui.open_popup(COPY_ADHESION_POPUP);
+        }
+    }
+    if ui.is_item_hovered_with_flags(imgui::ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+        ui.tooltip_text("Overwrite the adhesion settings of every other mode with this mode's");
+    }
+
+    let Some(_popup) = ui.modal_popup_config(COPY_ADHESION_POPUP).always_auto_resize(true).begin_popup() else {
+        return false;
+    };
+    ui.text(format!("Replace the adhesion settings of {} other modes with these?", other_modes));
+    let inert = all_modes
+        .iter()
+        .enumerate()
+        .filter(|(index, mode)| *index != mode_index && !mode.parent_make_adhesion)
+        .count();
+    if inert > 0 {
+        ui.text_disabled(format!(
+            "{} of them have 'Parent Make Adhesion' off; they get the values but make no adhesions.",
+            inert
+        ));
+    }
+    ui.spacing();
+    let mut confirmed = false;
+    if ui.button("Copy") {
+        confirmed = true;
+        ui.close_current_popup();
+    }
+    ui.same_line();
+    if ui.button("Cancel") {
+        ui.close_current_popup();
+    }
+    confirmed
+}
+
+/// Give every mode the adhesion settings of `source`, including modes that don't make adhesions
+fn copy_adhesion_to_all_modes(genome: &mut GenomeData, source: usize) {
+    let Some(settings) = genome.modes.get(source).map(|mode| mode.adhesion_settings.clone()) else {
+        return;
+    };
+    for mode in &mut genome.modes {
+        mode.adhesion_settings = settings.clone();
+    }
 }
 
 /// Draw parent settings
-fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[ModeSettings], _mode_index: usize) {
+fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[ModeSettings], _mode_index: usize, number_format: NumberFormat) {
     // Mode name
     ui.text("Mode Name:");
     help_marker(ui, "The display name for this mode. Leave empty to use the default name.");
@@ -700,15 +1569,14 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
 
     // Cell type dropdown
     ui.text("Cell Type:");
-    help_marker(ui, "The type of cell. Test cells gain nutrients automatically. Flagellocyte cells can swim and consume nutrients for propulsion.");
+    help_marker(ui, "The type of cell. Test cells gain nutrients automatically. Flagellocyte cells can swim and consume nutrients for propulsion. Photocytes also grow from light. Phagocytes digest the mass of nearby cells.");
     ui.same_line();
-    let cell_types = vec!["Test", "Flagellocyte"];
-    let current_cell_type = cell_types.get(mode.cell_type as usize).unwrap_or(&"Unknown");
-    if let Some(_token) = ui.begin_combo("##CellType", current_cell_type) {
-        for (i, cell_type_name) in cell_types.iter().enumerate() {
-            let is_selected = i == mode.cell_type as usize;
-            if ui.selectable_config(cell_type_name).selected(is_selected).build() {
-                mode.cell_type = i as i32;
+    let current_cell_type = CellType::from_index(mode.cell_type);
+    if let Some(_token) = ui.begin_combo("##CellType", current_cell_type.name()) {
+        for cell_type in CellType::all() {
+            let is_selected = *cell_type == current_cell_type;
+            if ui.selectable_config(cell_type.name()).selected(is_selected).build() {
+                mode.cell_type = cell_type.index();
             }
         }
     }
@@ -721,12 +1589,21 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
     ui.checkbox("Parent Make Adhesion", &mut mode.parent_make_adhesion);
     help_marker(ui, "When enabled, the parent cell creates an adhesion connection between the two child cells after division.");
 
+    ui.checkbox("Terminal", &mut mode.terminal);
+    help_marker(ui, "Terminal cells never split and never move. Useful for structural or skeletal cells.");
+    if mode.terminal {
+        ui.indent();
+        ui.checkbox("Stop Nutrients", &mut mode.terminal_stops_nutrients);
+        help_marker(ui, "Terminal cells also stop gaining and consuming nutrients.");
+        ui.unindent();
+    }
+
     ui.spacing();
     ui.separator();
     
     // Split mass threshold (range slider)
     ui.text("Split Mass:");
-    help_marker(ui, "Mass required for cell division. Drag the top handles to set a random range, or bring them together for a fixed value. The bottom handle moves the whole range.");
+    help_marker(ui, "Mass required for cell division. Drag the top handles to set a random range, or bring them together for a fixed value. The bottom handle moves the whole range. Scroll over a handle to nudge it (Shift for bigger steps).");
     
     // Convert Option<f32> to actual min value for the slider
     let mut split_mass_min = mode.split_mass_min.unwrap_or(mode.split_mass);
@@ -754,7 +1631,7 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
 
     // Split interval (range slider)
     ui.text("Split Interval:");
-    help_marker(ui, "Time in seconds between cell divisions. Drag the top handles to set a random range, or bring them together for a fixed value. Values >59s display as 'Never'.");
+    help_marker(ui, "Time in seconds between cell divisions. Drag the top handles to set a random range, or bring them together for a fixed value. Scroll over a handle to nudge it (Shift for bigger steps). Values >59s display as 'Never'.");
     
     // Convert Option<f32> to actual min value for the slider
     let mut split_interval_min = mode.split_interval_min.unwrap_or(mode.split_interval);
@@ -768,7 +1645,8 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
         1.0,
         60.0,
         "{:.1}s",
-        Some(59.0), // Show "Never" for values > 59
+        Some(NEVER_SPLIT_INTERVAL), // Show "Never" for values > 59
+        None,
     ) {
         // Update the mode values
         mode.split_interval = split_interval_max;
@@ -797,7 +1675,7 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
     
     ui.same_line();
     ui.set_next_item_width(70.0);
-    let mut text_buffer = format!("{:.2}", mode.split_ratio);
+    let mut text_buffer = number_format.format(mode.split_ratio);
     if ui.input_text("##inputSplitRatio", &mut text_buffer)
         .flags(InputTextFlags::CHARS_DECIMAL | InputTextFlags::AUTO_SELECT_ALL | InputTextFlags::ENTER_RETURNS_TRUE)
         .build()
@@ -823,15 +1701,15 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
         
         ui.text("Nutrient Gain Rate:");
         help_marker(ui, "Mass gained per second. Test cells automatically gain nutrients over time.");
-        slider_with_input_f32(ui, "##NutrientGainRate", &mut mode.nutrient_gain_rate, 0.0, 1.0, ui.content_region_avail()[0]);
+        slider_with_input_f32(ui, "##NutrientGainRate", &mut mode.nutrient_gain_rate, 0.0, 1.0, ui.content_region_avail()[0], number_format);
         
         ui.text("Max Cell Size:");
         help_marker(ui, "Maximum visual size the cell can grow to (0.5 to 2.0 units).");
-        slider_with_input_f32(ui, "##MaxCellSize", &mut mode.max_cell_size, 0.5, 2.0, ui.content_region_avail()[0]);
+        slider_with_input_f32(ui, "##MaxCellSize", &mut mode.max_cell_size, 0.5, 2.0, ui.content_region_avail()[0], number_format);
         
         ui.text("Nutrient Priority:");
         help_marker(ui, "Priority for nutrient transport between adhesion-connected cells.");
-        slider_with_input_f32(ui, "##NutrientPriority", &mut mode.nutrient_priority, 0.1, 10.0, ui.content_region_avail()[0]);
+        slider_with_input_f32(ui, "##NutrientPriority", &mut mode.nutrient_priority, 0.1, 10.0, ui.content_region_avail()[0], number_format);
         
         ui.checkbox("Prioritize When Low", &mut mode.prioritize_when_low);
         help_marker(ui, "When enabled, cells automatically increase their nutrient priority when dangerously low on nutrients.");
@@ -848,15 +1726,69 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
         
         ui.text("Swim Force:");
         help_marker(ui, "Forward thrust force applied to propel the cell.");
-        slider_with_input_f32(ui, "##SwimForce", &mut mode.swim_force, 0.0, 1.0, ui.content_region_avail()[0]);
+        slider_with_input_f32(ui, "##SwimForce", &mut mode.swim_force, 0.0, 1.0, ui.content_region_avail()[0], number_format);
+        
+        ui.text("Max Cell Size:");
+        help_marker(ui, "Maximum visual size the cell can grow to (0.5 to 2.0 units).");
+        slider_with_input_f32(ui, "##MaxCellSize", &mut mode.max_cell_size, 0.5, 2.0, ui.content_region_avail()[0], number_format);
+        
+        ui.text("Nutrient Priority:");
+        help_marker(ui, "Priority for nutrient transport between adhesion-connected cells.");
+        slider_with_input_f32(ui, "##NutrientPriority", &mut mode.nutrient_priority, 0.1, 10.0, ui.content_region_avail()[0], number_format);
+        
+        ui.checkbox("Prioritize When Low", &mut mode.prioritize_when_low);
+        help_marker(ui, "When enabled, cells automatically increase their nutrient priority when dangerously low on nutrients.");
+        
+        ui.spacing();
+        ui.separator();
+        ui.spacing();
+    }
+    
+    // Photocyte settings
+    if mode.cell_type == 2 {
+        ui.text("Photocyte Settings:");
+        ui.separator();
+        
+        ui.text("Light Absorption Rate:");
+        help_marker(ui, "Mass gained per second from light, added to the nutrient gain rate.");
+        slider_with_input_f32(ui, "##LightAbsorptionRate", &mut mode.light_absorption_rate, 0.0, 1.0, ui.content_region_avail()[0], number_format);
+        
+        ui.text("Max Cell Size:");
+        help_marker(ui, "Maximum visual size the cell can grow to (0.5 to 2.0 units).");
+        slider_with_input_f32(ui, "##MaxCellSize", &mut mode.max_cell_size, 0.5, 2.0, ui.content_region_avail()[0], number_format);
+        
+        ui.text("Nutrient Priority:");
+        help_marker(ui, "Priority for nutrient transport between adhesion-connected cells.");
+        slider_with_input_f32(ui, "##NutrientPriority", &mut mode.nutrient_priority, 0.1, 10.0, ui.content_region_avail()[0], number_format);
+        
+        ui.checkbox("Prioritize When Low", &mut mode.prioritize_when_low);
+        help_marker(ui, "When enabled, cells automatically increase their nutrient priority when dangerously low on nutrients.");
+        
+        ui.spacing();
+        ui.separator();
+        ui.spacing();
+    }
+    
+    // Phagocyte settings
+    if mode.cell_type == 3 {
+        ui.text("Phagocyte Settings:");
+        ui.separator();
+        
+        ui.text("Engulf Radius:");
+        help_marker(ui, "How far beyond touching another cell can be and still be digested. Phagocytes never digest each other.");
+        slider_with_input_f32(ui, "##EngulfRadius", &mut mode.engulf_radius, 0.0, 2.0, ui.content_region_avail()[0], number_format);
+        
+        ui.text("Digestion Rate:");
+        help_marker(ui, "Mass drained per second from each cell within reach and added to the phagocyte.");
+        slider_with_input_f32(ui, "##DigestionRate", &mut mode.digestion_rate, 0.0, 2.0, ui.content_region_avail()[0], number_format);
         
         ui.text("Max Cell Size:");
         help_marker(ui, "Maximum visual size the cell can grow to (0.5 to 2.0 units).");
-        slider_with_input_f32(ui, "##MaxCellSize", &mut mode.max_cell_size, 0.5, 2.0, ui.content_region_avail()[0]);
+        slider_with_input_f32(ui, "##MaxCellSize", &mut mode.max_cell_size, 0.5, 2.0, ui.content_region_avail()[0], number_format);
         
         ui.text("Nutrient Priority:");
         help_marker(ui, "Priority for nutrient transport between adhesion-connected cells.");
-        slider_with_input_f32(ui, "##NutrientPriority", &mut mode.nutrient_priority, 0.1, 10.0, ui.content_region_avail()[0]);
+        slider_with_input_f32(ui, "##NutrientPriority", &mut mode.nutrient_priority, 0.1, 10.0, ui.content_region_avail()[0], number_format);
         
         ui.checkbox("Prioritize When Low", &mut mode.prioritize_when_low);
         help_marker(ui, "When enabled, cells automatically increase their nutrient priority when dangerously low on nutrients.");
@@ -868,7 +1800,7 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
 
     // Parent split angle
     ui.text("Parent Split Angle:");
-    help_marker(ui, "The direction the parent cell splits, defined by pitch (up/down) and yaw (left/right) angles in degrees.");
+    help_marker(ui, "The direction the parent cell splits, defined by pitch (up/down) and yaw (left/right) angles in degrees. Double-click a dial to reset it to 0.");
     ui.checkbox("Enable Angle Snapping##Parent", &mut mode.enable_parent_angle_snapping);
     help_marker(ui, "When enabled, angles snap to 11.25° increments for precise alignment.");
     ui.spacing();
@@ -886,7 +1818,8 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
         "%.2f°",
         0.0,
         0.0,
-        mode.enable_parent_angle_snapping
+        mode.enable_parent_angle_snapping,
+        0.0,
     );
 
     ui.next_column();
@@ -901,10 +1834,16 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
         "%.2f°",
         0.0,
         0.0,
-        mode.enable_parent_angle_snapping
+        mode.enable_parent_angle_snapping,
+        0.0,
     );
     ui.columns(1, "", false);
 
+    ui.spacing();
+    ui.text("Split Angle Jitter:");
+    help_marker(ui, "Randomly tilts each split within a cone of this many degrees around the split direction. The same cell always gets the same tilt, so runs stay reproducible. 0 keeps splits perfectly aligned.");
+    slider_with_input_f32(ui, "##SplitAngleJitter", &mut mode.split_angle_jitter, 0.0, 90.0, ui.content_region_avail()[0], number_format);
+
     ui.spacing();
     ui.separator();
     ui.spacing();
@@ -918,10 +1857,6 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
     ui.text("Min Connections:");
     help_marker(ui, "Minimum number of adhesion connections required before the cell can split. Useful for coordinated growth.");
     slider_with_input_i32(ui, "##MinAdhesions", &mut mode.min_adhesions, 0, 20, ui.content_region_avail()[0]);
-    
-    if mode.min_adhesions > mode.max_adhesions {
-        mode.min_adhesions = mode.max_adhesions;
-    }
 
     // Max splits
     ui.text("Max Splits:");
@@ -971,7 +1906,14 @@ fn draw_parent_settings(ui: &imgui::Ui, mode: &mut ModeSettings, _all_modes: &[M
 }
 
 /// Draw child settings
-fn draw_child_settings(ui: &imgui::Ui, label: &str, child: &mut ChildSettings, all_modes: &[ModeSettings]) {
+fn draw_child_settings(
+    ui: &imgui::Ui,
+    label: &str,
+    child: &mut ChildSettings,
+    all_modes: &[ModeSettings],
+    split_direction: glam::Vec3,
+    is_child_a: bool,
+) {
     ui.text("Mode:");
     help_marker(ui, "The mode this child cell will adopt after division.");
     
@@ -1001,6 +1943,9 @@ fn draw_child_settings(ui: &imgui::Ui, label: &str, child: &mut ChildSettings, a
 
     ui.checkbox("Keep Adhesion", &mut child.keep_adhesion);
     help_marker(ui, "When enabled, this child inherits the parent's adhesion connections based on zone classification.");
+    
+    ui.spacing();
+    draw_adhesion_zone_preview(ui, child.keep_adhesion, is_child_a, split_direction);
 
     ui.spacing();
     ui.separator();
@@ -1022,47 +1967,245 @@ fn draw_child_settings(ui: &imgui::Ui, label: &str, child: &mut ChildSettings, a
         child.orientation = from_glam_quat(glam_quat);
     }
 
-    ui.spacing();
-    let button_label = format!("Reset Orientation ({})", label);
-    if ui.button(&button_label) {
-        child.orientation = Quat::IDENTITY;
+    ui.spacing();
+    let button_label = format!("Reset Orientation ({})", label);
+    if ui.button(&button_label) {
+        child.orientation = Quat::IDENTITY;
+    }
+
+    ui.separator();
+}
+
+/// Example bonds drawn in the zone diagrams, one every 30°
+const EXAMPLE_BOND_COUNT: usize = 12;
+const ZONE_CHILD_A_COLOR: [f32; 4] = [0.3, 0.5, 0.9, 1.0];
+const ZONE_CHILD_B_COLOR: [f32; 4] = [0.3, 0.8, 0.4, 1.0];
+const ZONE_C_COLOR: [f32; 4] = [1.0, 0.8, 0.3, 1.0];
+const ZONE_DIM_COLOR: [f32; 4] = [0.4, 0.4, 0.4, 0.6];
+
+/// Draw a schematic of how the parent's adhesions are split into zones and which ones this child keeps
+fn draw_adhesion_zone_preview(ui: &imgui::Ui, keep_adhesion: bool, is_child_a: bool, split_direction: glam::Vec3) {
+    let kept = draw_adhesion_zone_diagram(ui, split_direction, |zone| {
+        keep_adhesion && if is_child_a { zone.inherited_by_child_a() } else { zone.inherited_by_child_b() }
+    });
+    
+    if keep_adhesion {
+        ui.text(format!("This child keeps {} of {} example bonds.", kept, EXAMPLE_BOND_COUNT));
+    } else {
+        ui.text_colored(ZONE_DIM_COLOR, "Keep Adhesion is off: this child starts with no inherited bonds.");
+    }
+}
+
+/// Both children's share of the parent's adhesions, for the Adhesion Settings tab
+fn draw_adhesion_inheritance_overview(ui: &imgui::Ui, mode: &ModeSettings, split_direction: glam::Vec3) {
+    if !ui.collapsing_header("Inheritance Zones", imgui::TreeNodeFlags::empty()) {
+        return;
+    }
+    let (keep_a, keep_b) = (mode.child_a.keep_adhesion, mode.child_b.keep_adhesion);
+    draw_adhesion_zone_diagram(ui, split_direction, |zone| {
+        (keep_a && zone.inherited_by_child_a()) || (keep_b && zone.inherited_by_child_b())
+    });
+    
+    let zones = example_bond_zones(split_direction);
+    let inherited_a = zones.iter().filter(|(_, zone)| zone.inherited_by_child_a()).count();
+    let inherited_b = zones.iter().filter(|(_, zone)| zone.inherited_by_child_b()).count();
+    for (label, keep, color, inherited) in [
+        ("Child A", keep_a, ZONE_CHILD_A_COLOR, inherited_a),
+        ("Child B", keep_b, ZONE_CHILD_B_COLOR, inherited_b),
+    ] {
+        ui.text_colored(color, label);
+        ui.same_line();
+        if keep {
+            ui.text(format!("keeps {} of {} example bonds", inherited, EXAMPLE_BOND_COUNT));
+        } else {
+            ui.text_colored(ZONE_DIM_COLOR, "keeps none (Keep Adhesion is off in its tab)");
+        }
+    }
+}
+
+/// View axes for the zone diagrams: whichever of the side view (Z right, Y up) or
+/// top view (Z right, X up) shows more of the split axis
+fn adhesion_zone_view(split_direction: glam::Vec3) -> (&'static str, glam::Vec3, glam::Vec3) {
+    if split_direction.y.abs() >= split_direction.x.abs() {
+        ("side view", glam::Vec3::Z, glam::Vec3::Y)
+    } else {
+        ("top view", glam::Vec3::Z, glam::Vec3::X)
+    }
+}
+
+/// Example bonds in the view plane, with their zone classified against the full 3D split direction
+fn example_bond_zones(split_direction: glam::Vec3) -> Vec<(glam::Vec2, adhesion_zones::AdhesionZone)> {
+    let (_, right_axis, up_axis) = adhesion_zone_view(split_direction);
+    (0..EXAMPLE_BOND_COUNT)
+        .map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / EXAMPLE_BOND_COUNT as f32;
+            let bond_2d = glam::Vec2::new(angle.cos(), angle.sin());
+            let bond_3d = right_axis * bond_2d.x + up_axis * bond_2d.y;
+            (bond_2d, adhesion_zones::classify_bond_direction(bond_3d, split_direction))
+        })
+        .collect()
+}
+
+/// Cross-section of the parent through the split axis with the split plane, the half each child
+/// gets, and example bonds every 30°. Bonds `keeps` rejects are dimmed. Returns how many are kept.
+fn draw_adhesion_zone_diagram(
+    ui: &imgui::Ui,
+    split_direction: glam::Vec3,
+    keeps: impl Fn(adhesion_zones::AdhesionZone) -> bool,
+) -> usize {
+    let (view_name, right_axis, up_axis) = adhesion_zone_view(split_direction);
+    let split_2d = glam::Vec2::new(split_direction.dot(right_axis), split_direction.dot(up_axis)).normalize_or_zero();
+    
+    ui.text_disabled(format!("Adhesion inheritance ({}):", view_name));
+    
+    let size = 140.0_f32.min(ui.content_region_avail()[0]);
+    let origin = ui.cursor_screen_pos();
+    let center = [origin[0] + size * 0.5, origin[1] + size * 0.5];
+    let radius = size * 0.28;
+    // Screen Y points down
+    let to_screen = |v: glam::Vec2, scale: f32| [center[0] + v.x * scale, center[1] - v.y * scale];
+    
+    let draw_list = ui.get_window_draw_list();
+    
+    // Parent cell, tinted by which child keeps each half
+    let normal_angle = split_2d.y.atan2(split_2d.x);
+    for (side_angle, color) in [(normal_angle, ZONE_CHILD_A_COLOR), (normal_angle + std::f32::consts::PI, ZONE_CHILD_B_COLOR)] {
+        let half_disc: Vec<[f32; 2]> = (0..=16)
+            .map(|i| {
+                let a = side_angle - std::f32::consts::FRAC_PI_2 + std::f32::consts::PI * i as f32 / 16.0;
+                to_screen(glam::Vec2::new(a.cos(), a.sin()), radius)
+            })
+            .collect();
+        draw_list
+            .add_polyline(half_disc, [color[0], color[1], color[2], 0.25])
+            .filled(true)
+            .build();
+    }
+    draw_list.add_circle(center, radius, [0.8, 0.8, 0.8, 1.0]).num_segments(32).build();
+    
+    // Split plane
+    let plane_2d = glam::Vec2::new(-split_2d.y, split_2d.x);
+    draw_list
+        .add_line(to_screen(plane_2d, radius * 1.2), to_screen(-plane_2d, radius * 1.2), ZONE_C_COLOR)
+        .thickness(1.5)
+        .build();
+    
+    // Child markers
+    draw_list.add_text(to_screen(split_2d, radius * 0.5), ZONE_CHILD_A_COLOR, "A");
+    draw_list.add_text(to_screen(-split_2d, radius * 0.5), ZONE_CHILD_B_COLOR, "B");
+    
+    let mut kept = 0;
+    for (bond_2d, zone) in example_bond_zones(split_direction) {
+        let bond_kept = keeps(zone);
+        if bond_kept {
+            kept += 1;
+        }
+        
+        let color = if !bond_kept {
+            ZONE_DIM_COLOR
+        } else {
+            match zone {
+                adhesion_zones::AdhesionZone::ZoneA => ZONE_CHILD_B_COLOR,
+                adhesion_zones::AdhesionZone::ZoneB => ZONE_CHILD_A_COLOR,
+                adhesion_zones::AdhesionZone::ZoneC => ZONE_C_COLOR,
+            }
+        };
+        let start = to_screen(bond_2d, radius);
+        let end = to_screen(bond_2d, radius * 1.55);
+        draw_list.add_line(start, end, color).thickness(if bond_kept { 2.0 } else { 1.0 }).build();
+        draw_list.add_circle(end, 3.0, color).filled(bond_kept).build();
     }
+    
+    ui.dummy([size, size]);
+    
+    // Legend
+    ui.text_colored(ZONE_CHILD_B_COLOR, "Zone A");
+    ui.same_line();
+    ui.text("away from split direction -> Child B");
+    ui.text_colored(ZONE_CHILD_A_COLOR, "Zone B");
+    ui.same_line();
+    ui.text("along split direction -> Child A");
+    ui.text_colored(ZONE_C_COLOR, "Zone C");
+    ui.same_line();
+    ui.text(format!(
+        "within {:.0}° of split plane -> both",
+        adhesion_zones::EQUATORIAL_HALF_WIDTH_DEG
+    ));
+    kept
+}
 
-    ui.separator();
+/// Warn when the linear spring would be unstable under explicit integration at the current timestep
+fn draw_spring_stability_warning(ui: &imgui::Ui, adhesion: &mut AdhesionSettings, physics_dt: f32, cell_mass: f32) {
+    let cell_mass = cell_mass.max(0.01);
+    let stiffness = adhesion.linear_spring_stiffness;
+    let damping = adhesion.linear_spring_damping;
+    if adhesion_forces::is_spring_stable(stiffness, damping, cell_mass, cell_mass, physics_dt) {
+        return;
+    }
+    
+    let warning_color = [1.0, 0.6, 0.2, 1.0];
+    ui.text_colored(warning_color, "Warning: spring may be unstable");
+    help_marker(ui, &format!(
+        "Checked for two cells of mass {:.2} (the lighter child after a split) at a timestep of {:.4}s. \
+         Explicitly integrated springs explode when they oscillate faster than the timestep can resolve.",
+        cell_mass, physics_dt
+    ));
+    
+    let max_damping = adhesion_forces::max_stable_damping(cell_mass, cell_mass, physics_dt);
+    if damping > max_damping {
+        ui.text_wrapped(format!("Damping is too high for this timestep. Suggested max damping: {:.2}", max_damping));
+        if ui.button("Use Safe Damping") {
+            adhesion.linear_spring_damping = max_damping;
+        }
+    } else {
+        let max_stiffness = adhesion_forces::max_stable_stiffness(damping, cell_mass, cell_mass, physics_dt);
+        ui.text_wrapped(format!("Suggested max stiffness: {:.1}", max_stiffness));
+        if ui.button("Use Safe Stiffness") {
+            adhesion.linear_spring_stiffness = max_stiffness;
+        }
+    }
 }
 
 /// Draw adhesion settings
-fn draw_adhesion_settings(ui: &imgui::Ui, adhesion: &mut AdhesionSettings) {
+fn draw_adhesion_settings(ui: &imgui::Ui, adhesion: &mut AdhesionSettings, physics_dt: f32, cell_mass: f32, number_format: NumberFormat) {
     ui.checkbox("Adhesion Can Break", &mut adhesion.can_break);
     help_marker(ui, "When enabled, adhesion connections can break if the force exceeds the break force threshold.");
 
     ui.text("Adhesion Break Force:");
     help_marker(ui, "The force threshold at which adhesion connections break.");
-    slider_with_input_f32(ui, "##AdhesionBreakForce", &mut adhesion.break_force, 0.1, 100.0, ui.content_region_avail()[0]);
+    slider_with_input_f32(ui, "##AdhesionBreakForce", &mut adhesion.break_force, 0.1, 100.0, ui.content_region_avail()[0], number_format);
+
+    ui.checkbox("Auto Rest Length", &mut adhesion.auto_rest_length);
+    help_marker(ui, "Rest at the sum of the connected cells' current radii, so connections stay snug as cells grow.");
 
     ui.text("Adhesion Rest Length:");
     help_marker(ui, "The equilibrium distance for the adhesion spring.");
-    slider_with_input_f32(ui, "##AdhesionRestLength", &mut adhesion.rest_length, 0.5, 5.0, ui.content_region_avail()[0]);
+    {
+        let _disabled = ui.begin_disabled(adhesion.auto_rest_length);
+        slider_with_input_f32(ui, "##AdhesionRestLength", &mut adhesion.rest_length, 0.5, 5.0, ui.content_region_avail()[0], number_format);
+    }
 
     ui.text("Linear Spring Stiffness:");
     help_marker(ui, "Stiffness of the linear spring connecting cells.");
-    slider_with_input_f32(ui, "##LinearSpringStiffness", &mut adhesion.linear_spring_stiffness, 0.1, 500.0, ui.content_region_avail()[0]);
+    slider_with_input_f32(ui, "##LinearSpringStiffness", &mut adhesion.linear_spring_stiffness, 0.1, 500.0, ui.content_region_avail()[0], number_format);
 
     ui.text("Linear Spring Damping:");
     help_marker(ui, "Damping of linear oscillations.");
-    slider_with_input_f32(ui, "##LinearSpringDamping", &mut adhesion.linear_spring_damping, 0.0, 10.0, ui.content_region_avail()[0]);
+    slider_with_input_f32(ui, "##LinearSpringDamping", &mut adhesion.linear_spring_damping, 0.0, 10.0, ui.content_region_avail()[0], number_format);
+
+    draw_spring_stability_warning(ui, adhesion, physics_dt, cell_mass);
 
     ui.text("Angular Spring Stiffness:");
     help_marker(ui, "Stiffness of rotational alignment between connected cells.");
-    slider_with_input_f32(ui, "##AngularSpringStiffness", &mut adhesion.orientation_spring_stiffness, 0.1, 100.0, ui.content_region_avail()[0]);
+    slider_with_input_f32(ui, "##AngularSpringStiffness", &mut adhesion.orientation_spring_stiffness, 0.1, 100.0, ui.content_region_avail()[0], number_format);
 
     ui.text("Angular Spring Damping:");
     help_marker(ui, "Damping of rotational oscillations.");
-    slider_with_input_f32(ui, "##AngularSpringDamping", &mut adhesion.orientation_spring_damping, 0.0, 10.0, ui.content_region_avail()[0]);
+    slider_with_input_f32(ui, "##AngularSpringDamping", &mut adhesion.orientation_spring_damping, 0.0, 10.0, ui.content_region_avail()[0], number_format);
 
     ui.text("Max Angular Deviation:");
     help_marker(ui, "Maximum allowed angular deviation in degrees.");
-    slider_with_input_f32(ui, "##MaxAngularDeviation", &mut adhesion.max_angular_deviation, 0.0, 180.0, ui.content_region_avail()[0]);
+    slider_with_input_f32(ui, "##MaxAngularDeviation", &mut adhesion.max_angular_deviation, 0.0, 180.0, ui.content_region_avail()[0], number_format);
 
     ui.spacing();
     ui.separator();
@@ -1073,11 +2216,11 @@ fn draw_adhesion_settings(ui: &imgui::Ui, adhesion: &mut AdhesionSettings) {
 
     ui.text("Twist Constraint Stiffness:");
     help_marker(ui, "Resistance to twisting motion around the connection axis.");
-    slider_with_input_f32(ui, "##TwistConstraintStiffness", &mut adhesion.twist_constraint_stiffness, 0.0, 2.0, ui.content_region_avail()[0]);
+    slider_with_input_f32(ui, "##TwistConstraintStiffness", &mut adhesion.twist_constraint_stiffness, 0.0, 2.0, ui.content_region_avail()[0], number_format);
 
     ui.text("Twist Constraint Damping:");
     help_marker(ui, "Damping of twist oscillations.");
-    slider_with_input_f32(ui, "##TwistConstraintDamping", &mut adhesion.twist_constraint_damping, 0.0, 10.0, ui.content_region_avail()[0]);
+    slider_with_input_f32(ui, "##TwistConstraintDamping", &mut adhesion.twist_constraint_damping, 0.0, 10.0, ui.content_region_avail()[0], number_format);
 }
 
 /// Render the genome graph window with full node editor
@@ -1095,7 +2238,7 @@ fn render_genome_graph_window(
 
     // Calculate layout if needed
     if node_graph.needs_layout {
-        node_graph.calculate_grid_layout();
+        node_graph.calculate_layout(graph_state.layout, current_genome.genome.initial_mode);
     }
 
     let mut show_window = current_genome.show_genome_graph;
@@ -1106,7 +2249,46 @@ fn render_genome_graph_window(
         .size([1000.0, 640.0], Condition::FirstUseEver)
         .build(|| {
             // Show help text
-            ui.text_colored([0.7, 0.7, 0.7, 1.0], "Shift+Click: Add mode | Shift+Right-click node: Remove | Right-click link: Self-ref | Middle drag: Pan | Scroll: Zoom");
+            ui.text_colored([0.7, 0.7, 0.7, 1.0], "Right-click: Menu | Shift+Click: Add mode | Shift+Right-click node: Remove | Right-click link: Self-ref | Middle drag: Pan | Scroll: Zoom");
+            if ui.button("Auto Layout") {
+                node_graph.calculate_layout(graph_state.layout, current_genome.genome.initial_mode);
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Rearrange every node. Tree places children below their parents, starting from the initial mode; modes it never reaches go in a column to the right.");
+            }
+            ui.same_line();
+            ui.set_next_item_width(80.0);
+            if let Some(_combo) = ui.begin_combo("##GraphLayout", graph_state.layout.name()) {
+                for layout in GraphLayout::ALL {
+                    if ui.selectable_config(layout.name()).selected(graph_state.layout == layout).build() {
+                        graph_state.layout = layout;
+                    }
+                }
+            }
+            ui.same_line();
+            let fit_requested = ui.button("Fit to View");
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Move and shrink the graph so every node is visible");
+            }
+            ui.same_line();
+            let reset_pan_requested = ui.button("Reset Pan");
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Clear panning and zoom and lay the nodes out again");
+            }
+            ui.same_line();
+            ui.checkbox("Minimap", &mut graph_state.show_minimap);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Overview of every node; click or drag in it to move the view there");
+            }
+            ui.same_line();
+            ui.checkbox("Block Duplicate Children", &mut graph_state.block_duplicate_children);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Refuse links that send Child A and Child B to the same mode instead of only warning");
+            }
+            if let Some(warning) = &graph_state.link_warning {
+                ui.same_line();
+                ui.text_colored([1.0, 0.55, 0.0, 1.0], warning);
+            }
             ui.separator();
             
             // Thread-local storage for imnodes context
@@ -1254,16 +2436,50 @@ fn render_genome_graph_window(
                     let mut dropped_link_id = unsafe { std::mem::transmute(0i32) };
                     let mut hovered_node_id: i32 = 0;
 
+                    // Editor space is relative to the canvas origin and shifted by the panning
+                    let canvas_origin = ui.cursor_screen_pos();
+                    let canvas_size = ui.content_region_avail();
+                    // Toolbar requests wait until the canvas size is known and the editor context is set
+                    if fit_requested {
+                        fit_nodes_to_view(node_graph, canvas_size);
+                    }
+                    if reset_pan_requested {
+                        reset_graph_pan(node_graph, graph_state, current_genome.genome.initial_mode);
+                    }
+                    let to_editor_space = |screen: [f32; 2]| {
+                        let mut panning = imnodes_sys::ImVec2 { x: 0.0, y: 0.0 };
+                        unsafe { imnodes_sys::imnodes_EditorContextGetPanning(&mut panning as *mut _) };
+                        [screen[0] - canvas_origin[0] - panning.x, screen[1] - canvas_origin[1] - panning.y]
+                    };
+
+                    // Links on multi-mode division cycles, keyed by link index for the hover tooltip
+                    let cycle_groups = division_cycle_groups(&current_genome.genome);
+                    let cycle_links: Vec<bool> = node_graph
+                        .links
+                        .iter()
+                        .map(|(from_node, to_node, _)| {
+                            match (node_graph.get_mode_for_node(*from_node), node_graph.get_mode_for_node(*to_node)) {
+                                (Some(parent), Some(child)) => is_division_cycle_link(&cycle_groups, parent, child),
+                                _ => false,
+                            }
+                        })
+                        .collect();
+
                     editor(editor_context, |mut node_editor| {
                         // Draw nodes for each mode
                         for (mode_idx, mode) in current_genome.genome.modes.iter().enumerate() {
                             if let Some(node_id) = node_graph.get_node_for_mode(mode_idx) {
-                                draw_genome_node(ui, &mut node_editor, node_id, mode, mode_idx, &node_graph);
+                                let deadlocks = split_deadlocks(&current_genome.genome, mode_idx);
+                                draw_genome_node(ui, &mut node_editor, node_id, mode, mode_idx, &node_graph, &deadlocks);
                             }
                         }
 
-                        // Draw links between nodes
+                        // Draw links between nodes, flagging division cycles and parents whose children share a target
                         for (link_idx, (from_node, to_node, is_child_a)) in node_graph.links.iter().enumerate() {
+                            let duplicate = node_graph
+                                .get_mode_for_node(*from_node)
+                                .and_then(|idx| current_genome.genome.modes.get(idx).map(|mode| has_duplicate_children(mode, idx)))
+                                .unwrap_or(false);
                             let output_pin = if *is_child_a {
                                 *from_node * 100 + 1
                             } else {
@@ -1276,8 +2492,24 @@ fn render_genome_graph_window(
                                 let link_id: imnodes::LinkId = std::mem::transmute(link_idx as i32);
                                 let out_id: imnodes::OutputPinId = std::mem::transmute(output_pin);
                                 let in_id: imnodes::InputPinId = std::mem::transmute(input_pin);
+                                let highlight = if cycle_links[link_idx] {
+                                    Some(CYCLE_LINK_COLOR)
+                                } else if duplicate {
+                                    Some(DUPLICATE_CHILD_LINK_COLOR)
+                                } else {
+                                    None
+                                };
+                                if let Some(color) = highlight {
+                                    imnodes_sys::imnodes_PushColorStyle(
+                                        imnodes_sys::ImNodesCol__ImNodesCol_Link as i32,
+                                        color,
+                                    );
+                                }
                                 // Note: add_link signature is (link_id, input_pin_id, output_pin_id)
                                 node_editor.add_link(link_id, in_id, out_id);
+                                if highlight.is_some() {
+                                    imnodes_sys::imnodes_PopColorStyle();
+                                }
                             }
                         }
                     });
@@ -1301,7 +2533,7 @@ fn render_genome_graph_window(
 
                     // Handle link creation
                     if link_was_created {
-                        handle_link_created(current_genome, node_graph, created_start_pin, created_end_pin);
+                        graph_state.link_warning = handle_link_created(current_genome, node_graph, created_start_pin, created_end_pin, graph_state.block_duplicate_children);
                         graph_state.dragging_from_pin = None; // Clear drag state
                     }
 
@@ -1313,7 +2545,7 @@ fn render_genome_graph_window(
                             let parent_input_pin: imnodes::InputPinId = unsafe { std::mem::transmute(hovered_node_id * 100) };
                             
                             // Create the new connection
-                            handle_link_created(current_genome, node_graph, output_pin, parent_input_pin);
+                            graph_state.link_warning = handle_link_created(current_genome, node_graph, output_pin, parent_input_pin, graph_state.block_duplicate_children);
                         } else {
                             // Not over a node, so destroy the link (make it self-referential)
                             handle_link_destroyed(current_genome, node_graph, dropped_link_id);
@@ -1321,6 +2553,11 @@ fn render_genome_graph_window(
                         graph_state.dragging_from_pin = None; // Clear drag state
                     }
                     
+                    if link_is_hovered && cycle_links.get(hovered_link_id as usize).copied().unwrap_or(false) {
+                        ui.tooltip_text("This creates a division cycle: cells alternate between these modes and keep dividing.\n\
+                                         Right-click to make the link self-referential.");
+                    }
+                    
                     // Handle right-click on link to make it self-referential
                     if link_is_hovered && ui.is_mouse_clicked(imgui::MouseButton::Right) {
                         handle_link_make_self_referential(current_genome, node_graph, hovered_link_id);
@@ -1329,103 +2566,34 @@ fn render_genome_graph_window(
                     // Handle node click to select mode (without shift)
                     if node_is_hovered && ui.is_mouse_clicked(imgui::MouseButton::Left) && !ui.io().key_shift {
                         if let Some(mode_idx) = node_graph.get_mode_for_node(hovered_node_id) {
-                            current_genome.selected_mode_index = mode_idx as i32;
+                            current_genome.select_single_mode(mode_idx);
                         }
                     }
 
-                    // Handle Shift+Click to add new mode
-                    if ui.io().key_shift && ui.is_mouse_clicked(imgui::MouseButton::Left) && !node_is_hovered {
-                        // Get mouse position in editor space using imnodes API
-                        let mouse_pos_editor = unsafe {
-                            let mut pos = imnodes_sys::ImVec2 { x: 0.0, y: 0.0 };
-                            imnodes_sys::imnodes_EditorContextGetPanning(&mut pos as *mut _);
-                            let mouse_screen = ui.io().mouse_pos;
-                            let window_pos = ui.window_pos();
-                            [
-                                mouse_screen[0] - window_pos[0] - pos.x,
-                                mouse_screen[1] - window_pos[1] - 40.0 - pos.y, // Subtract title bar and help text height
-                            ]
-                        };
-                        
-                        // Insert after selected mode
-                        let selected_idx = current_genome.selected_mode_index as usize;
-                        let insert_idx = if selected_idx < current_genome.genome.modes.len() {
-                            selected_idx + 1
-                        } else {
-                            current_genome.genome.modes.len()
-                        };
-                        
-                        // Generate new mode name based on selected mode's default name
-                        let new_name = if selected_idx < current_genome.genome.modes.len() {
-                            generate_next_mode_name(&current_genome.genome.modes[selected_idx].default_name, &current_genome.genome.modes)
-                        } else {
-                            format!("Mode {}", current_genome.genome.modes.len())
-                        };
-                        
-                        let new_mode = ModeSettings::new_self_splitting(
-                            insert_idx as i32,
-                            new_name,
-                        );
-                        
-                        current_genome.genome.modes.insert(insert_idx, new_mode);
-                        
-                        // Update mode numbers for all modes after insertion point
-                        update_mode_numbers_after_insert(&mut current_genome.genome, insert_idx);
-                        
-                        // Keep the current mode selected (adjust index if needed)
-                        if insert_idx <= selected_idx && selected_idx < current_genome.genome.modes.len() {
-                            current_genome.selected_mode_index = (selected_idx + 1) as i32;
-                        }
-                        
-                        // Store the desired position for the new mode before rebuild
-                        node_graph.pending_position = Some((insert_idx, mouse_pos_editor[0], mouse_pos_editor[1]));
-                        
-                        // Mark node graph for rebuild
-                        node_graph.mark_for_rebuild();
+                    // Shift+Click on empty canvas is the accelerator for "Add Mode Here"
+                    let over_minimap = graph_state.show_minimap
+                        && rect_contains(minimap_rect(canvas_origin, canvas_size), ui.io().mouse_pos);
+                    if ui.io().key_shift && ui.is_mouse_clicked(imgui::MouseButton::Left) && !node_is_hovered && !over_minimap {
+                        add_mode_at(current_genome, node_graph, to_editor_space(ui.io().mouse_pos));
                     }
 
-                    // Handle Shift+Right-click to remove node
+                    // Shift+Right-click on a node is the accelerator for "Delete"
+                    let right_released = ui.is_mouse_released(imgui::MouseButton::Right);
                     if node_is_hovered && ui.is_mouse_clicked(imgui::MouseButton::Right) && ui.io().key_shift {
-                        // Get the mode index for the hovered node
                         if let Some(mode_idx) = node_graph.get_mode_for_node(hovered_node_id) {
-                            let initial_mode = current_genome.genome.initial_mode as usize;
-                            
-                            // Don't allow removing the last mode or the initial mode
-                            if current_genome.genome.modes.len() > 1 && mode_idx != initial_mode {
-                                // Remove the mode
-                                current_genome.genome.modes.remove(mode_idx);
-                                
-                                // Update references in other modes
-                                for (idx, mode) in current_genome.genome.modes.iter_mut().enumerate() {
-                                    // If child references the removed mode, make it self-splitting
-                                    if mode.child_a.mode_number == mode_idx as i32 {
-                                        mode.child_a.mode_number = idx as i32;
-                                    } else if mode.child_a.mode_number > mode_idx as i32 {
-                                        // Shift down references to modes after the removed one
-                                        mode.child_a.mode_number -= 1;
-                                    }
-                                    
-                                    if mode.child_b.mode_number == mode_idx as i32 {
-                                        mode.child_b.mode_number = idx as i32;
-                                    } else if mode.child_b.mode_number > mode_idx as i32 {
-                                        mode.child_b.mode_number -= 1;
-                                    }
-                                }
-                                
-                                // Update initial mode if needed
-                                if current_genome.genome.initial_mode == mode_idx as i32 {
-                                    current_genome.genome.initial_mode = 0;
-                                } else if current_genome.genome.initial_mode > mode_idx as i32 {
-                                    current_genome.genome.initial_mode -= 1;
-                                }
-                                
-                                // Update selected mode if needed
-                                if current_genome.selected_mode_index >= current_genome.genome.modes.len() as i32 {
-                                    current_genome.selected_mode_index = (current_genome.genome.modes.len() as i32) - 1;
-                                }
-                                
-                                // Mark node graph for rebuild
-                                node_graph.mark_for_rebuild();
+                            remove_mode(current_genome, node_graph, mode_idx);
+                        }
+                    } else if right_released && !ui.io().key_shift && !link_is_hovered && !over_minimap && ui.is_window_hovered_with_flags(imgui::WindowHoveredFlags::CHILD_WINDOWS) {
+                        // A plain right-click (not a right-drag pan) opens the context menu
+                        let drag = ui.mouse_drag_delta_with_button(imgui::MouseButton::Right);
+                        if drag[0].abs() < CONTEXT_MENU_DRAG_THRESHOLD && drag[1].abs() < CONTEXT_MENU_DRAG_THRESHOLD {
+                            graph_state.context_position = to_editor_space(ui.io().mouse_pos);
+                            if node_is_hovered {
+                                graph_state.context_node = node_graph.get_mode_for_node(hovered_node_id);
+                                ui.open_popup("GraphNodeContext");
+                            } else {
+                                graph_state.context_node = None;
+                                ui.open_popup("GraphCanvasContext");
                             }
                         }
                     }
@@ -1438,6 +2606,12 @@ fn render_genome_graph_window(
                             node_graph.set_node_position(node_id, pos.x, pos.y);
                         }
                     }
+
+                    // Anything that moves nodes from here on must come after the read-back above
+                    render_graph_context_menus(ui, current_genome, node_graph, graph_state, canvas_size);
+                    if graph_state.show_minimap {
+                        draw_graph_minimap(ui, current_genome, node_graph, graph_state, canvas_origin, canvas_size);
+                    }
                 }
             });
         });
@@ -1446,6 +2620,43 @@ fn render_genome_graph_window(
     current_genome.show_genome_graph = show_window;
 }
 
+/// Name search and cell type combo above the mode list
+fn draw_mode_list_filter(ui: &imgui::Ui, filter: &mut ModeListFilter, hidden_count: usize) {
+    const WIDTH: f32 = 200.0;
+
+    ui.set_next_item_width(WIDTH);
+    ui.input_text("##ModeFilter", &mut filter.text).hint("Filter modes...").build();
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Show only modes whose name contains this text. Selected modes are always listed.");
+    }
+
+    let type_label = filter.cell_type.map_or("All cell types", |cell_type| CellType::from_index(cell_type).name());
+    ui.set_next_item_width(if filter.is_active() { WIDTH - ui.frame_height() - ui.clone_style().item_spacing[0] } else { WIDTH });
+    if let Some(_combo) = ui.begin_combo("##ModeFilterType", type_label) {
+        if ui.selectable_config("All cell types").selected(filter.cell_type.is_none()).build() {
+            filter.cell_type = None;
+        }
+        for cell_type in CellType::all() {
+            let selected = filter.cell_type == Some(cell_type.index());
+            if ui.selectable_config(cell_type.name()).selected(selected).build() {
+                filter.cell_type = Some(cell_type.index());
+            }
+        }
+    }
+    if filter.is_active() {
+        ui.same_line();
+        if ui.button_with_size("X##ClearModeFilter", [ui.frame_height(), 0.0]) {
+            *filter = ModeListFilter::default();
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Clear the filter");
+        }
+        if hidden_count > 0 {
+            ui.text_disabled(format!("{} modes hidden", hidden_count));
+        }
+    }
+}
+
 /// Get cell type name from index
 fn get_cell_type_name(cell_type: i32) -> &'static str {
     match cell_type {
@@ -1534,6 +2745,7 @@ fn draw_genome_node(
     mode: &ModeSettings,
     _mode_idx: usize,
     node_graph: &GenomeNodeGraph,
+    deadlocks: &[SplitDeadlock],
 ) {
     // Use unsafe transmute to convert i32 to NodeId (both are 32-bit)
     unsafe {
@@ -1548,12 +2760,7 @@ fn draw_genome_node(
         let node_color = color_vec3_to_u32(mode.color);
         
         // Calculate text color based on brightness for readability
-        let brightness = mode.color.x * 0.299 + mode.color.y * 0.587 + mode.color.z * 0.114;
-        let text_color = if brightness > 0.5 {
-            [0.0, 0.0, 0.0, 1.0] // Dark text on light background
-        } else {
-            [1.0, 1.0, 1.0, 1.0] // Light text on dark background
-        };
+        let text_color = contrasting_text_color(mode.color);
         
         // Push node color styles
         imnodes_sys::imnodes_PushColorStyle(
@@ -1574,6 +2781,19 @@ fn draw_genome_node(
             node.add_titlebar(|| {
                 let _text_color = ui.push_style_color(StyleColor::Text, text_color);
                 ui.text(&mode.name);
+                // Warning badge for modes whose settings stop them from ever splitting
+                if !deadlocks.is_empty() {
+                    ui.same_line();
+                    ui.text_colored([1.0, 0.8, 0.2, 1.0], "(!)");
+                    if ui.is_item_hovered() {
+                        ui.tooltip(|| {
+                            ui.text("Can never split:");
+                            for deadlock in deadlocks {
+                                ui.bullet_text(deadlock.description());
+                            }
+                        });
+                    }
+                }
             });
 
             // Input pin (parent connection)
@@ -1585,7 +2805,7 @@ fn draw_genome_node(
             // Node body - show key settings
             ui.spacing();
             ui.text(&format!("Type: {}", get_cell_type_name(mode.cell_type)));
-            if mode.split_interval > 59.0 {
+            if mode.split_interval > NEVER_SPLIT_INTERVAL {
                 ui.text("Split: Never");
             } else {
                 ui.text(&format!("Split: {:.1}s", mode.split_interval));
@@ -1615,6 +2835,17 @@ fn draw_genome_node(
 }
 
 /// Convert Vec3 color to u32 for imnodes
+/// Black or white text, whichever reads better on `background`.
+/// Always one of the two so labels on mode colors stay readable under light and dark themes alike.
+fn contrasting_text_color(background: Vec3) -> [f32; 4] {
+    let brightness = background.x * 0.299 + background.y * 0.587 + background.z * 0.114;
+    if brightness > 0.5 {
+        [0.0, 0.0, 0.0, 1.0] // Dark text on light background
+    } else {
+        [1.0, 1.0, 1.0, 1.0] // Light text on dark background
+    }
+}
+
 fn color_vec3_to_u32(color: Vec3) -> u32 {
     let r = (color.x * 255.0) as u32;
     let g = (color.y * 255.0) as u32;
@@ -1622,13 +2853,420 @@ fn color_vec3_to_u32(color: Vec3) -> u32 {
     0xFF000000 | (b << 16) | (g << 8) | r
 }
 
+/// Right-button movement (pixels) below which a release counts as a click rather than a pan
+const CONTEXT_MENU_DRAG_THRESHOLD: f32 = 4.0;
+
+/// Approximate on-screen size of a node, used to keep whole nodes inside the view
+const NODE_EXTENT: [f32; 2] = [200.0, 150.0];
+
+/// Reset a mode to default settings, keeping its name and child references
+fn reset_mode(genome: &mut GenomeData, mode_idx: usize) {
+    let Some(mode) = genome.modes.get_mut(mode_idx) else {
+        return;
+    };
+
+    // Save the current name and references before resetting
+    let saved_name = mode.name.clone();
+    let saved_default_name = mode.default_name.clone();
+    let saved_child_a_mode = mode.child_a.mode_number;
+    let saved_child_b_mode = mode.child_b.mode_number;
+
+    // Reset to default settings
+    *mode = ModeSettings::new_self_splitting(mode_idx as i32, saved_default_name);
+
+    // Restore the saved name and references
+    mode.name = saved_name;
+    mode.child_a.mode_number = saved_child_a_mode;
+    mode.child_b.mode_number = saved_child_b_mode;
+}
+
+/// Insert a new self-splitting mode after the selected one, with its node at `position` (editor space)
+fn add_mode_at(current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph, position: [f32; 2]) {
+    // Insert after selected mode
+    let selected_idx = current_genome.selected_mode_index as usize;
+    let insert_idx = if selected_idx < current_genome.genome.modes.len() {
+        selected_idx + 1
+    } else {
+        current_genome.genome.modes.len()
+    };
+
+    // Generate new mode name based on selected mode's default name
+    let new_name = if selected_idx < current_genome.genome.modes.len() {
+        generate_next_mode_name(&current_genome.genome.modes[selected_idx].default_name, &current_genome.genome.modes)
+    } else {
+        format!("Mode {}", current_genome.genome.modes.len())
+    };
+
+    let new_mode = ModeSettings::new_self_splitting(insert_idx as i32, new_name);
+    current_genome.genome.modes.insert(insert_idx, new_mode);
+
+    // Update mode numbers for all modes after insertion point
+    update_mode_numbers_after_insert(&mut current_genome.genome, insert_idx);
+
+    // Keep the current mode selected (adjust index if needed)
+    if insert_idx <= selected_idx && selected_idx < current_genome.genome.modes.len() {
+        current_genome.selected_mode_index = (selected_idx + 1) as i32;
+    }
+    current_genome.select_single_mode(current_genome.selected_mode_index as usize);
+
+    // Store the desired position for the new mode before rebuild
+    node_graph.pending_position = Some((insert_idx, position[0], position[1]));
+    node_graph.mark_for_rebuild();
+}
+
+/// Insert a copy of a mode right after it; the copy keeps the original's settings and children
+fn duplicate_mode(current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph, mode_idx: usize, position: [f32; 2]) {
+    let Some(original) = current_genome.genome.modes.get(mode_idx) else {
+        return;
+    };
+    let insert_idx = mode_idx + 1;
+    let mut copy = original.clone();
+    copy.name = generate_next_mode_name(&original.default_name, &current_genome.genome.modes);
+    copy.default_name = copy.name.clone();
+
+    // References at or after the insertion point shift by one, like everywhere else
+    let shift = |mode_number: i32| if mode_number >= insert_idx as i32 { mode_number + 1 } else { mode_number };
+    let child_a = shift(copy.child_a.mode_number);
+    let child_b = shift(copy.child_b.mode_number);
+    copy.mode_a_after_splits = shift(copy.mode_a_after_splits);
+    copy.mode_b_after_splits = shift(copy.mode_b_after_splits);
+
+    current_genome.genome.modes.insert(insert_idx, copy);
+    update_mode_numbers_after_insert(&mut current_genome.genome, insert_idx);
+    let inserted = &mut current_genome.genome.modes[insert_idx];
+    inserted.child_a.mode_number = child_a;
+    inserted.child_b.mode_number = child_b;
+
+    current_genome.select_single_mode(insert_idx);
+    node_graph.pending_position = Some((insert_idx, position[0], position[1]));
+    node_graph.mark_for_rebuild();
+}
+
+/// Insert the copied mode after the selected one under a fresh name. Its children are made
+/// self-referential and its after-splits switches are cleared so it doesn't link into the
+/// topology of the genome it was copied from.
+fn paste_mode(current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph) {
+    let Some(mut pasted) = current_genome.mode_clipboard.clone() else {
+        return;
+    };
+    let selected_idx = current_genome.selected_mode_index as usize;
+    let insert_idx = (selected_idx + 1).min(current_genome.genome.modes.len());
+
+    let base_name = current_genome
+        .genome
+        .modes
+        .get(selected_idx)
+        .map(|mode| mode.default_name.clone())
+        .unwrap_or_else(|| pasted.default_name.clone());
+    pasted.name = generate_next_mode_name(&base_name, &current_genome.genome.modes);
+    pasted.default_name = pasted.name.clone();
+    pasted.mode_a_after_splits = -1;
+    pasted.mode_b_after_splits = -1;
+
+    current_genome.genome.modes.insert(insert_idx, pasted);
+    // Also points the pasted mode's children at itself
+    update_mode_numbers_after_insert(&mut current_genome.genome, insert_idx);
+
+    current_genome.select_single_mode(insert_idx);
+    node_graph.mark_for_rebuild();
+}
+
+/// Remove a mode and repoint references to it; the initial mode and the last mode can't be removed
+fn remove_mode(current_genome: &mut CurrentGenome, node_graph: &mut GenomeNodeGraph, mode_idx: usize) {
+    let initial_mode = current_genome.genome.initial_mode as usize;
+
+    // Don't allow removing the last mode or the initial mode
+    if current_genome.genome.modes.len() <= 1 || mode_idx == initial_mode || mode_idx >= current_genome.genome.modes.len() {
+        return;
+    }
+
+    // Remove the mode
+    current_genome.genome.modes.remove(mode_idx);
+
+    // Update references in other modes
+    for (idx, mode) in current_genome.genome.modes.iter_mut().enumerate() {
+        // If child references the removed mode, make it self-splitting
+        if mode.child_a.mode_number == mode_idx as i32 {
+            mode.child_a.mode_number = idx as i32;
+        } else if mode.child_a.mode_number > mode_idx as i32 {
+            // Shift down references to modes after the removed one
+            mode.child_a.mode_number -= 1;
+        }
+
+        if mode.child_b.mode_number == mode_idx as i32 {
+            mode.child_b.mode_number = idx as i32;
+        } else if mode.child_b.mode_number > mode_idx as i32 {
+            mode.child_b.mode_number -= 1;
+        }
+    }
+
+    // Update initial mode if needed
+    if current_genome.genome.initial_mode > mode_idx as i32 {
+        current_genome.genome.initial_mode -= 1;
+    }
+
+    // Update selected mode if needed
+    if current_genome.selected_mode_index >= current_genome.genome.modes.len() as i32 {
+        current_genome.selected_mode_index = (current_genome.genome.modes.len() as i32) - 1;
+    }
+    current_genome.select_single_mode(current_genome.selected_mode_index as usize);
+
+    // Mark node graph for rebuild
+    node_graph.mark_for_rebuild();
+}
+
+/// Move and spread the nodes so all of them fit inside a canvas of `canvas_size`
+fn fit_nodes_to_view(node_graph: &mut GenomeNodeGraph, canvas_size: [f32; 2]) {
+    let positions: Vec<(i32, (f32, f32))> = node_graph.node_positions.iter().map(|(id, pos)| (*id, *pos)).collect();
+
+    // Node positions are stored relative to the current panning
+    let mut panning = imnodes_sys::ImVec2 { x: 0.0, y: 0.0 };
+    unsafe { imnodes_sys::imnodes_EditorContextGetPanning(&mut panning as *mut _) };
+    for (id, (x, y)) in fit_positions(&positions, canvas_size) {
+        node_graph.set_node_position(id, x - panning.x, y - panning.y);
+    }
+}
+
+/// Node origins translated and uniformly shrunk (never enlarged) so every node body fits
+/// inside `canvas_size`, centred in it. A single node simply lands in the middle.
+fn fit_positions(positions: &[(i32, (f32, f32))], canvas_size: [f32; 2]) -> Vec<(i32, (f32, f32))> {
+    const MARGIN: f32 = 20.0;
+    if positions.is_empty() {
+        return Vec::new();
+    }
+
+    let (mut min, mut max) = ([f32::MAX; 2], [f32::MIN; 2]);
+    for (_, (x, y)) in positions {
+        min = [min[0].min(*x), min[1].min(*y)];
+        max = [max[0].max(*x), max[1].max(*y)];
+    }
+
+    // Spread of node origins that still leaves room for the last node's body
+    let room = [
+        (canvas_size[0] - NODE_EXTENT[0] - MARGIN * 2.0).max(1.0),
+        (canvas_size[1] - NODE_EXTENT[1] - MARGIN * 2.0).max(1.0),
+    ];
+    let span = [max[0] - min[0], max[1] - min[1]];
+    let scale = (room[0] / span[0].max(1.0)).min(room[1] / span[1].max(1.0)).min(1.0);
+    let offset = [
+        MARGIN + (room[0] - span[0] * scale) * 0.5,
+        MARGIN + (room[1] - span[1] * scale) * 0.5,
+    ];
+
+    positions
+        .iter()
+        .map(|(id, (x, y))| (*id, (offset[0] + (x - min[0]) * scale, offset[1] + (y - min[1]) * scale)))
+        .collect()
+}
+
+/// Undo all panning and zooming: clear the pan offsets and lay the nodes out afresh
+fn reset_graph_pan(node_graph: &mut GenomeNodeGraph, graph_state: &mut GenomeGraphState, initial_mode: i32) {
+    graph_state.panning_offset = [0.0, 0.0];
+    unsafe { imnodes_sys::imnodes_EditorContextResetPanning(imnodes_sys::ImVec2 { x: 0.0, y: 0.0 }) };
+    node_graph.calculate_layout(graph_state.layout, initial_mode);
+}
+
+/// Screen size of the graph minimap and its gap from the canvas corner
+const MINIMAP_SIZE: [f32; 2] = [200.0, 140.0];
+const MINIMAP_INSET: f32 = 10.0;
+
+/// Screen rectangle (min, max) of the minimap in the bottom-right corner of the canvas
+fn minimap_rect(canvas_origin: [f32; 2], canvas_size: [f32; 2]) -> ([f32; 2], [f32; 2]) {
+    let max = [
+        canvas_origin[0] + canvas_size[0] - MINIMAP_INSET,
+        canvas_origin[1] + canvas_size[1] - MINIMAP_INSET,
+    ];
+    ([max[0] - MINIMAP_SIZE[0], max[1] - MINIMAP_SIZE[1]], max)
+}
+
+fn rect_contains((min, max): ([f32; 2], [f32; 2]), point: [f32; 2]) -> bool {
+    point[0] >= min[0] && point[0] <= max[0] && point[1] >= min[1] && point[1] <= max[1]
+}
+
+/// Uniform mapping from editor space into a minimap rectangle
+#[derive(Debug, Clone, Copy)]
+struct MinimapTransform {
+    editor_min: [f32; 2],
+    screen_min: [f32; 2],
+    scale: f32,
+}
+
+impl MinimapTransform {
+    /// Fit the editor-space bounds `(min, max)` inside `screen`, centred
+    fn fit((min, max): ([f32; 2], [f32; 2]), screen: ([f32; 2], [f32; 2])) -> Self {
+        const PADDING: f32 = 6.0;
+        let room = [screen.1[0] - screen.0[0] - PADDING * 2.0, screen.1[1] - screen.0[1] - PADDING * 2.0];
+        let span = [(max[0] - min[0]).max(1.0), (max[1] - min[1]).max(1.0)];
+        let scale = (room[0] / span[0]).min(room[1] / span[1]);
+        Self {
+            editor_min: min,
+            screen_min: [
+                screen.0[0] + PADDING + (room[0] - span[0] * scale) * 0.5,
+                screen.0[1] + PADDING + (room[1] - span[1] * scale) * 0.5,
+            ],
+            scale,
+        }
+    }
+
+    fn to_screen(self, editor: [f32; 2]) -> [f32; 2] {
+        [
+            self.screen_min[0] + (editor[0] - self.editor_min[0]) * self.scale,
+            self.screen_min[1] + (editor[1] - self.editor_min[1]) * self.scale,
+        ]
+    }
+
+    fn to_editor(self, screen: [f32; 2]) -> [f32; 2] {
+        [
+            self.editor_min[0] + (screen[0] - self.screen_min[0]) / self.scale,
+            self.editor_min[1] + (screen[1] - self.screen_min[1]) / self.scale,
+        ]
+    }
+}
+
+/// Overview of all nodes as dots in their mode colors, with the visible part of the canvas
+/// outlined. Clicking or dragging inside it pans the view so that spot is centred. It is a
+/// child window so the node editor underneath does not also receive those clicks.
+fn draw_graph_minimap(
+    ui: &imgui::Ui,
+    current_genome: &CurrentGenome,
+    node_graph: &mut GenomeNodeGraph,
+    graph_state: &mut GenomeGraphState,
+    canvas_origin: [f32; 2],
+    canvas_size: [f32; 2],
+) {
+    const BACKGROUND_COLOR: [f32; 4] = [0.08, 0.08, 0.1, 0.85];
+    const BORDER_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 0.8];
+    const VIEWPORT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.9];
+    const DOT_RADIUS: f32 = 3.0;
+
+    if canvas_size[0] < MINIMAP_SIZE[0] + MINIMAP_INSET * 2.0 || canvas_size[1] < MINIMAP_SIZE[1] + MINIMAP_INSET * 2.0 {
+        return;
+    }
+
+    // Visible part of editor space; stored positions are relative to the imnodes panning
+    let mut panning = imnodes_sys::ImVec2 { x: 0.0, y: 0.0 };
+    unsafe { imnodes_sys::imnodes_EditorContextGetPanning(&mut panning as *mut _) };
+    let view_min = [-panning.x, -panning.y];
+    let view_max = [view_min[0] + canvas_size[0], view_min[1] + canvas_size[1]];
+
+    let nodes: Vec<(i32, [f32; 2], Vec3)> = current_genome
+        .genome
+        .modes
+        .iter()
+        .enumerate()
+        .filter_map(|(mode_idx, mode)| {
+            let node_id = node_graph.get_node_for_mode(mode_idx)?;
+            let (x, y) = node_graph.get_node_position(node_id)?;
+            Some((node_id, [x, y], mode.color))
+        })
+        .collect();
+    let (mut min, mut max) = (view_min, view_max);
+    for (_, position, _) in &nodes {
+        min = [min[0].min(position[0]), min[1].min(position[1])];
+        max = [max[0].max(position[0] + NODE_EXTENT[0]), max[1].max(position[1] + NODE_EXTENT[1])];
+    }
+
+    let rect = minimap_rect(canvas_origin, canvas_size);
+    let transform = MinimapTransform::fit((min, max), rect);
+
+    ui.set_cursor_screen_pos(rect.0);
+    ui.child_window("##GraphMinimap")
+        .size(MINIMAP_SIZE)
+        .flags(WindowFlags::NO_SCROLLBAR | WindowFlags::NO_SCROLL_WITH_MOUSE | WindowFlags::NO_BACKGROUND)
+        .build(|| {
+            let draw_list = ui.get_window_draw_list();
+            draw_list.add_rect(rect.0, rect.1, BACKGROUND_COLOR).filled(true).rounding(4.0).build();
+            draw_list.add_rect(rect.0, rect.1, BORDER_COLOR).rounding(4.0).build();
+
+            for (_, position, color) in &nodes {
+                let center = transform.to_screen([position[0] + NODE_EXTENT[0] * 0.5, position[1] + NODE_EXTENT[1] * 0.5]);
+                draw_list.add_circle(center, DOT_RADIUS, [color.x, color.y, color.z, 1.0]).filled(true).build();
+            }
+            draw_list
+                .add_rect(transform.to_screen(view_min), transform.to_screen(view_max), VIEWPORT_COLOR)
+                .build();
+
+            ui.invisible_button("##MinimapArea", MINIMAP_SIZE);
+            if ui.is_item_active() {
+                // Shift every node (like a right-drag pan) so the clicked spot lands in the middle
+                let target = transform.to_editor(ui.io().mouse_pos);
+                let delta = [
+                    (view_min[0] + view_max[0]) * 0.5 - target[0],
+                    (view_min[1] + view_max[1]) * 0.5 - target[1],
+                ];
+                for (node_id, position, _) in &nodes {
+                    node_graph.set_node_position(*node_id, position[0] + delta[0], position[1] + delta[1]);
+                }
+                graph_state.panning_offset[0] += delta[0];
+                graph_state.panning_offset[1] += delta[1];
+            }
+        });
+}
+
+/// Right-click menus for a node and for the empty canvas
+fn render_graph_context_menus(
+    ui: &imgui::Ui,
+    current_genome: &mut CurrentGenome,
+    node_graph: &mut GenomeNodeGraph,
+    graph_state: &mut GenomeGraphState,
+    canvas_size: [f32; 2],
+) {
+    let position = graph_state.context_position;
+
+    if let Some(_popup) = ui.begin_popup("GraphNodeContext") {
+        let Some(mode_idx) = graph_state.context_node.filter(|idx| *idx < current_genome.genome.modes.len()) else {
+            ui.close_current_popup();
+            return;
+        };
+        ui.text_disabled(&current_genome.genome.modes[mode_idx].name);
+        ui.separator();
+
+        let is_initial = current_genome.genome.initial_mode == mode_idx as i32;
+        if ui.menu_item_config("Set as Initial").enabled(!is_initial).build() {
+            current_genome.genome.initial_mode = mode_idx as i32;
+        }
+        if ui.menu_item("Duplicate") {
+            let offset = [position[0] + 40.0, position[1] + 40.0];
+            duplicate_mode(current_genome, node_graph, mode_idx, offset);
+        }
+        let can_delete = !is_initial && current_genome.genome.modes.len() > 1;
+        if ui.menu_item_config("Delete").shortcut("Shift+Right-click").enabled(can_delete).build() {
+            remove_mode(current_genome, node_graph, mode_idx);
+        }
+        if ui.menu_item("Reset") {
+            reset_mode(&mut current_genome.genome, mode_idx);
+        }
+    }
+
+    if let Some(_popup) = ui.begin_popup("GraphCanvasContext") {
+        if ui.menu_item_config("Add Mode Here").shortcut("Shift+Click").build() {
+            add_mode_at(current_genome, node_graph, position);
+        }
+        if let Some(_menu) = ui.begin_menu("Auto Layout") {
+            for layout in GraphLayout::ALL {
+                if ui.menu_item(layout.name()) {
+                    graph_state.layout = layout;
+                    node_graph.calculate_layout(layout, current_genome.genome.initial_mode);
+                }
+            }
+        }
+        if ui.menu_item("Fit to View") {
+            fit_nodes_to_view(node_graph, canvas_size);
+        }
+        if ui.menu_item("Reset Pan") {
+            reset_graph_pan(node_graph, graph_state, current_genome.genome.initial_mode);
+        }
+    }
+}
+
 /// Handle link creation in the node graph
 fn handle_link_created(
     current_genome: &mut CurrentGenome,
     node_graph: &mut GenomeNodeGraph,
     output_pin: OutputPinId,
     input_pin: InputPinId,
-) {
+    block_duplicate_children: bool,
+) -> Option<String> {
     unsafe {
         // Convert pin IDs back to i32
         let output_pin_id: i32 = std::mem::transmute(output_pin);
@@ -1646,7 +3284,16 @@ fn handle_link_created(
         ) {
             // Update the genome data
             if parent_mode_idx < current_genome.genome.modes.len() {
+                let parent = &current_genome.genome.modes[parent_mode_idx];
+                let other_child = if is_child_a { parent.child_b.mode_number } else { parent.child_a.mode_number };
+                let duplicate = other_child == child_mode_idx as i32 && child_mode_idx != parent_mode_idx;
+                let target_name = current_genome.genome.modes[child_mode_idx].name.clone();
                 let mode = &mut current_genome.genome.modes[parent_mode_idx];
+
+                if duplicate && block_duplicate_children {
+                    return Some(format!("Blocked: both children of '{}' would become '{}'", mode.name, target_name));
+                }
+
                 if is_child_a {
                     mode.child_a.mode_number = child_mode_idx as i32;
                 } else {
@@ -1655,9 +3302,14 @@ fn handle_link_created(
 
                 // Update the node graph
                 node_graph.add_link(parent_node_id, child_node_id, is_child_a);
+
+                if duplicate {
+                    return Some(format!("Warning: both children of '{}' now become '{}'", mode.name, target_name));
+                }
             }
         }
     }
+    None
 }
 
 /// Handle link destruction in the node graph
@@ -1716,4 +3368,150 @@ fn handle_link_make_self_referential(
         // Rebuild the graph to reflect changes
         node_graph.mark_for_rebuild();
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_mode_keeps_last_mode() {
+        let mut current = CurrentGenome::default();
+        let mut node_graph = GenomeNodeGraph::default();
+
+        remove_mode(&mut current, &mut node_graph, 0);
+
+        assert_eq!(current.genome.modes.len(), 1);
+        assert_eq!(current.selected_mode_index, 0);
+    }
+
+    #[test]
+    fn test_add_then_remove_from_single_mode() {
+        let mut current = CurrentGenome::default();
+        let mut node_graph = GenomeNodeGraph::default();
+
+        add_mode_at(&mut current, &mut node_graph, [0.0, 0.0]);
+        assert_eq!(current.genome.modes.len(), 2);
+        assert_eq!(current.genome.initial_mode, 0);
+
+        remove_mode(&mut current, &mut node_graph, 1);
+        assert_eq!(current.genome.modes.len(), 1);
+        assert_eq!(current.genome.initial_mode, 0);
+        assert_eq!(current.selected_mode_index, 0);
+        assert_eq!(current.genome.modes[0].child_a.mode_number, 0);
+        assert_eq!(current.genome.modes[0].child_b.mode_number, 0);
+    }
+
+    #[test]
+    fn test_paste_mode_is_self_referential_with_fresh_name() {
+        let mut current = CurrentGenome::default();
+        let mut node_graph = GenomeNodeGraph::default();
+        add_mode_at(&mut current, &mut node_graph, [0.0, 0.0]);
+        current.genome.modes[0].child_a.mode_number = 1;
+        current.genome.modes[0].split_mass = 3.5;
+        current.genome.modes[0].mode_a_after_splits = 1;
+        current.select_single_mode(0);
+        current.mode_clipboard = Some(current.genome.modes[0].clone());
+
+        paste_mode(&mut current, &mut node_graph);
+
+        let pasted = &current.genome.modes[1];
+        assert_eq!(current.genome.modes.len(), 3);
+        assert_eq!(current.selected_mode_index, 1);
+        assert_eq!(pasted.split_mass, 3.5);
+        assert_eq!(pasted.child_a.mode_number, 1);
+        assert_eq!(pasted.child_b.mode_number, 1);
+        assert_eq!(pasted.mode_a_after_splits, -1);
+        assert_eq!(pasted.mode_b_after_splits, -1);
+        assert!(current.genome.modes.iter().enumerate().all(|(i, mode)| i == 1 || mode.name != pasted.name));
+        // The source mode's links to the old mode 1 follow it to index 2
+        assert_eq!(current.genome.modes[0].child_a.mode_number, 2);
+        assert_eq!(current.genome.modes[0].mode_a_after_splits, 2);
+    }
+
+    #[test]
+    fn test_duplicate_mode_shifts_after_splits_switches() {
+        let mut current = CurrentGenome::default();
+        let mut node_graph = GenomeNodeGraph::default();
+        add_mode_at(&mut current, &mut node_graph, [0.0, 0.0]);
+        current.genome.modes[0].child_b.mode_number = 1;
+        current.genome.modes[0].mode_a_after_splits = 1;
+        current.genome.modes[0].mode_b_after_splits = 0;
+
+        duplicate_mode(&mut current, &mut node_graph, 0, [0.0, 0.0]);
+
+        let copy = &current.genome.modes[1];
+        assert_eq!(copy.child_b.mode_number, 2);
+        assert_eq!(copy.mode_a_after_splits, 2);
+        assert_eq!(copy.mode_b_after_splits, 0);
+        assert_eq!(current.genome.modes[0].mode_a_after_splits, 2);
+    }
+
+    #[test]
+    fn test_fit_positions_centres_and_contains_nodes() {
+        let canvas = [800.0, 600.0];
+        let fitted = fit_positions(&[(7, (-5000.0, 3000.0))], canvas);
+        let (x, y) = fitted[0].1;
+        assert!((x + NODE_EXTENT[0] * 0.5 - canvas[0] * 0.5).abs() < 1e-3);
+        assert!((y + NODE_EXTENT[1] * 0.5 - canvas[1] * 0.5).abs() < 1e-3);
+
+        let spread = [(1, (-4000.0, 0.0)), (2, (6000.0, 2500.0)), (3, (100.0, -900.0))];
+        for (_, (x, y)) in fit_positions(&spread, canvas) {
+            assert!(x >= 0.0 && x + NODE_EXTENT[0] <= canvas[0]);
+            assert!(y >= 0.0 && y + NODE_EXTENT[1] <= canvas[1]);
+        }
+    }
+
+    #[test]
+    fn test_minimap_transform_round_trips_inside_its_rect() {
+        let rect = minimap_rect([100.0, 50.0], [900.0, 700.0]);
+        let transform = MinimapTransform::fit(([-300.0, 0.0], [1700.0, 400.0]), rect);
+
+        for point in [[-300.0, 0.0], [1700.0, 400.0], [250.0, 123.0]] {
+            let screen = transform.to_screen(point);
+            assert!(rect_contains(rect, screen));
+            let back = transform.to_editor(screen);
+            assert!((back[0] - point[0]).abs() < 1e-2 && (back[1] - point[1]).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_gradient_hues_and_hsv_conversion() {
+        assert_eq!(hsv_to_rgb(0.0, 1.0, 1.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(hsv_to_rgb(120.0, 1.0, 1.0), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(hsv_to_rgb(600.0, 1.0, 0.5), Vec3::new(0.0, 0.0, 0.5));
+        assert_eq!(hsv_to_rgb(45.0, 0.0, 0.8), Vec3::new(0.8, 0.8, 0.8));
+
+        // A full turn spreads four modes a quarter turn apart; a partial range includes both ends
+        let full: Vec<f32> = (0..4).map(|slot| gradient_hue(0.0, 360.0, slot, 4)).collect();
+        assert_eq!(full, vec![0.0, 90.0, 180.0, 270.0]);
+        let partial: Vec<f32> = (0..3).map(|slot| gradient_hue(200.0, 100.0, slot, 3)).collect();
+        assert_eq!(partial, vec![200.0, 150.0, 100.0]);
+        assert_eq!(gradient_hue(30.0, 90.0, 0, 1), 30.0);
+    }
+
+    #[test]
+    fn test_copy_adhesion_reaches_modes_without_adhesion() {
+        let mut genome = GenomeData::default();
+        genome.modes.push(ModeSettings::new_self_splitting(1, "Loner".to_string()));
+        genome.modes[1].parent_make_adhesion = false;
+        genome.modes[0].adhesion_settings.break_force = 42.0;
+        genome.modes[0].adhesion_settings.can_break = false;
+
+        copy_adhesion_to_all_modes(&mut genome, 0);
+
+        assert_eq!(genome.modes[1].adhesion_settings, genome.modes[0].adhesion_settings);
+        assert!(!genome.modes[1].parent_make_adhesion);
+    }
+
+    #[test]
+    fn test_add_mode_after_repaired_empty_genome() {
+        let mut current = CurrentGenome::default();
+        let mut node_graph = GenomeNodeGraph::default();
+        current.load_genome(GenomeData { name: "Empty".to_string(), initial_mode: 0, modes: Vec::new() });
+
+        add_mode_at(&mut current, &mut node_graph, [0.0, 0.0]);
+
+        assert_eq!(current.genome.modes.len(), 2);
+        assert!(current.genome.modes.iter().enumerate().all(|(i, mode)| mode.child_a.mode_number == i as i32));
+    }
+}
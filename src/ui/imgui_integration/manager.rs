@@ -223,6 +223,9 @@ impl ImguiManager {
     ) {
         let fonts = context.fonts();
         
+        // Start from an empty atlas so rebuilding doesn't stack fonts
+        fonts.clear();
+        
         // Calculate scaled font size
         let font_size = if config.apply_display_scale_to_font_size {
             config.font_size * display_scale
@@ -250,8 +253,9 @@ impl ImguiManager {
             }),
         }]);
         
-        // Set display framebuffer scale
-        context.io_mut().display_framebuffer_scale = [display_scale, display_scale];
+        // Display size and mouse positions are already in physical pixels, so the framebuffer
+        // maps 1:1; the display scale only enlarges the fonts and the style sizes
+        context.io_mut().display_framebuffer_scale = [1.0, 1.0];
     }
     
     /// Prepare a new frame for rendering
@@ -325,7 +329,14 @@ impl ImguiManager {
         Ok(())
     }
     
-    /// Update the display scale and regenerate fonts
+    /// Monitor scale factor the fonts are currently built for
+    pub fn display_scale(&self) -> f32 {
+        self.display_scale
+    }
+    
+    /// Update the display scale and regenerate fonts.
+    /// Style sizes are scaled by `apply_imgui_style` so they survive theme switches.
+    /// Call between frames; the atlas can't change while a frame is being built.
     pub fn update_display_scale(
         &mut self,
         device: &wgpu::Device,
@@ -345,13 +356,33 @@ impl ImguiManager {
         self.renderer
             .reload_font_texture(&mut self.context, device, queue);
         
-        // Scale UI style proportionally
-        let style = self.context.style_mut();
-        style.scale_all_sizes(scale);
-        
         log::info!("Display scale updated to: {}", scale);
     }
     
+    /// Apply new font size and oversampling settings, rebuild the font atlas and reload the font texture.
+    /// Call between frames; the atlas can't change while a frame is being built.
+    pub fn update_font_config(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        config: &ImguiConfig,
+    ) {
+        self.config.font_size = config.font_size;
+        self.config.font_oversample_h = config.font_oversample_h;
+        self.config.font_oversample_v = config.font_oversample_v;
+        self.config.apply_display_scale_to_font_size = config.apply_display_scale_to_font_size;
+        self.config.apply_display_scale_to_font_oversample = config.apply_display_scale_to_font_oversample;
+        
+        Self::configure_fonts(&mut self.context, &self.config, self.display_scale);
+        self.renderer
+            .reload_font_texture(&mut self.context, device, queue);
+        
+        log::info!(
+            "Font atlas rebuilt: {}px, oversample {}x{}",
+            self.config.font_size, self.config.font_oversample_h, self.config.font_oversample_v
+        );
+    }
+    
     /// Register a texture for use in ImGui
     pub fn register_texture(&mut self, handle: TextureHandle) -> imgui::TextureId {
         self.texture_registry.register(handle)
@@ -377,9 +408,12 @@ impl ImguiManager {
         self.current_cursor = cursor;
     }
     
-    /// Handle winit events with custom edge resizing
+    /// Handle winit events with custom edge resizing.
+    /// Returns whether imgui wants the event, i.e. the cursor is over a panel or a widget has focus.
     pub fn handle_event(&mut self, event: &winit::event::WindowEvent) -> bool {
         let io = self.context.io_mut();
+        let want_mouse = io.want_capture_mouse;
+        let want_keyboard = io.want_capture_keyboard;
         
         match event {
             winit::event::WindowEvent::CursorMoved { position, .. } => {
@@ -388,7 +422,7 @@ impl ImguiManager {
                 // Custom edge resize detection
                 self.handle_edge_resize_detection([position.x as f32, position.y as f32]);
                 
-                true
+                want_mouse
             }
             winit::event::WindowEvent::MouseInput { state, button, .. } => {
                 let pressed = *state == winit::event::ElementState::Pressed;
@@ -407,7 +441,7 @@ impl ImguiManager {
                     winit::event::MouseButton::Middle => io.add_mouse_button_event(imgui::MouseButton::Middle, pressed),
                     _ => {}
                 }
-                true
+                want_mouse
             }
             winit::event::WindowEvent::MouseWheel { delta, .. } => {
                 match delta {
@@ -423,7 +457,7 @@ impl ImguiManager {
                         io.add_mouse_wheel_event([line_delta_x, line_delta_y]);
                     }
                 }
-                true
+                want_mouse
             }
             winit::event::WindowEvent::KeyboardInput { event, .. } => {
                 let pressed = event.state == winit::event::ElementState::Pressed;
@@ -450,7 +484,7 @@ impl ImguiManager {
                     }
                 }
                 
-                true
+                want_keyboard
             }
             // Note: Text input is now handled through the keyboard input event
             // with the logical key and text information
@@ -459,7 +493,7 @@ impl ImguiManager {
                 io.key_ctrl = modifiers.state().control_key();
                 io.key_alt = modifiers.state().alt_key();
                 io.key_super = modifiers.state().super_key();
-                true
+                want_keyboard
             }
             _ => false,
         }
@@ -1,4 +1,6 @@
 //ar ImGui panel utilities
+use imgui::sys;
+use std::path::Path;
 
 pub struct ImguiPanelState {
     pub show_debug_info: bool,
@@ -12,91 +14,53 @@ impl Default for ImguiPanelState {
     }
 }
 
-/// Create default imgui.ini for first-time users with proper docked layout
-pub fn ensure_default_imgui_ini() {
-    use std::path::Path;
-    
-    let imgui_ini = Path::new("imgui.ini");
-    
-    // Only create if it doesn't exist (first-time user)
-    if !imgui_ini.exists() {
-        let default_layout = r#"[Window][Debug##Default]
-Pos=60,60
-Size=400,400
-Collapsed=0
-
-[Window][Genome Editor]
-Pos=4,31
-Size=894,1408
-Collapsed=0
-
-[Window][Scene Manager]
-Pos=3079,31
-Size=355,312
-Collapsed=0
-DockId=0x00000002,0
-
-[Window][Time Scrubber]
-Pos=900,1227
-Size=2169,212
-Collapsed=0
-
-[Window][Rendering Controls]
-Pos=3079,654
-Size=355,411
-Collapsed=0
-DockId=0x00000005,0
-
-[Window][Cell Inspector]
-Pos=3079,1067
-Size=355,368
-Collapsed=0
-DockId=0x00000008,0
-
-[Window][Advanced Performance Monitor]
-Pos=3079,345
-Size=355,307
-Collapsed=0
-DockId=0x00000003,0
-
-[Window][Theme Editor]
-Pos=994,421
-Size=398,615
-Collapsed=0
-
-[Window][Camera Settings]
-Pos=2223,215
-Size=815,613
-Collapsed=0
-
-[Window][Exit Confirmation]
-Pos=1570,660
-Size=300,120
-Collapsed=0
-
-[Window][Lighting Settings]
-Pos=983,588
-Size=730,556
-Collapsed=0
+/// Whether imgui has a saved window layout to restore on startup
+pub fn saved_layout_exists(ini_filename: Option<&Path>) -> bool {
+    ini_filename.is_some_and(Path::exists)
+}
 
-[Window][Genome Graph]
-Pos=610,430
-Size=1000,640
-Collapsed=0
+/// Create the dockspace covering the main viewport. When `apply_default_layout` is set the
+/// default panel arrangement is built into it once and the flag is cleared.
+pub fn dockspace_over_main_viewport(_ui: &imgui::Ui, apply_default_layout: &mut bool) {
+    let dockspace_id = unsafe {
+        sys::igDockSpaceOverViewport(
+            sys::igGetMainViewport(),
+            sys::ImGuiDockNodeFlags_PassthruCentralNode as i32,
+            std::ptr::null(),
+        )
+    };
+
+    if std::mem::take(apply_default_layout) {
+        build_default_dock_layout(dockspace_id);
+    }
+}
 
-[Docking][Data]
-DockNode        ID=0x00000001 Pos=3079,31 Size=355,1404 Split=Y
-  DockNode      ID=0x00000007 Parent=0x00000001 SizeRef=388,1034 Split=Y
-    DockNode    ID=0x00000004 Parent=0x00000007 SizeRef=621,621 Split=Y
-      DockNode  ID=0x00000002 Parent=0x00000004 SizeRef=401,312 Selected=0x6B58BA6D
-      DockNode  ID=0x00000003 Parent=0x00000004 SizeRef=401,307 Selected=0x9B936203
-    DockNode    ID=0x00000005 Parent=0x00000007 SizeRef=403,411 Selected=0x018F13E1
-  DockNode      ID=0x00000008 Parent=0x00000001 SizeRef=388,368 Selected=0x0CE0C78D
-"#;
-        
-        if let Err(e) = std::fs::write(imgui_ini, default_layout) {
-            eprintln!("Failed to create default imgui.ini: {}", e);
-        }
+/// Dock the Genome Editor on the left, Scene Manager and Cell Inspector on the right and the
+/// Time Scrubber along the bottom, leaving the center free for the viewport
+fn build_default_dock_layout(dockspace_id: sys::ImGuiID) {
+    unsafe {
+        let viewport = sys::igGetMainViewport();
+        sys::igDockBuilderRemoveNode(dockspace_id);
+        sys::igDockBuilderAddNode(
+            dockspace_id,
+            sys::ImGuiDockNodeFlags_DockSpace | sys::ImGuiDockNodeFlags_PassthruCentralNode as i32,
+        );
+        sys::igDockBuilderSetNodeSize(dockspace_id, (*viewport).WorkSize);
+
+        let mut center = dockspace_id;
+        let left = sys::igDockBuilderSplitNode(center, sys::ImGuiDir_Left, 0.26, std::ptr::null_mut(), &mut center);
+        let right = sys::igDockBuilderSplitNode(center, sys::ImGuiDir_Right, 0.14, std::ptr::null_mut(), &mut center);
+        let bottom = sys::igDockBuilderSplitNode(center, sys::ImGuiDir_Down, 0.16, std::ptr::null_mut(), &mut center);
+        let mut right_top = right;
+        let right_bottom = sys::igDockBuilderSplitNode(right, sys::ImGuiDir_Down, 0.5, std::ptr::null_mut(), &mut right_top);
+
+        sys::igDockBuilderDockWindow(c"Genome Editor".as_ptr(), left);
+        sys::igDockBuilderDockWindow(c"Scene Manager".as_ptr(), right_top);
+        sys::igDockBuilderDockWindow(c"Advanced Performance Monitor".as_ptr(), right_top);
+        sys::igDockBuilderDockWindow(c"Cell Inspector".as_ptr(), right_bottom);
+        sys::igDockBuilderDockWindow(c"Rendering Controls".as_ptr(), right_bottom);
+        sys::igDockBuilderDockWindow(c"Time Scrubber".as_ptr(), bottom);
+        sys::igDockBuilderFinish(dockspace_id);
     }
 }
 
@@ -1,31 +1,241 @@
+use imgui::sys::{ImGuiCol_, ImGuiStyle, ImVec2, ImVec4};
+use std::collections::BTreeMap;
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum ImguiTheme {
     ModernDark,
     Industrial,
     WarmOrange,
     CellLab,
+    Light,
+    /// Black on white with saturated accents, for low vision
+    HighContrast,
+    /// User theme saved from the Theme Editor, by index into `ImguiThemeState::custom_themes`
+    Custom(usize),
 }
 
 impl ImguiTheme {
-    pub fn name(&self) -> &'static str {
+    /// Built-in themes
+    pub const PRESETS: [ImguiTheme; 6] = [
+        ImguiTheme::ModernDark,
+        ImguiTheme::Industrial,
+        ImguiTheme::WarmOrange,
+        ImguiTheme::CellLab,
+        ImguiTheme::Light,
+        ImguiTheme::HighContrast,
+    ];
+
+    pub fn name<'a>(&self, custom_themes: &'a [CustomTheme]) -> &'a str {
         match self {
             ImguiTheme::ModernDark => "Modern Dark",
             ImguiTheme::Industrial => "Industrial",
             ImguiTheme::WarmOrange => "Warm Orange",
             ImguiTheme::CellLab => "Cell Lab",
+            ImguiTheme::Light => "Light",
+            ImguiTheme::HighContrast => "High Contrast",
+            ImguiTheme::Custom(index) => custom_themes.get(*index).map(|theme| theme.name.as_str()).unwrap_or("Missing Custom Theme"),
+        }
+    }
+
+    /// Presets followed by the saved custom themes
+    pub fn all(custom_themes: &[CustomTheme]) -> Vec<ImguiTheme> {
+        Self::PRESETS
+            .iter()
+            .copied()
+            .chain((0..custom_themes.len()).map(ImguiTheme::Custom))
+            .collect()
+    }
+}
+
+/// Every color the themes set, with the name shown in the Theme Editor and used in saved themes
+pub const THEME_COLORS: [(ImGuiCol_, &str); 39] = [
+    (imgui::sys::ImGuiCol_Text, "Text"),
+    (imgui::sys::ImGuiCol_TextDisabled, "TextDisabled"),
+    (imgui::sys::ImGuiCol_TextSelectedBg, "TextSelectedBg"),
+    (imgui::sys::ImGuiCol_WindowBg, "WindowBg"),
+    (imgui::sys::ImGuiCol_ChildBg, "ChildBg"),
+    (imgui::sys::ImGuiCol_PopupBg, "PopupBg"),
+    (imgui::sys::ImGuiCol_MenuBarBg, "MenuBarBg"),
+    (imgui::sys::ImGuiCol_TitleBg, "TitleBg"),
+    (imgui::sys::ImGuiCol_TitleBgActive, "TitleBgActive"),
+    (imgui::sys::ImGuiCol_TitleBgCollapsed, "TitleBgCollapsed"),
+    (imgui::sys::ImGuiCol_Border, "Border"),
+    (imgui::sys::ImGuiCol_BorderShadow, "BorderShadow"),
+    (imgui::sys::ImGuiCol_FrameBg, "FrameBg"),
+    (imgui::sys::ImGuiCol_FrameBgHovered, "FrameBgHovered"),
+    (imgui::sys::ImGuiCol_FrameBgActive, "FrameBgActive"),
+    (imgui::sys::ImGuiCol_Tab, "Tab"),
+    (imgui::sys::ImGuiCol_TabHovered, "TabHovered"),
+    (imgui::sys::ImGuiCol_TabActive, "TabActive"),
+    (imgui::sys::ImGuiCol_TabUnfocused, "TabUnfocused"),
+    (imgui::sys::ImGuiCol_TabUnfocusedActive, "TabUnfocusedActive"),
+    (imgui::sys::ImGuiCol_Button, "Button"),
+    (imgui::sys::ImGuiCol_ButtonHovered, "ButtonHovered"),
+    (imgui::sys::ImGuiCol_ButtonActive, "ButtonActive"),
+    (imgui::sys::ImGuiCol_Header, "Header"),
+    (imgui::sys::ImGuiCol_HeaderHovered, "HeaderHovered"),
+    (imgui::sys::ImGuiCol_HeaderActive, "HeaderActive"),
+    (imgui::sys::ImGuiCol_Separator, "Separator"),
+    (imgui::sys::ImGuiCol_SeparatorHovered, "SeparatorHovered"),
+    (imgui::sys::ImGuiCol_SeparatorActive, "SeparatorActive"),
+    (imgui::sys::ImGuiCol_ResizeGrip, "ResizeGrip"),
+    (imgui::sys::ImGuiCol_ResizeGripHovered, "ResizeGripHovered"),
+    (imgui::sys::ImGuiCol_ResizeGripActive, "ResizeGripActive"),
+    (imgui::sys::ImGuiCol_ScrollbarBg, "ScrollbarBg"),
+    (imgui::sys::ImGuiCol_ScrollbarGrab, "ScrollbarGrab"),
+    (imgui::sys::ImGuiCol_ScrollbarGrabHovered, "ScrollbarGrabHovered"),
+    (imgui::sys::ImGuiCol_ScrollbarGrabActive, "ScrollbarGrabActive"),
+    (imgui::sys::ImGuiCol_SliderGrab, "SliderGrab"),
+    (imgui::sys::ImGuiCol_SliderGrabActive, "SliderGrabActive"),
+    (imgui::sys::ImGuiCol_CheckMark, "CheckMark"),
+];
+
+/// Rounding and spacing style vars a custom theme carries
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ThemeStyleVars {
+    pub window_rounding: f32,
+    pub child_rounding: f32,
+    pub popup_rounding: f32,
+    pub frame_rounding: f32,
+    pub scrollbar_rounding: f32,
+    pub grab_rounding: f32,
+    pub tab_rounding: f32,
+    pub window_padding: [f32; 2],
+    pub frame_padding: [f32; 2],
+    pub item_spacing: [f32; 2],
+    pub item_inner_spacing: [f32; 2],
+}
+
+impl ThemeStyleVars {
+    fn capture(style: &ImGuiStyle) -> Self {
+        Self {
+            window_rounding: style.WindowRounding,
+            child_rounding: style.ChildRounding,
+            popup_rounding: style.PopupRounding,
+            frame_rounding: style.FrameRounding,
+            scrollbar_rounding: style.ScrollbarRounding,
+            grab_rounding: style.GrabRounding,
+            tab_rounding: style.TabRounding,
+            window_padding: [style.WindowPadding.x, style.WindowPadding.y],
+            frame_padding: [style.FramePadding.x, style.FramePadding.y],
+            item_spacing: [style.ItemSpacing.x, style.ItemSpacing.y],
+            item_inner_spacing: [style.ItemInnerSpacing.x, style.ItemInnerSpacing.y],
         }
     }
 
-    pub fn all() -> &'static [ImguiTheme] {
-        &[ImguiTheme::ModernDark, ImguiTheme::Industrial, ImguiTheme::WarmOrange, ImguiTheme::CellLab]
+    /// The same vars with every size multiplied by `factor`
+    pub fn scaled(&self, factor: f32) -> Self {
+        let scale2 = |[x, y]: [f32; 2]| [x * factor, y * factor];
+        Self {
+            window_rounding: self.window_rounding * factor,
+            child_rounding: self.child_rounding * factor,
+            popup_rounding: self.popup_rounding * factor,
+            frame_rounding: self.frame_rounding * factor,
+            scrollbar_rounding: self.scrollbar_rounding * factor,
+            grab_rounding: self.grab_rounding * factor,
+            tab_rounding: self.tab_rounding * factor,
+            window_padding: scale2(self.window_padding),
+            frame_padding: scale2(self.frame_padding),
+            item_spacing: scale2(self.item_spacing),
+            item_inner_spacing: scale2(self.item_inner_spacing),
+        }
+    }
+
+    pub fn apply(&self, style: &mut ImGuiStyle) {
+        let vec2 = |[x, y]: [f32; 2]| ImVec2 { x, y };
+        style.WindowRounding = self.window_rounding;
+        style.ChildRounding = self.child_rounding;
+        style.PopupRounding = self.popup_rounding;
+        style.FrameRounding = self.frame_rounding;
+        style.ScrollbarRounding = self.scrollbar_rounding;
+        style.GrabRounding = self.grab_rounding;
+        style.TabRounding = self.tab_rounding;
+        style.WindowPadding = vec2(self.window_padding);
+        style.FramePadding = vec2(self.frame_padding);
+        style.ItemSpacing = vec2(self.item_spacing);
+        style.ItemInnerSpacing = vec2(self.item_inner_spacing);
+    }
+}
+
+/// Named palette and style vars saved from the Theme Editor
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CustomTheme {
+    pub name: String,
+    /// RGBA by `THEME_COLORS` name; colors missing here keep the Modern Dark value
+    pub colors: BTreeMap<String, [f32; 4]>,
+    pub style: ThemeStyleVars,
+}
+
+impl CustomTheme {
+    /// Snapshot the theme colors and style vars of `style`
+    pub fn capture(name: String, style: &ImGuiStyle) -> Self {
+        let colors = THEME_COLORS
+            .iter()
+            .map(|(index, color_name)| {
+                let color = style.Colors[*index as usize];
+                (color_name.to_string(), [color.x, color.y, color.z, color.w])
+            })
+            .collect();
+        Self { name, colors, style: ThemeStyleVars::capture(style) }
+    }
+
+    pub fn apply(&self, style: &mut ImGuiStyle) {
+        for (index, color_name) in THEME_COLORS {
+            if let Some([x, y, z, w]) = self.colors.get(color_name) {
+                style.Colors[index as usize] = ImVec4 { x: *x, y: *y, z: *z, w: *w };
+            }
+        }
+        self.style.apply(style);
     }
 }
 
+/// Read saved custom themes; a missing or unreadable file means none
+pub fn load_custom_themes(path: &std::path::Path) -> Vec<CustomTheme> {
+    let Ok(json) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&json).unwrap_or_else(|e| {
+        eprintln!("Failed to parse custom themes file: {}. Ignoring it.", e);
+        Vec::new()
+    })
+}
+
+pub fn save_custom_themes(path: &std::path::Path, themes: &[CustomTheme]) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(themes)?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+/// Frames over which colors blend from the old theme into the new one
+const THEME_FADE_FRAMES: u32 = 8;
+
+/// Color blend in progress after a theme switch
+#[derive(Clone)]
+struct ThemeTransition {
+    from: [imgui::sys::ImVec4; imgui::sys::ImGuiCol_COUNT as usize],
+    to: [imgui::sys::ImVec4; imgui::sys::ImGuiCol_COUNT as usize],
+    frame: u32,
+}
+
+/// Active theme, persisted to `theme_settings.json` and re-applied on the first frame after startup
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(default)]
 pub struct ImguiThemeState {
     pub current_theme: ImguiTheme,
     #[serde(skip)]
     pub theme_changed: bool,
+    /// ImGui's own style before any theme was applied; every theme is built on top of it
+    /// so nothing from the previous theme leaks into the next
+    #[serde(skip)]
+    base_style: Option<imgui::sys::ImGuiStyle>,
+    #[serde(skip)]
+    transition: Option<ThemeTransition>,
+    /// Themes saved from the Theme Editor, kept in their own file
+    #[serde(skip)]
+    pub custom_themes: Vec<CustomTheme>,
+    /// Factor the installed style sizes were scaled by (display scale times UI scale)
+    #[serde(skip)]
+    style_scale: Option<f32>,
 }
 
 impl Default for ImguiThemeState {
@@ -33,6 +243,10 @@ impl Default for ImguiThemeState {
         Self {
             current_theme: ImguiTheme::ModernDark,
             theme_changed: true, // Apply theme on first frame
+            base_style: None,
+            transition: None,
+            custom_themes: Vec::new(),
+            style_scale: None,
         }
     }
 }
@@ -45,8 +259,20 @@ impl ImguiThemeState {
         Ok(())
     }
 
-    /// Load theme settings from file, falling back to default if file doesn't exist or is invalid
+    /// Load theme settings from file, falling back to default if file doesn't exist or is invalid.
+    /// Custom themes come from `default_custom_themes_path`.
     pub fn load_from_file(path: &std::path::Path) -> Self {
+        let mut theme_state = Self::load_settings(path);
+        theme_state.custom_themes = load_custom_themes(&Self::default_custom_themes_path());
+        if let ImguiTheme::Custom(index) = theme_state.current_theme {
+            if index >= theme_state.custom_themes.len() {
+                theme_state.current_theme = ImguiTheme::ModernDark;
+            }
+        }
+        theme_state
+    }
+
+    fn load_settings(path: &std::path::Path) -> Self {
         match std::fs::read_to_string(path) {
             Ok(json) => {
                 match serde_json::from_str::<Self>(&json) {
@@ -71,33 +297,142 @@ impl ImguiThemeState {
     pub fn default_theme_path() -> std::path::PathBuf {
         std::path::PathBuf::from("theme_settings.json")
     }
+
+    pub fn default_custom_themes_path() -> std::path::PathBuf {
+        std::path::PathBuf::from("custom_themes.json")
+    }
+
+    /// The current theme's colors and style vars as they are once any fade finishes,
+    /// without the live edits made in the Theme Editor
+    pub fn current_palette(&self) -> CustomTheme {
+        let live = unsafe { *imgui::sys::igGetStyle() };
+        let mut target = self.base_style.unwrap_or(live);
+        unsafe {
+            apply_theme_unsafe(&mut target, self.current_theme, &self.custom_themes);
+        }
+        let name = self.current_theme.name(&self.custom_themes).to_string();
+        CustomTheme::capture(name, &target)
+    }
+
+    /// Install `theme` in the live style right away, scaled like the current theme, without a fade
+    pub fn preview(&self, theme: &CustomTheme) {
+        let scaled = CustomTheme {
+            style: theme.style.scaled(self.style_scale.unwrap_or(1.0)),
+            ..theme.clone()
+        };
+        unsafe {
+            scaled.apply(&mut *imgui::sys::igGetStyle());
+        }
+    }
+
+    /// Add `theme`, replacing a custom theme of the same name, write the custom themes file
+    /// and switch to it
+    pub fn save_custom_theme(&mut self, theme: CustomTheme) -> Result<(), Box<dyn std::error::Error>> {
+        let index = match self.custom_themes.iter().position(|existing| existing.name == theme.name) {
+            Some(index) => {
+                self.custom_themes[index] = theme;
+                index
+            }
+            None => {
+                self.custom_themes.push(theme);
+                self.custom_themes.len() - 1
+            }
+        };
+        self.current_theme = ImguiTheme::Custom(index);
+        self.theme_changed = true;
+        save_custom_themes(&Self::default_custom_themes_path(), &self.custom_themes)
+    }
+
+    /// Remove a custom theme and write the custom themes file, falling back to Modern Dark if it was in use
+    pub fn delete_custom_theme(&mut self, index: usize) -> Result<(), Box<dyn std::error::Error>> {
+        if index >= self.custom_themes.len() {
+            return Ok(());
+        }
+        self.custom_themes.remove(index);
+        match self.current_theme {
+            ImguiTheme::Custom(current) if current == index => {
+                self.current_theme = ImguiTheme::ModernDark;
+                self.theme_changed = true;
+            }
+            ImguiTheme::Custom(current) if current > index => {
+                self.current_theme = ImguiTheme::Custom(current - 1);
+            }
+            _ => {}
+        }
+        save_custom_themes(&Self::default_custom_themes_path(), &self.custom_themes)
+    }
 }
 
-/// Apply ImGui styling and scaling
+/// Apply ImGui styling and scaling.
+/// `display_scale` is the monitor scale factor; the user's `ui_scale` multiplies on top of it.
+/// Call before the frame starts so every window of a frame sees the same complete style.
 pub fn apply_imgui_style(
-    ui: &imgui::Ui,
+    context: &mut imgui::Context,
     theme_state: &mut ImguiThemeState,
     ui_scale: f32,
+    display_scale: f32,
 ) {
-    // Apply theme only when it changes
-    if theme_state.theme_changed {
+    let ui_scale = super::clamp_ui_scale(ui_scale);
+
+    // Apply theme only when it changes, or rebuild it when the scale does
+    let style_scale = display_scale * ui_scale;
+    if theme_state.theme_changed || theme_state.style_scale != Some(style_scale) {
         unsafe {
-            apply_theme_unsafe(theme_state.current_theme);
+            switch_theme(theme_state, style_scale);
         }
         theme_state.theme_changed = false;
     }
 
-    // Apply UI scale
-    unsafe {
-        let io_ptr = ui.io() as *const imgui::Io as *mut imgui::Io;
-        (*io_ptr).font_global_scale = ui_scale;
+    // Advance a running color fade
+    if let Some(transition) = &mut theme_state.transition {
+        transition.frame += 1;
+        let t = (transition.frame as f32 / THEME_FADE_FRAMES as f32).min(1.0);
+        let t = t * t * (3.0 - 2.0 * t);
+        unsafe {
+            let colors = &mut (*imgui::sys::igGetStyle()).Colors;
+            for (i, color) in colors.iter_mut().enumerate() {
+                let (from, to) = (transition.from[i], transition.to[i]);
+                *color = imgui::sys::ImVec4 {
+                    x: from.x + (to.x - from.x) * t,
+                    y: from.y + (to.y - from.y) * t,
+                    z: from.z + (to.z - from.z) * t,
+                    w: from.w + (to.w - from.w) * t,
+                };
+            }
+        }
+        if transition.frame >= THEME_FADE_FRAMES {
+            theme_state.transition = None;
+        }
     }
+
+    // The font atlas is already built at the display scale; only the UI scale is left
+    context.io_mut().font_global_scale = ui_scale;
 }
 
-/// Apply theme using unsafe imgui API to actually change colors
-unsafe fn apply_theme_unsafe(theme: ImguiTheme) {
+/// Build the full target style for the current theme at `scale` and install it in one step.
+/// Style vars switch immediately; colors start a short fade unless this is the first theme applied.
+unsafe fn switch_theme(theme_state: &mut ImguiThemeState, scale: f32) {
     let style = imgui::sys::igGetStyle();
-    
+    let first_theme = theme_state.base_style.is_none();
+    let mut target = *theme_state.base_style.get_or_insert(*style);
+    apply_theme_unsafe(&mut target, theme_state.current_theme, &theme_state.custom_themes);
+    imgui::sys::ImGuiStyle_ScaleAllSizes(&mut target, scale);
+    theme_state.style_scale = Some(scale);
+
+    let from = (*style).Colors;
+    *style = target;
+    theme_state.transition = (!first_theme).then(|| {
+        (*style).Colors = from;
+        ThemeTransition {
+            from,
+            to: target.Colors,
+            frame: 0,
+        }
+    });
+}
+
+/// Apply theme using unsafe imgui API to actually change colors
+unsafe fn apply_theme_unsafe(style: *mut imgui::sys::ImGuiStyle, theme: ImguiTheme, custom_themes: &[CustomTheme]) {
     // Apply rounded styling for most themes
     if theme != ImguiTheme::CellLab {
         apply_rounded_style(style);
@@ -108,6 +443,15 @@ unsafe fn apply_theme_unsafe(theme: ImguiTheme) {
         ImguiTheme::Industrial => apply_industrial_theme(style),
         ImguiTheme::WarmOrange => apply_warm_orange_theme(style),
         ImguiTheme::CellLab => apply_cell_lab_theme(style),
+        ImguiTheme::Light => apply_light_theme(style),
+        ImguiTheme::HighContrast => apply_high_contrast_theme(style),
+        ImguiTheme::Custom(index) => {
+            // Custom themes start from Modern Dark so colors they don't set stay sensible
+            apply_modern_dark_theme(style);
+            if let Some(custom) = custom_themes.get(index) {
+                custom.apply(&mut *style);
+            }
+        }
     }
 }
 
@@ -187,6 +531,148 @@ unsafe fn apply_modern_dark_theme(style: *mut imgui::sys::ImGuiStyle) {
     colors[imgui::sys::ImGuiCol_TextSelectedBg as usize] = imgui::sys::ImVec4 { x: 0.20, y: 0.45, z: 0.80, w: 0.35 };
 }
 
+/// Light theme - Dark text on soft off-white, with blue accents
+unsafe fn apply_light_theme(style: *mut imgui::sys::ImGuiStyle) {
+    let colors = &mut (*style).Colors;
+
+    // Backgrounds - soft off-white
+    colors[imgui::sys::ImGuiCol_WindowBg as usize] = imgui::sys::ImVec4 { x: 0.95, y: 0.95, z: 0.96, w: 0.97 };
+    colors[imgui::sys::ImGuiCol_ChildBg as usize] = imgui::sys::ImVec4 { x: 0.98, y: 0.98, z: 0.99, w: 0.90 };
+    colors[imgui::sys::ImGuiCol_PopupBg as usize] = imgui::sys::ImVec4 { x: 1.00, y: 1.00, z: 1.00, w: 0.98 };
+    colors[imgui::sys::ImGuiCol_MenuBarBg as usize] = imgui::sys::ImVec4 { x: 0.90, y: 0.90, z: 0.92, w: 1.00 };
+
+    // Title bars
+    colors[imgui::sys::ImGuiCol_TitleBg as usize] = imgui::sys::ImVec4 { x: 0.86, y: 0.86, z: 0.88, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TitleBgActive as usize] = imgui::sys::ImVec4 { x: 0.78, y: 0.85, z: 0.95, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TitleBgCollapsed as usize] = imgui::sys::ImVec4 { x: 0.86, y: 0.86, z: 0.88, w: 0.75 };
+
+    // Borders
+    colors[imgui::sys::ImGuiCol_Border as usize] = imgui::sys::ImVec4 { x: 0.65, y: 0.65, z: 0.70, w: 0.60 };
+    colors[imgui::sys::ImGuiCol_BorderShadow as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.00, z: 0.00, w: 0.00 };
+
+    // Frames
+    colors[imgui::sys::ImGuiCol_FrameBg as usize] = imgui::sys::ImVec4 { x: 0.87, y: 0.87, z: 0.89, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_FrameBgHovered as usize] = imgui::sys::ImVec4 { x: 0.80, y: 0.86, z: 0.95, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_FrameBgActive as usize] = imgui::sys::ImVec4 { x: 0.72, y: 0.81, z: 0.94, w: 1.00 };
+
+    // Tabs
+    colors[imgui::sys::ImGuiCol_Tab as usize] = imgui::sys::ImVec4 { x: 0.86, y: 0.86, z: 0.88, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TabHovered as usize] = imgui::sys::ImVec4 { x: 0.45, y: 0.65, z: 0.95, w: 0.80 };
+    colors[imgui::sys::ImGuiCol_TabActive as usize] = imgui::sys::ImVec4 { x: 0.60, y: 0.75, z: 0.96, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TabUnfocused as usize] = imgui::sys::ImVec4 { x: 0.90, y: 0.90, z: 0.92, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TabUnfocusedActive as usize] = imgui::sys::ImVec4 { x: 0.80, y: 0.85, z: 0.93, w: 1.00 };
+
+    // Buttons - translucent blue so the dark text stays readable
+    colors[imgui::sys::ImGuiCol_Button as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.35 };
+    colors[imgui::sys::ImGuiCol_ButtonHovered as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.60 };
+    colors[imgui::sys::ImGuiCol_ButtonActive as usize] = imgui::sys::ImVec4 { x: 0.16, y: 0.42, z: 0.85, w: 0.80 };
+
+    // Headers
+    colors[imgui::sys::ImGuiCol_Header as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.28 };
+    colors[imgui::sys::ImGuiCol_HeaderHovered as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.45 };
+    colors[imgui::sys::ImGuiCol_HeaderActive as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.60 };
+
+    // Separator
+    colors[imgui::sys::ImGuiCol_Separator as usize] = imgui::sys::ImVec4 { x: 0.70, y: 0.70, z: 0.74, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_SeparatorHovered as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.78 };
+    colors[imgui::sys::ImGuiCol_SeparatorActive as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 1.00 };
+
+    // Resize grip
+    colors[imgui::sys::ImGuiCol_ResizeGrip as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.25 };
+    colors[imgui::sys::ImGuiCol_ResizeGripHovered as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.60 };
+    colors[imgui::sys::ImGuiCol_ResizeGripActive as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.90 };
+
+    // Scrollbar
+    colors[imgui::sys::ImGuiCol_ScrollbarBg as usize] = imgui::sys::ImVec4 { x: 0.93, y: 0.93, z: 0.94, w: 0.80 };
+    colors[imgui::sys::ImGuiCol_ScrollbarGrab as usize] = imgui::sys::ImVec4 { x: 0.72, y: 0.72, z: 0.75, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ScrollbarGrabHovered as usize] = imgui::sys::ImVec4 { x: 0.62, y: 0.62, z: 0.66, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ScrollbarGrabActive as usize] = imgui::sys::ImVec4 { x: 0.52, y: 0.52, z: 0.56, w: 1.00 };
+
+    // Sliders and checkmark
+    colors[imgui::sys::ImGuiCol_SliderGrab as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.85 };
+    colors[imgui::sys::ImGuiCol_SliderGrabActive as usize] = imgui::sys::ImVec4 { x: 0.16, y: 0.42, z: 0.85, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_CheckMark as usize] = imgui::sys::ImVec4 { x: 0.16, y: 0.42, z: 0.85, w: 1.00 };
+
+    // Text - near black
+    colors[imgui::sys::ImGuiCol_Text as usize] = imgui::sys::ImVec4 { x: 0.08, y: 0.08, z: 0.10, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TextDisabled as usize] = imgui::sys::ImVec4 { x: 0.52, y: 0.52, z: 0.55, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TextSelectedBg as usize] = imgui::sys::ImVec4 { x: 0.26, y: 0.52, z: 0.92, w: 0.35 };
+}
+
+/// High Contrast theme - Pure black text on white with saturated accents, for low vision
+unsafe fn apply_high_contrast_theme(style: *mut imgui::sys::ImGuiStyle) {
+    // Outline every frame so fields and buttons stand out from the white background
+    (*style).FrameBorderSize = 1.0;
+    (*style).WindowBorderSize = 2.0;
+    (*style).PopupBorderSize = 2.0;
+    (*style).TabBorderSize = 1.0;
+
+    let colors = &mut (*style).Colors;
+
+    // Backgrounds - pure white, fully opaque
+    colors[imgui::sys::ImGuiCol_WindowBg as usize] = imgui::sys::ImVec4 { x: 1.00, y: 1.00, z: 1.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ChildBg as usize] = imgui::sys::ImVec4 { x: 1.00, y: 1.00, z: 1.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_PopupBg as usize] = imgui::sys::ImVec4 { x: 1.00, y: 1.00, z: 1.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_MenuBarBg as usize] = imgui::sys::ImVec4 { x: 1.00, y: 1.00, z: 1.00, w: 1.00 };
+
+    // Title bars - yellow marks the focused window
+    colors[imgui::sys::ImGuiCol_TitleBg as usize] = imgui::sys::ImVec4 { x: 0.85, y: 0.85, z: 0.85, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TitleBgActive as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.85, z: 0.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TitleBgCollapsed as usize] = imgui::sys::ImVec4 { x: 0.85, y: 0.85, z: 0.85, w: 1.00 };
+
+    // Borders - solid black
+    colors[imgui::sys::ImGuiCol_Border as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.00, z: 0.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_BorderShadow as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.00, z: 0.00, w: 0.00 };
+
+    // Frames - white fields with yellow hover
+    colors[imgui::sys::ImGuiCol_FrameBg as usize] = imgui::sys::ImVec4 { x: 1.00, y: 1.00, z: 1.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_FrameBgHovered as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.95, z: 0.55, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_FrameBgActive as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.85, z: 0.00, w: 1.00 };
+
+    // Tabs
+    colors[imgui::sys::ImGuiCol_Tab as usize] = imgui::sys::ImVec4 { x: 0.85, y: 0.85, z: 0.85, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TabHovered as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.95, z: 0.55, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TabActive as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.85, z: 0.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TabUnfocused as usize] = imgui::sys::ImVec4 { x: 0.85, y: 0.85, z: 0.85, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TabUnfocusedActive as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.95, z: 0.55, w: 1.00 };
+
+    // Buttons - light accents only, so black labels keep full contrast
+    colors[imgui::sys::ImGuiCol_Button as usize] = imgui::sys::ImVec4 { x: 0.85, y: 0.85, z: 0.85, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ButtonHovered as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.85, z: 0.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ButtonActive as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.60, z: 0.00, w: 1.00 };
+
+    // Headers - cyan
+    colors[imgui::sys::ImGuiCol_Header as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.85, z: 1.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_HeaderHovered as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.85, z: 0.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_HeaderActive as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.60, z: 0.00, w: 1.00 };
+
+    // Separator
+    colors[imgui::sys::ImGuiCol_Separator as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.00, z: 0.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_SeparatorHovered as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.20, z: 1.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_SeparatorActive as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.20, z: 1.00, w: 1.00 };
+
+    // Resize grip
+    colors[imgui::sys::ImGuiCol_ResizeGrip as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.00, z: 0.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ResizeGripHovered as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.20, z: 1.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ResizeGripActive as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.00, z: 0.60, w: 1.00 };
+
+    // Scrollbar
+    colors[imgui::sys::ImGuiCol_ScrollbarBg as usize] = imgui::sys::ImVec4 { x: 0.85, y: 0.85, z: 0.85, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ScrollbarGrab as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.00, z: 0.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ScrollbarGrabHovered as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.20, z: 1.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_ScrollbarGrabActive as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.00, z: 0.60, w: 1.00 };
+
+    // Sliders and checkmark - saturated blue and magenta, never behind text
+    colors[imgui::sys::ImGuiCol_SliderGrab as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.20, z: 1.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_SliderGrabActive as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.00, z: 0.60, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_CheckMark as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.20, z: 1.00, w: 1.00 };
+
+    // Text - pure black
+    colors[imgui::sys::ImGuiCol_Text as usize] = imgui::sys::ImVec4 { x: 0.00, y: 0.00, z: 0.00, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TextDisabled as usize] = imgui::sys::ImVec4 { x: 0.30, y: 0.30, z: 0.30, w: 1.00 };
+    colors[imgui::sys::ImGuiCol_TextSelectedBg as usize] = imgui::sys::ImVec4 { x: 1.00, y: 0.85, z: 0.00, w: 1.00 };
+}
+
 /// Industrial theme - Orange, yellow, and black construction/caution aesthetic
 unsafe fn apply_industrial_theme(style: *mut imgui::sys::ImGuiStyle) {
     let colors = &mut (*style).Colors;
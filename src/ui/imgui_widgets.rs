@@ -38,11 +38,19 @@ pub fn range_slider(
     range_max: f32,
     format: &str,
 ) -> bool {
-    range_slider_ex(ui, label, min_val, max_val, range_min, range_max, format, None)
+    range_slider_ex(ui, label, min_val, max_val, range_min, range_max, format, None, None)
 }
 
+/// Fraction of the slider range a scroll notch nudges a handle by, unless a step is given
+const DEFAULT_SCROLL_STEP_FRACTION: f32 = 0.01;
+
+/// Shift multiplies the scroll step by this
+const SHIFT_SCROLL_MULTIPLIER: f32 = 10.0;
+
 /// A range slider widget with optional "never" threshold.
 /// When a value exceeds never_threshold, it displays "Never" instead of the number.
+/// Scrolling over a handle nudges it by `scroll_step` (1% of the range if `None`), Shift for 10x.
+#[allow(clippy::too_many_arguments)]
 pub fn range_slider_ex(
     ui: &Ui,
     label: &str,
@@ -52,6 +60,7 @@ pub fn range_slider_ex(
     range_max: f32,
     _format: &str,
     never_threshold: Option<f32>,
+    scroll_step: Option<f32>,
 ) -> bool {
     let widget_id = format!("range_slider_{}", label);
     let mut changed = false;
@@ -312,6 +321,9 @@ pub fn range_slider_ex(
 
         let top_active = ui.is_item_active();
         let top_clicked = ui.is_item_activated();
+        if hovering_min || hovering_max {
+            claim_mouse_wheel();
+        }
 
         if top_active && ui.is_mouse_dragging(MouseButton::Left) && !state.dragging_center {
             // On first click, determine which handle to drag based on proximity
@@ -360,6 +372,10 @@ pub fn range_slider_ex(
             [slider_width + grab_width, center_slider_height],
         );
 
+        if hovering_center {
+            claim_mouse_wheel();
+        }
+
         // Double-click on center diamond to collapse range to single value
         if ui.is_item_hovered() && ui.is_mouse_double_clicked(MouseButton::Left) {
             let center_val = (*min_val + *max_val) / 2.0;
@@ -391,6 +407,31 @@ pub fn range_slider_ex(
         } else {
             state.dragging_center = false;
         }
+
+        // Scroll over a handle to nudge it, keeping min <= max
+        let wheel = ui.io().mouse_wheel;
+        let dragging = state.dragging_center || state.drag_target != DragTarget::None;
+        if wheel != 0.0 && !dragging {
+            let multiplier = if ui.io().key_shift { SHIFT_SCROLL_MULTIPLIER } else { 1.0 };
+            let step = scroll_step.unwrap_or((range_max - range_min) * DEFAULT_SCROLL_STEP_FRACTION);
+            let delta = wheel * step * multiplier;
+            let (new_min, new_max) = if hovering_min {
+                ((*min_val + delta).clamp(range_min, *max_val), *max_val)
+            } else if hovering_max {
+                (*min_val, (*max_val + delta).clamp(*min_val, range_max))
+            } else if hovering_center {
+                let range_size = *max_val - *min_val;
+                let new_min = (*min_val + delta).clamp(range_min, range_max - range_size);
+                (new_min, new_min + range_size)
+            } else {
+                (*min_val, *max_val)
+            };
+            if new_min != *min_val || new_max != *max_val {
+                *min_val = new_min;
+                *max_val = new_max;
+                changed = true;
+            }
+        }
     });
 
     // Reserve space for the widget
@@ -401,6 +442,17 @@ pub fn range_slider_ex(
     changed
 }
 
+/// Keep the mouse wheel for the item just submitted while it is hovered, so scrolling
+/// nudges a handle instead of scrolling the window
+fn claim_mouse_wheel() {
+    unsafe {
+        imgui::sys::igSetItemKeyOwner(
+            imgui::sys::ImGuiKey_MouseWheelY,
+            imgui::sys::ImGuiInputFlags_CondHovered as imgui::sys::ImGuiInputFlags,
+        );
+    }
+}
+
 /// State management for circular sliders
 #[derive(Default)]
 struct CircularSliderState {
@@ -413,7 +465,8 @@ thread_local! {
     static CIRCULAR_SLIDER_STATES: RefCell<HashMap<String, CircularSliderState>> = RefCell::new(HashMap::new());
 }
 
-/// Circular slider for float values with angle snapping
+/// Circular slider for float values with angle snapping.
+/// Double-clicking the ring resets the value to `default_value`.
 pub fn circular_slider_float(
     ui: &Ui,
     label: &str,
@@ -425,6 +478,7 @@ pub fn circular_slider_float(
     _align_x: f32,
     _align_y: f32,
     enable_snapping: bool,
+    default_value: f32,
 ) -> bool {
     let widget_id = format!("circular_slider_{}", label);
 
@@ -569,7 +623,12 @@ pub fn circular_slider_float(
         let button_id = format!("##button_{}", label);
         ui.invisible_button(&button_id, button_size);
 
-        if ui.is_item_active() && ui.is_mouse_dragging(imgui::MouseButton::Left) {
+        // Double-click the ring to reset, like collapsing a range slider
+        if ui.is_item_hovered() && is_mouse_in_grab_zone && ui.is_mouse_double_clicked(imgui::MouseButton::Left) {
+            *v = default_value.clamp(v_min, v_max);
+            state.text_buffer = format!("{}", *v);
+            changed = true;
+        } else if ui.is_item_active() && ui.is_mouse_dragging(imgui::MouseButton::Left) {
             let mouse_pos = ui.io().mouse_pos;
             let mouse_rel_x = mouse_pos[0] - center[0];
             let mouse_rel_y = mouse_pos[1] - center[1];
@@ -913,9 +972,75 @@ pub fn quaternion_ball(
     ui.same_line_with_spacing(0.0, 8.0);
     ui.text_colored(col_axes_z, format!("Z: {:.2}°, {:.2}°", z_lat, z_lon));
 
+    // Precise entry as pitch/yaw/roll, kept in sync with the ball
+    let mut euler = displayed_euler_degrees(label, *orientation);
+    let spacing = ui.clone_style().item_spacing[0];
+    let field_width = ((container_size[0] - 2.0 * spacing) / 3.0).max(40.0);
+    let mut euler_edited = false;
+    let mut euler_released = false;
+    for (index, name) in ["Pitch", "Yaw", "Roll"].iter().enumerate() {
+        if index > 0 {
+            ui.same_line();
+        }
+        ui.set_next_item_width(field_width);
+        euler_edited |= imgui::Drag::new(format!("##{}{}", name, label))
+            .speed(0.5)
+            .display_format(format!("{}: %.2f°", name))
+            .build(ui, &mut euler[index]);
+        euler_released |= ui.is_item_deactivated_after_edit();
+        if ui.is_item_hovered() {
+            ui.tooltip_text(format!("{} in degrees (rotation about {}). Drag or Ctrl+click to type.", name, ["X", "Y", "Z"][index]));
+        }
+    }
+    if euler_edited || euler_released {
+        // Like the ball, snap once the edit is finished so dragging stays smooth
+        if euler_released && enable_snapping {
+            euler = euler.map(|angle| (angle / EULER_SNAP_DEGREES).round() * EULER_SNAP_DEGREES);
+        }
+        *orientation = quat_from_euler_degrees(euler);
+        EULER_FIELDS.with(|fields| fields.borrow_mut().insert(label.to_string(), (euler, *orientation)));
+        changed = true;
+    }
+
     changed
 }
 
+/// Euler field snap increment, matching the ball's grid
+const EULER_SNAP_DEGREES: f32 = 11.25;
+
+thread_local! {
+    /// Last Euler angles shown or entered per quaternion ball, with the orientation they describe
+    static EULER_FIELDS: RefCell<HashMap<String, ([f32; 3], Quat)>> = RefCell::new(HashMap::new());
+}
+
+/// Pitch (X), yaw (Y) and roll (Z) in degrees, applied yaw first
+fn euler_degrees_from_quat(q: Quat) -> [f32; 3] {
+    let (yaw, pitch, roll) = q.to_euler(glam::EulerRot::YXZ);
+    [pitch.to_degrees(), yaw.to_degrees(), roll.to_degrees()]
+}
+
+fn quat_from_euler_degrees(euler: [f32; 3]) -> Quat {
+    let [pitch, yaw, roll] = euler.map(f32::to_radians);
+    Quat::from_euler(glam::EulerRot::YXZ, yaw, pitch, roll).normalize()
+}
+
+/// Euler angles to show for `orientation`. The last shown or entered values are reused
+/// while the orientation still matches them, so the fields don't drift through round-trip
+/// error or jump between equivalent decompositions near the poles.
+fn displayed_euler_degrees(label: &str, orientation: Quat) -> [f32; 3] {
+    EULER_FIELDS.with(|fields| {
+        let mut fields = fields.borrow_mut();
+        if let Some((euler, cached)) = fields.get(label) {
+            if cached.dot(orientation).abs() > 1.0 - 1e-6 {
+                return *euler;
+            }
+        }
+        let euler = euler_degrees_from_quat(orientation);
+        fields.insert(label.to_string(), (euler, orientation));
+        euler
+    })
+}
+
 /// Snap quaternion to nearest grid angles (11.25 degree increments)
 fn snap_quaternion_to_grid(q: Quat, grid_angle_deg: f32) -> Quat {
     let rotation_matrix = Mat3::from_quat(q);
@@ -996,3 +1121,29 @@ fn snap_quaternion_to_grid(q: Quat, grid_angle_deg: f32) -> Quat {
     Quat::from_mat3(&snapped_matrix).normalize()
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_euler_round_trip() {
+        for euler in [[0.0, 0.0, 0.0], [30.0, -45.0, 11.25], [-80.0, 170.0, -90.0], [12.5, 0.0, 179.0]] {
+            let back = euler_degrees_from_quat(quat_from_euler_degrees(euler));
+            for (a, b) in euler.iter().zip(back) {
+                assert!((a - b).abs() < 1e-3, "{:?} came back as {:?}", euler, back);
+            }
+        }
+    }
+
+    #[test]
+    fn test_displayed_euler_is_stable() {
+        let label = "##test_displayed_euler";
+        let orientation = quat_from_euler_degrees([90.0, 33.0, 0.0]);
+        let first = displayed_euler_degrees(label, orientation);
+        for _ in 0..100 {
+            let again = quat_from_euler_degrees(first);
+            assert_eq!(displayed_euler_degrees(label, again), first);
+        }
+    }
+}
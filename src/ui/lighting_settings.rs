@@ -96,6 +96,20 @@ impl Default for LightingSettingsState {
     }
 }
 
+/// How strongly the skybox tint shows through in the viewport background
+const SKYBOX_BACKGROUND_WEIGHT: f32 = 0.05;
+
+impl LightingSettingsState {
+    /// Viewport clear color: the ambient light, plus a hint of the skybox tint when enabled
+    pub fn background_color(&self) -> [f32; 3] {
+        std::array::from_fn(|i| {
+            let ambient = self.ambient_color[i] * self.ambient_intensity;
+            let sky = if self.skybox_enabled { self.skybox_tint[i] * SKYBOX_BACKGROUND_WEIGHT } else { 0.0 };
+            (ambient + sky).clamp(0.0, 1.0)
+        })
+    }
+}
+
 /// Render the lighting settings window
 pub fn render_lighting_settings_window(
     ui: &imgui::Ui,
@@ -1,17 +1,34 @@
 use crate::ui::{GlobalUiState, imgui_style::{ImguiThemeState, ImguiTheme}};
-use crate::simulation::SimulationState;
+use crate::simulation::{SimulationMode, SimulationState};
+use crate::ui::measurement_hud::HudCorner;
+use crate::ui::camera::CameraView;
+use crate::scene::adapter::GpuPowerPreference;
+use crate::rendering::mesh_export::{MeshFormat, MAX_EXPORT_SUBDIVISIONS};
 use imgui::Ui;
 
+/// Actions picked from the menu bar this frame, carried out by the scene
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MenuRequests {
+    /// Save settings and scenes now
+    pub manual_save: bool,
+    pub exit: bool,
+    /// Export the colony as a mesh in this format
+    pub export_mesh: Option<MeshFormat>,
+    /// Reset the camera or snap it to an axis
+    pub camera_view: Option<CameraView>,
+    /// Ask for a path and save a screenshot there
+    pub screenshot: bool,
+}
+
 /// Render the main menu bar at the top of the screen
-/// Returns (manual_save_requested, exit_requested)
 pub fn render_main_menu_bar(
     ui: &Ui,
     global_ui_state: &mut GlobalUiState,
-    _simulation_state: &mut SimulationState,
+    simulation_state: &mut SimulationState,
     theme_state: &mut ImguiThemeState,
-) -> (bool, bool) {
-    let mut manual_save_requested = false;
-    let mut exit_requested = false;
+    available_adapters: &[String],
+) -> MenuRequests {
+    let mut requests = MenuRequests::default();
     if let Some(_menu_bar) = ui.begin_main_menu_bar() {
         // File menu
         if let Some(_menu) = ui.begin_menu("File") {
@@ -47,11 +64,40 @@ pub fn render_main_menu_bar(
                 ui.tooltip_text("Export current scene state");
             }
             
+            if let Some(_export_menu) = ui.begin_menu("Export Mesh") {
+                ui.text("Sphere Detail:");
+                for level in 0..=MAX_EXPORT_SUBDIVISIONS {
+                    let faces = 20 * 4_u32.pow(level);
+                    let label = format!("Level {} ({} faces/cell)", level, faces);
+                    if ui.radio_button_bool(&label, global_ui_state.mesh_export_subdivisions == level) {
+                        global_ui_state.mesh_export_subdivisions = level;
+                    }
+                }
+                
+                ui.separator();
+                
+                for format in [MeshFormat::Obj, MeshFormat::Ply] {
+                    if ui.menu_item(format!("{}...", format.name())) {
+                        requests.export_mesh = Some(format);
+                    }
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Write all current cells as colored spheres, e.g. for Blender");
+                }
+            }
+            
+            if ui.menu_item_config("Save Screenshot...").shortcut("F12").build() {
+                requests.screenshot = true;
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Save the window as a PNG; F12 saves straight to the screenshots folder");
+            }
+            
             ui.separator();
             
             if ui.menu_item("Exit") {
                 println!("Exit requested from menu");
-                exit_requested = true;
+                requests.exit = true;
             }
             if ui.is_item_hovered() {
                 ui.tooltip_text("Exit the application");
@@ -67,17 +113,66 @@ pub fn render_main_menu_bar(
             
             // Quick theme selection - Presets
             ui.text("Preset Themes:");
-            for theme in ImguiTheme::all() {
-                let is_current = theme_state.current_theme == *theme;
-                if ui.menu_item_config(theme.name()).selected(is_current).build() {
-                    if theme_state.current_theme != *theme {
-                        theme_state.current_theme = *theme;
+            for theme in ImguiTheme::all(&theme_state.custom_themes) {
+                if theme == ImguiTheme::Custom(0) {
+                    ui.separator();
+                    ui.text("Custom Themes:");
+                }
+                let is_current = theme_state.current_theme == theme;
+                if ui.menu_item_config(theme.name(&theme_state.custom_themes)).selected(is_current).build() {
+                    if theme_state.current_theme != theme {
+                        theme_state.current_theme = theme;
                         theme_state.theme_changed = true;
                     }
                 }
             }
         }
         
+        // View menu - viewport overlays
+        if let Some(_menu) = ui.begin_menu("View") {
+            ui.menu_item_config("Debug Overlay")
+                .shortcut("F3")
+                .build_with_ref(&mut global_ui_state.show_debug_overlay);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Frame time, FPS, cell and adhesion counts, sim time and physics mode");
+            }
+            
+            ui.menu_item_config("Measurement HUD")
+                .build_with_ref(&mut global_ui_state.show_measurement_hud);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Small corner readout of sim time, cell count and FPS");
+            }
+            
+            if let Some(_corner_menu) = ui.begin_menu("HUD Corner") {
+                for corner in HudCorner::all() {
+                    let is_current = global_ui_state.measurement_hud_corner == *corner;
+                    if ui.menu_item_config(corner.name()).selected(is_current).build() {
+                        global_ui_state.measurement_hud_corner = *corner;
+                    }
+                }
+            }
+            
+            ui.separator();
+            
+            for view in CameraView::all() {
+                if ui.menu_item_config(view.name()).shortcut(view.shortcut()).build() {
+                    requests.camera_view = Some(*view);
+                }
+                if *view == CameraView::Reset && ui.is_item_hovered() {
+                    ui.tooltip_text("Frame the whole colony from the default angle");
+                }
+            }
+            
+            ui.separator();
+            
+            if ui.menu_item_config("Hide UI").shortcut("F11").build() {
+                global_ui_state.toggle_ui_hidden();
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Hide the menu bar and all windows for presenting; press F11 to bring them back");
+            }
+        }
+        
         // Windows menu - for toggling window visibility
         if let Some(_menu) = ui.begin_menu("Windows") {
             ui.checkbox("Cell Inspector", &mut global_ui_state.show_cell_inspector);
@@ -94,6 +189,26 @@ pub fn render_main_menu_bar(
             ui.checkbox("Lighting Settings", &mut global_ui_state.show_lighting_settings);
             ui.checkbox("Time Scrubber", &mut global_ui_state.show_time_scrubber);
             ui.checkbox("Theme Editor", &mut global_ui_state.show_theme_editor);
+            
+            ui.checkbox("Parameter Sweep", &mut global_ui_state.show_parameter_sweep);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Run headless simulations across a range of parameter values");
+            }
+            
+            ui.checkbox("Mass Histogram", &mut global_ui_state.show_mass_histogram);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Live distribution of cell masses");
+            }
+            
+            ui.checkbox("Physics Settings", &mut global_ui_state.show_physics_settings);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Timestep, substeps, damping, collisions and world size");
+            }
+            
+            ui.checkbox("Settings", &mut global_ui_state.show_settings);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Font size and other application settings");
+            }
         }
         
         // Options menu
@@ -135,11 +250,74 @@ pub fn render_main_menu_bar(
             let mut current_scale = global_ui_state.ui_scale;
             for (scale_value, label) in scale_options.iter() {
                 if ui.radio_button(label, &mut current_scale, *scale_value) {
-                    global_ui_state.ui_scale = *scale_value;
+                    global_ui_state.set_ui_scale(*scale_value);
+                }
+                ui.same_line();
+            }
+            ui.new_line();
+
+            ui.separator();
+
+            // Number display precision
+            ui.text("Number Display");
+            for precision in crate::ui::number_format::NumberFormat::PRECISION_OPTIONS {
+                let label = format!("{} decimals", precision);
+                if ui.radio_button_bool(&label, global_ui_state.display_precision == precision) {
+                    global_ui_state.display_precision = precision;
                 }
                 ui.same_line();
             }
             ui.new_line();
+            ui.checkbox("Scientific Notation", &mut global_ui_state.scientific_notation);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Show values too small for the chosen precision in scientific notation (e.g. 1.50e-4)");
+            }
+
+            ui.separator();
+
+            // Graphics adapter
+            ui.text("Graphics Adapter");
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Changes take effect the next time BioSpheres starts");
+            }
+            for preference in GpuPowerPreference::all() {
+                if ui.radio_button_bool(preference.name(), global_ui_state.gpu_power_preference == *preference) {
+                    global_ui_state.gpu_power_preference = *preference;
+                }
+            }
+            if available_adapters.len() > 1 {
+                let preview = global_ui_state.preferred_adapter.as_deref().unwrap_or("Automatic");
+                if let Some(_combo) = ui.begin_combo("Adapter", preview) {
+                    if ui.selectable_config("Automatic").selected(global_ui_state.preferred_adapter.is_none()).build() {
+                        global_ui_state.preferred_adapter = None;
+                    }
+                    for adapter in available_adapters {
+                        let is_selected = global_ui_state.preferred_adapter.as_deref() == Some(adapter.as_str());
+                        if ui.selectable_config(adapter).selected(is_selected).build() {
+                            global_ui_state.preferred_adapter = Some(adapter.clone());
+                        }
+                    }
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Force a specific GPU, e.g. the discrete GPU on a hybrid-graphics laptop");
+                }
+            }
+            ui.text_disabled("Restart to apply");
+
+            ui.separator();
+
+            // Auto-save
+            ui.text("Auto-Save");
+            ui.checkbox("Auto-Save Genome", &mut global_ui_state.autosave_enabled);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Periodically write the genome to autosave/ when it has changed");
+            }
+            ui.disabled(!global_ui_state.autosave_enabled, || {
+                let mut minutes = global_ui_state.autosave_interval_secs / 60.0;
+                if ui.slider_config("Interval (min)", 0.5, 30.0).display_format("%.1f").build(&mut minutes) {
+                    global_ui_state.autosave_interval_secs = minutes * 60.0;
+                }
+            });
 
             ui.separator();
 
@@ -153,7 +331,7 @@ pub fn render_main_menu_bar(
 
             // Manual save settings option
             if ui.menu_item("Save Settings Now") {
-                manual_save_requested = true;
+                requests.manual_save = true;
             }
             if ui.is_item_hovered() {
                 ui.tooltip_text("Manually save current UI and theme settings");
@@ -172,24 +350,37 @@ pub fn render_main_menu_bar(
         
         // Simulation menu
         if let Some(_menu) = ui.begin_menu("Simulation") {
-            ui.text("Mode:");
-            ui.same_line();
-            ui.text_colored([0.7, 0.7, 0.7, 1.0], "Preview");
+            if let Some(_mode_menu) = ui.begin_menu("Mode") {
+                for mode in SimulationMode::ALL {
+                    let supported = mode != SimulationMode::Gpu || simulation_state.gpu_support.is_supported();
+                    if ui.menu_item_config(mode.name()).selected(simulation_state.mode == mode).enabled(supported).build() {
+                        simulation_state.mode = mode;
+                    }
+                }
+            }
             
             ui.separator();
             
-            if ui.menu_item_config("Reset Simulation").shortcut("Ctrl+R").build() {
-                println!("Reset simulation requested");
+            let pause_label = if simulation_state.paused { "Resume" } else { "Pause" };
+            if ui.menu_item(pause_label) {
+                simulation_state.paused = !simulation_state.paused;
             }
             if ui.is_item_hovered() {
-                ui.tooltip_text("Reset the simulation to initial state");
+                ui.tooltip_text("Toggle simulation pause state");
+            }
+            
+            if ui.menu_item_config("Step One Frame").enabled(simulation_state.paused).build() {
+                simulation_state.step_requested = true;
+            }
+            if ui.is_item_hovered_with_flags(imgui::ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+                ui.tooltip_text("Advance one physics step while paused");
             }
             
-            if ui.menu_item_config("Pause/Resume").shortcut("Space").build() {
-                println!("Pause/Resume simulation requested");
+            if ui.menu_item("Reset Simulation") {
+                simulation_state.needs_respawn = true;
             }
             if ui.is_item_hovered() {
-                ui.tooltip_text("Toggle simulation pause state");
+                ui.tooltip_text("Respawn the starting colony with the edited genome");
             }
             
             ui.separator();
@@ -211,8 +402,10 @@ pub fn render_main_menu_bar(
         // Help menu
         if let Some(_menu) = ui.begin_menu("Help") {
             if ui.menu_item("About") {
-                // Placeholder for about dialog
-                println!("About dialog requested");
+                global_ui_state.show_about = true;
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Version, GPU adapter and where logs and autosaves are written");
             }
             
             ui.separator();
@@ -229,8 +422,8 @@ pub fn render_main_menu_bar(
         }
         
         // Add version text on the right side of the menu bar
-        let version_text = "BioSpheres (v0.1.8)";
-        let text_width = ui.calc_text_size(version_text)[0];
+        let version_text = format!("BioSpheres (v{})", crate::app_info::VERSION);
+        let text_width = ui.calc_text_size(&version_text)[0];
         let window_width = ui.window_size()[0];
         let padding = 10.0;
         
@@ -239,5 +432,5 @@ pub fn render_main_menu_bar(
         ui.text(version_text);
     }
     
-    (manual_save_requested, exit_requested)
+    requests
 }
\ No newline at end of file
@@ -0,0 +1,184 @@
+use crate::genome::GenomeData;
+use crate::simulation::cpu_sim::CpuSimulation;
+use imgui::{Condition, StyleColor, WindowFlags};
+use super::number_format::NumberFormat;
+
+/// Seconds between histogram rebuilds
+const HISTOGRAM_UPDATE_INTERVAL: f32 = 0.25;
+
+/// Cell counts for one mode, drawn in that mode's color
+struct ModeBins {
+    name: String,
+    color: [f32; 4],
+    bins: Vec<f32>,
+}
+
+/// State for the Mass Histogram window
+pub struct MassHistogramState {
+    pub bin_count: usize,
+    /// Draw one histogram per mode instead of a single combined one
+    pub split_by_mode: bool,
+    elapsed: f32,
+    bins: Vec<f32>,
+    mode_bins: Vec<ModeBins>,
+    /// Upper edge of the last bin; bins cover 0..max_mass
+    max_mass: f32,
+    mean_mass: f32,
+    cell_count: usize,
+    /// Cells at or above their mode's split mass
+    ready_to_split: usize,
+}
+
+impl Default for MassHistogramState {
+    fn default() -> Self {
+        Self {
+            bin_count: 24,
+            split_by_mode: false,
+            // Build on the first update
+            elapsed: HISTOGRAM_UPDATE_INTERVAL,
+            bins: Vec::new(),
+            mode_bins: Vec::new(),
+            max_mass: 0.0,
+            mean_mass: 0.0,
+            cell_count: 0,
+            ready_to_split: 0,
+        }
+    }
+}
+
+impl MassHistogramState {
+    /// Rebucket cell masses once per update interval
+    pub fn update(&mut self, delta_time: f32, simulation: &CpuSimulation, genome: &GenomeData) {
+        self.elapsed += delta_time;
+        if self.elapsed < HISTOGRAM_UPDATE_INTERVAL {
+            return;
+        }
+        self.elapsed = 0.0;
+
+        let bin_count = self.bin_count.max(1);
+        // Keep every mode's split mass in view so "ready to split" sits inside the plot
+        let largest_split_mass = genome.modes.iter().map(|m| m.split_mass).fold(0.0, f32::max);
+        let largest_mass = simulation.cells.iter().map(|c| c.mass).fold(0.0, f32::max);
+        self.max_mass = largest_split_mass.max(largest_mass).max(f32::EPSILON);
+
+        self.bins = vec![0.0; bin_count];
+        self.mode_bins.clear();
+        self.ready_to_split = 0;
+        let mut total_mass = 0.0;
+
+        for cell in &simulation.cells {
+            let bin = ((cell.mass / self.max_mass * bin_count as f32) as usize).min(bin_count - 1);
+            self.bins[bin] += 1.0;
+            total_mass += cell.mass;
+
+            let mode = simulation.mode_of(genome, cell);
+            if cell.mass >= mode.split_mass {
+                self.ready_to_split += 1;
+            }
+            if self.split_by_mode {
+                let entry = match self.mode_bins.iter().position(|m| m.name == mode.name) {
+                    Some(index) => &mut self.mode_bins[index],
+                    None => {
+                        self.mode_bins.push(ModeBins {
+                            name: mode.name.clone(),
+                            color: [mode.color.x, mode.color.y, mode.color.z, 1.0],
+                            bins: vec![0.0; bin_count],
+                        });
+                        self.mode_bins.last_mut().unwrap()
+                    }
+                };
+                entry.bins[bin] += 1.0;
+            }
+        }
+
+        self.cell_count = simulation.cells.len();
+        self.mean_mass = if self.cell_count > 0 { total_mass / self.cell_count as f32 } else { 0.0 };
+    }
+}
+
+/// Main Mass Histogram window rendering function
+pub fn render_mass_histogram_window(
+    ui: &imgui::Ui,
+    histogram_state: &mut MassHistogramState,
+    global_ui_state: &super::GlobalUiState,
+) {
+    // Only show if visibility is enabled
+    if !global_ui_state.show_mass_histogram {
+        return;
+    }
+
+    // Build flags based on lock state
+    let flags = if global_ui_state.windows_locked {
+        WindowFlags::NO_MOVE | WindowFlags::NO_RESIZE
+    } else {
+        WindowFlags::empty()
+    };
+
+    ui.window("Mass Histogram")
+        .size([380.0, 300.0], Condition::FirstUseEver)
+        .position([1000.0, 300.0], Condition::FirstUseEver)
+        .flags(flags)
+        .build(|| {
+            render_mass_histogram_content(ui, histogram_state, global_ui_state.number_format());
+        });
+}
+
+/// Render just the content of the Mass Histogram window (without the window wrapper)
+pub fn render_mass_histogram_content(
+    ui: &imgui::Ui,
+    histogram_state: &mut MassHistogramState,
+    number_format: NumberFormat,
+) {
+    let fmt = |value: f32| number_format.format(value);
+
+    ui.checkbox("Split by Mode", &mut histogram_state.split_by_mode);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Draw a separate histogram for each mode in its color");
+    }
+    ui.same_line();
+    ui.set_next_item_width(120.0);
+    let mut bin_count = histogram_state.bin_count as i32;
+    if ui.slider("Bins", 4, 64, &mut bin_count) {
+        histogram_state.bin_count = bin_count as usize;
+    }
+
+    ui.text(format!(
+        "Cells: {}   Mean: {}   Ready to split: {}",
+        histogram_state.cell_count,
+        fmt(histogram_state.mean_mass),
+        histogram_state.ready_to_split
+    ));
+    ui.text_disabled(format!("Mass 0 .. {}", fmt(histogram_state.max_mass)));
+    ui.separator();
+
+    if histogram_state.cell_count == 0 {
+        ui.text_disabled("No cells in the simulation");
+        return;
+    }
+
+    let width = ui.content_region_avail()[0];
+    if histogram_state.split_by_mode && !histogram_state.mode_bins.is_empty() {
+        // Shared scale so bar heights compare across modes
+        let scale_max = histogram_state
+            .mode_bins
+            .iter()
+            .flat_map(|m| m.bins.iter().copied())
+            .fold(1.0, f32::max);
+        for mode in &histogram_state.mode_bins {
+            let _color = ui.push_style_color(StyleColor::PlotHistogram, mode.color);
+            ui.plot_histogram(format!("##mass_{}", mode.name), &mode.bins)
+                .graph_size([width, 60.0])
+                .scale_min(0.0)
+                .scale_max(scale_max)
+                .overlay_text(&mode.name)
+                .build();
+        }
+    } else {
+        let scale_max = histogram_state.bins.iter().copied().fold(1.0, f32::max);
+        ui.plot_histogram("##mass_all", &histogram_state.bins)
+            .graph_size([width, ui.content_region_avail()[1].max(80.0)])
+            .scale_min(0.0)
+            .scale_max(scale_max)
+            .build();
+    }
+}
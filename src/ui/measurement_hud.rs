@@ -0,0 +1,87 @@
+use imgui::{Condition, WindowFlags};
+use super::number_format::NumberFormat;
+
+/// Distance from the viewport edge to the HUD
+const HUD_MARGIN: f32 = 10.0;
+
+/// Screen corner the measurement HUD is pinned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum HudCorner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl HudCorner {
+    pub fn all() -> &'static [HudCorner] {
+        &[
+            HudCorner::TopLeft,
+            HudCorner::TopRight,
+            HudCorner::BottomLeft,
+            HudCorner::BottomRight,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HudCorner::TopLeft => "Top Left",
+            HudCorner::TopRight => "Top Right",
+            HudCorner::BottomLeft => "Bottom Left",
+            HudCorner::BottomRight => "Bottom Right",
+        }
+    }
+
+    /// Window position and pivot for this corner of a viewport
+    fn anchor(&self, display_size: [f32; 2], top_offset: f32) -> ([f32; 2], [f32; 2]) {
+        let left = HUD_MARGIN;
+        let right = display_size[0] - HUD_MARGIN;
+        let top = top_offset + HUD_MARGIN;
+        let bottom = display_size[1] - HUD_MARGIN;
+        match self {
+            HudCorner::TopLeft => ([left, top], [0.0, 0.0]),
+            HudCorner::TopRight => ([right, top], [1.0, 0.0]),
+            HudCorner::BottomLeft => ([left, bottom], [0.0, 1.0]),
+            HudCorner::BottomRight => ([right, bottom], [1.0, 1.0]),
+        }
+    }
+}
+
+/// Draw the always-on corner readout of sim time, cell count and FPS.
+/// The window is re-anchored every frame so it follows its corner when the viewport resizes.
+/// `debug_overlay_height` is the space the debug overlay takes in the top-left corner.
+pub fn render_measurement_hud(
+    ui: &imgui::Ui,
+    corner: HudCorner,
+    debug_overlay_height: f32,
+    sim_time: f32,
+    cell_count: usize,
+    fps: f32,
+    number_format: NumberFormat,
+) {
+    // Keep clear of the main menu bar
+    let menu_bar_height = ui.frame_height();
+    let stacked = if corner == HudCorner::TopLeft { debug_overlay_height } else { 0.0 };
+    let (position, pivot) = corner.anchor(ui.io().display_size, menu_bar_height + stacked);
+
+    ui.window("##measurement_hud")
+        .position(position, Condition::Always)
+        .position_pivot(pivot)
+        .bg_alpha(0.35)
+        .flags(
+            WindowFlags::NO_DECORATION
+                | WindowFlags::ALWAYS_AUTO_RESIZE
+                | WindowFlags::NO_MOVE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_FOCUS_ON_APPEARING
+                | WindowFlags::NO_NAV
+                | WindowFlags::NO_INPUTS
+                | WindowFlags::NO_DOCKING,
+        )
+        .build(|| {
+            ui.text(format!("Time:  {}s", number_format.format(sim_time)));
+            ui.text(format!("Cells: {}", cell_count));
+            ui.text(format!("FPS:   {:.0}", fps));
+        });
+}
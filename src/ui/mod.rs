@@ -1,7 +1,9 @@
+pub mod about;
 pub mod camera;
 pub mod camera_settings;
 pub mod cell_inspector;
 pub mod debug_info;
+pub mod expression;
 pub mod genome_editor;
 pub mod imgui_integration;
 pub mod imgui_panel;
@@ -10,7 +12,12 @@ pub mod imgui_widgets;
 pub mod imnodes_extensions;
 pub mod lighting_settings;
 pub mod main_menu_bar;
+pub mod mass_histogram;
+pub mod measurement_hud;
+pub mod number_format;
+pub mod parameter_sweep;
 pub mod performance_monitor;
+pub mod physics_settings;
 pub mod rendering_controls;
 pub mod scene_manager;
 pub mod settings;
@@ -19,6 +26,20 @@ pub mod theme_editor;
 pub mod time_scrubber;
 pub mod edge_resize;
 
+/// Smallest UI scale; below this text becomes unreadable
+pub const UI_SCALE_MIN: f32 = 0.5;
+/// Largest UI scale, matching the biggest option in the View menu
+pub const UI_SCALE_MAX: f32 = 4.0;
+
+/// Bring a UI scale into the usable range; non-finite values fall back to 100%
+pub fn clamp_ui_scale(scale: f32) -> f32 {
+    if scale.is_finite() {
+        scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX)
+    } else {
+        1.0
+    }
+}
+
 /// Global UI state shared across all UI components
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
 pub struct GlobalUiState {
@@ -34,6 +55,109 @@ pub struct GlobalUiState {
     pub show_theme_editor: bool,
     pub show_camera_settings: bool,
     pub show_lighting_settings: bool,
+    #[serde(default)]
+    pub show_parameter_sweep: bool,
+    #[serde(default)]
+    pub show_mass_histogram: bool,
+    #[serde(default)]
+    pub show_settings: bool,
+    #[serde(default)]
+    pub show_physics_settings: bool,
+    /// About dialog, opened from the Help menu; never persisted
+    #[serde(skip)]
+    pub show_about: bool,
+    /// Presentation mode: the menu bar and every panel are hidden, toggled with F11; never persisted
+    #[serde(skip)]
+    pub ui_hidden: bool,
+    /// Which windows were open when the UI was hidden, restored when it comes back
+    #[serde(skip)]
+    hidden_window_visibility: Option<WindowVisibility>,
+    /// Decimal places shown in numeric readouts
+    #[serde(default = "default_display_precision")]
+    pub display_precision: usize,
+    /// Show tiny values in scientific notation
+    #[serde(default)]
+    pub scientific_notation: bool,
+    /// Sphere subdivision level used when exporting the colony as a mesh
+    #[serde(default = "default_mesh_export_subdivisions")]
+    pub mesh_export_subdivisions: u32,
+    /// Saved camera viewpoints, Ctrl+1..9 to store and 1..9 to recall
+    #[serde(default)]
+    pub camera_bookmarks: [Option<camera::CameraBookmark>; camera::CAMERA_BOOKMARK_SLOTS],
+    /// Viewport camera pose, lens and control tuning
+    #[serde(default)]
+    pub camera: camera::OrbitCamera,
+    /// Debug gizmo toggles and cell cap
+    #[serde(default)]
+    pub gizmos: crate::rendering::debug::GizmoSettings,
+    /// Light cell colors the old way (authored colors treated as linear, no emissive)
+    #[serde(default)]
+    pub legacy_color_handling: bool,
+    /// Cell property used to tint the rendered spheres
+    #[serde(default)]
+    pub cell_color_by: crate::rendering::cells::CellColorBy,
+    /// Sphere detail, distance LOD and impostor switching for cells
+    #[serde(default)]
+    pub cell_lod: crate::rendering::cells::CellLodSettings,
+    /// Corner readout of sim time, cell count and FPS
+    #[serde(default)]
+    pub show_measurement_hud: bool,
+    #[serde(default)]
+    pub measurement_hud_corner: measurement_hud::HudCorner,
+    /// Top-left overlay of frame time, FPS and colony counters
+    #[serde(default)]
+    pub show_debug_overlay: bool,
+    /// Frame samples the performance monitor keeps for its graphs
+    #[serde(default = "default_perf_history_size")]
+    pub perf_history_size: usize,
+    /// Seconds between performance monitor readout refreshes
+    #[serde(default = "default_perf_update_interval")]
+    pub perf_update_interval: f32,
+    /// Periodically write the genome to rotating autosave files
+    #[serde(default = "default_true")]
+    pub autosave_enabled: bool,
+    #[serde(default = "default_autosave_interval_secs")]
+    pub autosave_interval_secs: f32,
+    /// GPU class requested at startup
+    #[serde(default)]
+    pub gpu_power_preference: crate::scene::adapter::GpuPowerPreference,
+    /// Specific adapter to use at startup, by label; `None` picks automatically
+    #[serde(default)]
+    pub preferred_adapter: Option<String>,
+    /// Base font size in pixels, before display and UI scale
+    #[serde(default = "default_font_size")]
+    pub font_size: f32,
+    /// Oversample font glyphs for smoother text
+    #[serde(default)]
+    pub font_oversampling: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_autosave_interval_secs() -> f32 {
+    crate::genome::autosave::DEFAULT_AUTOSAVE_INTERVAL_SECS
+}
+
+fn default_font_size() -> f32 {
+    settings::DEFAULT_FONT_SIZE
+}
+
+fn default_perf_history_size() -> usize {
+    performance_monitor::DEFAULT_HISTORY_SIZE
+}
+
+fn default_perf_update_interval() -> f32 {
+    performance_monitor::DEFAULT_UPDATE_INTERVAL
+}
+
+fn default_display_precision() -> usize {
+    2
+}
+
+fn default_mesh_export_subdivisions() -> u32 {
+    2
 }
 
 impl Default for GlobalUiState {
@@ -50,11 +174,131 @@ impl Default for GlobalUiState {
             show_theme_editor: true,
             show_camera_settings: true,
             show_lighting_settings: true,
+            show_parameter_sweep: false,
+            show_mass_histogram: false,
+            show_settings: false,
+            show_physics_settings: false,
+            show_about: false,
+            ui_hidden: false,
+            hidden_window_visibility: None,
+            display_precision: default_display_precision(),
+            scientific_notation: false,
+            mesh_export_subdivisions: default_mesh_export_subdivisions(),
+            camera_bookmarks: Default::default(),
+            camera: camera::OrbitCamera::default(),
+            gizmos: crate::rendering::debug::GizmoSettings::default(),
+            legacy_color_handling: false,
+            cell_color_by: crate::rendering::cells::CellColorBy::default(),
+            cell_lod: crate::rendering::cells::CellLodSettings::default(),
+            show_measurement_hud: false,
+            show_debug_overlay: false,
+            perf_history_size: default_perf_history_size(),
+            perf_update_interval: default_perf_update_interval(),
+            measurement_hud_corner: measurement_hud::HudCorner::default(),
+            autosave_enabled: true,
+            autosave_interval_secs: default_autosave_interval_secs(),
+            gpu_power_preference: crate::scene::adapter::GpuPowerPreference::default(),
+            preferred_adapter: None,
+            font_size: default_font_size(),
+            font_oversampling: false,
         }
     }
 }
 
+/// Key that hides and restores the whole UI
+pub const TOGGLE_UI_KEY: winit::keyboard::KeyCode = winit::keyboard::KeyCode::F11;
+
+/// Key that saves a screenshot of the window to the screenshot directory
+pub const SCREENSHOT_KEY: winit::keyboard::KeyCode = winit::keyboard::KeyCode::F12;
+
+/// Key that shows and hides the debug overlay
+pub const DEBUG_OVERLAY_KEY: winit::keyboard::KeyCode = winit::keyboard::KeyCode::F3;
+
+/// Open/closed state of every window and overlay, snapshotted while the UI is hidden
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WindowVisibility {
+    cell_inspector: bool,
+    genome_editor: bool,
+    scene_manager: bool,
+    performance_monitor: bool,
+    rendering_controls: bool,
+    time_scrubber: bool,
+    theme_editor: bool,
+    camera_settings: bool,
+    lighting_settings: bool,
+    parameter_sweep: bool,
+    mass_histogram: bool,
+    settings: bool,
+    physics_settings: bool,
+    measurement_hud: bool,
+    debug_overlay: bool,
+}
+
+impl WindowVisibility {
+    fn capture(state: &GlobalUiState) -> Self {
+        Self {
+            cell_inspector: state.show_cell_inspector,
+            genome_editor: state.show_genome_editor,
+            scene_manager: state.show_scene_manager,
+            performance_monitor: state.show_performance_monitor,
+            rendering_controls: state.show_rendering_controls,
+            time_scrubber: state.show_time_scrubber,
+            theme_editor: state.show_theme_editor,
+            camera_settings: state.show_camera_settings,
+            lighting_settings: state.show_lighting_settings,
+            parameter_sweep: state.show_parameter_sweep,
+            mass_histogram: state.show_mass_histogram,
+            settings: state.show_settings,
+            physics_settings: state.show_physics_settings,
+            measurement_hud: state.show_measurement_hud,
+            debug_overlay: state.show_debug_overlay,
+        }
+    }
+
+    fn restore(&self, state: &mut GlobalUiState) {
+        state.show_cell_inspector = self.cell_inspector;
+        state.show_genome_editor = self.genome_editor;
+        state.show_scene_manager = self.scene_manager;
+        state.show_performance_monitor = self.performance_monitor;
+        state.show_rendering_controls = self.rendering_controls;
+        state.show_time_scrubber = self.time_scrubber;
+        state.show_theme_editor = self.theme_editor;
+        state.show_camera_settings = self.camera_settings;
+        state.show_lighting_settings = self.lighting_settings;
+        state.show_parameter_sweep = self.parameter_sweep;
+        state.show_mass_histogram = self.mass_histogram;
+        state.show_settings = self.settings;
+        state.show_physics_settings = self.physics_settings;
+        state.show_measurement_hud = self.measurement_hud;
+        state.show_debug_overlay = self.debug_overlay;
+    }
+}
+
 impl GlobalUiState {
+    /// Hide every panel and the menu bar, or bring them back exactly as they were
+    pub fn toggle_ui_hidden(&mut self) {
+        if let Some(visibility) = self.hidden_window_visibility.take() {
+            visibility.restore(self);
+            self.ui_hidden = false;
+        } else {
+            self.hidden_window_visibility = Some(WindowVisibility::capture(self));
+            self.ui_hidden = true;
+        }
+    }
+
+    /// Set the UI scale, clamped to the usable range
+    pub fn set_ui_scale(&mut self, scale: f32) {
+        self.ui_scale = clamp_ui_scale(scale);
+    }
+
+    /// Number formatting for readouts, from the display settings
+    pub fn number_format(&self) -> number_format::NumberFormat {
+        number_format::NumberFormat {
+            precision: self.display_precision,
+            scientific: self.scientific_notation,
+        }
+    }
+
     /// Save settings to file
     pub fn save_to_file(&self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::to_string_pretty(self)?;
@@ -66,8 +310,21 @@ impl GlobalUiState {
     pub fn load_from_file(path: &std::path::Path) -> Self {
         match std::fs::read_to_string(path) {
             Ok(json) => {
-                match serde_json::from_str(&json) {
-                    Ok(settings) => settings,
+                match serde_json::from_str::<Self>(&json) {
+                    Ok(mut settings) => {
+                        // A bad scale can make the UI too small or too large to fix from inside the app
+                        let scale = settings.ui_scale;
+                        settings.set_ui_scale(scale);
+                        if settings.ui_scale != scale {
+                            eprintln!("UI scale {} is out of range; using {}", scale, settings.ui_scale);
+                        }
+                        let precision = settings.display_precision;
+                        settings.display_precision = number_format::NumberFormat::clamp_precision(precision);
+                        if settings.display_precision != precision {
+                            eprintln!("Display precision {} is out of range; using {}", precision, settings.display_precision);
+                        }
+                        settings
+                    }
                     Err(e) => {
                         eprintln!("Failed to parse settings file: {}. Using defaults.", e);
                         Self::default()
@@ -86,3 +343,38 @@ impl GlobalUiState {
         std::path::PathBuf::from("ui_settings.json")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_range_ui_scale_is_clamped_on_load() {
+        let path = std::env::temp_dir().join(format!("biospheres_ui_scale_{}.json", std::process::id()));
+        for (saved, expected) in [(0.0, UI_SCALE_MIN), (-2.0, UI_SCALE_MIN), (1000.0, UI_SCALE_MAX), (1.5, 1.5)] {
+            let settings = GlobalUiState { ui_scale: saved, ..GlobalUiState::default() };
+            settings.save_to_file(&path).unwrap();
+            let loaded = GlobalUiState::load_from_file(&path);
+            assert_eq!(loaded.ui_scale, expected, "saved scale {}", saved);
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_out_of_range_display_precision_is_clamped_on_load() {
+        let path = std::env::temp_dir().join(format!("biospheres_precision_{}.json", std::process::id()));
+        for (saved, expected) in [(0, 2), (1000, 4), (3, 3)] {
+            let settings = GlobalUiState { display_precision: saved, ..GlobalUiState::default() };
+            settings.save_to_file(&path).unwrap();
+            let loaded = GlobalUiState::load_from_file(&path);
+            assert_eq!(loaded.display_precision, expected, "saved precision {}", saved);
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_non_finite_ui_scale_falls_back_to_default() {
+        assert_eq!(clamp_ui_scale(f32::NAN), 1.0);
+        assert_eq!(clamp_ui_scale(f32::INFINITY), 1.0);
+    }
+}
@@ -0,0 +1,54 @@
+// Number formatting for UI readouts
+
+/// How numeric readouts are displayed across the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Digits after the decimal point
+    pub precision: usize,
+    /// Show very small (and very large) values in scientific notation
+    pub scientific: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            precision: 2,
+            scientific: false,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Precision choices offered in the Options menu
+    pub const PRECISION_OPTIONS: [usize; 3] = [2, 3, 4];
+
+    /// Bring a precision into the range offered in the Options menu
+    pub fn clamp_precision(precision: usize) -> usize {
+        let options = Self::PRECISION_OPTIONS;
+        precision.clamp(options[0], options[options.len() - 1])
+    }
+
+    /// Whether a value would be unreadable in fixed notation at this precision
+    fn needs_scientific(&self, value: f32) -> bool {
+        let magnitude = value.abs();
+        self.scientific && magnitude != 0.0 && (magnitude < 10f32.powi(-(self.precision as i32)) || magnitude >= 1.0e6)
+    }
+
+    /// Format a value for display
+    pub fn format(&self, value: f32) -> String {
+        if self.needs_scientific(value) {
+            format!("{:.*e}", self.precision, value)
+        } else {
+            format!("{:.*}", self.precision, value)
+        }
+    }
+
+    /// printf-style format string for imgui sliders showing this value
+    pub fn imgui_format(&self, value: f32) -> String {
+        if self.needs_scientific(value) {
+            format!("%.{}e", self.precision)
+        } else {
+            format!("%.{}f", self.precision)
+        }
+    }
+}
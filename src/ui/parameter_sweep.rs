@@ -0,0 +1,219 @@
+use crate::genome::CurrentGenome;
+use crate::simulation::parameter_sweep::{run_sweep, SweepConfig, SweepParameter, SweepPoint};
+use crate::simulation::SimulationState;
+use imgui::{Condition, WindowFlags};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+/// Results shared between the sweep worker thread and the UI
+#[derive(Default)]
+struct SweepProgress {
+    points: Vec<SweepPoint>,
+    total: usize,
+    running: bool,
+    parameter: Option<SweepParameter>,
+}
+
+/// State for the Parameter Sweep window
+pub struct ParameterSweepState {
+    pub config: SweepConfig,
+    progress: Arc<Mutex<SweepProgress>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Default for ParameterSweepState {
+    fn default() -> Self {
+        Self {
+            config: SweepConfig::default(),
+            progress: Arc::new(Mutex::new(SweepProgress::default())),
+            worker: None,
+        }
+    }
+}
+
+impl ParameterSweepState {
+    pub fn is_running(&self) -> bool {
+        self.progress.lock().running
+    }
+
+    /// Start a sweep on a worker thread so the UI keeps running
+    fn start(&mut self, current_genome: &CurrentGenome, simulation_state: &SimulationState) {
+        if self.is_running() {
+            return;
+        }
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+
+        {
+            let mut progress = self.progress.lock();
+            progress.points.clear();
+            progress.total = self.config.steps;
+            progress.running = true;
+            progress.parameter = Some(self.config.parameter);
+        }
+
+        let genome = current_genome.genome.clone();
+        let physics = simulation_state.physics_config.clone();
+        let config = self.config.clone();
+        let progress = self.progress.clone();
+        self.worker = Some(std::thread::spawn(move || {
+            run_sweep(&genome, &physics, &config, |point| {
+                progress.lock().points.push(point);
+            });
+            progress.lock().running = false;
+        }));
+    }
+}
+
+/// Main Parameter Sweep window rendering function
+pub fn render_parameter_sweep_window(
+    ui: &imgui::Ui,
+    sweep_state: &mut ParameterSweepState,
+    current_genome: &CurrentGenome,
+    simulation_state: &SimulationState,
+    global_ui_state: &super::GlobalUiState,
+) {
+    // Only show if visibility is enabled
+    if !global_ui_state.show_parameter_sweep {
+        return;
+    }
+
+    // Build flags based on lock state
+    let flags = if global_ui_state.windows_locked {
+        WindowFlags::NO_MOVE | WindowFlags::NO_RESIZE
+    } else {
+        WindowFlags::empty()
+    };
+
+    ui.window("Parameter Sweep")
+        .size([420.0, 520.0], Condition::FirstUseEver)
+        .position([1000.0, 200.0], Condition::FirstUseEver)
+        .flags(flags)
+        .build(|| {
+            render_parameter_sweep_content(ui, sweep_state, current_genome, simulation_state);
+        });
+}
+
+/// Render just the content of the Parameter Sweep window (without the window wrapper)
+pub fn render_parameter_sweep_content(
+    ui: &imgui::Ui,
+    sweep_state: &mut ParameterSweepState,
+    current_genome: &CurrentGenome,
+    simulation_state: &SimulationState,
+) {
+    let running = sweep_state.is_running();
+    let config = &mut sweep_state.config;
+    let mut start_requested = false;
+
+    ui.text("Sweep Setup:");
+    ui.separator();
+
+    ui.disabled(running, || {
+        // Parameter selector
+        ui.text("Parameter:");
+        if let Some(_combo) = ui.begin_combo("##sweep_parameter", config.parameter.name()) {
+            for parameter in SweepParameter::all() {
+                if ui.selectable_config(parameter.name()).selected(config.parameter == *parameter).build() {
+                    config.parameter = *parameter;
+                }
+            }
+        }
+
+        // Mode selector
+        ui.text("Mode:");
+        let modes = &current_genome.genome.modes;
+        if config.mode_index.is_some_and(|i| i >= modes.len()) {
+            config.mode_index = None;
+        }
+        let preview = match config.mode_index {
+            Some(i) => format!("[{}] {}", i, modes[i].name),
+            None => "All Modes".to_string(),
+        };
+        if let Some(_combo) = ui.begin_combo("##sweep_mode", &preview) {
+            if ui.selectable_config("All Modes").selected(config.mode_index.is_none()).build() {
+                config.mode_index = None;
+            }
+            for (i, mode) in modes.iter().enumerate() {
+                let label = format!("[{}] {}", i, mode.name);
+                if ui.selectable_config(&label).selected(config.mode_index == Some(i)).build() {
+                    config.mode_index = Some(i);
+                }
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Which mode receives the swept value");
+        }
+
+        ui.text("Range:");
+        ui.input_float("Start##sweep", &mut config.start).build();
+        ui.input_float("End##sweep", &mut config.end).build();
+
+        ui.text("Steps:");
+        let mut steps = config.steps as i32;
+        if ui.slider("##sweep_steps", 2, 50, &mut steps) {
+            config.steps = steps.max(2) as usize;
+        }
+
+        ui.text("Run Duration (s):");
+        ui.slider("##sweep_duration", 5.0, 600.0, &mut config.duration);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Simulated time per headless run");
+        }
+
+        if ui.button("Run Sweep") {
+            start_requested = true;
+        }
+    });
+    
+    if start_requested {
+        sweep_state.start(current_genome, simulation_state);
+    }
+
+    // Progress and results
+    let progress = sweep_state.progress.lock();
+    if progress.total == 0 {
+        return;
+    }
+
+    ui.separator();
+    if progress.running {
+        let fraction = progress.points.len() as f32 / progress.total as f32;
+        imgui::ProgressBar::new(fraction)
+            .overlay_text(format!("Run {}/{}", progress.points.len(), progress.total))
+            .build(ui);
+    }
+
+    if progress.points.is_empty() {
+        return;
+    }
+
+    let parameter_name = progress.parameter.map(|p| p.name()).unwrap_or("Value");
+    ui.text(format!("Final population vs {}:", parameter_name));
+
+    let populations: Vec<f32> = progress.points.iter().map(|p| p.final_population as f32).collect();
+    let max_population = populations.iter().cloned().fold(1.0_f32, f32::max);
+    ui.plot_lines("##sweep_plot", &populations)
+        .graph_size([ui.content_region_avail()[0], 150.0])
+        .scale_min(0.0)
+        .scale_max(max_population * 1.1)
+        .overlay_text(format!("max {}", max_population as usize))
+        .build();
+
+    if let (Some(first), Some(last)) = (progress.points.first(), progress.points.last()) {
+        ui.text_disabled(format!("{:.2}", first.value));
+        ui.same_line_with_pos(ui.content_region_avail()[0] - 40.0);
+        ui.text_disabled(format!("{:.2}", last.value));
+    }
+
+    // Raw values
+    if ui.collapsing_header("Results", imgui::TreeNodeFlags::empty()) {
+        for point in &progress.points {
+            ui.text(format!(
+                "{:>8.2}  ->  {:>5} cells  ({:.1} mass)",
+                point.value, point.final_population, point.total_mass
+            ));
+        }
+    }
+}
@@ -1,5 +1,21 @@
 use imgui::{Condition, WindowFlags, StyleVar};
 use std::collections::VecDeque;
+use crate::simulation::benchmark::TimingStats;
+use crate::simulation::cpu_sim::StepCounters;
+use crate::simulation::SimulationStats;
+
+/// Frame samples kept for the graphs unless the user picks another length
+pub const DEFAULT_HISTORY_SIZE: usize = 120;
+pub const HISTORY_SIZE_MIN: usize = 60;
+pub const HISTORY_SIZE_MAX: usize = 600;
+
+/// Seconds between refreshes of the FPS and frame time readouts
+pub const DEFAULT_UPDATE_INTERVAL: f32 = 0.25;
+pub const UPDATE_INTERVAL_MIN: f32 = 0.05;
+pub const UPDATE_INTERVAL_MAX: f32 = 2.0;
+
+/// A 1% low below this share of the average FPS counts as hitching
+const HITCH_LOW_RATIO: f32 = 0.5;
 
 /// Performance monitoring data
 pub struct PerformanceMonitor {
@@ -17,23 +33,43 @@ pub struct PerformanceMonitor {
     pub min_frame_time: f32,
     pub max_frame_time: f32,
     pub avg_frame_time: f32,
+    /// FPS at the 99th / 99.9th percentile frame time of the history, refreshed every interval
+    pub low_1_percent_fps: f32,
+    pub low_01_percent_fps: f32,
 
-    // History buffers (120 samples = 2 seconds at 60fps)
+    // History buffers (120 samples = 2 seconds at 60fps by default)
     pub frame_time_history: VecDeque<f32>,
     pub fps_history: VecDeque<f32>,
+    /// Samples kept in each history buffer; change it with `set_history_size`
+    history_size: usize,
 
     // Reset timer for min/max
     pub reset_timer: f32,
 
     // Window state
     pub window_open: bool,
+
+    /// Graphics adapter the app is running on
+    pub adapter_name: String,
+
+    /// Full adapter details, included in metric exports
+    pub adapter_info: Option<wgpu::AdapterInfo>,
+
+    /// Name of the active scene
+    pub scene_name: String,
+
+    /// Simulation work done during the last frame
+    pub sim_counters: StepCounters,
+
+    /// Result of the last "Export Metrics" (message, is_error)
+    pub export_status: Option<(String, bool)>,
 }
 
 impl Default for PerformanceMonitor {
     fn default() -> Self {
         Self {
             last_perf_update: -1.0, // Start at -1 to force immediate first update
-            perf_update_interval: 0.25, // Update every 250ms
+            perf_update_interval: DEFAULT_UPDATE_INTERVAL,
             frame_count: 0,
             frame_time_accumulator: 0.0,
 
@@ -43,18 +79,126 @@ impl Default for PerformanceMonitor {
             min_frame_time: 1000.0,
             max_frame_time: 0.0,
             avg_frame_time: 16.67,
+            low_1_percent_fps: 60.0,
+            low_01_percent_fps: 60.0,
 
-            frame_time_history: VecDeque::with_capacity(120),
-            fps_history: VecDeque::with_capacity(120),
+            frame_time_history: VecDeque::with_capacity(DEFAULT_HISTORY_SIZE),
+            fps_history: VecDeque::with_capacity(DEFAULT_HISTORY_SIZE),
+            history_size: DEFAULT_HISTORY_SIZE,
 
             reset_timer: 0.0,
 
             window_open: true,
+
+            adapter_name: String::new(),
+
+            adapter_info: None,
+
+            scene_name: String::new(),
+
+            sim_counters: StepCounters::default(),
+
+            export_status: None,
         }
     }
 }
 
-const HISTORY_SIZE: usize = 120;
+/// Graphics adapter description for metric exports
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AdapterExport {
+    pub name: String,
+    pub vendor: u32,
+    pub device: u32,
+    pub device_type: String,
+    pub driver: String,
+    pub driver_info: String,
+    pub backend: String,
+}
+
+impl From<&wgpu::AdapterInfo> for AdapterExport {
+    fn from(info: &wgpu::AdapterInfo) -> Self {
+        Self {
+            name: info.name.clone(),
+            vendor: info.vendor,
+            device: info.device,
+            device_type: format!("{:?}", info.device_type),
+            driver: info.driver.clone(),
+            driver_info: info.driver_info.clone(),
+            backend: format!("{:?}", info.backend),
+        }
+    }
+}
+
+/// Snapshot of the performance monitor written by "Export Metrics"
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MetricsExport {
+    /// Seconds since the Unix epoch when the export was made
+    pub exported_at: u64,
+    pub scene: String,
+    pub adapter: Option<AdapterExport>,
+    pub frame_time_ms: TimingStats,
+    pub fps: TimingStats,
+    /// Oldest sample first, one sample per monitor update
+    pub frame_time_history_ms: Vec<f32>,
+    pub fps_history: Vec<f32>,
+    /// Simulation work done during the last frame
+    pub sim_counters: StepCounters,
+}
+
+impl PerformanceMonitor {
+    pub fn history_size(&self) -> usize {
+        self.history_size
+    }
+
+    /// Change how many samples the history keeps. Shrinking drops the oldest samples;
+    /// growing keeps everything and lets the buffers fill up.
+    pub fn set_history_size(&mut self, size: usize) {
+        let size = size.clamp(HISTORY_SIZE_MIN, HISTORY_SIZE_MAX);
+        self.history_size = size;
+        for history in [&mut self.frame_time_history, &mut self.fps_history] {
+            let excess = history.len().saturating_sub(size);
+            history.drain(..excess);
+            history.reserve(size - history.len());
+        }
+    }
+
+    /// Gather the history, summary statistics and counters for export
+    pub fn metrics_export(&self) -> MetricsExport {
+        let exported_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        MetricsExport {
+            exported_at,
+            scene: self.scene_name.clone(),
+            adapter: self.adapter_info.as_ref().map(AdapterExport::from),
+            frame_time_ms: TimingStats::from_millis(self.frame_time_history.iter().map(|&ms| ms as f64)),
+            fps: TimingStats::from_millis(self.fps_history.iter().map(|&fps| fps as f64)),
+            frame_time_history_ms: self.frame_time_history.iter().copied().collect(),
+            fps_history: self.fps_history.iter().copied().collect(),
+            sim_counters: self.sim_counters,
+        }
+    }
+
+    /// Write the metrics to a timestamped JSON file in the working directory
+    pub fn export_metrics(&mut self) {
+        let export = self.metrics_export();
+        let path = std::path::PathBuf::from(format!("performance_metrics_{}.json", export.exported_at));
+        let result = serde_json::to_string_pretty(&export)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+        self.export_status = Some(match result {
+            Ok(()) => {
+                println!("Exported performance metrics to {}", path.display());
+                (format!("Saved {}", path.display()), false)
+            }
+            Err(e) => {
+                eprintln!("Failed to export performance metrics: {}", e);
+                (format!("Export failed: {}", e), true)
+            }
+        });
+    }
+}
 
 /// Update performance metrics
 pub fn update_performance_metrics(
@@ -78,13 +222,13 @@ pub fn update_performance_metrics(
 
     // Update history (circular buffer)
     perf_monitor.frame_time_history.push_back(frame_time_ms);
-    if perf_monitor.frame_time_history.len() > HISTORY_SIZE {
+    if perf_monitor.frame_time_history.len() > perf_monitor.history_size {
         perf_monitor.frame_time_history.pop_front();
     }
 
     let current_fps = if delta_time > 0.0 { 1.0 / delta_time } else { 0.0 };
     perf_monitor.fps_history.push_back(current_fps);
-    if perf_monitor.fps_history.len() > HISTORY_SIZE {
+    if perf_monitor.fps_history.len() > perf_monitor.history_size {
         perf_monitor.fps_history.pop_front();
     }
 
@@ -102,7 +246,7 @@ pub fn update_performance_metrics(
         perf_monitor.reset_timer = 0.0;
     }
 
-    // Update display values every interval (250ms)
+    // Update display values every interval
     let should_update = if perf_monitor.last_perf_update < 0.0 {
         true // Force first update
     } else {
@@ -120,6 +264,7 @@ pub fn update_performance_metrics(
             perf_monitor.display_fps = perf_monitor.frame_count as f32 / elapsed;
             perf_monitor.display_frame_time = (perf_monitor.frame_time_accumulator / perf_monitor.frame_count as f32) * 1000.0;
         }
+        (perf_monitor.low_1_percent_fps, perf_monitor.low_01_percent_fps) = percentile_lows(&perf_monitor.frame_time_history);
 
         perf_monitor.frame_count = 0;
         perf_monitor.frame_time_accumulator = 0.0;
@@ -127,10 +272,42 @@ pub fn update_performance_metrics(
     }
 }
 
+/// 1% and 0.1% low FPS: the FPS at the 99th and 99.9th percentile frame times.
+/// Sorts a copy of the history, so it runs once per update interval rather than every frame.
+fn percentile_lows(frame_time_history: &VecDeque<f32>) -> (f32, f32) {
+    let mut sorted: Vec<f32> = frame_time_history.iter().copied().collect();
+    if sorted.is_empty() {
+        return (0.0, 0.0);
+    }
+    sorted.sort_by(f32::total_cmp);
+    let fps_at = |p: f32| {
+        let frame_time = sorted[((sorted.len() - 1) as f32 * p).round() as usize];
+        if frame_time > 0.0 { 1000.0 / frame_time } else { 0.0 }
+    };
+    (fps_at(0.99), fps_at(0.999))
+}
+
+/// 1% / 0.1% lows, with a warning when the 1% low sits far below the average
+fn draw_percentile_lows(ui: &imgui::Ui, perf_monitor: &PerformanceMonitor, avg_frame_time: f32) {
+    let low_1 = if perf_monitor.low_1_percent_fps.is_finite() { perf_monitor.low_1_percent_fps } else { 0.0 };
+    let low_01 = if perf_monitor.low_01_percent_fps.is_finite() { perf_monitor.low_01_percent_fps } else { 0.0 };
+    ui.text(format!("1% Low: {:.1} FPS  0.1% Low: {:.1} FPS", low_1, low_01));
+    if ui.is_item_hovered() {
+        ui.tooltip_text("FPS of the slowest 1% and 0.1% of frames in the history");
+    }
+
+    let avg_fps = if avg_frame_time > 0.0 { 1000.0 / avg_frame_time } else { 0.0 };
+    if low_1 > 0.0 && low_1 < avg_fps * HITCH_LOW_RATIO {
+        ui.text_colored([1.0, 0.5, 0.0, 1.0],
+            format!("⚠ Hitching: 1% low is {:.0}% of the {:.0} FPS average", low_1 / avg_fps * 100.0, avg_fps));
+    }
+}
+
 /// Render the performance monitor window
 pub fn render_performance_window(
     ui: &imgui::Ui,
-    perf_monitor: &PerformanceMonitor,
+    perf_monitor: &mut PerformanceMonitor,
+    stats: &SimulationStats,
     global_ui_state: &super::GlobalUiState,
 ) {
     if !perf_monitor.window_open {
@@ -189,6 +366,7 @@ pub fn render_performance_window(
                 avg_frame_time,
                 max_frame_time
             ));
+            draw_percentile_lows(ui, perf_monitor, avg_frame_time);
 
             ui.spacing();
 
@@ -214,7 +392,15 @@ pub fn render_performance_window(
 
             ui.spacing();
 
-            // Performance Indicators Section
+            draw_export_button(ui, perf_monitor);
+
+            ui.spacing();
+
+            draw_history_settings(ui, perf_monitor);
+
+    ui.spacing();
+
+    // Performance Indicators Section
             ui.text_colored([1.0, 1.0, 1.0, 1.0], "Performance Indicators");
             ui.separator();
 
@@ -269,13 +455,10 @@ pub fn render_performance_window(
             ui.text_colored([1.0, 1.0, 1.0, 1.0], "Simulation Metrics");
             ui.separator();
 
-            // Placeholder simulation data
-            let cell_count = 42;
-            let max_capacity = 4096;
-            ui.text(format!("Cells: {} / {}", cell_count, max_capacity));
+            ui.text(format!("Cells: {} / {}", stats.live_cell_count, stats.max_capacity));
             
             // Show capacity percentage
-            let capacity_percent = (cell_count as f32 / max_capacity as f32) * 100.0;
+            let capacity_percent = stats.capacity_percent();
             let capacity_color = if capacity_percent > 90.0 {
                 [1.0, 0.0, 0.0, 1.0] // Red when near capacity
             } else if capacity_percent > 75.0 {
@@ -285,13 +468,15 @@ pub fn render_performance_window(
             };
             ui.text_colored(capacity_color, format!("Capacity: {:.1}%", capacity_percent));
 
-            ui.text("Scene: Main Simulation");
+            ui.text(format!("Scene: {}", perf_monitor.scene_name));
+            ui.text(format!("GPU: {}", perf_monitor.adapter_name));
+            draw_sim_counters(ui, &perf_monitor.sim_counters);
             ui.text("Physics:");
             ui.same_line();
             ui.text_colored([0.0, 1.0, 0.5, 1.0], "CPU (Multi-threaded)");
-            ui.text("Status: Running");
-            ui.text(format!("Sim Time: {:.2}s", 12.34));
-            ui.text(format!("Memory: {:.2} MB", 15.6));
+            ui.text(if stats.paused { "Status: Paused" } else { "Status: Running" });
+            ui.text(format!("Sim Time: {:.2}s", stats.sim_time));
+            ui.text(format!("Memory: {:.2} MB", stats.approx_memory_bytes as f64 / (1024.0 * 1024.0)));
 
             ui.spacing();
 
@@ -307,6 +492,60 @@ pub fn render_performance_window(
         });
 }
 
+/// Per-frame simulation work counters
+/// "Export Metrics" button and the result of the last export
+fn draw_export_button(ui: &imgui::Ui, perf_monitor: &mut PerformanceMonitor) {
+    if ui.button("Export Metrics") {
+        perf_monitor.export_metrics();
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Write the frame time and FPS history, summary statistics, counters and adapter details to a timestamped JSON file");
+    }
+    if let Some((message, is_error)) = &perf_monitor.export_status {
+        if *is_error {
+            ui.text_colored([1.0, 0.4, 0.4, 1.0], message);
+        } else {
+            ui.text_disabled(message);
+        }
+    }
+}
+
+/// Sliders for how much history the graphs keep and how often the readouts refresh
+fn draw_history_settings(ui: &imgui::Ui, perf_monitor: &mut PerformanceMonitor) {
+    if !ui.collapsing_header("History Settings", imgui::TreeNodeFlags::empty()) {
+        return;
+    }
+    let mut history_size = perf_monitor.history_size() as u32;
+    if ui
+        .slider_config("History Length", HISTORY_SIZE_MIN as u32, HISTORY_SIZE_MAX as u32)
+        .display_format("%d samples")
+        .flags(imgui::SliderFlags::ALWAYS_CLAMP)
+        .build(&mut history_size)
+    {
+        perf_monitor.set_history_size(history_size as usize);
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Frames shown in the graphs and used for the averages; shortening it drops the oldest frames");
+    }
+    ui.slider_config("Update Interval", UPDATE_INTERVAL_MIN, UPDATE_INTERVAL_MAX)
+        .display_format("%.2f s")
+        .flags(imgui::SliderFlags::ALWAYS_CLAMP)
+        .build(&mut perf_monitor.perf_update_interval);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("How often the FPS and frame time readouts refresh; longer intervals smooth them more");
+    }
+}
+
+fn draw_sim_counters(ui: &imgui::Ui, counters: &StepCounters) {
+    ui.text(format!("Substeps: {}", counters.substeps));
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Fixed physics steps run this frame; spikes here mean the sim is catching up");
+    }
+    ui.text(format!("Collision Pairs Tested: {}", counters.collision_pairs_tested));
+    ui.text(format!("Adhesion Constraints: {}", counters.adhesion_constraints));
+    ui.text(format!("Nutrient Transfers: {}", counters.nutrient_transfers));
+}
+
 // Color helper functions
 
 fn get_fps_color(fps: f32) -> [f32; 4] {
@@ -379,7 +618,8 @@ fn get_frame_time_bar_color(frame_time: f32) -> [f32; 4] {
 /// Render just the content of the Performance Monitor window (without the window wrapper)
 pub fn render_performance_content(
     ui: &imgui::Ui,
-    perf_monitor: &PerformanceMonitor,
+    perf_monitor: &mut PerformanceMonitor,
+    stats: &SimulationStats,
 ) {
     // Ensure all values are valid (not NaN or infinity)
     let fps = if perf_monitor.display_fps.is_finite() { perf_monitor.display_fps } else { 0.0 };
@@ -416,6 +656,7 @@ pub fn render_performance_content(
         avg_frame_time,
         max_frame_time
     ));
+    draw_percentile_lows(ui, perf_monitor, avg_frame_time);
 
     ui.spacing();
 
@@ -441,6 +682,14 @@ pub fn render_performance_content(
 
     ui.spacing();
 
+    draw_export_button(ui, perf_monitor);
+
+    ui.spacing();
+
+    draw_history_settings(ui, perf_monitor);
+
+    ui.spacing();
+
     // Performance Indicators Section
     ui.text_colored([1.0, 1.0, 1.0, 1.0], "Performance Indicators");
     ui.separator();
@@ -496,13 +745,10 @@ pub fn render_performance_content(
     ui.text_colored([1.0, 1.0, 1.0, 1.0], "Simulation Metrics");
     ui.separator();
 
-    // Placeholder simulation data
-    let cell_count = 42;
-    let max_capacity = 4096;
-    ui.text(format!("Cells: {} / {}", cell_count, max_capacity));
+    ui.text(format!("Cells: {} / {}", stats.live_cell_count, stats.max_capacity));
     
     // Show capacity percentage
-    let capacity_percent = (cell_count as f32 / max_capacity as f32) * 100.0;
+    let capacity_percent = stats.capacity_percent();
     let capacity_color = if capacity_percent > 90.0 {
         [1.0, 0.0, 0.0, 1.0] // Red when near capacity
     } else if capacity_percent > 75.0 {
@@ -512,13 +758,15 @@ pub fn render_performance_content(
     };
     ui.text_colored(capacity_color, format!("Capacity: {:.1}%", capacity_percent));
 
-    ui.text("Scene: Main Simulation");
+    ui.text(format!("Scene: {}", perf_monitor.scene_name));
+    ui.text(format!("GPU: {}", perf_monitor.adapter_name));
+    draw_sim_counters(ui, &perf_monitor.sim_counters);
     ui.text("Physics:");
     ui.same_line();
     ui.text_colored([0.0, 1.0, 0.5, 1.0], "CPU (Multi-threaded)");
-    ui.text("Status: Running");
-    ui.text(format!("Sim Time: {:.2}s", 12.34));
-    ui.text(format!("Memory: {:.2} MB", 15.6));
+    ui.text(if stats.paused { "Status: Paused" } else { "Status: Running" });
+    ui.text(format!("Sim Time: {:.2}s", stats.sim_time));
+    ui.text(format!("Memory: {:.2} MB", stats.approx_memory_bytes as f64 / (1024.0 * 1024.0)));
 
     ui.spacing();
 
@@ -531,4 +779,40 @@ pub fn render_performance_content(
         ui.text_colored([1.0, 0.5, 0.0, 1.0],
             "⚠ High frame time detected! Frame rendering is taking too long.");
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resizing_history_keeps_recent_samples() {
+        let mut monitor = PerformanceMonitor::default();
+        monitor.set_history_size(HISTORY_SIZE_MAX);
+        for frame in 0..200 {
+            update_performance_metrics(&mut monitor, 0.001 * (frame + 1) as f32, frame as f32);
+        }
+        assert_eq!(monitor.frame_time_history.len(), 200);
+
+        monitor.set_history_size(HISTORY_SIZE_MIN);
+        assert_eq!(monitor.frame_time_history.len(), HISTORY_SIZE_MIN);
+        assert_eq!(monitor.fps_history.len(), HISTORY_SIZE_MIN);
+        assert_eq!(monitor.frame_time_history.back().copied(), Some(200.0));
+
+        // Growing keeps what is there and fills from new frames
+        monitor.set_history_size(DEFAULT_HISTORY_SIZE);
+        update_performance_metrics(&mut monitor, 0.001, 200.0);
+        assert_eq!(monitor.frame_time_history.len(), HISTORY_SIZE_MIN + 1);
+    }
+
+    #[test]
+    fn test_percentile_lows_catch_stutter_hidden_by_the_average() {
+        // 98 smooth frames and two 50 ms hitches barely move the average
+        let mut history: VecDeque<f32> = std::iter::repeat_n(10.0, 98).collect();
+        history.push_back(50.0);
+        history.push_front(50.0);
+        let (low_1, low_01) = percentile_lows(&history);
+        assert!((low_1 - 20.0).abs() < 1e-3);
+        assert!((low_01 - 20.0).abs() < 1e-3);
+        assert_eq!(percentile_lows(&VecDeque::new()), (0.0, 0.0));
+    }
+}
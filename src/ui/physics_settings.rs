@@ -0,0 +1,122 @@
+// Global physics parameters UI
+use imgui::{Condition, WindowFlags};
+use crate::cell::adhesion_forces::{is_spring_stable, max_stable_stiffness};
+use crate::simulation::physics_config::{PhysicsConfig, MAX_SUBSTEPS};
+
+/// Render the physics settings window
+pub fn render_physics_settings_window(
+    ui: &imgui::Ui,
+    physics: &mut PhysicsConfig,
+    global_ui_state: &super::GlobalUiState,
+) {
+    // Only show if visibility is enabled
+    if !global_ui_state.show_physics_settings {
+        return;
+    }
+
+    let flags = if global_ui_state.windows_locked {
+        WindowFlags::NO_MOVE | WindowFlags::NO_RESIZE
+    } else {
+        WindowFlags::empty()
+    };
+
+    ui.window("Physics Settings")
+        .size([380.0, 340.0], Condition::FirstUseEver)
+        .position([1000.0, 440.0], Condition::FirstUseEver)
+        .flags(flags)
+        .build(|| {
+            render_physics_settings_content(ui, physics);
+        });
+}
+
+/// Render just the content of the Physics Settings window (without the window wrapper).
+/// Every value is read again on the next physics step, so nothing here needs a respawn.
+pub fn render_physics_settings_content(ui: &imgui::Ui, physics: &mut PhysicsConfig) {
+    if ui.collapsing_header("Time Stepping", imgui::TreeNodeFlags::DEFAULT_OPEN) {
+        ui.indent();
+
+        ui.text("Timestep:");
+        ui.slider_config("##Timestep", 1.0 / 480.0, 1.0 / 15.0)
+            .display_format("%.4f s")
+            .flags(imgui::SliderFlags::LOGARITHMIC | imgui::SliderFlags::ALWAYS_CLAMP)
+            .build(&mut physics.fixed_timestep);
+        if ui.is_item_hovered() {
+            ui.tooltip_text(format!(
+                "Simulated seconds per fixed step ({:.0} steps per simulated second). Smaller steps are more stable but cost more per simulated second.",
+                1.0 / physics.fixed_timestep
+            ));
+        }
+
+        ui.text("Substeps:");
+        ui.slider_config("##Substeps", 1, MAX_SUBSTEPS)
+            .flags(imgui::SliderFlags::ALWAYS_CLAMP)
+            .build(&mut physics.substeps);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Force and integration passes per step. Growth and division still run once per step.");
+        }
+
+        ui.unindent();
+    }
+
+    if ui.collapsing_header("Forces", imgui::TreeNodeFlags::DEFAULT_OPEN) {
+        ui.indent();
+
+        ui.text("Global Damping:");
+        ui.slider_config("##GlobalDamping", 0.0, 10.0)
+            .display_format("%.2f /s")
+            .build(&mut physics.velocity_damping);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Fluid drag applied to every cell's velocity");
+        }
+
+        ui.text("Collision Stiffness:");
+        ui.slider_config("##CollisionStiffness", 1.0, 2000.0)
+            .display_format("%.1f")
+            .flags(imgui::SliderFlags::LOGARITHMIC)
+            .build(&mut physics.collision_stiffness);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("How hard overlapping cells and the world boundary push back");
+        }
+        // The lightest cells bounce off each other the fastest
+        let mass = physics.min_cell_mass.max(0.01);
+        if !is_spring_stable(physics.collision_stiffness, 0.0, mass, mass, physics.substep_dt()) {
+            ui.text_colored(
+                [1.0, 0.55, 0.0, 1.0],
+                format!(
+                    "Too stiff for this timestep; light cells may explode (stable below {:.1})",
+                    max_stable_stiffness(0.0, mass, mass, physics.substep_dt())
+                ),
+            );
+        }
+
+        ui.text("World Radius:");
+        ui.slider_config("##WorldRadius", 5.0, 500.0)
+            .display_format("%.1f")
+            .flags(imgui::SliderFlags::LOGARITHMIC)
+            .build(&mut physics.world_radius);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Radius of the spherical boundary; cells outside a shrunken world are pushed back in");
+        }
+
+        ui.unindent();
+    }
+
+    ui.separator();
+    if ui.button("Reset to Defaults") {
+        reset_physics_settings(physics);
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Restore the values in this window; the integrator and cell limits are kept");
+    }
+    ui.text_disabled("Saved with the active scene");
+}
+
+/// Restore the parameters this window edits, leaving the Scene Manager's ones alone
+fn reset_physics_settings(physics: &mut PhysicsConfig) {
+    let defaults = PhysicsConfig::default();
+    physics.fixed_timestep = defaults.fixed_timestep;
+    physics.substeps = defaults.substeps;
+    physics.velocity_damping = defaults.velocity_damping;
+    physics.collision_stiffness = defaults.collision_stiffness;
+    physics.world_radius = defaults.world_radius;
+}
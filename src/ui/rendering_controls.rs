@@ -1,9 +1,17 @@
 use imgui::{Condition, WindowFlags};
+use crate::rendering::cells::{CellColorBy, CellLodSettings, CellQuality, ImpostorMode, OnionSkinSettings};
+use crate::rendering::config::{BloomComposite, RenderConfig};
+use crate::rendering::debug::GizmoSettings;
+use crate::rendering::frame_capture::{EncoderStatus, FrameRecorder, RecordingFormat};
 
 /// System to render the rendering controls UI panel
 pub fn render_controls_ui(
     ui: &imgui::Ui,
     global_ui_state: &mut super::GlobalUiState,
+    recorder: &mut FrameRecorder,
+    onion_skin: &mut OnionSkinSettings,
+    render_config: &mut RenderConfig,
+    cell_count: usize,
 ) {
     // Only show if visibility is enabled
     if !global_ui_state.show_rendering_controls {
@@ -37,159 +45,26 @@ pub fn render_controls_ui(
             ui.text("Visualization:");
             ui.separator();
             
-            // Placeholder checkboxes - these would connect to actual rendering config
-            let mut show_orientation_gizmos = false;
-            if ui.checkbox("Show Orientation Gizmos", &mut show_orientation_gizmos) {
-                // Update rendering config
-            }
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Display forward (blue), right (green), and up (red) orientation axes for each cell");
-            }
+            draw_color_by_selector(ui, global_ui_state);
             
-            let mut show_split_plane_gizmos = false;
-            if ui.checkbox("Show Split Plane Gizmos", &mut show_split_plane_gizmos) {
-                // Update rendering config
-            }
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Display split plane rings showing the division direction for each cell");
-            }
-            
-            let mut show_adhesions = false;
-            if ui.checkbox("Show Adhesions", &mut show_adhesions) {
-                // Update rendering config
-            }
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Display adhesion connections between cells");
-            }
+            draw_gizmo_controls(ui, &mut global_ui_state.gizmos, cell_count);
+    
+    ui.separator();
+    draw_cell_lod_controls(ui, &mut global_ui_state.cell_lod, cell_count);
             
             ui.separator();
-            let mut wireframe_mode = false;
-            ui.checkbox("Wireframe Mode", &mut wireframe_mode);
+            draw_cell_lod_controls(ui, &mut global_ui_state.cell_lod, cell_count);
             
-            // World Sphere Settings
             ui.separator();
-            ui.text("World Sphere:");
-            
-            ui.text("Opacity:");
-            let mut world_opacity = 0.1;
-            ui.slider("##world_opacity", 0.0, 1.0, &mut world_opacity);
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Transparency of the world boundary sphere");
-            }
-            
-            ui.text("Color:");
-            let mut world_color = [0.5, 0.5, 0.5];
-            if ui.color_edit3("##world_color", &mut world_color) {
-                // Update world sphere color
-            }
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Base color of the world sphere");
-            }
-            
-            ui.text("Edge Glow:");
-            let mut world_emissive = 0.1;
-            ui.slider("##world_emissive", 0.0, 0.5, &mut world_emissive);
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Emissive lighting intensity for Fresnel edge glow");
-            }
+            draw_render_config_controls(ui, render_config);
             
-            // Volumetric Fog Settings
+            // Onion skin
             ui.separator();
-            ui.text("Volumetric Fog:");
+            draw_onion_skin_controls(ui, onion_skin);
             
-            let mut fog_enabled = true;
-            if ui.checkbox("Enable Fog", &mut fog_enabled) {
-                // Update fog settings
-            }
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Toggle volumetric fog rendering");
-            }
-            
-            ui.text("Density:");
-            let mut fog_density = 0.5;
-            ui.slider("##fog_density", 0.0, 1.0, &mut fog_density);
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Overall fog density");
-            }
-            
-            ui.text("Absorption:");
-            let mut fog_absorption = 0.3;
-            ui.slider("##fog_absorption", 0.0, 1.0, &mut fog_absorption);
-            if ui.is_item_hovered() {
-                ui.tooltip_text("How much light is absorbed by the fog");
-            }
-            
-            ui.text("Scattering:");
-            let mut fog_scattering = 0.7;
-            ui.slider("##fog_scattering", 0.0, 1.0, &mut fog_scattering);
-            if ui.is_item_hovered() {
-                ui.tooltip_text("How much light is scattered by the fog");
-            }
-            
-            ui.text("Ambient:");
-            let mut fog_ambient = 0.05;
-            ui.slider("##fog_ambient", 0.0, 0.1, &mut fog_ambient);
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Ambient light intensity in fog");
-            }
-            
-            ui.text("Fog Color:");
-            let mut fog_color = [0.2, 0.3, 0.4];
-            if ui.color_edit3("##fog_color", &mut fog_color) {
-                // Update fog color
-            }
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Color of the volumetric fog");
-            }
-            
-            // Bloom Settings
+            // Recording
             ui.separator();
-            ui.text("Bloom (Emissive Glow):");
-            
-            let mut bloom_enabled = true;
-            ui.checkbox("Enable Bloom", &mut bloom_enabled);
-            if ui.is_item_hovered() {
-                ui.tooltip_text("Enable bloom effect for emissive materials");
-            }
-            
-            if bloom_enabled {
-                ui.text("Intensity:");
-                let mut bloom_intensity = 0.3;
-                ui.slider("##bloom_intensity", 0.0, 1.0, &mut bloom_intensity);
-                if ui.is_item_hovered() {
-                    ui.tooltip_text("Overall bloom intensity");
-                }
-                
-                ui.text("Low Freq Boost:");
-                let mut bloom_low_freq = 0.7;
-                ui.slider("##bloom_low_freq", 0.0, 1.0, &mut bloom_low_freq);
-                if ui.is_item_hovered() {
-                    ui.tooltip_text("Boost for soft, wide glow (low frequency)");
-                }
-                
-                ui.text("High Pass:");
-                let mut bloom_high_pass = 0.6;
-                ui.slider("##bloom_high_pass", 0.0, 1.0, &mut bloom_high_pass);
-                if ui.is_item_hovered() {
-                    ui.tooltip_text("Threshold for bloom - higher values = only brightest emissives bloom");
-                }
-                
-                // Composite mode selector
-                ui.text("Composite Mode:");
-                let is_additive = true;
-                if ui.radio_button_bool("Additive", is_additive) {
-                    // Update composite mode to additive
-                }
-                if ui.is_item_hovered() {
-                    ui.tooltip_text("Adds bloom on top - brighter but can wash out");
-                }
-                if ui.radio_button_bool("Energy Conserving", !is_additive) {
-                    // Update composite mode to energy conserving
-                }
-                if ui.is_item_hovered() {
-                    ui.tooltip_text("Preserves overall brightness - more natural look");
-                }
-            }
+            draw_recording_controls(ui, recorder);
             
             // Theme selector
             ui.separator();
@@ -209,6 +84,10 @@ pub fn render_controls_ui(
 pub fn render_controls_content(
     ui: &imgui::Ui,
     global_ui_state: &mut super::GlobalUiState,
+    recorder: &mut FrameRecorder,
+    onion_skin: &mut OnionSkinSettings,
+    render_config: &mut RenderConfig,
+    cell_count: usize,
 ) {
     // Window Controls
     ui.text("Window Controls:");
@@ -225,58 +104,93 @@ pub fn render_controls_content(
     ui.text("Visualization:");
     ui.separator();
     
-    // Placeholder checkboxes - these would connect to actual rendering config
-    let mut show_orientation_gizmos = false;
-    if ui.checkbox("Show Orientation Gizmos", &mut show_orientation_gizmos) {
-        // Update rendering config
+    draw_color_by_selector(ui, global_ui_state);
+    
+    draw_gizmo_controls(ui, &mut global_ui_state.gizmos, cell_count);
+    
+    ui.separator();
+    draw_render_config_controls(ui, render_config);
+    
+    // Onion skin
+    ui.separator();
+    draw_onion_skin_controls(ui, onion_skin);
+    
+    // Recording
+    ui.separator();
+    draw_recording_controls(ui, recorder);
+    
+    // Theme selector
+    ui.separator();
+    ui.text("UI Theme:");
+    
+    let themes = ["Dark", "Light", "Classic"];
+    let mut current_theme = 0;
+    for (i, theme) in themes.iter().enumerate() {
+        let is_selected = current_theme == i;
+        if ui.radio_button_bool(theme, is_selected) && !is_selected {
+            current_theme = i;
+        }
     }
+}
+
+/// Gizmo toggles, with a warning when the colony is over the gizmo cell cap
+fn draw_gizmo_controls(ui: &imgui::Ui, gizmos: &mut GizmoSettings, cell_count: usize) {
+    ui.checkbox("Show Orientation Gizmos", &mut gizmos.show_orientation);
     if ui.is_item_hovered() {
         ui.tooltip_text("Display forward (blue), right (green), and up (red) orientation axes for each cell");
     }
     
-    let mut show_split_plane_gizmos = false;
-    if ui.checkbox("Show Split Plane Gizmos", &mut show_split_plane_gizmos) {
-        // Update rendering config
-    }
+    ui.checkbox("Show Split Plane Gizmos", &mut gizmos.show_split_planes);
     if ui.is_item_hovered() {
         ui.tooltip_text("Display split plane rings showing the division direction for each cell");
     }
     
-    let mut show_adhesions = false;
-    if ui.checkbox("Show Adhesions", &mut show_adhesions) {
-        // Update rendering config
-    }
+    ui.checkbox("Show Adhesions", &mut gizmos.show_adhesions);
     if ui.is_item_hovered() {
         ui.tooltip_text("Display adhesion connections between cells");
     }
     
-    ui.separator();
-    let mut wireframe_mode = false;
-    ui.checkbox("Wireframe Mode", &mut wireframe_mode);
+    ui.checkbox("Show Nutrient Field", &mut gizmos.show_nutrient_field);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Draw a bar over each cell showing its stored nutrients (red = starving, green = ready to split), \
+                         greyed where the surrounding medium is poorer than the ambient level");
+    }
+    
+    let mut max_cells = gizmos.max_cells as i32;
+    if ui.slider("Gizmo Cell Cap", 1000, 100_000, &mut max_cells) {
+        gizmos.max_cells = max_cells.max(1) as usize;
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Gizmos are skipped when the colony has more cells than this");
+    }
+    if gizmos.over_cap(cell_count) {
+        ui.text_colored([1.0, 0.6, 0.0, 1.0], format!("Gizmos hidden: {} cells exceeds the cap", cell_count));
+    }
+}
+
+/// Wireframe, world sphere, fog and bloom settings
+fn draw_render_config_controls(ui: &imgui::Ui, config: &mut RenderConfig) {
+    ui.checkbox("Wireframe Mode", &mut config.wireframe);
     
     // World Sphere Settings
     ui.separator();
     ui.text("World Sphere:");
     
+    let world = &mut config.world_sphere;
     ui.text("Opacity:");
-    let mut world_opacity = 0.1;
-    ui.slider("##world_opacity", 0.0, 1.0, &mut world_opacity);
+    ui.slider("##world_opacity", 0.0, 1.0, &mut world.opacity);
     if ui.is_item_hovered() {
         ui.tooltip_text("Transparency of the world boundary sphere");
     }
     
     ui.text("Color:");
-    let mut world_color = [0.5, 0.5, 0.5];
-    if ui.color_edit3("##world_color", &mut world_color) {
-        // Update world sphere color
-    }
+    ui.color_edit3("##world_color", &mut world.color);
     if ui.is_item_hovered() {
         ui.tooltip_text("Base color of the world sphere");
     }
     
     ui.text("Edge Glow:");
-    let mut world_emissive = 0.1;
-    ui.slider("##world_emissive", 0.0, 0.5, &mut world_emissive);
+    ui.slider("##world_emissive", 0.0, 0.5, &mut world.edge_glow);
     if ui.is_item_hovered() {
         ui.tooltip_text("Emissive lighting intensity for Fresnel edge glow");
     }
@@ -285,47 +199,38 @@ pub fn render_controls_content(
     ui.separator();
     ui.text("Volumetric Fog:");
     
-    let mut fog_enabled = true;
-    if ui.checkbox("Enable Fog", &mut fog_enabled) {
-        // Update fog settings
-    }
+    let fog = &mut config.fog;
+    ui.checkbox("Enable Fog", &mut fog.enabled);
     if ui.is_item_hovered() {
         ui.tooltip_text("Toggle volumetric fog rendering");
     }
     
     ui.text("Density:");
-    let mut fog_density = 0.5;
-    ui.slider("##fog_density", 0.0, 1.0, &mut fog_density);
+    ui.slider("##fog_density", 0.0, 1.0, &mut fog.density);
     if ui.is_item_hovered() {
         ui.tooltip_text("Overall fog density");
     }
     
     ui.text("Absorption:");
-    let mut fog_absorption = 0.3;
-    ui.slider("##fog_absorption", 0.0, 1.0, &mut fog_absorption);
+    ui.slider("##fog_absorption", 0.0, 1.0, &mut fog.absorption);
     if ui.is_item_hovered() {
         ui.tooltip_text("How much light is absorbed by the fog");
     }
     
     ui.text("Scattering:");
-    let mut fog_scattering = 0.7;
-    ui.slider("##fog_scattering", 0.0, 1.0, &mut fog_scattering);
+    ui.slider("##fog_scattering", 0.0, 1.0, &mut fog.scattering);
     if ui.is_item_hovered() {
         ui.tooltip_text("How much light is scattered by the fog");
     }
     
     ui.text("Ambient:");
-    let mut fog_ambient = 0.05;
-    ui.slider("##fog_ambient", 0.0, 0.1, &mut fog_ambient);
+    ui.slider("##fog_ambient", 0.0, 0.1, &mut fog.ambient);
     if ui.is_item_hovered() {
         ui.tooltip_text("Ambient light intensity in fog");
     }
     
     ui.text("Fog Color:");
-    let mut fog_color = [0.2, 0.3, 0.4];
-    if ui.color_edit3("##fog_color", &mut fog_color) {
-        // Update fog color
-    }
+    ui.color_edit3("##fog_color", &mut fog.color);
     if ui.is_item_hovered() {
         ui.tooltip_text("Color of the volumetric fog");
     }
@@ -334,61 +239,264 @@ pub fn render_controls_content(
     ui.separator();
     ui.text("Bloom (Emissive Glow):");
     
-    let mut bloom_enabled = true;
-    ui.checkbox("Enable Bloom", &mut bloom_enabled);
+    let bloom = &mut config.bloom;
+    ui.checkbox("Enable Bloom", &mut bloom.enabled);
     if ui.is_item_hovered() {
         ui.tooltip_text("Enable bloom effect for emissive materials");
     }
     
-    if bloom_enabled {
+    if bloom.enabled {
         ui.text("Intensity:");
-        let mut bloom_intensity = 0.3;
-        ui.slider("##bloom_intensity", 0.0, 1.0, &mut bloom_intensity);
+        ui.slider("##bloom_intensity", 0.0, 1.0, &mut bloom.intensity);
         if ui.is_item_hovered() {
             ui.tooltip_text("Overall bloom intensity");
         }
         
         ui.text("Low Freq Boost:");
-        let mut bloom_low_freq = 0.7;
-        ui.slider("##bloom_low_freq", 0.0, 1.0, &mut bloom_low_freq);
+        ui.slider("##bloom_low_freq", 0.0, 1.0, &mut bloom.low_freq_boost);
         if ui.is_item_hovered() {
             ui.tooltip_text("Boost for soft, wide glow (low frequency)");
         }
         
         ui.text("High Pass:");
-        let mut bloom_high_pass = 0.6;
-        ui.slider("##bloom_high_pass", 0.0, 1.0, &mut bloom_high_pass);
+        ui.slider("##bloom_high_pass", 0.0, 1.0, &mut bloom.high_pass);
         if ui.is_item_hovered() {
             ui.tooltip_text("Threshold for bloom - higher values = only brightest emissives bloom");
         }
         
         // Composite mode selector
         ui.text("Composite Mode:");
-        let is_additive = true;
-        if ui.radio_button_bool("Additive", is_additive) {
-            // Update composite mode to additive
+        for composite in BloomComposite::all() {
+            if ui.radio_button_bool(composite.name(), bloom.composite == *composite) {
+                bloom.composite = *composite;
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text(match composite {
+                    BloomComposite::Additive => "Adds bloom on top - brighter but can wash out",
+                    BloomComposite::EnergyConserving => "Preserves overall brightness - more natural look",
+                });
+            }
         }
+    }
+}
+
+/// Sphere quality, distance LOD and impostor options, with the mode currently in use
+fn draw_cell_lod_controls(ui: &imgui::Ui, lod: &mut CellLodSettings, cell_count: usize) {
+    ui.text("Cell Detail:");
+    if let Some(_combo) = ui.begin_combo("Quality##cell_quality", lod.quality.name()) {
+        for quality in CellQuality::all() {
+            if ui.selectable_config(quality.name()).selected(lod.quality == *quality).build() {
+                lod.quality = *quality;
+            }
+        }
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Sphere mesh subdivision; lower quality draws faster with large colonies");
+    }
+    
+    ui.checkbox("Distance LOD", &mut lod.distance_lod);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Draw cells far from the camera with a coarser sphere");
+    }
+    ui.disabled(!lod.distance_lod, || {
+        ui.slider("LOD Distance", 10.0, 500.0, &mut lod.lod_distance);
         if ui.is_item_hovered() {
-            ui.tooltip_text("Adds bloom on top - brighter but can wash out");
+            ui.tooltip_text("Cells further than this from the camera use the coarser sphere");
+        }
+    });
+    
+    if let Some(_combo) = ui.begin_combo("Impostors##cell_impostors", lod.impostors.name()) {
+        for mode in ImpostorMode::all() {
+            if ui.selectable_config(mode.name()).selected(lod.impostors == *mode).build() {
+                lod.impostors = *mode;
+            }
         }
-        if ui.radio_button_bool("Energy Conserving", !is_additive) {
-            // Update composite mode to energy conserving
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Draw each cell as a shaded camera-facing quad instead of a mesh. \
+                         Auto switches over above the threshold; onion-skin ghosts always use meshes");
+    }
+    ui.disabled(lod.impostors != ImpostorMode::Auto, || {
+        let mut threshold = lod.impostor_threshold as i32;
+        if ui.slider("Impostor Threshold", 1000, 200_000, &mut threshold) {
+            lod.impostor_threshold = threshold.max(1) as usize;
         }
         if ui.is_item_hovered() {
-            ui.tooltip_text("Preserves overall brightness - more natural look");
+            ui.tooltip_text("Cell count above which Auto switches to impostors");
         }
+    });
+    if lod.use_impostors(cell_count) {
+        ui.text_disabled(format!("Drawing {} cells as impostors", cell_count));
+    }
+}
+
+/// Combo choosing which cell property tints the spheres
+fn draw_color_by_selector(ui: &imgui::Ui, global_ui_state: &mut super::GlobalUiState) {
+    ui.text("Color By:");
+    if let Some(_combo) = ui.begin_combo("##cell_color_by", global_ui_state.cell_color_by.name()) {
+        for color_by in CellColorBy::all() {
+            if ui.selectable_config(color_by.name()).selected(global_ui_state.cell_color_by == *color_by).build() {
+                global_ui_state.cell_color_by = *color_by;
+            }
+        }
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Tint cells by mode color, mass, age, nutrient level (red = starving, green = ready to split), \
+                         or colony (one color per group of adhesion-linked cells, to spot fragmentation)");
     }
     
-    // Theme selector
-    ui.separator();
-    ui.text("UI Theme:");
+    ui.checkbox("Legacy Color Handling", &mut global_ui_state.legacy_color_handling);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Light mode colors the old way, without sRGB conversion or emissive glow, to compare how existing genomes looked");
+    }
+}
+
+/// Draw the onion-skin section: toggle, trail length, snapshot spacing and ghost opacity
+fn draw_onion_skin_controls(ui: &imgui::Ui, onion_skin: &mut OnionSkinSettings) {
+    ui.text("Onion Skin:");
+    ui.checkbox("Show Ghost Trail", &mut onion_skin.enabled);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Draw faint copies of cells from earlier steps, fading with age");
+    }
     
-    let themes = ["Dark", "Light", "Classic"];
-    let mut current_theme = 0;
-    for (i, theme) in themes.iter().enumerate() {
-        let is_selected = current_theme == i;
-        if ui.radio_button_bool(theme, is_selected) && !is_selected {
-            current_theme = i;
+    ui.disabled(!onion_skin.enabled, || {
+        ui.text("Trail Length:");
+        let mut trail_length = onion_skin.trail_length as i32;
+        if ui.slider("##onion_trail_length", 1, 32, &mut trail_length) {
+            onion_skin.trail_length = trail_length.max(1) as usize;
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Number of ghost snapshots kept");
+        }
+        
+        ui.text("Steps Between Ghosts:");
+        let mut steps_between = onion_skin.steps_between as i32;
+        if ui.slider("##onion_steps_between", 1, 60, &mut steps_between) {
+            onion_skin.steps_between = steps_between.max(1) as u32;
+        }
+        
+        ui.text("Ghost Opacity:");
+        ui.slider("##onion_opacity", 0.05, 1.0, &mut onion_skin.opacity);
+    });
+}
+
+/// Draw the recording section: format, rate, duration, output path and the Record toggle
+fn draw_recording_controls(ui: &imgui::Ui, recorder: &mut FrameRecorder) {
+    ui.text("Recording:");
+    
+    let recording = recorder.is_recording();
+    let encoding = recorder.is_encoding();
+    
+    // Settings are locked while a recording is in progress
+    ui.disabled(recording || encoding, || {
+        let formats = [RecordingFormat::Gif, RecordingFormat::PngSequence];
+        for format in formats {
+            if ui.radio_button_bool(format.name(), recorder.settings.format == format) && recorder.settings.format != format {
+                recorder.settings.format = format;
+                // Swap the default extension so the path matches the format
+                recorder.settings.output_path = match format {
+                    RecordingFormat::Gif => recorder.settings.output_path.with_extension("gif"),
+                    RecordingFormat::PngSequence => recorder.settings.output_path.with_extension(""),
+                };
+            }
+        }
+        
+        ui.text("FPS:");
+        let mut fps = recorder.settings.fps as i32;
+        if ui.slider("##record_fps", 1, 60, &mut fps) {
+            recorder.settings.fps = fps.max(1) as u32;
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Frames captured per second");
+        }
+        
+        ui.text("Max Duration (s):");
+        ui.slider("##record_duration", 1.0, 120.0, &mut recorder.settings.max_duration);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Recording stops automatically after this long");
+        }
+        
+        ui.text("Output:");
+        ui.text_wrapped(recorder.settings.output_path.display().to_string());
+        if ui.button("Browse...") {
+            let picked = match recorder.settings.format {
+                RecordingFormat::Gif => rfd::FileDialog::new()
+                    .add_filter("GIF", &["gif"])
+                    .set_file_name("recording.gif")
+                    .save_file(),
+                RecordingFormat::PngSequence => rfd::FileDialog::new().pick_folder(),
+            };
+            if let Some(path) = picked {
+                recorder.settings.output_path = path;
+            }
+        }
+    });
+    
+    let label = if recording { "Stop Recording" } else { "Record" };
+    ui.disabled(encoding, || {
+        if ui.button(label) {
+            if recording {
+                recorder.stop();
+            } else {
+                recorder.start();
+            }
+        }
+    });
+    
+    match recorder.status() {
+        EncoderStatus::Idle => {}
+        EncoderStatus::Encoding { frames_done, frames_total } => {
+            ui.text(format!("Encoding... {}/{}", frames_done, frames_total));
+        }
+        EncoderStatus::Finished(path) => {
+            ui.text_colored([0.5, 0.9, 0.5, 1.0], format!("Saved: {}", path.display()));
         }
+        EncoderStatus::Failed(error) => {
+            ui.text_colored([0.9, 0.4, 0.4, 1.0], format!("Recording failed: {}", error));
+        }
+    }
+}
+
+/// Draw a small overlay in the top-right corner while recording or encoding
+pub fn render_recording_indicator(ui: &imgui::Ui, recorder: &FrameRecorder) {
+    let recording = recorder.is_recording();
+    if !recording && !recorder.is_encoding() {
+        return;
     }
-}
\ No newline at end of file
+    
+    let display_size = ui.io().display_size;
+    ui.window("##recording_indicator")
+        .position([display_size[0] - 16.0, 40.0], Condition::Always)
+        .position_pivot([1.0, 0.0])
+        .bg_alpha(0.6)
+        .flags(
+            WindowFlags::NO_DECORATION
+                | WindowFlags::ALWAYS_AUTO_RESIZE
+                | WindowFlags::NO_MOVE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_FOCUS_ON_APPEARING
+                | WindowFlags::NO_NAV
+                | WindowFlags::NO_DOCKING,
+        )
+        .build(|| {
+            if recording {
+                let elapsed = recorder.elapsed();
+                // Blink the label once per second
+                let rec_color = if elapsed.fract() < 0.5 {
+                    [1.0, 0.2, 0.2, 1.0]
+                } else {
+                    [0.5, 0.1, 0.1, 1.0]
+                };
+                ui.text_colored(rec_color, "REC");
+                ui.same_line();
+                ui.text(format!(
+                    "{:02}:{:02}  ({} frames)",
+                    elapsed as u32 / 60,
+                    elapsed as u32 % 60,
+                    recorder.frames_captured()
+                ));
+            } else {
+                ui.text("Encoding recording...");
+            }
+        });
+}
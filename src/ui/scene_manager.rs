@@ -1,10 +1,27 @@
-use crate::simulation::{SimulationState, SimulationMode};
+use crate::simulation::{SimulationState, SimulationMode, cpu_sim::GenomeApplyMode, initial_state::{SpawnPattern, random_seed}, physics_config::Integrator, scenes::SceneLibrary};
 use imgui::{Condition, StyleColor, WindowFlags};
 
+/// Scene change the UI asked for; needs the live genome and colony, so the scene applies it
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneRequest {
+    /// Store the running configuration in the active scene, then rebuild the colony from this one
+    Switch(usize),
+    /// Store the running configuration as a new scene with this name and make it active
+    SaveAsNew(String),
+}
+
 /// Resource to track Scene Manager window state
 pub struct SceneManagerState {
     pub window_open: bool,
     pub show_exit_confirmation: bool,
+    /// Saved scenes; persisted to `SceneLibrary::default_path()`
+    pub scenes: SceneLibrary,
+    /// Set by the UI, consumed by the scene on the next frame
+    pub scene_request: Option<SceneRequest>,
+    /// The scene list was edited and should be saved
+    pub scenes_changed: bool,
+    /// Name typed for the next "Save as New Scene"
+    pub new_scene_name: String,
 }
 
 impl Default for SceneManagerState {
@@ -12,6 +29,10 @@ impl Default for SceneManagerState {
         Self {
             window_open: true,
             show_exit_confirmation: false,
+            scenes: SceneLibrary::default(),
+            scene_request: None,
+            scenes_changed: false,
+            new_scene_name: String::new(),
         }
     }
 }
@@ -42,6 +63,305 @@ impl Default for PendingGridDensity {
     }
 }
 
+/// Genome Editor / CPU Scene / GPU Scene picker. GPU is greyed out when the adapter can't run it;
+/// switching keeps the running colony.
+fn draw_mode_selector(ui: &imgui::Ui, simulation_state: &mut SimulationState) {
+    for mode in SimulationMode::ALL {
+        let unsupported = match mode {
+            SimulationMode::Gpu => simulation_state.gpu_support.unsupported_reason(),
+            _ => None,
+        };
+        let _disabled = ui.begin_disabled(unsupported.is_some());
+        if ui.selectable_config(mode.name()).selected(simulation_state.mode == mode).build() {
+            simulation_state.mode = mode;
+            simulation_state.gpu_fallback = None;
+        }
+        if let Some(reason) = unsupported {
+            if ui.is_item_hovered_with_flags(imgui::ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+                ui.tooltip_text(format!("Unavailable: {}", reason));
+            }
+        }
+    }
+
+    if simulation_state.mode == SimulationMode::Gpu {
+        ui.text_disabled("Collisions, adhesion springs and integration run in compute shaders; growth and division stay on the CPU");
+    }
+
+    if let Some(reason) = &simulation_state.gpu_fallback {
+        ui.text_colored([1.0, 0.55, 0.0, 1.0], "GPU physics unavailable, running the CPU scene");
+        ui.text_wrapped(reason);
+        if ui.small_button("Dismiss##GpuFallback") {
+            simulation_state.gpu_fallback = None;
+        }
+    }
+}
+
+/// Named scenes: switch, rename, remove, or save the running setup as a new one
+fn draw_scene_list(ui: &imgui::Ui, state: &mut SceneManagerState) {
+    ui.text("Scenes");
+    ui.separator();
+    
+    let mut remove = None;
+    for (index, scene) in state.scenes.scenes.iter().enumerate() {
+        let active = index == state.scenes.active;
+        if ui.selectable_config(format!("{}##scene_{}", scene.name, index)).selected(active).build() && !active {
+            state.scene_request = Some(SceneRequest::Switch(index));
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text(if active {
+                "Active scene"
+            } else {
+                "Switch to this scene; the running colony is rebuilt from its genome, spawn and physics settings"
+            });
+        }
+        if !active {
+            if let Some(_popup) = ui.begin_popup_context_item() {
+                if ui.menu_item("Remove Scene") {
+                    remove = Some(index);
+                }
+            }
+        }
+    }
+    if let Some(index) = remove {
+        state.scenes_changed |= state.scenes.remove(index);
+    }
+    
+    let active = state.scenes.active_scene_mut();
+    if ui.input_text("Name##active_scene_name", &mut active.name).build() {
+        state.scenes_changed = true;
+    }
+    
+    ui.input_text("##new_scene_name", &mut state.new_scene_name)
+        .hint("New scene name")
+        .build();
+    ui.same_line();
+    if ui.button("Save as New Scene") {
+        let name = state.new_scene_name.trim();
+        let name = if name.is_empty() { "Scene" } else { name };
+        state.scene_request = Some(SceneRequest::SaveAsNew(name.to_string()));
+        state.new_scene_name.clear();
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Keep the current genome, spawn and physics settings as a new scene (right-click a scene to remove it)");
+    }
+    
+    ui.separator();
+}
+
+/// Prominent warning after the simulation auto-paused on a blow-up, with a rollback option
+pub fn render_instability_warning(ui: &imgui::Ui, simulation_state: &mut SimulationState) {
+    let Some(instability) = simulation_state.instability.clone() else {
+        return;
+    };
+    
+    let display_size = ui.io().display_size;
+    let mut dismiss = false;
+    ui.window("Simulation Unstable")
+        .position([display_size[0] * 0.5, display_size[1] * 0.3], Condition::Always)
+        .position_pivot([0.5, 0.5])
+        .flags(
+            WindowFlags::NO_COLLAPSE
+                | WindowFlags::ALWAYS_AUTO_RESIZE
+                | WindowFlags::NO_MOVE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_DOCKING,
+        )
+        .build(|| {
+            let what = if instability.non_finite { "became NaN/infinite" } else { "is moving impossibly fast" };
+            ui.text_colored([1.0, 0.3, 0.3, 1.0], format!(
+                "Paused at t={:.2}s: cell {} {}",
+                instability.time, instability.cell_id, what
+            ));
+            ui.separator();
+            ui.text("Likely cause:");
+            ui.text_wrapped(&instability.likely_cause);
+            ui.separator();
+            
+            if let Some(time) = simulation_state.stable_snapshot_time {
+                if ui.button(format!("Roll Back to t={:.1}s", time)) {
+                    simulation_state.rollback_requested = true;
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Restore the last colony that passed the stability check; the simulation stays paused");
+                }
+                ui.same_line();
+            }
+            dismiss = ui.button("Dismiss");
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Hide this warning; the simulation stays paused");
+            }
+        });
+    
+    if dismiss {
+        simulation_state.instability = None;
+    }
+}
+
+/// Apply-mode choice and the button that pushes the edited genome into the running colony
+fn draw_genome_apply_controls(ui: &imgui::Ui, simulation_state: &mut SimulationState) {
+    ui.text("Running Genome");
+    ui.separator();
+    
+    if let Some(blocker) = simulation_state.growth_blocker {
+        ui.text_colored([1.0, 0.5, 0.0, 1.0], "Colony will not grow: initial mode never divides");
+        ui.text_disabled(format!("({})", blocker.description()));
+    }
+    
+    for mode in [GenomeApplyMode::UpdateExisting, GenomeApplyMode::NewbornsOnly] {
+        if ui.radio_button_bool(mode.name(), simulation_state.genome_apply_mode == mode) {
+            simulation_state.genome_apply_mode = mode;
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text(match mode {
+                GenomeApplyMode::UpdateExisting => "Existing cells pick up the new mode parameters immediately",
+                GenomeApplyMode::NewbornsOnly => "Existing cells keep their old parameters; cells born from now on use the new genome",
+            });
+        }
+    }
+    
+    ui.disabled(!simulation_state.genome_has_unapplied_changes, || {
+        if ui.button("Apply Genome to Running Sim") {
+            simulation_state.genome_apply_requested = true;
+        }
+    });
+    if simulation_state.genome_has_unapplied_changes {
+        ui.text_colored([1.0, 0.8, 0.3, 1.0], "Genome has unapplied edits");
+    } else {
+        ui.text_disabled("Colony is running the current genome");
+    }
+    
+    ui.separator();
+}
+
+/// Starting colony layout, applied by Respawn and Reset Scene
+fn draw_spawn_controls(ui: &imgui::Ui, simulation_state: &mut SimulationState) {
+    ui.text("Spawn");
+    ui.separator();
+    
+    let spawn = &mut simulation_state.spawn_config;
+    if let Some(_combo) = ui.begin_combo("Pattern", spawn.pattern.name()) {
+        for pattern in SpawnPattern::all() {
+            if ui.selectable_config(pattern.name()).selected(spawn.pattern == *pattern).build() {
+                spawn.pattern = *pattern;
+            }
+        }
+    }
+    
+    if spawn.pattern != SpawnPattern::SingleSeed {
+        let mut count = spawn.count as i32;
+        if ui.slider("Cell Count", 1, 256, &mut count) {
+            spawn.count = count.max(1) as usize;
+        }
+    }
+    if spawn.pattern == SpawnPattern::Scatter {
+        ui.slider_config("Scatter Radius", 1.0, 50.0)
+            .display_format("%.1f")
+            .build(&mut spawn.radius);
+    }
+    
+    ui.input_int("Initial Mode", &mut spawn.initial_mode).build();
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Mode index spawned cells start in; -1 uses the genome's initial mode");
+    }
+    spawn.initial_mode = spawn.initial_mode.max(-1);
+    
+    let mut seed = spawn.seed as i32;
+    ui.set_next_item_width(120.0);
+    if ui.input_int("Seed", &mut seed).build() {
+        spawn.seed = seed.max(0) as u32;
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Drives placement, split ranges and split jitter; the same seed, genome and physics settings replay the same run");
+    }
+    ui.same_line();
+    if ui.button("Randomize") {
+        spawn.seed = random_seed() & i32::MAX as u32;
+    }
+    
+    if ui.button("Respawn") {
+        simulation_state.needs_respawn = true;
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Clear every cell and adhesion and restart the colony at time 0 from this layout and seed, using the edited genome");
+    }
+    
+    ui.separator();
+}
+
+/// Start/stop, file and sampling interval for the CSV statistics log
+fn draw_telemetry_controls(ui: &imgui::Ui, simulation_state: &mut SimulationState) {
+    ui.text("Data Logging");
+    ui.separator();
+    
+    let telemetry = &mut simulation_state.telemetry;
+    let logging = telemetry.is_logging();
+    ui.disabled(logging, || {
+        let mut path = telemetry.output_path.display().to_string();
+        if ui.input_text("CSV File", &mut path).build() {
+            telemetry.output_path = path.into();
+        }
+    });
+    
+    let mut interval = telemetry.interval_steps as i32;
+    if ui.slider("Every N Steps", 1, 1000, &mut interval) {
+        telemetry.interval_steps = interval.max(1) as u32;
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Physics steps between rows: time, cell count, total and average mass, and cells per mode");
+    }
+    
+    if logging {
+        if ui.button("Stop Logging") {
+            if let Err(e) = telemetry.stop() {
+                eprintln!("Failed to finish telemetry log: {}", e);
+            }
+        }
+        ui.same_line();
+        ui.text_colored([0.4, 0.9, 0.4, 1.0], format!("Logging ({} rows)", telemetry.rows_written()));
+    } else {
+        if ui.button("Start Logging") {
+            if let Err(e) = telemetry.start() {
+                eprintln!("Failed to start telemetry log {}: {}", telemetry.output_path.display(), e);
+            }
+        }
+        if let Some(error) = telemetry.last_error() {
+            ui.text_colored([1.0, 0.4, 0.4, 1.0], format!("Logging failed: {}", error));
+        }
+    }
+    
+    ui.separator();
+}
+
+/// Sliders for physics parameters that are safe to change while the colony runs
+fn draw_physics_controls(ui: &imgui::Ui, simulation_state: &mut SimulationState) {
+    ui.text("Physics");
+    ui.separator();
+    
+    ui.slider_config("Min Cell Mass", 0.05, 2.0)
+        .display_format("%.2f")
+        .build(&mut simulation_state.physics_config.min_cell_mass);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Cells lighter than this die, and cells only split when both children stay above it");
+    }
+    
+    let physics = &mut simulation_state.physics_config;
+    if let Some(_combo) = ui.begin_combo("Integrator", physics.integrator.name()) {
+        for integrator in Integrator::all() {
+            if ui.selectable_config(integrator.name()).selected(physics.integrator == *integrator).build() {
+                physics.integrator = *integrator;
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text(integrator.description());
+            }
+        }
+    }
+    if ui.is_item_hovered() {
+        ui.tooltip_text(physics.integrator.description());
+    }
+    
+    ui.separator();
+}
+
 /// Main Scene Manager window rendering function
 /// Returns true if exit was requested
 pub fn render_scene_manager_window(
@@ -93,38 +413,34 @@ pub fn render_scene_manager_window(
             ui.separator();
             
             // Scene selection using selectable items (radio button behavior)
-            let mut selected_mode = simulation_state.mode;
-            
-            if ui.selectable_config("Genome Editor")
-                .selected(selected_mode == SimulationMode::Preview)
-                .build()
-            {
-                selected_mode = SimulationMode::Preview;
-            }
-            
-            if ui.selectable_config("CPU Scene")
-                .selected(selected_mode == SimulationMode::Cpu)
-                .build()
-            {
-                selected_mode = SimulationMode::Cpu;
-            }
-            
-            // Handle scene transition if mode changed
-            if selected_mode != simulation_state.mode {
-                simulation_state.mode = selected_mode;
-            }
+            draw_mode_selector(ui, simulation_state);
             
             ui.separator();
             
-            // Reset scene button (only for CPU scene)
+            // Reset scene button (only for the live scenes)
             if simulation_state.mode != SimulationMode::Preview {
                 if ui.button("Reset Scene") {
-                    // Handle reset scene event
+                    simulation_state.needs_respawn = true;
                 }
                 
                 ui.separator();
             }
             
+            // Named scenes
+            draw_scene_list(ui, scene_manager_state);
+            
+            // Starting colony
+            draw_spawn_controls(ui, simulation_state);
+            
+            // Genome hot-apply
+            draw_genome_apply_controls(ui, simulation_state);
+            
+            // Physics parameters
+            draw_physics_controls(ui, simulation_state);
+            
+            // CSV statistics log
+            draw_telemetry_controls(ui, simulation_state);
+            
             // Time controls section
             ui.text("Time Controls");
             ui.separator();
@@ -132,7 +448,7 @@ pub fn render_scene_manager_window(
             // Show pause/play toggle for CPU mode
             // Show message for Preview mode
             match simulation_state.mode {
-                SimulationMode::Cpu => {
+                SimulationMode::Cpu | SimulationMode::Gpu => {
                     // Toggle pause/play button
                     let button_label = if simulation_state.paused { "Play" } else { "Pause" };
                     if ui.button(button_label) {
@@ -263,38 +579,34 @@ pub fn render_scene_manager_content(
     ui.separator();
     
     // Scene selection using selectable items (radio button behavior)
-    let mut selected_mode = simulation_state.mode;
-    
-    if ui.selectable_config("Genome Editor")
-        .selected(selected_mode == SimulationMode::Preview)
-        .build()
-    {
-        selected_mode = SimulationMode::Preview;
-    }
-    
-    if ui.selectable_config("CPU Scene")
-        .selected(selected_mode == SimulationMode::Cpu)
-        .build()
-    {
-        selected_mode = SimulationMode::Cpu;
-    }
-    
-    // Handle scene transition if mode changed
-    if selected_mode != simulation_state.mode {
-        simulation_state.mode = selected_mode;
-    }
+    draw_mode_selector(ui, simulation_state);
     
     ui.separator();
     
-    // Reset scene button (only for CPU scene)
+    // Reset scene button (only for the live scenes)
     if simulation_state.mode != SimulationMode::Preview {
         if ui.button("Reset Scene") {
-            // Handle reset scene event
+            simulation_state.needs_respawn = true;
         }
         
         ui.separator();
     }
     
+    // Named scenes
+    draw_scene_list(ui, scene_manager_state);
+    
+    // Starting colony
+    draw_spawn_controls(ui, simulation_state);
+    
+    // Genome hot-apply
+    draw_genome_apply_controls(ui, simulation_state);
+    
+    // Physics parameters
+    draw_physics_controls(ui, simulation_state);
+    
+    // CSV statistics log
+    draw_telemetry_controls(ui, simulation_state);
+    
     // Time controls section
     ui.text("Time Controls");
     ui.separator();
@@ -302,7 +614,7 @@ pub fn render_scene_manager_content(
     // Show pause/play toggle for CPU mode
     // Show message for Preview mode
     match simulation_state.mode {
-        SimulationMode::Cpu => {
+        SimulationMode::Cpu | SimulationMode::Gpu => {
             // Toggle pause/play button
             let button_label = if simulation_state.paused { "Play" } else { "Pause" };
             if ui.button(button_label) {
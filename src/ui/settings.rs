@@ -1 +1,154 @@
 // Application settings UI
+use imgui::{Condition, WindowFlags};
+use std::time::{Duration, Instant};
+use super::imgui_integration::ImguiConfig;
+
+/// Base font size used until the user picks another
+pub const DEFAULT_FONT_SIZE: f32 = 13.0;
+pub const FONT_SIZE_MIN: f32 = 8.0;
+pub const FONT_SIZE_MAX: f32 = 32.0;
+
+/// Glyph oversampling when oversampling is on; off renders glyphs 1:1
+const FONT_OVERSAMPLE_H: i32 = 3;
+const FONT_OVERSAMPLE_V: i32 = 2;
+
+/// How long the font settings must stay unchanged before the atlas is rebuilt
+const FONT_REBUILD_DELAY: Duration = Duration::from_millis(300);
+
+/// Font options that can change at runtime
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontSettings {
+    pub size: f32,
+    pub oversampling: bool,
+}
+
+impl FontSettings {
+    pub fn from_ui_state(global_ui_state: &super::GlobalUiState) -> Self {
+        Self {
+            size: global_ui_state.font_size.clamp(FONT_SIZE_MIN, FONT_SIZE_MAX),
+            oversampling: global_ui_state.font_oversampling,
+        }
+    }
+
+    /// Write the font size and oversampling into an imgui config
+    pub fn apply_to(&self, config: &mut ImguiConfig) {
+        config.font_size = self.size;
+        (config.font_oversample_h, config.font_oversample_v) = if self.oversampling {
+            (FONT_OVERSAMPLE_H, FONT_OVERSAMPLE_V)
+        } else {
+            (1, 1)
+        };
+    }
+}
+
+/// Holds back font atlas rebuilds until the settings stop changing,
+/// so dragging the font size slider doesn't rebuild the atlas every frame
+pub struct FontRebuildDebounce {
+    applied: FontSettings,
+    pending: Option<(FontSettings, Instant)>,
+}
+
+impl FontRebuildDebounce {
+    /// Start from the settings the atlas was built with
+    pub fn new(applied: FontSettings) -> Self {
+        Self { applied, pending: None }
+    }
+
+    /// Settings to rebuild the atlas with now, once they differ from the applied ones and have settled
+    pub fn poll(&mut self, requested: FontSettings, now: Instant) -> Option<FontSettings> {
+        if requested == self.applied {
+            self.pending = None;
+            return None;
+        }
+        match self.pending {
+            Some((pending, since)) if pending == requested => {
+                if now.duration_since(since) < FONT_REBUILD_DELAY {
+                    return None;
+                }
+                self.pending = None;
+                self.applied = requested;
+                Some(requested)
+            }
+            _ => {
+                self.pending = Some((requested, now));
+                None
+            }
+        }
+    }
+}
+
+/// Render the settings window
+pub fn render_settings_window(
+    ui: &imgui::Ui,
+    global_ui_state: &mut super::GlobalUiState,
+) {
+    // Only show if visibility is enabled
+    if !global_ui_state.show_settings {
+        return;
+    }
+
+    let flags = if global_ui_state.windows_locked {
+        WindowFlags::NO_MOVE | WindowFlags::NO_RESIZE
+    } else {
+        WindowFlags::empty()
+    };
+
+    ui.window("Settings")
+        .size([360.0, 220.0], Condition::FirstUseEver)
+        .position([1000.0, 200.0], Condition::FirstUseEver)
+        .flags(flags)
+        .build(|| {
+            render_settings_content(ui, global_ui_state);
+        });
+}
+
+/// Render just the content of the Settings window (without the window wrapper)
+pub fn render_settings_content(ui: &imgui::Ui, global_ui_state: &mut super::GlobalUiState) {
+    if ui.collapsing_header("Font", imgui::TreeNodeFlags::DEFAULT_OPEN) {
+        ui.indent();
+
+        ui.text("Font Size:");
+        ui.slider_config("##FontSize", FONT_SIZE_MIN, FONT_SIZE_MAX)
+            .display_format("%.0f px")
+            .build(&mut global_ui_state.font_size);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Base font size before UI scale; applied shortly after you stop dragging");
+        }
+
+        ui.checkbox("Oversampling", &mut global_ui_state.font_oversampling);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Render glyphs at a higher resolution for smoother text, at the cost of a larger font texture");
+        }
+
+        if ui.button("Reset Font") {
+            global_ui_state.font_size = DEFAULT_FONT_SIZE;
+            global_ui_state.font_oversampling = false;
+        }
+
+        ui.unindent();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebuild_waits_for_settings_to_settle() {
+        let start = Instant::now();
+        let applied = FontSettings { size: DEFAULT_FONT_SIZE, oversampling: false };
+        let mut debounce = FontRebuildDebounce::new(applied);
+        assert_eq!(debounce.poll(applied, start), None);
+
+        // Dragging the slider keeps restarting the wait
+        let mut now = start;
+        for size in [14.0, 15.0, 16.0] {
+            now += FONT_REBUILD_DELAY / 2;
+            assert_eq!(debounce.poll(FontSettings { size, ..applied }, now), None);
+        }
+
+        let settled = FontSettings { size: 16.0, ..applied };
+        assert_eq!(debounce.poll(settled, now + FONT_REBUILD_DELAY), Some(settled));
+        assert_eq!(debounce.poll(settled, now + FONT_REBUILD_DELAY * 2), None);
+    }
+}
@@ -1 +1,167 @@
 // System tray UI
+
+/// Something picked from the tray menu, handled by the event loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    ToggleWindow,
+    TogglePause,
+    Quit,
+}
+
+/// Tray icon with Show/Hide Window, Pause/Resume Simulation and Quit.
+/// Where there is no tray (Linux builds, or the icon failed to register) every call does nothing.
+pub struct SystemTray {
+    #[cfg(any(windows, target_os = "macos"))]
+    tray: Option<platform::Tray>,
+}
+
+impl SystemTray {
+    /// Add the icon to the tray. On macOS this must happen once the event loop is running.
+    pub fn new() -> Self {
+        #[cfg(any(windows, target_os = "macos"))]
+        {
+            let tray = platform::Tray::new()
+                .inspect_err(|e| eprintln!("System tray unavailable: {}", e))
+                .ok();
+            Self { tray }
+        }
+        #[cfg(not(any(windows, target_os = "macos")))]
+        {
+            Self {}
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        #[cfg(any(windows, target_os = "macos"))]
+        {
+            self.tray.is_some()
+        }
+        #[cfg(not(any(windows, target_os = "macos")))]
+        {
+            false
+        }
+    }
+
+    /// Next menu pick since the last call, if any
+    pub fn poll(&self) -> Option<TrayAction> {
+        #[cfg(any(windows, target_os = "macos"))]
+        {
+            self.tray.as_ref()?.poll()
+        }
+        #[cfg(not(any(windows, target_os = "macos")))]
+        {
+            None
+        }
+    }
+
+    /// Keep the toggle items' labels in step with the window and simulation
+    pub fn update_labels(&mut self, window_visible: bool, paused: bool) {
+        #[cfg(any(windows, target_os = "macos"))]
+        if let Some(tray) = self.tray.as_mut() {
+            tray.update_labels(window_visible, paused);
+        }
+        #[cfg(not(any(windows, target_os = "macos")))]
+        let _ = (window_visible, paused);
+    }
+}
+
+impl Default for SystemTray {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+mod platform {
+    use super::TrayAction;
+    use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+    use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+
+    /// Size of the generated tray icon in pixels
+    const ICON_SIZE: u32 = 32;
+
+    pub struct Tray {
+        /// Removed from the tray when dropped
+        _icon: TrayIcon,
+        toggle_window: MenuItem,
+        toggle_pause: MenuItem,
+        quit: MenuItem,
+        /// (window visible, paused) the labels were last set for
+        shown_state: (bool, bool),
+    }
+
+    impl Tray {
+        pub fn new() -> Result<Self, String> {
+            let toggle_window = MenuItem::new(window_label(true), true, None);
+            let toggle_pause = MenuItem::new(pause_label(false), true, None);
+            let quit = MenuItem::new("Quit", true, None);
+            let menu = Menu::new();
+            for item in [&toggle_window, &toggle_pause] {
+                menu.append(item).map_err(|e| e.to_string())?;
+            }
+            menu.append(&PredefinedMenuItem::separator()).map_err(|e| e.to_string())?;
+            menu.append(&quit).map_err(|e| e.to_string())?;
+
+            let icon = Icon::from_rgba(icon_pixels(), ICON_SIZE, ICON_SIZE).map_err(|e| e.to_string())?;
+            let tray_icon = TrayIconBuilder::new()
+                .with_menu(Box::new(menu))
+                .with_tooltip("BioSpheres")
+                .with_icon(icon)
+                .build()
+                .map_err(|e| e.to_string())?;
+
+            Ok(Self { _icon: tray_icon, toggle_window, toggle_pause, quit, shown_state: (true, false) })
+        }
+
+        pub fn poll(&self) -> Option<TrayAction> {
+            while let Ok(event) = MenuEvent::receiver().try_recv() {
+                if event.id == *self.toggle_window.id() {
+                    return Some(TrayAction::ToggleWindow);
+                } else if event.id == *self.toggle_pause.id() {
+                    return Some(TrayAction::TogglePause);
+                } else if event.id == *self.quit.id() {
+                    return Some(TrayAction::Quit);
+                }
+            }
+            None
+        }
+
+        pub fn update_labels(&mut self, window_visible: bool, paused: bool) {
+            if self.shown_state == (window_visible, paused) {
+                return;
+            }
+            self.shown_state = (window_visible, paused);
+            self.toggle_window.set_text(window_label(window_visible));
+            self.toggle_pause.set_text(pause_label(paused));
+        }
+    }
+
+    fn window_label(window_visible: bool) -> &'static str {
+        if window_visible { "Hide Window" } else { "Show Window" }
+    }
+
+    fn pause_label(paused: bool) -> &'static str {
+        if paused { "Resume Simulation" } else { "Pause Simulation" }
+    }
+
+    /// A green cell: a filled circle with a darker rim
+    fn icon_pixels() -> Vec<u8> {
+        let center = (ICON_SIZE as f32 - 1.0) * 0.5;
+        let radius = ICON_SIZE as f32 * 0.45;
+        let mut pixels = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+        for y in 0..ICON_SIZE {
+            for x in 0..ICON_SIZE {
+                let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt();
+                let pixel = if distance > radius {
+                    [0, 0, 0, 0]
+                } else if distance > radius - 2.5 {
+                    [30, 120, 60, 255]
+                } else {
+                    [90, 200, 120, 255]
+                };
+                pixels.extend_from_slice(&pixel);
+            }
+        }
+        pixels
+    }
+}
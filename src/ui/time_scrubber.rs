@@ -1,5 +1,24 @@
 use imgui::{self, StyleColor, WindowFlags, Condition};
-use crate::simulation::{SimulationState, SimulationMode};
+use crate::simulation::{SimulationState, SimulationMode, events::SimulationEvent};
+
+/// How far event markers extend above/below the slider track (pixels)
+const MARKER_OVERHANG: f32 = 3.0;
+/// Horizontal distance from a marker that still counts as hovering it (pixels)
+const MARKER_HOVER_RADIUS: f32 = 3.0;
+/// Maximum number of events listed in a marker tooltip
+const MAX_TOOLTIP_EVENTS: usize = 8;
+/// Speed multiplier preset buttons (label, multiplier)
+const SPEED_PRESETS: [(&str, f32); 6] = [
+    ("0.25x", 0.25),
+    ("0.5x", 0.5),
+    ("1x", 1.0),
+    ("2x", 2.0),
+    ("5x", 5.0),
+    ("10x", 10.0),
+];
+/// Range of the speed slider; it is logarithmic so slow and fast speeds get equal room
+const SPEED_MIN: f32 = 0.1;
+const SPEED_MAX: f32 = 100.0;
 
 /// State for the time scrubber UI
 pub struct TimeScrubberState {
@@ -47,65 +66,132 @@ pub fn render_time_scrubber(
         .position([900.0, 1227.0], Condition::FirstUseEver)
         .flags(flags)
         .build(|| {
-            let mut current_time = sim_state.current_time;
-            
-            // Time display
-            ui.text(format!("Current Time: {:.2}s", current_time));
-            ui.same_line();
-            ui.text(format!("/ {:.0}s", scrubber_state.max_time));
-            
-            ui.separator();
-            
-            // Main time slider
-            ui.text("Scrub Time:");
-            ui.set_next_item_width(-1.0); // Full width
-            
-            let slider_changed = ui
-                .slider_config("##time_slider", 0.0, scrubber_state.max_time)
-                .display_format("%.2fs")
-                .build(&mut current_time);
-            
-            // Check if slider is being actively dragged
-            let is_active = ui.is_item_active();
-            
-            if slider_changed {
-                // Update target time for resimulation
-                sim_state.target_time = Some(current_time);
-                scrubber_state.is_dragging = is_active;
-            } else if scrubber_state.is_dragging && !is_active {
-                // Just finished dragging
-                scrubber_state.is_dragging = false;
-            }
-            
-            ui.separator();
-            
-            // Info about simulation state
-            if sim_state.is_resimulating {
-                let col_yellow = ui.style_color(StyleColor::PlotHistogram);
-                ui.text_colored(col_yellow, "Simulating...");
-            } else {
-                let col_green = ui.style_color(StyleColor::PlotLines);
-                ui.text_colored(col_green, "Ready");
-            }
-            
-            // Display simulation info
-            ui.separator();
-            ui.text(format!("Timestep: {:.4}s", 0.016)); // Fixed timestep placeholder
+            render_time_scrubber_content(ui, scrubber_state, sim_state);
         });
 }
+
+/// Draw tick marks for recorded simulation events on top of the time slider.
+/// Must be called directly after the slider so the last item rect is the slider track.
+/// Hovering a marker shows what happened; clicking it seeks to that time.
+fn draw_event_markers(
+    ui: &imgui::Ui,
+    scrubber_state: &TimeScrubberState,
+    sim_state: &mut SimulationState,
+) {
+    if sim_state.event_log.is_empty() || scrubber_state.max_time <= 0.0 {
+        return;
+    }
+    
+    let track_min = ui.item_rect_min();
+    let track_max = ui.item_rect_max();
+    
+    // Match ImGui's slider grab inset so markers line up with the grab position
+    let grab_half = ui.clone_style().grab_min_size * 0.5 + 2.0;
+    let usable_min = track_min[0] + grab_half;
+    let usable_width = (track_max[0] - track_min[0] - grab_half * 2.0).max(1.0);
+    
+    let draw_list = ui.get_window_draw_list();
+    let mouse_pos = ui.io().mouse_pos;
+    let mouse_over_track = mouse_pos[1] >= track_min[1] - MARKER_OVERHANG
+        && mouse_pos[1] <= track_max[1] + MARKER_OVERHANG;
+    
+    let mut hovered: Vec<SimulationEvent> = Vec::new();
+    for event in sim_state.event_log.events() {
+        if event.time > scrubber_state.max_time {
+            break;
+        }
+        
+        let x = usable_min + (event.time / scrubber_state.max_time) * usable_width;
+        draw_list
+            .add_line(
+                [x, track_min[1] - MARKER_OVERHANG],
+                [x, track_max[1] + MARKER_OVERHANG],
+                event.kind.color(),
+            )
+            .thickness(2.0)
+            .build();
+        
+        if mouse_over_track && (mouse_pos[0] - x).abs() <= MARKER_HOVER_RADIUS {
+            hovered.push(*event);
+        }
+    }
+    
+    if hovered.is_empty() {
+        return;
+    }
+    
+    ui.tooltip(|| {
+        for event in hovered.iter().take(MAX_TOOLTIP_EVENTS) {
+            ui.text_colored(
+                event.kind.color(),
+                format!("{:.2}s  {} (cell {})", event.time, event.kind.name(), event.cell_id),
+            );
+        }
+        if hovered.len() > MAX_TOOLTIP_EVENTS {
+            ui.text_disabled(format!("... and {} more", hovered.len() - MAX_TOOLTIP_EVENTS));
+        }
+    });
+    
+    // Seek to the marker on click
+    if ui.is_mouse_clicked(imgui::MouseButton::Left) {
+        sim_state.target_time = Some(hovered[0].time);
+    }
+}
+
+/// Speed preset buttons and a logarithmic slider for the speed multiplier.
+/// The physics loop caps steps per frame, so high speeds slow down rather than stall the app;
+/// the speed actually reached is shown while the cap applies.
+fn draw_speed_controls(ui: &imgui::Ui, sim_state: &mut SimulationState) {
+    ui.text("Speed:");
+    for (label, speed) in SPEED_PRESETS {
+        ui.same_line();
+        let is_current = (sim_state.speed_multiplier - speed).abs() < 0.01;
+        let _highlight = is_current.then(|| ui.push_style_color(StyleColor::Button, ui.style_color(StyleColor::ButtonActive)));
+        if ui.button(label) {
+            sim_state.speed_multiplier = speed;
+        }
+    }
+    
+    ui.same_line();
+    ui.set_next_item_width(200.0);
+    ui.slider_config("##speed_slider", SPEED_MIN, SPEED_MAX)
+        .display_format("%.2fx")
+        .flags(imgui::SliderFlags::LOGARITHMIC | imgui::SliderFlags::ALWAYS_CLAMP)
+        .build(&mut sim_state.speed_multiplier);
+    if ui.is_item_hovered() {
+        ui.tooltip_text("Simulated seconds per real second. Very high speeds are limited by how many physics steps fit in a frame.");
+    }
+    
+    // The step cap quietly drops the backlog, so say when the chosen speed isn't being reached
+    if !sim_state.paused && sim_state.speed_capped {
+        ui.same_line();
+        ui.text_colored([1.0, 0.6, 0.2, 1.0], format!("Capped: {:.1}x", sim_state.achieved_speed));
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Physics steps per frame are limited, so the simulation runs slower than the selected speed");
+        }
+    }
+}
+
 /// Render just the content of the Time Scrubber window (without the window wrapper)
 pub fn render_time_scrubber_content(
     ui: &imgui::Ui,
     scrubber_state: &mut TimeScrubberState,
     sim_state: &mut SimulationState,
 ) {
-    let mut current_time = sim_state.current_time;
+    // While seeking, the slider shows where it is heading rather than where the sim is
+    let mut scrub_time = sim_state.target_time.unwrap_or(sim_state.current_time);
     
-    // Time display
-    ui.text(format!("Current Time: {:.2}s", current_time));
+    // Play/pause and time display
+    if ui.button(if sim_state.paused { "Play" } else { "Pause" }) {
+        sim_state.paused = !sim_state.paused;
+    }
+    ui.same_line();
+    ui.text(format!("Current Time: {:.2}s", sim_state.current_time));
     ui.same_line();
     ui.text(format!("/ {:.0}s", scrubber_state.max_time));
     
+    draw_speed_controls(ui, sim_state);
+    
     ui.separator();
     
     // Main time slider
@@ -115,26 +201,36 @@ pub fn render_time_scrubber_content(
     let slider_changed = ui
         .slider_config("##time_slider", 0.0, scrubber_state.max_time)
         .display_format("%.2fs")
-        .build(&mut current_time);
+        .build(&mut scrub_time);
     
     // Check if slider is being actively dragged
     let is_active = ui.is_item_active();
     
     if slider_changed {
         // Update target time for resimulation
-        sim_state.target_time = Some(current_time);
+        sim_state.target_time = Some(scrub_time);
         scrubber_state.is_dragging = is_active;
     } else if scrubber_state.is_dragging && !is_active {
         // Just finished dragging
         scrubber_state.is_dragging = false;
     }
     
+    // Event markers drawn over the slider track (after the slider so a marker click wins)
+    draw_event_markers(ui, scrubber_state, sim_state);
+    
     ui.separator();
     
     // Info about simulation state
-    if sim_state.is_resimulating {
+    if let (true, Some(target)) = (sim_state.is_resimulating, sim_state.target_time) {
         let col_yellow = ui.style_color(StyleColor::PlotHistogram);
-        ui.text_colored(col_yellow, "Simulating...");
+        ui.text_colored(col_yellow, format!("Simulating to {:.2}s...", target));
+        // Only a jump that didn't finish within a frame gets this far
+        if let Some(seek) = sim_state.preview_seek {
+            ui.same_line();
+            imgui::ProgressBar::new(seek.progress(sim_state.current_time))
+                .size([200.0, 0.0])
+                .build(ui);
+        }
     } else {
         let col_green = ui.style_color(StyleColor::PlotLines);
         ui.text_colored(col_green, "Ready");
@@ -142,5 +238,5 @@ pub fn render_time_scrubber_content(
     
     // Display simulation info
     ui.separator();
-    ui.text(format!("Timestep: {:.4}s", 0.016)); // Fixed timestep placeholder
+    ui.text(format!("Timestep: {:.4}s", sim_state.physics_config.fixed_timestep));
 }
\ No newline at end of file